@@ -55,6 +55,77 @@ describe('Blind Threshold BLS Static Tests', () => {
       expect(Buffer.from(blindSignature).toString('hex')).toBe(expectedBlindSignature);
       expect(Buffer.from(unblindedSignature).toString('hex')).toBe(expectedUnblindedSignature);
     });
+
+    it('should derive the OPRF output from the signature, blinded or not', () => {
+      const signature = threshold.sign(keypair.privateKey, STATIC_MESSAGE);
+      const output = threshold.oprfFinalize(keypair.publicKey, STATIC_MESSAGE, signature);
+
+      const blinded = threshold.blind(STATIC_MESSAGE, STATIC_BLINDING_SEED);
+      const blindSignature = threshold.signBlindedMessage(keypair.privateKey, blinded.message);
+      const unblindedOutput = threshold.oprfUnblindFinalize(
+        keypair.publicKey, STATIC_MESSAGE, blindSignature, blinded.blindingFactor);
+
+      const expectedOutput = '387061e0527d1d70df2e7f151c40bc953abf7b35b16380bdd41c60736174df06';
+      expect(Buffer.from(output).toString('hex')).toBe(expectedOutput);
+      expect(Buffer.from(unblindedOutput).toString('hex')).toBe(expectedOutput);
+
+      // A signature on another input has no output.
+      expect(() => threshold.oprfFinalize(keypair.publicKey, Buffer.from('other', 'utf8'), signature)).toThrow();
+    });
+
+    it('should back up keys and shares as mnemonics and restore them', () => {
+      const words = threshold.privateKeyToMnemonic(keypair.privateKey);
+      expect(words.split(' ').length).toBe(29);
+      expect(Buffer.from(threshold.privateKeyFromMnemonic(words)).toString('hex'))
+        .toBe(Buffer.from(keypair.privateKey).toString('hex'));
+
+      const keys = threshold.thresholdKeygen(5, 3, STATIC_BLINDING_SEED);
+      const share = keys.getShare(2);
+      const shareWords = threshold.shareToMnemonic(share);
+      expect(shareWords.split(' ').length).toBe(32);
+      expect(Buffer.from(threshold.shareFromMnemonic(shareWords)).toString('hex'))
+        .toBe(Buffer.from(share).toString('hex'));
+
+      // A key backed up under one scheme is not restored under the other, nor as a share.
+      expect(() => threshold.g1PrivateKeyFromMnemonic(words)).toThrow();
+      expect(() => threshold.shareFromMnemonic(words)).toThrow();
+    });
+
+    it('should split the key into shares that sign for its public key', () => {
+      const keys = threshold.splitPrivateKey(keypair.privateKey, 5, 3, STATIC_THRESHOLD_SEED);
+      expect(Buffer.from(keys.thresholdPublicKey).toString('hex'))
+        .toBe(Buffer.from(keypair.publicKey).toString('hex'));
+
+      const partialSigs = [4, 1, 3].map((i) => threshold.partialSign(keys.getShare(i), STATIC_MESSAGE));
+      const combinedSig = threshold.combine(keys.polynomial, flattenSigsArray(partialSigs));
+      threshold.verify(keypair.publicKey, STATIC_MESSAGE, combinedSig);
+      expect(Buffer.from(combinedSig).toString('hex'))
+        .toBe(Buffer.from(threshold.sign(keypair.privateKey, STATIC_MESSAGE)).toString('hex'));
+
+      expect(() => threshold.splitPrivateKey(keypair.privateKey, 3, 4, STATIC_THRESHOLD_SEED)).toThrow();
+    });
+
+    it('should blind, unblind and verify a batch, reporting each item', () => {
+      const messages = ['one', 'two', 'three'].map((m) => Buffer.from(m, 'utf8'));
+      const lengths = new Uint32Array(messages.map((m) => m.length));
+      const concatenated = Buffer.concat(messages);
+
+      const blinded = threshold.batchBlind(concatenated, lengths, STATIC_BLINDING_SEED);
+      expect(blinded.messages.length).toBe(3 * 48);
+      expect(blinded.blindingFactors.length).toBe(3 * 32);
+
+      const blindSignatures = Buffer.concat([0, 1, 2].map((i) =>
+        threshold.signBlindedMessage(keypair.privateKey, blinded.messages.slice(i * 48, (i + 1) * 48))));
+      const signatures = threshold.batchUnblind(blindSignatures, blinded.blindingFactors);
+
+      const valid = threshold.batchVerify(keypair.publicKey, concatenated, lengths, signatures);
+      expect(Array.from(valid)).toEqual([0b111]);
+
+      // The first two signatures swapped: only the third still matches.
+      const swapped = Buffer.concat([signatures.slice(48, 96), signatures.slice(0, 48), signatures.slice(96)]);
+      const someValid = threshold.batchVerify(keypair.publicKey, concatenated, lengths, swapped);
+      expect(Array.from(someValid)).toEqual([0b100]);
+    });
   });
   
   describe('Threshold operations', () => {
@@ -161,6 +232,23 @@ describe('Blind Threshold BLS Static Tests', () => {
       const expectedCombinedSig = '2078af1b0b286de06d0ae63c152c04932cdc08a4a4b5ec3089149c173e11760a0f4c11ea54e92370661ea4524c95b100'
       expect(Buffer.from(combinedSig).toString('hex')).toBe(expectedCombinedSig);
     });
+
+    it('should inspect committee keys from the polynomial alone', () => {
+      expect(threshold.thresholdPublicKey(keys.polynomial)).toEqual(keys.thresholdPublicKey);
+
+      const publicShares = [];
+      for (let i = 0; i < n; i++) {
+        threshold.verifyShare(keys.polynomial, keys.getShare(i));
+
+        const publicShare = threshold.evaluatePolynomial(keys.polynomial, i);
+        expect(publicShare.length).toBe(100);
+        publicShares.push(publicShare);
+      }
+
+      // Any t public shares recover the polynomial; the rest are checked against it
+      expect(threshold.recoverPolynomial(t, flattenSigsArray(publicShares.slice(2)))).toEqual(keys.polynomial);
+      expect(threshold.recoverPolynomial(t, flattenSigsArray(publicShares))).toEqual(keys.polynomial);
+    });
   });
 });
 
@@ -109,6 +109,22 @@ describe('Invalid inputs', () => {
         threshold.combine(keys.polynomial, flattened.slice(0, -1))
       ).toThrow('expected a multiple of');
     });
+
+    it('verifyShare throws on a share the polynomial does not commit to', () => {
+      // The share index is the leading 4 bytes; hand share 0's scalar to share 1
+      const moved = Uint8Array.from(keys.getShare(0));
+      moved[0] = 1;
+      expect(() => threshold.verifyShare(keys.polynomial, moved)).toThrow('does not match the polynomial');
+    });
+
+    it('recoverPolynomial throws on a public share that is not on the polynomial', () => {
+      const other = threshold.thresholdKeygen(n, t, STATIC_SEED);
+      const publicShares = [0, 1, 2].map((i) => threshold.evaluatePolynomial(keys.polynomial, i));
+      publicShares.push(threshold.evaluatePolynomial(other.polynomial, 3));
+      const flattened = Uint8Array.from(publicShares.reduce((all, share) => [...all, ...share], []));
+      expect(() => threshold.recoverPolynomial(t, flattened)).toThrow('does not lie on the polynomial');
+      expect(() => threshold.recoverPolynomial(t, flattened.slice(0, -1))).toThrow('expected a multiple of');
+    });
   });
 
   // These arguments used to panic, which in wasm traps and leaves the instance
@@ -181,4 +197,32 @@ describe('Invalid inputs', () => {
       ).toThrow('could not unblind signature');
     });
   });
+
+  describe('batches', () => {
+    const keypair = threshold.keygen(STATIC_SEED);
+    const messages = Buffer.from('onetwo', 'utf8');
+    const signatures = Buffer.concat([
+      threshold.sign(keypair.privateKey, messages.slice(0, 3)),
+      threshold.sign(keypair.privateKey, messages.slice(3)),
+    ]);
+
+    it('batchVerify throws when the message lengths do not add up', () => {
+      expect(() =>
+        threshold.batchVerify(keypair.publicKey, messages, new Uint32Array([3, 2]), signatures)
+      ).toThrow('the message lengths do not add up');
+    });
+
+    it('batchVerify throws when the signatures do not match the messages', () => {
+      expect(() =>
+        threshold.batchVerify(keypair.publicKey, messages, new Uint32Array([6]), signatures)
+      ).toThrow('got 1 messages but 2 signatures');
+    });
+
+    it('batchUnblind throws when the blinding factors do not match the signatures', () => {
+      const blinded = threshold.batchBlind(messages, new Uint32Array([3, 3]), OTHER_SEED);
+      expect(() =>
+        threshold.batchUnblind(signatures, blinded.blindingFactors.slice(32))
+      ).toThrow('2 blinded signatures but 1 blinding factors');
+    });
+  });
 });
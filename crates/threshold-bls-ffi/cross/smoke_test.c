@@ -112,6 +112,33 @@ static const uint8_t THRESHOLD_PUBKEY[96] = {
     0x54, 0xd5, 0xc0, 0xb2, 0x6f, 0x3c, 0x20, 0xf0, 0xe5, 0x7a, 0x35, 0x81,
 };
 
+/*
+ * The same polynomial committed on G1, for the keys-on-G1 functions. The shares
+ * above are its shares too: a share is a scalar, the same under either scheme.
+ * Regenerate with `threshold_keygen_for::<G1SigScheme>` and the same seed.
+ */
+static const uint8_t G1_PUBLIC_POLY[8 + THRESHOLD * G1_PUBKEY_LEN] = {
+    0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xd9, 0x31, 0xf6, 0xea,
+    0x7f, 0x3b, 0xf8, 0x51, 0x11, 0xd1, 0xad, 0xf2, 0x8f, 0x9a, 0xb9, 0xbd,
+    0xc5, 0x4d, 0x05, 0x93, 0x4c, 0x9f, 0xb4, 0x53, 0xbc, 0x52, 0x25, 0xad,
+    0x60, 0x97, 0xfb, 0x3f, 0x1a, 0x95, 0x19, 0x7a, 0x0b, 0xcd, 0x42, 0x96,
+    0x5f, 0xf2, 0x69, 0x0f, 0x90, 0xcc, 0xe4, 0x80, 0xac, 0x84, 0x3e, 0x09,
+    0xfd, 0x6c, 0x26, 0x63, 0x54, 0xd3, 0x15, 0x67, 0x3c, 0x2e, 0x91, 0x36,
+    0x76, 0x43, 0x2c, 0xcf, 0xc7, 0xa2, 0xa7, 0xb6, 0xab, 0x86, 0x97, 0xe5,
+    0xb9, 0xac, 0xa4, 0x99, 0xbd, 0x4a, 0x4d, 0x82, 0x01, 0xc5, 0x6e, 0xa4,
+    0x74, 0x5e, 0x1a, 0x31, 0x35, 0x80, 0xee, 0x00, 0xb3, 0x79, 0x97, 0x11,
+    0x42, 0xa4, 0xc6, 0xc9, 0xda, 0x78, 0xb1, 0x34, 0xe5, 0x38, 0xd2, 0xb2,
+    0x8a, 0x27, 0x77, 0xbc, 0x3a, 0xf7, 0x88, 0x38, 0x34, 0x87, 0xdc, 0x06,
+    0x8f, 0xd1, 0x9b, 0xc0, 0xc6, 0x0c, 0xae, 0x1c, 0x6f, 0xd0, 0xca, 0x91,
+    0x19, 0x51, 0xd6, 0xe0, 0xac, 0x4e, 0x51, 0x81,
+};
+static const uint8_t G1_THRESHOLD_PUBKEY[G1_PUBKEY_LEN] = {
+    0xd9, 0x31, 0xf6, 0xea, 0x7f, 0x3b, 0xf8, 0x51, 0x11, 0xd1, 0xad, 0xf2,
+    0x8f, 0x9a, 0xb9, 0xbd, 0xc5, 0x4d, 0x05, 0x93, 0x4c, 0x9f, 0xb4, 0x53,
+    0xbc, 0x52, 0x25, 0xad, 0x60, 0x97, 0xfb, 0x3f, 0x1a, 0x95, 0x19, 0x7a,
+    0x0b, 0xcd, 0x42, 0x96, 0x5f, 0xf2, 0x69, 0x0f, 0x90, 0xcc, 0xe4, 0x80,
+};
+
 static const uint8_t *const SHARES[THRESHOLD] = {SHARE_0, SHARE_1, SHARE_2};
 
 static Buffer buf(const uint8_t *ptr, size_t len) {
@@ -347,6 +374,8 @@ static void null_arguments_are_rejected(void) {
 
     CHECK(public_key_ptr(NULL) == NULL);
     CHECK(private_key_ptr(NULL) == NULL);
+    CHECK(g1_public_key_ptr(NULL) == NULL);
+    CHECK(g1_private_key_ptr(NULL) == NULL);
 }
 
 /*
@@ -564,6 +593,392 @@ static void empty_polynomials_are_rejected(void) {
     free_vector(partial.ptr, partial.len);
 }
 
+/*
+ * The keys-on-G1 counterparts of plain_signing and blind_signing: g1_keygen,
+ * its accessors, g1_sign, g1_verify, g1_blind, g1_sign_blinded_message,
+ * g1_unblind, g1_destroy_keypair.
+ */
+static void g1_signing(void) {
+    Buffer seed = buf(SEED, sizeof SEED);
+    struct G1Keypair *keypair = NULL;
+    CHECK(g1_keygen(&seed, &keypair));
+    CHECK(keypair != NULL);
+
+    const PrivateKey *priv = g1_private_key_ptr(keypair);
+    const G1PublicKey *pub = g1_public_key_ptr(keypair);
+    CHECK(priv != NULL && pub != NULL);
+
+    Buffer message = buf(MESSAGE, sizeof MESSAGE);
+    Buffer signature;
+    CHECK(g1_sign(priv, &message, &signature));
+    CHECK(signature.len == G1_SIGNATURE_LEN);
+    CHECK(g1_verify(pub, &message, &signature));
+
+    Buffer user_seed = buf(USER_SEED, sizeof USER_SEED);
+    Buffer blinded;
+    BlindingFactor *blinding_factor = NULL;
+    CHECK(g1_blind(&message, &user_seed, &blinded, &blinding_factor));
+
+    Buffer blind_sig;
+    CHECK(g1_sign_blinded_message(priv, &blinded, &blind_sig));
+
+    Buffer unblinded;
+    CHECK(g1_unblind(&blind_sig, blinding_factor, &unblinded));
+    CHECK(g1_verify(pub, &message, &unblinded));
+
+    /* The G2 functions do not take a G1 signature. */
+    struct Keypair *g2_keypair = NULL;
+    CHECK(keygen(&seed, &g2_keypair));
+    CHECK(!verify(public_key_ptr(g2_keypair), &message, &signature));
+
+    free_vector(unblinded.ptr, unblinded.len);
+    free_vector(blind_sig.ptr, blind_sig.len);
+    free_vector(blinded.ptr, blinded.len);
+    free_vector(signature.ptr, signature.len);
+    destroy_token(blinding_factor);
+    destroy_keypair(g2_keypair);
+    g1_destroy_keypair(keypair);
+}
+
+/* The keys-on-G1 serialize/deserialize/destroy pairs, at the G1 lengths. */
+static void g1_serialization(void) {
+    Buffer seed = buf(SEED, sizeof SEED);
+    struct G1Keypair *keypair = NULL;
+    CHECK(g1_keygen(&seed, &keypair));
+
+    uint8_t *pub_bytes = NULL;
+    CHECK(g1_serialize_pubkey(g1_public_key_ptr(keypair), &pub_bytes));
+    Buffer pub_buf = buf(pub_bytes, G1_PUBKEY_LEN);
+    G1PublicKey *pub = NULL;
+    CHECK(g1_deserialize_pubkey(&pub_buf, &pub));
+
+    Buffer message = buf(MESSAGE, sizeof MESSAGE);
+    Buffer signature;
+    CHECK(g1_sign(g1_private_key_ptr(keypair), &message, &signature));
+    CHECK(g1_verify(pub, &message, &signature));
+
+    uint8_t *sig_bytes = NULL;
+    G1Signature *sig = NULL;
+    CHECK(g1_deserialize_sig(&signature, &sig));
+    CHECK(g1_serialize_sig(sig, &sig_bytes));
+    CHECK(memcmp(sig_bytes, signature.ptr, G1_SIGNATURE_LEN) == 0);
+
+    /* Neither scheme's deserializers read the other's encodings. */
+    PublicKey *g2_pub = NULL;
+    Signature *g2_sig = NULL;
+    CHECK(!deserialize_pubkey(&pub_buf, &g2_pub));
+    CHECK(!deserialize_sig(&signature, &g2_sig));
+
+    CHECK(!g1_deserialize_pubkey(NULL, &pub));
+    CHECK(!g1_deserialize_sig(NULL, &sig));
+    CHECK(!g1_serialize_pubkey(NULL, &pub_bytes));
+    CHECK(!g1_serialize_sig(NULL, &sig_bytes));
+
+    free_vector(sig_bytes, G1_SIGNATURE_LEN);
+    free_vector(pub_bytes, G1_PUBKEY_LEN);
+    free_vector(signature.ptr, signature.len);
+    g1_destroy_sig(sig);
+    g1_destroy_pubkey(pub);
+    g1_destroy_keypair(keypair);
+}
+
+/*
+ * threshold_signing and blind_threshold_signing under the keys-on-G1 scheme,
+ * with the same shares and the polynomial committed on G1. The partials are
+ * G1_PARTIAL_SIG_LENGTH bytes, which is what g1_combine chunks by.
+ */
+static void g1_threshold_signing(void) {
+    Buffer message = buf(MESSAGE, sizeof MESSAGE);
+    Buffer user_seed = buf(USER_SEED, sizeof USER_SEED);
+    Buffer polynomial = buf(G1_PUBLIC_POLY, sizeof G1_PUBLIC_POLY);
+
+    Buffer blinded;
+    BlindingFactor *blinding_factor = NULL;
+    CHECK(g1_blind(&message, &user_seed, &blinded, &blinding_factor));
+
+    uint8_t concatenated[THRESHOLD * G1_PARTIAL_SIG_LENGTH];
+    uint8_t blind_concatenated[THRESHOLD * G1_PARTIAL_SIG_LENGTH];
+    Buffer partials[THRESHOLD];
+    Buffer blind_partials[THRESHOLD];
+    for (size_t i = 0; i < THRESHOLD; i++) {
+        Buffer share = buf(SHARES[i], 36);
+        CHECK(g1_partial_sign(&share, &message, &partials[i]));
+        CHECK(partials[i].len == G1_PARTIAL_SIG_LENGTH);
+        CHECK(g1_partial_verify(&polynomial, &message, &partials[i]));
+        memcpy(concatenated + i * G1_PARTIAL_SIG_LENGTH, partials[i].ptr,
+               G1_PARTIAL_SIG_LENGTH);
+
+        CHECK(g1_partial_sign_blinded_message(&share, &blinded, &blind_partials[i]));
+        CHECK(g1_partial_verify_blind_signature(&polynomial, &blinded,
+                                                &blind_partials[i]));
+        memcpy(blind_concatenated + i * G1_PARTIAL_SIG_LENGTH,
+               blind_partials[i].ptr, G1_PARTIAL_SIG_LENGTH);
+    }
+
+    Buffer threshold_pubkey = buf(G1_THRESHOLD_PUBKEY, sizeof G1_THRESHOLD_PUBKEY);
+    G1PublicKey *pub = NULL;
+    CHECK(g1_deserialize_pubkey(&threshold_pubkey, &pub));
+
+    Buffer flattened = buf(concatenated, sizeof concatenated);
+    Buffer threshold_sig;
+    CHECK(g1_combine(&polynomial, &flattened, &threshold_sig));
+    CHECK(g1_verify(pub, &message, &threshold_sig));
+
+    Buffer blind_flattened = buf(blind_concatenated, sizeof blind_concatenated);
+    Buffer blinded_sig;
+    CHECK(g1_combine(&polynomial, &blind_flattened, &blinded_sig));
+    Buffer signature;
+    CHECK(g1_unblind(&blinded_sig, blinding_factor, &signature));
+    CHECK(g1_verify(pub, &message, &signature));
+
+    /* The G2 polynomial is not a G1 one, and G2 combine chunks differently. */
+    Buffer g2_polynomial = buf(PUBLIC_POLY, sizeof PUBLIC_POLY);
+    Buffer rejected;
+    CHECK(!g1_partial_verify(&g2_polynomial, &message, &partials[0]));
+    CHECK(!combine(&g2_polynomial, &flattened, &rejected));
+
+    free_vector(signature.ptr, signature.len);
+    free_vector(blinded_sig.ptr, blinded_sig.len);
+    free_vector(threshold_sig.ptr, threshold_sig.len);
+    for (size_t i = 0; i < THRESHOLD; i++) {
+        free_vector(partials[i].ptr, partials[i].len);
+        free_vector(blind_partials[i].ptr, blind_partials[i].len);
+    }
+    free_vector(blinded.ptr, blinded.len);
+    destroy_token(blinding_factor);
+    g1_destroy_pubkey(pub);
+}
+
+/*
+ * batch_blind, batch_unblind, batch_verify and batch_partial_verify: each item
+ * comes back at its own position, and a bad one clears its own bit.
+ */
+static void batch_signing(void) {
+    Buffer seed = buf(SEED, sizeof SEED);
+    struct Keypair *keypair = NULL;
+    CHECK(keygen(&seed, &keypair));
+
+    static const uint8_t ONE[] = "one", TWO[] = "two", THREE[] = "three";
+    Buffer messages[3] = {buf(ONE, 3), buf(TWO, 3), buf(THREE, 5)};
+    Buffer user_seed = buf(USER_SEED, sizeof USER_SEED);
+    Buffer blinded;
+    BlindingFactor *factors[3] = {NULL, NULL, NULL};
+    CHECK(batch_blind(messages, 3, &user_seed, &blinded, factors));
+    CHECK(blinded.len == 3 * SIGNATURE_LEN);
+
+    uint8_t blind_sigs[3 * SIGNATURE_LEN];
+    for (size_t i = 0; i < 3; i++) {
+        Buffer message = buf(blinded.ptr + i * SIGNATURE_LEN, SIGNATURE_LEN);
+        Buffer blind_sig;
+        CHECK(sign_blinded_message(private_key_ptr(keypair), &message, &blind_sig));
+        memcpy(blind_sigs + i * SIGNATURE_LEN, blind_sig.ptr, SIGNATURE_LEN);
+        free_vector(blind_sig.ptr, blind_sig.len);
+    }
+
+    Buffer blind_flattened = buf(blind_sigs, sizeof blind_sigs);
+    const BlindingFactor *const in_order[3] = {factors[0], factors[1], factors[2]};
+    Buffer signatures;
+    CHECK(batch_unblind(&blind_flattened, in_order, 3, &signatures));
+    CHECK(signatures.len == 3 * SIGNATURE_LEN);
+
+    Buffer valid;
+    CHECK(batch_verify(public_key_ptr(keypair), messages, 3, &signatures, &valid));
+    CHECK(valid.len == 1 && valid.ptr[0] == 0x07);
+    free_vector(valid.ptr, valid.len);
+
+    /* Verified against the wrong messages, only "three" still matches. */
+    Buffer shuffled[3] = {messages[1], messages[0], messages[2]};
+    CHECK(batch_verify(public_key_ptr(keypair), shuffled, 3, &signatures, &valid));
+    CHECK(valid.len == 1 && valid.ptr[0] == 0x04);
+    free_vector(valid.ptr, valid.len);
+
+    /* Two messages for three signatures is a mistake, not two results. */
+    CHECK(!batch_verify(public_key_ptr(keypair), messages, 2, &signatures, &valid));
+    CHECK(!batch_unblind(&blind_flattened, in_order, 2, &valid));
+
+    /* Partials from the shares, with the last one signed on another message. */
+    Buffer message = buf(MESSAGE, sizeof MESSAGE);
+    Buffer polynomial = buf(PUBLIC_POLY, sizeof PUBLIC_POLY);
+    uint8_t concatenated[THRESHOLD * PARTIAL_SIG_LENGTH];
+    for (size_t i = 0; i < THRESHOLD; i++) {
+        Buffer share = buf(SHARES[i], 36);
+        Buffer partial;
+        CHECK(partial_sign(&share, i + 1 < THRESHOLD ? &message : &messages[0],
+                           &partial));
+        memcpy(concatenated + i * PARTIAL_SIG_LENGTH, partial.ptr,
+               PARTIAL_SIG_LENGTH);
+        free_vector(partial.ptr, partial.len);
+    }
+    Buffer flattened = buf(concatenated, sizeof concatenated);
+    CHECK(batch_partial_verify(&polynomial, &message, &flattened, &valid));
+    CHECK(valid.len == 1 && valid.ptr[0] == 0x03);
+    free_vector(valid.ptr, valid.len);
+
+    Buffer misaligned = buf(concatenated, sizeof concatenated - 1);
+    CHECK(!batch_partial_verify(&polynomial, &message, &misaligned, &valid));
+
+    CHECK(!batch_verify(NULL, messages, 3, &signatures, &valid));
+    CHECK(!batch_verify(public_key_ptr(keypair), NULL, 3, &signatures, &valid));
+    CHECK(!batch_blind(messages, 3, &user_seed, &blinded, NULL));
+    const BlindingFactor *const with_null[3] = {factors[0], NULL, factors[2]};
+    CHECK(!batch_unblind(&blind_flattened, with_null, 3, &valid));
+
+    free_vector(signatures.ptr, signatures.len);
+    free_vector(blinded.ptr, blinded.len);
+    for (size_t i = 0; i < 3; i++) {
+        destroy_token(factors[i]);
+    }
+    destroy_keypair(keypair);
+}
+
+/* The same batches with keys on G1, sized by the G1_ lengths. */
+static void g1_batch_signing(void) {
+    Buffer seed = buf(SEED, sizeof SEED);
+    struct G1Keypair *keypair = NULL;
+    CHECK(g1_keygen(&seed, &keypair));
+
+    static const uint8_t ONE[] = "one", TWO[] = "two";
+    Buffer messages[2] = {buf(ONE, 3), buf(TWO, 3)};
+    Buffer user_seed = buf(USER_SEED, sizeof USER_SEED);
+    Buffer blinded;
+    BlindingFactor *factors[2] = {NULL, NULL};
+    CHECK(g1_batch_blind(messages, 2, &user_seed, &blinded, factors));
+    CHECK(blinded.len == 2 * G1_SIGNATURE_LEN);
+
+    uint8_t blind_sigs[2 * G1_SIGNATURE_LEN];
+    for (size_t i = 0; i < 2; i++) {
+        Buffer message = buf(blinded.ptr + i * G1_SIGNATURE_LEN, G1_SIGNATURE_LEN);
+        Buffer blind_sig;
+        CHECK(g1_sign_blinded_message(g1_private_key_ptr(keypair), &message,
+                                      &blind_sig));
+        memcpy(blind_sigs + i * G1_SIGNATURE_LEN, blind_sig.ptr, G1_SIGNATURE_LEN);
+        free_vector(blind_sig.ptr, blind_sig.len);
+    }
+
+    Buffer blind_flattened = buf(blind_sigs, sizeof blind_sigs);
+    const BlindingFactor *const in_order[2] = {factors[0], factors[1]};
+    Buffer signatures;
+    CHECK(g1_batch_unblind(&blind_flattened, in_order, 2, &signatures));
+
+    Buffer valid;
+    CHECK(g1_batch_verify(g1_public_key_ptr(keypair), messages, 2, &signatures,
+                          &valid));
+    CHECK(valid.len == 1 && valid.ptr[0] == 0x03);
+    free_vector(valid.ptr, valid.len);
+
+    /* G1 signatures are twice the G2 width, so the G2 batch miscounts them. */
+    CHECK(!batch_unblind(&blind_flattened, in_order, 2, &valid));
+
+    Buffer message = buf(MESSAGE, sizeof MESSAGE);
+    Buffer polynomial = buf(G1_PUBLIC_POLY, sizeof G1_PUBLIC_POLY);
+    uint8_t concatenated[THRESHOLD * G1_PARTIAL_SIG_LENGTH];
+    for (size_t i = 0; i < THRESHOLD; i++) {
+        Buffer share = buf(SHARES[i], 36);
+        Buffer partial;
+        CHECK(g1_partial_sign(&share, &message, &partial));
+        memcpy(concatenated + i * G1_PARTIAL_SIG_LENGTH, partial.ptr,
+               G1_PARTIAL_SIG_LENGTH);
+        free_vector(partial.ptr, partial.len);
+    }
+    Buffer flattened = buf(concatenated, sizeof concatenated);
+    CHECK(g1_batch_partial_verify(&polynomial, &message, &flattened, &valid));
+    CHECK(valid.len == 1 && valid.ptr[0] == 0x07);
+    free_vector(valid.ptr, valid.len);
+
+    free_vector(signatures.ptr, signatures.len);
+    free_vector(blinded.ptr, blinded.len);
+    for (size_t i = 0; i < 2; i++) {
+        destroy_token(factors[i]);
+    }
+    g1_destroy_keypair(keypair);
+}
+
+/*
+ * Stands in for a platform CSPRNG: an xorshift stream, predictable and fine for
+ * a test, that refuses once it has served `remaining` calls.
+ */
+struct TestSource {
+    uint64_t state;
+    int calls;
+    int remaining;
+};
+
+static bool fill_from_test_source(void *context, uint8_t *bytes, size_t len) {
+    struct TestSource *source = context;
+    if (source->remaining-- == 0) {
+        return false;
+    }
+    source->calls++;
+    for (size_t i = 0; i < len; i++) {
+        source->state ^= source->state << 13;
+        source->state ^= source->state >> 7;
+        source->state ^= source->state << 17;
+        bytes[i] = (uint8_t)source->state;
+    }
+    return true;
+}
+
+/* keygen_with_rng, blind_with_rng and batch_blind_with_rng. */
+static void caller_provided_randomness(void) {
+    struct TestSource source = {0x9e3779b97f4a7c15u, 0, -1};
+    struct Keypair *keypair = NULL;
+    CHECK(keygen_with_rng(fill_from_test_source, &source, &keypair));
+    CHECK(keypair != NULL && source.calls > 0);
+
+    Buffer message = buf(MESSAGE, sizeof MESSAGE);
+    Buffer blinded;
+    BlindingFactor *blinding_factor = NULL;
+    CHECK(blind_with_rng(&message, fill_from_test_source, &source, &blinded,
+                         &blinding_factor));
+
+    Buffer blind_sig;
+    CHECK(sign_blinded_message(private_key_ptr(keypair), &blinded, &blind_sig));
+    Buffer signature;
+    CHECK(unblind(&blind_sig, blinding_factor, &signature));
+    CHECK(verify(public_key_ptr(keypair), &message, &signature));
+
+    Buffer messages[2] = {message, message};
+    Buffer batch;
+    BlindingFactor *factors[2] = {NULL, NULL};
+    CHECK(batch_blind_with_rng(messages, 2, fill_from_test_source, &source,
+                               &batch, factors));
+    CHECK(batch.len == 2 * SIGNATURE_LEN);
+    /* Two draws of the same message blind it two different ways. */
+    CHECK(memcmp(batch.ptr, batch.ptr + SIGNATURE_LEN, SIGNATURE_LEN) != 0);
+
+    /* A callback that fails, at once or partway, writes nothing. */
+    for (int remaining = 0; remaining < 2; remaining++) {
+        struct TestSource failing = {0x9e3779b97f4a7c15u, 0, remaining};
+        struct Keypair *refused = NULL;
+        CHECK(!keygen_with_rng(fill_from_test_source, &failing, &refused));
+        CHECK(refused == NULL);
+
+        failing.remaining = remaining;
+        G1Keypair *g1_refused = NULL;
+        CHECK(!g1_keygen_with_rng(fill_from_test_source, &failing, &g1_refused));
+        CHECK(g1_refused == NULL);
+
+        failing.remaining = remaining;
+        BlindingFactor *refused_factor = NULL;
+        Buffer refused_blinded;
+        CHECK(!g1_blind_with_rng(&message, fill_from_test_source, &failing,
+                                 &refused_blinded, &refused_factor));
+        CHECK(refused_factor == NULL);
+    }
+
+    CHECK(!keygen_with_rng(NULL, &source, &keypair));
+    CHECK(!blind_with_rng(&message, NULL, &source, &blinded, &blinding_factor));
+
+    free_vector(batch.ptr, batch.len);
+    destroy_token(factors[0]);
+    destroy_token(factors[1]);
+    free_vector(signature.ptr, signature.len);
+    free_vector(blind_sig.ptr, blind_sig.len);
+    free_vector(blinded.ptr, blinded.len);
+    destroy_token(blinding_factor);
+    destroy_keypair(keypair);
+}
+
 /* Freeing NULL is a no-op, as it is for free(3). */
 static void destructors_accept_null(void) {
     destroy_token(NULL);
@@ -571,6 +986,9 @@ static void destructors_accept_null(void) {
     destroy_privkey(NULL);
     destroy_pubkey(NULL);
     destroy_sig(NULL);
+    g1_destroy_keypair(NULL);
+    g1_destroy_pubkey(NULL);
+    g1_destroy_sig(NULL);
     free_vector(NULL, 0);
     free_vector(NULL, PUBKEY_LEN);
 }
@@ -588,6 +1006,12 @@ int main(void) {
     misaligned_partials_are_rejected();
     short_seeds_are_rejected();
     empty_polynomials_are_rejected();
+    g1_signing();
+    g1_serialization();
+    g1_threshold_signing();
+    batch_signing();
+    g1_batch_signing();
+    caller_provided_randomness();
     destructors_accept_null();
 
     printf("threshold.h agrees with the library\n");
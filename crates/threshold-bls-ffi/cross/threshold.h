@@ -12,6 +12,8 @@
 typedef struct PrivateKey PrivateKey;
 typedef struct PublicKey PublicKey;
 typedef struct Signature Signature;
+typedef struct G1PublicKey G1PublicKey;
+typedef struct G1Signature G1Signature;
 typedef struct BlindingFactor BlindingFactor;
 
 
@@ -49,6 +51,46 @@ typedef struct BlindingFactor BlindingFactor;
  */
 #define PARTIAL_SIG_LENGTH ((VEC_LENGTH + SIGNATURE_LEN) + IDX_LEN)
 
+/**
+ * Bytes in one serialized share: its index followed by its private key. A
+ * split key's shares are written concatenated, each this long.
+ */
+#define SHARE_LEN (IDX_LEN + PRIVKEY_LEN)
+
+/**
+ * The largest group the bindings deal keys for. A threshold group is a
+ * handful of signers, so the bound is far above any real one; it is here so
+ * that a mistyped `n` fails rather than asking the allocator for gigabytes,
+ * which in wasm traps and poisons the instance.
+ */
+#define MAX_SHARES 1024
+
+/**
+ * Bytes in an OPRF output, a SHA-256 digest.
+ */
+#define OPRF_OUTPUT_LEN 32
+
+/**
+ * Bytes in a serialized public key of the keys-on-G1 scheme, compressed G1.
+ */
+#define G1_PUBKEY_LEN 48
+
+/**
+ * Bytes in a serialized signature of the keys-on-G1 scheme, compressed G2.
+ */
+#define G1_SIGNATURE_LEN 96
+
+/**
+ * Bytes in one serialized partial signature of the keys-on-G1 scheme, the
+ * chunk size `g1_combine` splits its flattened input into.
+ */
+#define G1_PARTIAL_SIG_LENGTH ((VEC_LENGTH + G1_SIGNATURE_LEN) + IDX_LEN)
+
+/**
+ * A BLS12-377 Keypair with its public key on G1
+ */
+typedef struct G1Keypair G1Keypair;
+
 /**
  * A BLS12-377 Keypair
  */
@@ -68,6 +110,17 @@ typedef struct Buffer {
   size_t len;
 } Buffer;
 
+/**
+ * Fills `len` bytes at `bytes` with random data, returning true if it did.
+ *
+ * `context` is passed back unchanged from the call that took the callback, for
+ * whatever state the caller's generator needs; the library never reads it. The
+ * callback is only invoked during that call, on the calling thread.
+ *
+ * Passing NULL where a callback is expected makes the call return false.
+ */
+typedef bool (*FillRandom)(void *context, uint8_t *bytes, size_t len);
+
 /**
  * Given a message and a seed, it will blind it and return the blinded message
  *
@@ -136,6 +189,52 @@ bool verify(const PublicKey *public_key,
             const struct Buffer *message,
             const struct Buffer *signature);
 
+/**
+ * Derives the OPRF output for an input from the group's signature on it, after verifying
+ * the signature. This is the finalize step every client has to perform identically, as
+ * defined in `threshold_bls::oprf`.
+ *
+ * * public_key: The public key the signature is checked against
+ * * input: The OPRF input, the message that was blinded and signed
+ * * signature: The unblinded signature on `input`
+ * * output: Pointer to the memory where the `OPRF_OUTPUT_LEN` byte output will be written to
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false. A signature that does not verify returns false.
+ */
+bool oprf_finalize(const PublicKey *public_key,
+                   const struct Buffer *input,
+                   const struct Buffer *signature,
+                   struct Buffer *output);
+
+/**
+ * Unblinds the group's blind signature on a blinded OPRF input and derives the output from
+ * it, as `unblind` followed by `oprf_finalize`.
+ *
+ * * public_key: The public key the unblinded signature is checked against
+ * * input: The OPRF input, the message that was blinded
+ * * blinded_signature: The blind signature on the blinded input
+ * * blinding_factor: The blinding_factor used to blind the input
+ * * output: Pointer to the memory where the `OPRF_OUTPUT_LEN` byte output will be written to
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false. A signature that does not verify once
+ * unblinded returns false.
+ */
+bool oprf_unblind_finalize(const PublicKey *public_key,
+                           const struct Buffer *input,
+                           const struct Buffer *blinded_signature,
+                           const BlindingFactor *blinding_factor,
+                           struct Buffer *output);
+
 /**
  * Signs the message with the provided private key and returns the signature
  *
@@ -256,6 +355,299 @@ bool partial_verify_blind_signature(const struct Buffer *polynomial,
  */
 bool combine(const struct Buffer *polynomial, const struct Buffer *signatures, struct Buffer *asig);
 
+/**
+ * Verifies signatures on many messages against one public key
+ *
+ * * public_key: The public key every signature is checked against
+ * * messages: An array of `count` messages
+ * * count: The number of messages, and of signatures
+ * * signatures: The signatures, concatenated in the order of `messages`, each `SIGNATURE_LEN`
+ *   bytes
+ * * valid_out: Pointer to the memory where the result bitmap will be written to, one bit per
+ *   item: bit `i % 8` of byte `i / 8` is set if item `i` verified. Free it with `free_vector`
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+ *   `count` is zero
+ * - If `signatures` is not exactly `count` signatures, the function will return false
+ *
+ * Returns true if successful, otherwise false. A signature that does not verify is reported in
+ * the bitmap, not by the return value.
+ */
+bool batch_verify(const PublicKey *public_key,
+                  const struct Buffer *messages,
+                  size_t count,
+                  const struct Buffer *signatures,
+                  struct Buffer *valid_out);
+
+/**
+ * Verifies many partial signatures on one message against the public polynomial
+ *
+ * The polynomial is deserialized and the message hashed to the curve once, rather than once
+ * per partial as repeated calls to `partial_verify` would.
+ *
+ * * polynomial: The serialized public commitment polynomial from key generation
+ * * message: The message every partial signature is on
+ * * signatures: The partial signatures, concatenated, each `PARTIAL_SIG_LENGTH` bytes
+ * * valid_out: Pointer to the memory where the result bitmap will be written to, one bit per
+ *   item: bit `i % 8` of byte `i / 8` is set if item `i` verified. Free it with `free_vector`
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If the polynomial cannot be deserialized, the function will return false
+ * - If the flattened buffer is not a whole number of `PARTIAL_SIG_LENGTH` chunks, the function
+ *   will return false
+ *
+ * Returns true if successful, otherwise false. A partial signature that does not verify is
+ * reported in the bitmap, not by the return value.
+ */
+bool batch_partial_verify(const struct Buffer *polynomial,
+                          const struct Buffer *message,
+                          const struct Buffer *signatures,
+                          struct Buffer *valid_out);
+
+/**
+ * Verifies many partial *blinded* signatures on one blinded message against the public
+ * polynomial
+ *
+ * * polynomial: The serialized public commitment polynomial from key generation
+ * * blinded_message: The blinded message every partial signature is on
+ * * signatures: The partial signatures, concatenated, each `PARTIAL_SIG_LENGTH` bytes
+ * * valid_out: Pointer to the memory where the result bitmap will be written to, one bit per
+ *   item: bit `i % 8` of byte `i / 8` is set if item `i` verified. Free it with `free_vector`
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If the polynomial cannot be deserialized, the function will return false
+ * - If the flattened buffer is not a whole number of `PARTIAL_SIG_LENGTH` chunks, the function
+ *   will return false
+ *
+ * Returns true if successful, otherwise false. A partial signature that does not verify is
+ * reported in the bitmap, not by the return value.
+ */
+bool batch_partial_verify_blind_signature(const struct Buffer *polynomial,
+                                          const struct Buffer *blinded_message,
+                                          const struct Buffer *signatures,
+                                          struct Buffer *valid_out);
+
+/**
+ * Blinds many messages with randomness from one seed
+ *
+ * Each message gets a blinding factor of its own, drawn in turn from the RNG the seed
+ * initializes, so the factors are as independent as those from separate calls to `blind` with
+ * separate seeds.
+ *
+ * * messages: An array of `count` cleartext messages
+ * * count: The number of messages
+ * * seed: A `SEED_LEN` byte seed for randomness
+ * * blinded_messages_out: Pointer to the memory where the blinded messages will be written to,
+ *   concatenated in the order of `messages`, each `SIGNATURE_LEN` bytes. Free it with
+ *   `free_vector`
+ * * blinding_factors_out: An array of `count` slots, each of which receives the blinding factor
+ *   of the message at the same position. Destroy each with `destroy_token`
+ *
+ * # Safety
+ * - If the same seed is used twice, the blinded results WILL be the same
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**. `blinding_factors_out` must have room for `count` pointers
+ * - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+ *   `count` is zero
+ * - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+ * - If any message cannot be blinded, the function will return false and write nothing
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool batch_blind(const struct Buffer *messages,
+                 size_t count,
+                 const struct Buffer *seed,
+                 struct Buffer *blinded_messages_out,
+                 BlindingFactor **blinding_factors_out);
+
+/**
+ * Unblinds many blind signatures, each with its own blinding factor
+ *
+ * * blinded_signatures: The blind signatures, concatenated, each `SIGNATURE_LEN` bytes
+ * * blinding_factors: An array of `count` blinding factors, the one at each position unblinding
+ *   the signature at the same position
+ * * count: The number of blinding factors, and of blind signatures
+ * * signatures_out: Pointer to the memory where the unblinded signatures will be written to,
+ *   concatenated in the same order, each `SIGNATURE_LEN` bytes. Free it with `free_vector`
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, including any of the `count` blinding factors, the function
+ *   will return false. `blinding_factors` may be NULL when `count` is zero
+ * - If `blinded_signatures` is not exactly `count` signatures, the function will return false
+ * - If any signature cannot be unblinded, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool batch_unblind(const struct Buffer *blinded_signatures,
+                   const BlindingFactor *const *blinding_factors,
+                   size_t count,
+                   struct Buffer *signatures_out);
+
+/**
+ * Splits an existing private key into `n` shares, any `t` of which sign for it
+ *
+ * * private_key: The private key to split
+ * * n: The number of shares, at most `MAX_SHARES`
+ * * t: The threshold, between 1 and `n`
+ * * seed: A `SEED_LEN` byte seed for the polynomial's random coefficients
+ * * shares_out: Pointer to the memory where the shares will be written to, concatenated in
+ *   index order, each `SHARE_LEN` bytes and accepted wherever a share is. Free it with
+ *   `free_vector`
+ * * polynomial_out: Pointer to the memory where the public polynomial will be written to.
+ *   Its public key is `private_key`'s own. Free it with `free_vector`
+ *
+ * # Safety
+ * - Whoever calls this holds the whole key, as they did before the split. Once the shares
+ *   are handed out, the private key should be destroyed
+ * - If the same seed is used twice, the shares WILL be the same
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If `n` is not between 1 and `MAX_SHARES`, or `t` not between 1 and `n`, the function
+ *   will return false
+ * - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool split_private_key(const PrivateKey *private_key,
+                       size_t n,
+                       size_t t,
+                       const struct Buffer *seed,
+                       struct Buffer *shares_out,
+                       struct Buffer *polynomial_out);
+
+/**
+ * Generates a single private key with randomness from `fill_random`.
+ *
+ * The return value should be destroyed with `destroy_keypair`.
+ *
+ * * fill_random: Fills a buffer with cryptographically secure random bytes
+ * * context: Passed to every call of `fill_random`, unread by the library
+ * * keypair: Pointer to the memory where the keypair handle will be written to
+ *
+ * # Safety
+ * - `fill_random` must be a cryptographically secure generator. The library cannot tell
+ *   predictable bytes from random ones, and a key drawn from them is predictable too
+ * - `fill_random` must write `len` bytes at the pointer it is given, or return false. It is
+ *   only called during this call, on the calling thread
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If `fill_random` returns false, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool keygen_with_rng(FillRandom fill_random, void *context, struct Keypair **keypair);
+
+/**
+ * Blinds a message with randomness from `fill_random`. See `blind`.
+ *
+ * * message: A cleartext message which you want to blind
+ * * fill_random: Fills a buffer with cryptographically secure random bytes
+ * * context: Passed to every call of `fill_random`, unread by the library
+ * * blinded_message_out : Pointer to the memory where the blinded message will be written to
+ * * blinding_factor_out : Pointer to the object storing the blinding factor
+ *
+ * # Safety
+ * - `fill_random` must be a cryptographically secure generator. Predictable blinding factors
+ *   let the signer link a blinded message to the message
+ * - `fill_random` must write `len` bytes at the pointer it is given, or return false. It is
+ *   only called during this call, on the calling thread
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If `fill_random` returns false, the function will return false and write nothing
+ * - If the message cannot be blinded, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool blind_with_rng(const struct Buffer *message,
+                    FillRandom fill_random,
+                    void *context,
+                    struct Buffer *blinded_message_out,
+                    BlindingFactor **blinding_factor_out);
+
+/**
+ * Blinds many messages with randomness from `fill_random`. See `batch_blind`.
+ *
+ * * messages: An array of `count` cleartext messages
+ * * count: The number of messages
+ * * fill_random: Fills a buffer with cryptographically secure random bytes
+ * * context: Passed to every call of `fill_random`, unread by the library
+ * * blinded_messages_out: Pointer to the memory where the blinded messages will be written to,
+ *   concatenated in the order of `messages`, each `SIGNATURE_LEN` bytes. Free it with
+ *   `free_vector`
+ * * blinding_factors_out: An array of `count` slots, each of which receives the blinding factor
+ *   of the message at the same position. Destroy each with `destroy_token`
+ *
+ * # Safety
+ * - `fill_random` must be a cryptographically secure generator. Predictable blinding factors
+ *   let the signer link a blinded message to the message
+ * - `fill_random` must write `len` bytes at the pointer it is given, or return false. It is
+ *   only called during this call, on the calling thread
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**. `blinding_factors_out` must have room for `count` pointers
+ * - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+ *   `count` is zero
+ * - If `fill_random` returns false, the function will return false and write nothing
+ * - If any message cannot be blinded, the function will return false and write nothing
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool batch_blind_with_rng(const struct Buffer *messages,
+                          size_t count,
+                          FillRandom fill_random,
+                          void *context,
+                          struct Buffer *blinded_messages_out,
+                          BlindingFactor **blinding_factors_out);
+
+/**
+ * Splits an existing private key into `n` shares with randomness from `fill_random`. See
+ * `split_private_key`.
+ *
+ * * private_key: The private key to split
+ * * n: The number of shares, at most `MAX_SHARES`
+ * * t: The threshold, between 1 and `n`
+ * * fill_random: Fills a buffer with cryptographically secure random bytes
+ * * context: Passed to every call of `fill_random`, unread by the library
+ * * shares_out: Pointer to the memory where the shares will be written to, each `SHARE_LEN`
+ *   bytes. Free it with `free_vector`
+ * * polynomial_out: Pointer to the memory where the public polynomial will be written to.
+ *   Free it with `free_vector`
+ *
+ * # Safety
+ * - `fill_random` must be a cryptographically secure generator. Predictable coefficients let
+ *   fewer than `t` shareholders recover the key
+ * - `fill_random` must write `len` bytes at the pointer it is given, or return false. It is
+ *   only called during this call, on the calling thread
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If `n` is not between 1 and `MAX_SHARES`, or `t` not between 1 and `n`, the function
+ *   will return false
+ * - If `fill_random` returns false, the function will return false and write nothing
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool split_private_key_with_rng(const PrivateKey *private_key,
+                                size_t n,
+                                size_t t,
+                                FillRandom fill_random,
+                                void *context,
+                                struct Buffer *shares_out,
+                                struct Buffer *polynomial_out);
+
 /**
  * Deserializes a public key from the provided buffer
  *
@@ -415,6 +807,531 @@ void destroy_pubkey(PublicKey *public_key);
  */
 void destroy_sig(Signature *signature);
 
+/**
+ * Blinds a message for signing under the keys-on-G1 scheme. See `blind`.
+ *
+ * The blinding factor is destroyed with `destroy_token`, and signatures on the
+ * blinded message are unblinded with `g1_unblind`.
+ *
+ * # Safety
+ * - If the same seed is used twice, the blinded result WILL be the same
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+ * - If the message cannot be blinded, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_blind(const struct Buffer *message,
+              const struct Buffer *seed,
+              struct Buffer *blinded_message_out,
+              BlindingFactor **blinding_factor_out);
+
+/**
+ * Unblinds a keys-on-G1 blind signature. See `unblind`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_unblind(const struct Buffer *blinded_signature,
+                const BlindingFactor *blinding_factor,
+                struct Buffer *unblinded_signature);
+
+/**
+ * Verifies a `G1_SIGNATURE_LEN` byte signature against a public key on G1. See `verify`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_verify(const G1PublicKey *public_key,
+               const struct Buffer *message,
+               const struct Buffer *signature);
+
+/**
+ * Derives the OPRF output from a keys-on-G1 signature. See `oprf_finalize`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_oprf_finalize(const G1PublicKey *public_key,
+                      const struct Buffer *input,
+                      const struct Buffer *signature,
+                      struct Buffer *output);
+
+/**
+ * Unblinds a keys-on-G1 blind signature and derives the OPRF output from it. See
+ * `oprf_unblind_finalize`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_oprf_unblind_finalize(const G1PublicKey *public_key,
+                              const struct Buffer *input,
+                              const struct Buffer *blinded_signature,
+                              const BlindingFactor *blinding_factor,
+                              struct Buffer *output);
+
+/**
+ * Signs the message under the keys-on-G1 scheme. See `sign`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_sign(const PrivateKey *private_key, const struct Buffer *message, struct Buffer *signature);
+
+/**
+ * Signs a message blinded with `g1_blind`. See `sign_blinded_message`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_sign_blinded_message(const PrivateKey *private_key,
+                             const struct Buffer *message,
+                             struct Buffer *signature);
+
+/**
+ * Partially signs the message under the keys-on-G1 scheme. See `partial_sign`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_partial_sign(const struct Buffer *share,
+                     const struct Buffer *message,
+                     struct Buffer *signature);
+
+/**
+ * Partially signs a message blinded with `g1_blind`. See `partial_sign_blinded_message`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_partial_sign_blinded_message(const struct Buffer *share,
+                                     const struct Buffer *blinded_message,
+                                     struct Buffer *signature);
+
+/**
+ * Verifies a keys-on-G1 partial signature against a polynomial committed on G1. See
+ * `partial_verify`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_partial_verify(const struct Buffer *polynomial,
+                       const struct Buffer *blinded_message,
+                       const struct Buffer *signature);
+
+/**
+ * Verifies a keys-on-G1 partial blind signature against a polynomial committed on G1. See
+ * `partial_verify_blind_signature`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_partial_verify_blind_signature(const struct Buffer *polynomial,
+                                       const struct Buffer *blinded_message,
+                                       const struct Buffer *signature);
+
+/**
+ * Combines keys-on-G1 partial signatures, each `G1_PARTIAL_SIG_LENGTH` bytes, into a
+ * threshold signature. See `combine`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If the polynomial cannot be deserialized, the function will return false
+ * - If the flattened buffer is not a whole number of `G1_PARTIAL_SIG_LENGTH` chunks, the
+ *   function will return false
+ * - This function does not check if the signatures are valid!
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_combine(const struct Buffer *polynomial,
+                const struct Buffer *signatures,
+                struct Buffer *asig);
+
+/**
+ * Verifies signatures on many messages against one public key on G1, each signature
+ * `G1_SIGNATURE_LEN` bytes. See `batch_verify`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+ *   `count` is zero
+ * - If `signatures` is not exactly `count` signatures, the function will return false
+ *
+ * Returns true if successful, otherwise false. A signature that does not verify is reported in
+ * the bitmap, not by the return value.
+ */
+bool g1_batch_verify(const G1PublicKey *public_key,
+                     const struct Buffer *messages,
+                     size_t count,
+                     const struct Buffer *signatures,
+                     struct Buffer *valid_out);
+
+/**
+ * Verifies many keys-on-G1 partial signatures on one message, each `G1_PARTIAL_SIG_LENGTH`
+ * bytes. See `batch_partial_verify`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If the polynomial cannot be deserialized, the function will return false
+ * - If the flattened buffer is not a whole number of `G1_PARTIAL_SIG_LENGTH` chunks, the
+ *   function will return false
+ *
+ * Returns true if successful, otherwise false. A partial signature that does not verify is
+ * reported in the bitmap, not by the return value.
+ */
+bool g1_batch_partial_verify(const struct Buffer *polynomial,
+                             const struct Buffer *message,
+                             const struct Buffer *signatures,
+                             struct Buffer *valid_out);
+
+/**
+ * Verifies many keys-on-G1 partial blinded signatures on one blinded message, each
+ * `G1_PARTIAL_SIG_LENGTH` bytes. See `batch_partial_verify_blind_signature`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If the polynomial cannot be deserialized, the function will return false
+ * - If the flattened buffer is not a whole number of `G1_PARTIAL_SIG_LENGTH` chunks, the
+ *   function will return false
+ *
+ * Returns true if successful, otherwise false. A partial signature that does not verify is
+ * reported in the bitmap, not by the return value.
+ */
+bool g1_batch_partial_verify_blind_signature(const struct Buffer *polynomial,
+                                             const struct Buffer *blinded_message,
+                                             const struct Buffer *signatures,
+                                             struct Buffer *valid_out);
+
+/**
+ * Blinds many messages for signing under the keys-on-G1 scheme, each blinded message
+ * `G1_SIGNATURE_LEN` bytes. See `batch_blind`.
+ *
+ * # Safety
+ * - If the same seed is used twice, the blinded results WILL be the same
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**. `blinding_factors_out` must have room for `count` pointers
+ * - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+ *   `count` is zero
+ * - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+ * - If any message cannot be blinded, the function will return false and write nothing
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_batch_blind(const struct Buffer *messages,
+                    size_t count,
+                    const struct Buffer *seed,
+                    struct Buffer *blinded_messages_out,
+                    BlindingFactor **blinding_factors_out);
+
+/**
+ * Unblinds many keys-on-G1 blind signatures, each `G1_SIGNATURE_LEN` bytes. See
+ * `batch_unblind`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, including any of the `count` blinding factors, the function
+ *   will return false. `blinding_factors` may be NULL when `count` is zero
+ * - If `blinded_signatures` is not exactly `count` signatures, the function will return false
+ * - If any signature cannot be unblinded, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_batch_unblind(const struct Buffer *blinded_signatures,
+                      const BlindingFactor *const *blinding_factors,
+                      size_t count,
+                      struct Buffer *signatures_out);
+
+/**
+ * Splits an existing private key into `n` shares under the keys-on-G1 scheme. See
+ * `split_private_key`.
+ *
+ * * shares_out: Each share is `SHARE_LEN` bytes, as under the other scheme
+ * * polynomial_out: The public polynomial, committed on G1
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If `n` is not between 1 and `MAX_SHARES`, or `t` not between 1 and `n`, the function
+ *   will return false
+ * - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_split_private_key(const PrivateKey *private_key,
+                          size_t n,
+                          size_t t,
+                          const struct Buffer *seed,
+                          struct Buffer *shares_out,
+                          struct Buffer *polynomial_out);
+
+/**
+ * Deserializes a public key on G1 from the provided buffer
+ *
+ * * pubkey_buf: A buffer of exactly `G1_PUBKEY_LEN` bytes
+ * * pubkey: Pointer to the memory where the public key handle will be written to
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If the buffer does not hold exactly `G1_PUBKEY_LEN` bytes, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_deserialize_pubkey(const struct Buffer *pubkey_buf, G1PublicKey **pubkey);
+
+/**
+ * Deserializes a signature on G2 from the provided buffer
+ *
+ * * sig_buf: A buffer of exactly `G1_SIGNATURE_LEN` bytes
+ * * sig: Pointer to the memory where the signature handle will be written to
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If the buffer does not hold exactly `G1_SIGNATURE_LEN` bytes, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_deserialize_sig(const struct Buffer *sig_buf, G1Signature **sig);
+
+/**
+ * Serializes a public key on G1 to the provided buffer, `G1_PUBKEY_LEN` bytes
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_serialize_pubkey(const G1PublicKey *pubkey, uint8_t **pubkey_buf);
+
+/**
+ * Serializes a signature on G2 to the provided buffer, `G1_SIGNATURE_LEN` bytes
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_serialize_sig(const G1Signature *sig, uint8_t **sig_buf);
+
+/**
+ * Frees the memory allocated for a public key on G1
+ *
+ * # Safety
+ *
+ * The pointer must be NULL, or come from `g1_deserialize_pubkey` and not have
+ * been freed already. In particular it must not come from `g1_public_key_ptr`,
+ * which borrows from a keypair instead of allocating. Freeing a pointer twice
+ * corrupts the heap; NULL does nothing.
+ */
+void g1_destroy_pubkey(G1PublicKey *public_key);
+
+/**
+ * Frees the memory allocated for a signature on G2
+ *
+ * # Safety
+ *
+ * The pointer must be NULL, or point to a valid instance of the data type that
+ * has not already been freed. Freeing a pointer twice corrupts the heap; NULL
+ * does nothing.
+ */
+void g1_destroy_sig(G1Signature *signature);
+
+/**
+ * Generates a single private key from the provided seed, with its public key on G1.
+ *
+ * The return value should be destroyed with `g1_destroy_keypair`.
+ *
+ * # Safety
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_keygen(const struct Buffer *seed, struct G1Keypair **keypair);
+
+/**
+ * Gets a pointer to the public key of the provided `G1Keypair`
+ *
+ * Borrowed from the keypair, as with `public_key_ptr`: it stays valid until
+ * `g1_destroy_keypair` and must never be passed to `g1_destroy_pubkey`.
+ *
+ * # Safety
+ * The provided pointer will be dereferenced, so there must be valid data beneath it.
+ * Returns NULL if a NULL keypair is passed.
+ */
+const G1PublicKey *g1_public_key_ptr(const struct G1Keypair *keypair);
+
+/**
+ * Gets a pointer to the private key of the provided `G1Keypair`
+ *
+ * Borrowed from the keypair, as with `private_key_ptr`: it stays valid until
+ * `g1_destroy_keypair` and must never be passed to `destroy_privkey`.
+ *
+ * # Safety
+ * The provided pointer will be dereferenced, so there must be valid data beneath it.
+ * Returns NULL if a NULL keypair is passed.
+ */
+const PrivateKey *g1_private_key_ptr(const struct G1Keypair *keypair);
+
+/**
+ * Frees the memory allocated for the keys-on-G1 keypair helper
+ *
+ * This also frees the keys behind `g1_public_key_ptr` and `g1_private_key_ptr`.
+ *
+ * # Safety
+ *
+ * The pointer must be NULL, or point to a valid instance of the data type that
+ * has not already been freed. Freeing a pointer twice corrupts the heap; NULL
+ * does nothing.
+ */
+void g1_destroy_keypair(struct G1Keypair *keypair);
+
+/**
+ * Generates a single private key with its public key on G1, with randomness from
+ * `fill_random`. See `keygen_with_rng`.
+ *
+ * The return value should be destroyed with `g1_destroy_keypair`.
+ *
+ * # Safety
+ * - `fill_random` must be a cryptographically secure generator, and must write `len` bytes at
+ *   the pointer it is given or return false
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If `fill_random` returns false, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_keygen_with_rng(FillRandom fill_random, void *context, struct G1Keypair **keypair);
+
+/**
+ * Blinds a message for signing under the keys-on-G1 scheme, with randomness from
+ * `fill_random`. See `blind_with_rng`.
+ *
+ * # Safety
+ * - `fill_random` must be a cryptographically secure generator, and must write `len` bytes at
+ *   the pointer it is given or return false
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If `fill_random` returns false, the function will return false and write nothing
+ * - If the message cannot be blinded, the function will return false
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_blind_with_rng(const struct Buffer *message,
+                       FillRandom fill_random,
+                       void *context,
+                       struct Buffer *blinded_message_out,
+                       BlindingFactor **blinding_factor_out);
+
+/**
+ * Blinds many messages for signing under the keys-on-G1 scheme, with randomness from
+ * `fill_random`. See `batch_blind_with_rng`.
+ *
+ * # Safety
+ * - `fill_random` must be a cryptographically secure generator, and must write `len` bytes at
+ *   the pointer it is given or return false
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**. `blinding_factors_out` must have room for `count` pointers
+ * - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+ *   `count` is zero
+ * - If `fill_random` returns false, the function will return false and write nothing
+ * - If any message cannot be blinded, the function will return false and write nothing
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_batch_blind_with_rng(const struct Buffer *messages,
+                             size_t count,
+                             FillRandom fill_random,
+                             void *context,
+                             struct Buffer *blinded_messages_out,
+                             BlindingFactor **blinding_factors_out);
+
+/**
+ * Splits an existing private key into `n` shares under the keys-on-G1 scheme, with
+ * randomness from `fill_random`. See `split_private_key_with_rng`.
+ *
+ * # Safety
+ * - `fill_random` must be a cryptographically secure generator, and must write `len` bytes at
+ *   the pointer it is given or return false
+ * - **This function will dereference the provided pointers. If any invalid pointers are passed
+ *   then the software will crash**.
+ * - If NULL pointers are passed, the function will return false
+ * - If `n` is not between 1 and `MAX_SHARES`, or `t` not between 1 and `n`, the function
+ *   will return false
+ * - If `fill_random` returns false, the function will return false and write nothing
+ *
+ * Returns true if successful, otherwise false.
+ */
+bool g1_split_private_key_with_rng(const PrivateKey *private_key,
+                                   size_t n,
+                                   size_t t,
+                                   FillRandom fill_random,
+                                   void *context,
+                                   struct Buffer *shares_out,
+                                   struct Buffer *polynomial_out);
+
 /**
  * Generates a single private key from the provided seed.
  *
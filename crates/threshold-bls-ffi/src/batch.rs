@@ -0,0 +1,284 @@
+//! The batch operations behind `ffi.rs` and `wasm.rs`.
+//!
+//! Each takes many items that share one input — a public key, a polynomial, a
+//! message, a seed — and decodes the shared input once, where a loop over the
+//! single-item entry points would decode it once per item. A shared input that
+//! is malformed fails the whole call. A verification reports each item on its
+//! own, so one bad signature clears its bit instead of hiding the others.
+//!
+//! Both surfaces report these errors their own way, `ffi` as `false` and `wasm`
+//! as a thrown message, so they are returned here as the message.
+use rand_core::RngCore;
+
+use threshold_bls::{
+    group::{Element, Point},
+    poly::Poly,
+    sig::Token,
+};
+
+use crate::*;
+
+type BatchResult<T> = std::result::Result<T, String>;
+
+/// Packs per-item results into a bitmap: item `i` is bit `i % 8` of byte
+/// `i / 8`, least significant first, and is set if the item verified. The
+/// unused high bits of the last byte are clear.
+pub(crate) fn bitmap(results: impl IntoIterator<Item = bool>) -> Vec<u8> {
+    let mut bits = Vec::new();
+    for (i, ok) in results.into_iter().enumerate() {
+        if i % 8 == 0 {
+            bits.push(0);
+        }
+        if ok {
+            bits[i / 8] |= 1 << (i % 8);
+        }
+    }
+    bits
+}
+
+/// Splits items the caller concatenated, each `len` bytes. As in `combine`, a
+/// remainder means the concatenation was wrong and every boundary after the
+/// mistake is cut in the wrong place, so it is refused rather than truncated.
+fn split<'a>(items: &'a [u8], len: usize, what: &str) -> BatchResult<Vec<&'a [u8]>> {
+    if !items.len().is_multiple_of(len) {
+        return Err(format!(
+            "expected a multiple of {} bytes, one per {}, got {}",
+            len,
+            what,
+            items.len()
+        ));
+    }
+
+    Ok(items.chunks(len).collect())
+}
+
+/// Verifies `signatures[i]` on `messages[i]` for every `i`, all under `public`.
+pub(crate) fn verify<S: BindingScheme>(
+    public: &S::Public,
+    messages: &[&[u8]],
+    signatures: &[u8],
+) -> BatchResult<Vec<u8>> {
+    let signatures = split(signatures, S::SIGNATURE_LENGTH, "signature")?;
+    if signatures.len() != messages.len() {
+        return Err(format!(
+            "got {} messages but {} signatures",
+            messages.len(),
+            signatures.len()
+        ));
+    }
+
+    Ok(bitmap(messages.iter().zip(signatures).map(
+        |(message, signature)| S::verify(public, message, signature).is_ok(),
+    )))
+}
+
+/// Verifies partial signatures on one message against the public polynomial.
+///
+/// The message is hashed to the curve once, and each partial is checked against
+/// that point as if it were a blinded message. This is the same pairing check
+/// `partial_verify` makes after hashing, without repeating try-and-increment
+/// for every signer.
+pub(crate) fn partial_verify<S: BindingScheme>(
+    polynomial: &Poly<S::Public>,
+    message: &[u8],
+    partials: &[u8],
+) -> BatchResult<Vec<u8>> {
+    let mut point = S::Signature::zero();
+    point
+        .map(message)
+        .map_err(|err| format!("could not hash message to the curve: {:?}", err))?;
+    let point = bincode::serialize(&point)
+        .map_err(|err| format!("could not serialize message point: {}", err))?;
+
+    partial_verify_blind_signature::<S>(polynomial, &point, partials)
+}
+
+/// Verifies partial blind signatures on one blinded message against the public
+/// polynomial.
+pub(crate) fn partial_verify_blind_signature<S: BindingScheme>(
+    polynomial: &Poly<S::Public>,
+    blinded_message: &[u8],
+    partials: &[u8],
+) -> BatchResult<Vec<u8>> {
+    let partials = split(partials, S::PARTIAL_SIG_LENGTH, "partial signature")?;
+
+    Ok(bitmap(partials.into_iter().map(|partial| {
+        S::verify_blind_partial(polynomial, blinded_message, partial).is_ok()
+    })))
+}
+
+/// Blinds every message, drawing a fresh blinding factor for each from the one
+/// RNG. Returns the blinded messages concatenated, `S::SIGNATURE_LENGTH` bytes
+/// each, and the blinding factors in the same order.
+pub(crate) fn blind<S: BindingScheme>(
+    messages: &[&[u8]],
+    rng: &mut impl RngCore,
+) -> BatchResult<(Vec<u8>, Vec<Token<PrivateKey>>)> {
+    let mut blinded = Vec::with_capacity(messages.len() * S::SIGNATURE_LENGTH);
+    let mut blinding_factors = Vec::with_capacity(messages.len());
+    for message in messages {
+        let (blinding_factor, blinded_message) = S::blind_msg(message, rng)
+            .map_err(|err| format!("could not blind message: {}", err))?;
+        blinded.extend(blinded_message);
+        blinding_factors.push(blinding_factor);
+    }
+
+    Ok((blinded, blinding_factors))
+}
+
+/// Unblinds concatenated blind signatures, each with the blinding factor at the
+/// same position, and returns the signatures concatenated in that order.
+pub(crate) fn unblind<S: BindingScheme>(
+    blinded_signatures: &[u8],
+    blinding_factors: &[&Token<PrivateKey>],
+) -> BatchResult<Vec<u8>> {
+    let blinded_signatures = split(blinded_signatures, S::SIGNATURE_LENGTH, "blinded signature")?;
+    if blinded_signatures.len() != blinding_factors.len() {
+        return Err(format!(
+            "got {} blinded signatures but {} blinding factors",
+            blinded_signatures.len(),
+            blinding_factors.len()
+        ));
+    }
+
+    let mut signatures = Vec::with_capacity(blinded_signatures.len() * S::SIGNATURE_LENGTH);
+    for (i, (blinded_signature, blinding_factor)) in blinded_signatures
+        .into_iter()
+        .zip(blinding_factors)
+        .enumerate()
+    {
+        let signature = S::unblind_sig(blinding_factor, blinded_signature)
+            .map_err(|err| format!("could not unblind signature {}: {}", i, err))?;
+        signatures.extend(signature);
+    }
+
+    Ok(signatures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaChaRng;
+    use rand_core::SeedableRng;
+    use threshold_bls::{poly::Idx, sig::Share};
+
+    #[test]
+    fn the_bitmap_is_least_significant_bit_first() {
+        assert!(bitmap([]).is_empty());
+        assert_eq!(bitmap([true]), [0b1]);
+        assert_eq!(bitmap([false, true, true]), [0b110]);
+
+        // The ninth item starts a second byte, whose other bits stay clear.
+        let mut nine = [true; 9];
+        nine[3] = false;
+        assert_eq!(bitmap(nine), [0b1111_0111, 0b1]);
+    }
+
+    #[test]
+    fn verify_reports_each_signature_on_its_own() {
+        verify_reports_each_signature::<SigScheme>();
+        verify_reports_each_signature::<G1SigScheme>();
+    }
+
+    fn verify_reports_each_signature<S: BindingScheme>() {
+        let mut rng = ChaChaRng::from_seed([7; SEED_LEN]);
+        let (private, public) = S::keypair(&mut rng);
+        let messages: Vec<&[u8]> = vec![b"one", b"two", b"three"];
+
+        let mut signatures = messages
+            .iter()
+            .map(|message| S::sign(&private, message).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            verify::<S>(&public, &messages, &signatures.concat()).unwrap(),
+            [0b111]
+        );
+
+        // A signature on the wrong message, and bytes that are not a point at
+        // all, clear their own bits and nothing else.
+        signatures.swap(0, 1);
+        assert_eq!(
+            verify::<S>(&public, &messages, &signatures.concat()).unwrap(),
+            [0b100]
+        );
+        signatures[2] = vec![0xff; S::SIGNATURE_LENGTH];
+        assert_eq!(
+            verify::<S>(&public, &messages, &signatures.concat()).unwrap(),
+            [0b000]
+        );
+
+        let err = verify::<S>(&public, &messages[..2], &signatures.concat()).unwrap_err();
+        assert!(err.contains("got 2 messages but 3 signatures"), "{err}");
+        let err = verify::<S>(&public, &messages, &signatures.concat()[1..]).unwrap_err();
+        assert!(err.contains("expected a multiple of"), "{err}");
+    }
+
+    #[test]
+    fn partial_verify_agrees_with_the_single_item_check() {
+        partial_verify_agrees::<SigScheme>();
+        partial_verify_agrees::<G1SigScheme>();
+    }
+
+    fn partial_verify_agrees<S: BindingScheme>() {
+        let mut rng = ChaChaRng::from_seed([7; SEED_LEN]);
+        let private = Poly::<PrivateKey>::new_from(2, &mut rng);
+        let polynomial: Poly<S::Public> = private.commit();
+        let share = |index: Idx| Share {
+            index,
+            private: private.eval(index).value,
+        };
+        let message = b"hello";
+
+        let mut partials = (0..4)
+            .map(|index| S::partial_sign(&share(index), message).unwrap())
+            .collect::<Vec<_>>();
+        partials[2] = S::partial_sign(&share(2), b"goodbye").unwrap();
+
+        for (i, partial) in partials.iter().enumerate() {
+            assert_eq!(
+                S::partial_verify(&polynomial, message, partial).is_ok(),
+                i != 2
+            );
+        }
+        assert_eq!(
+            partial_verify::<S>(&polynomial, message, &partials.concat()).unwrap(),
+            [0b1011]
+        );
+    }
+
+    #[test]
+    fn blind_and_unblind_round_trip_in_order() {
+        let mut rng = ChaChaRng::from_seed([7; SEED_LEN]);
+        let (private, public) = SigScheme::keypair(&mut rng);
+        let messages: Vec<&[u8]> = vec![b"one", b"two", b"three"];
+
+        let (blinded, blinding_factors) = blind::<SigScheme>(&messages, &mut rng).unwrap();
+        assert_eq!(blinded.len(), messages.len() * SIGNATURE_LEN);
+
+        let blind_signatures = blinded
+            .chunks(SIGNATURE_LEN)
+            .map(|message| SigScheme::blind_sign(&private, message).unwrap())
+            .collect::<Vec<_>>()
+            .concat();
+        let factors = blinding_factors.iter().collect::<Vec<_>>();
+        let signatures = unblind::<SigScheme>(&blind_signatures, &factors).unwrap();
+        assert_eq!(
+            verify::<SigScheme>(&public, &messages, &signatures).unwrap(),
+            [0b111]
+        );
+
+        // Factors out of order unblind into signatures that do not verify.
+        let swapped = [factors[1], factors[0], factors[2]];
+        let signatures = unblind::<SigScheme>(&blind_signatures, &swapped).unwrap();
+        assert_eq!(
+            verify::<SigScheme>(&public, &messages, &signatures).unwrap(),
+            [0b100]
+        );
+
+        let err = unblind::<SigScheme>(&blind_signatures, &factors[..2]).unwrap_err();
+        assert!(
+            err.contains("3 blinded signatures but 2 blinding factors"),
+            "{err}"
+        );
+    }
+}
@@ -1,14 +1,14 @@
 //! # BLS12-377 FFI Bindings for Blind Threshold Signatures.
 use rand_chacha::ChaChaRng;
-use rand_core::{RngCore, SeedableRng};
+use rand_core::{CryptoRng, RngCore, SeedableRng};
+use std::ffi::c_void;
 
 use serde::{Serialize, de::DeserializeOwned};
 use threshold_bls::{
+    oprf,
     poly::Poly,
     serialization,
-    sig::{
-        BlindScheme, BlindThresholdScheme, Scheme, Share, SignatureScheme, ThresholdScheme, Token,
-    },
+    sig::{Share, Token},
 };
 
 use crate::*;
@@ -63,6 +63,39 @@ unsafe fn buffer_slice<'a>(buffer: *const Buffer) -> Option<&'a [u8]> {
     Some(unsafe { std::slice::from_raw_parts(buffer.ptr, buffer.len) })
 }
 
+/// Borrows an array of `count` values the caller supplied.
+///
+/// NULL is accepted for an empty array, as `buffer_slice` accepts it for an
+/// empty buffer, and refused otherwise.
+///
+/// # Safety
+/// A non-NULL `array` must point to `count` initialized values that stay valid
+/// for as long as the returned slice is used.
+unsafe fn array_slice<'a, T>(array: *const T, count: usize) -> Option<&'a [T]> {
+    if count == 0 {
+        return Some(&[]);
+    }
+    if array.is_null() {
+        return None;
+    }
+
+    Some(unsafe { std::slice::from_raw_parts(array, count) })
+}
+
+/// Borrows an array of `count` buffers the caller supplied, each through
+/// `buffer_slice`, so that one bad buffer refuses the whole array.
+///
+/// # Safety
+/// As `array_slice`, and each buffer as `buffer_slice`.
+unsafe fn buffer_slices<'a>(buffers: *const Buffer, count: usize) -> Option<Vec<&'a [u8]>> {
+    let buffers = unsafe { array_slice(buffers, count) }?;
+
+    buffers
+        .iter()
+        .map(|buffer| unsafe { buffer_slice(buffer) })
+        .collect()
+}
+
 /// Hands a Rust allocation to C, giving up ownership of it.
 ///
 /// The vector becomes a boxed slice first because `free_vector` rebuilds the
@@ -133,6 +166,29 @@ pub unsafe extern "C" fn blind(
     seed: *const Buffer,
     blinded_message_out: *mut Buffer,
     blinding_factor_out: *mut *mut BlindingFactor,
+) -> bool {
+    unsafe { blind_for::<SigScheme>(message, seed, blinded_message_out, blinding_factor_out) }
+}
+
+unsafe fn blind_for<S: BindingScheme>(
+    message: *const Buffer,
+    seed: *const Buffer,
+    blinded_message_out: *mut Buffer,
+    blinding_factor_out: *mut *mut BlindingFactor,
+) -> bool {
+    // convert the seed to randomness
+    let rng = unsafe { buffer_slice(seed) }.and_then(get_rng);
+
+    unsafe { blind_from::<S>(message, rng, blinded_message_out, blinding_factor_out) }
+}
+
+/// `blind` with the randomness already in hand, whichever source it came from.
+/// `None` is a source the caller got wrong, reported as `false`.
+unsafe fn blind_from<S: BindingScheme>(
+    message: *const Buffer,
+    rng: Option<impl FallibleRng>,
+    blinded_message_out: *mut Buffer,
+    blinding_factor_out: *mut *mut BlindingFactor,
 ) -> bool {
     if blinded_message_out.is_null() || blinding_factor_out.is_null() {
         return false;
@@ -140,20 +196,18 @@ pub unsafe extern "C" fn blind(
     let Some(message) = (unsafe { buffer_slice(message) }) else {
         return false;
     };
-    let Some(seed) = (unsafe { buffer_slice(seed) }) else {
-        return false;
-    };
-
-    // convert the seed to randomness
-    let Some(mut rng) = get_rng(seed) else {
+    let Some(mut rng) = rng else {
         return false;
     };
 
     // blind the message with this randomness
-    let (blinding_factor, blinded_message_bytes) = match SigScheme::blind_msg(message, &mut rng) {
+    let (blinding_factor, blinded_message_bytes) = match S::blind_msg(message, &mut rng) {
         Ok(blinded) => blinded,
         Err(_) => return false,
     };
+    if rng.failed() {
+        return false;
+    }
 
     unsafe { *blinded_message_out = into_buffer(blinded_message_bytes) };
     unsafe { *blinding_factor_out = Box::into_raw(Box::new(BlindingFactor(blinding_factor))) };
@@ -179,6 +233,14 @@ pub unsafe extern "C" fn unblind(
     blinded_signature: *const Buffer,
     blinding_factor: *const BlindingFactor,
     unblinded_signature: *mut Buffer,
+) -> bool {
+    unsafe { unblind_for::<SigScheme>(blinded_signature, blinding_factor, unblinded_signature) }
+}
+
+unsafe fn unblind_for<S: BindingScheme>(
+    blinded_signature: *const Buffer,
+    blinding_factor: *const BlindingFactor,
+    unblinded_signature: *mut Buffer,
 ) -> bool {
     if blinding_factor.is_null() || unblinded_signature.is_null() {
         return false;
@@ -189,7 +251,7 @@ pub unsafe extern "C" fn unblind(
 
     let blinding_factor = &unsafe { &*blinding_factor }.0;
 
-    let sig = match SigScheme::unblind_sig(blinding_factor, blinded_signature) {
+    let sig = match S::unblind_sig(blinding_factor, blinded_signature) {
         Ok(s) => s,
         Err(_) => return false,
     };
@@ -217,6 +279,14 @@ pub unsafe extern "C" fn verify(
     public_key: *const PublicKey,
     message: *const Buffer,
     signature: *const Buffer,
+) -> bool {
+    unsafe { verify_for::<SigScheme>(public_key, message, signature) }
+}
+
+unsafe fn verify_for<S: BindingScheme>(
+    public_key: *const S::Public,
+    message: *const Buffer,
+    signature: *const Buffer,
 ) -> bool {
     if public_key.is_null() {
         return false;
@@ -231,7 +301,125 @@ pub unsafe extern "C" fn verify(
     let public_key = unsafe { &*public_key };
 
     // checks the signature on the message hash
-    SigScheme::verify(public_key, message, signature).is_ok()
+    S::verify(public_key, message, signature).is_ok()
+}
+
+/// Derives the OPRF output for an input from the group's signature on it, after verifying
+/// the signature. This is the finalize step every client has to perform identically, as
+/// defined in `threshold_bls::oprf`.
+///
+/// * public_key: The public key the signature is checked against
+/// * input: The OPRF input, the message that was blinded and signed
+/// * signature: The unblinded signature on `input`
+/// * output: Pointer to the memory where the `OPRF_OUTPUT_LEN` byte output will be written to
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false. A signature that does not verify returns false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn oprf_finalize(
+    public_key: *const PublicKey,
+    input: *const Buffer,
+    signature: *const Buffer,
+    output: *mut Buffer,
+) -> bool {
+    unsafe { oprf_finalize_for::<SigScheme>(public_key, input, signature, output) }
+}
+
+unsafe fn oprf_finalize_for<S: BindingScheme>(
+    public_key: *const S::Public,
+    input: *const Buffer,
+    signature: *const Buffer,
+    output: *mut Buffer,
+) -> bool {
+    if public_key.is_null() || output.is_null() {
+        return false;
+    }
+    let Some(input) = (unsafe { buffer_slice(input) }) else {
+        return false;
+    };
+    let Some(signature) = (unsafe { buffer_slice(signature) }) else {
+        return false;
+    };
+
+    let public_key = unsafe { &*public_key };
+
+    let Ok(bytes) = oprf::finalize::<S>(public_key, input, signature) else {
+        return false;
+    };
+
+    unsafe { *output = into_buffer(bytes.to_vec()) };
+
+    true
+}
+
+/// Unblinds the group's blind signature on a blinded OPRF input and derives the output from
+/// it, as `unblind` followed by `oprf_finalize`.
+///
+/// * public_key: The public key the unblinded signature is checked against
+/// * input: The OPRF input, the message that was blinded
+/// * blinded_signature: The blind signature on the blinded input
+/// * blinding_factor: The blinding_factor used to blind the input
+/// * output: Pointer to the memory where the `OPRF_OUTPUT_LEN` byte output will be written to
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false. A signature that does not verify once
+/// unblinded returns false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn oprf_unblind_finalize(
+    public_key: *const PublicKey,
+    input: *const Buffer,
+    blinded_signature: *const Buffer,
+    blinding_factor: *const BlindingFactor,
+    output: *mut Buffer,
+) -> bool {
+    unsafe {
+        oprf_unblind_finalize_for::<SigScheme>(
+            public_key,
+            input,
+            blinded_signature,
+            blinding_factor,
+            output,
+        )
+    }
+}
+
+unsafe fn oprf_unblind_finalize_for<S: BindingScheme>(
+    public_key: *const S::Public,
+    input: *const Buffer,
+    blinded_signature: *const Buffer,
+    blinding_factor: *const BlindingFactor,
+    output: *mut Buffer,
+) -> bool {
+    if public_key.is_null() || blinding_factor.is_null() || output.is_null() {
+        return false;
+    }
+    let Some(input) = (unsafe { buffer_slice(input) }) else {
+        return false;
+    };
+    let Some(blinded_signature) = (unsafe { buffer_slice(blinded_signature) }) else {
+        return false;
+    };
+
+    let public_key = unsafe { &*public_key };
+    let blinding_factor = &unsafe { &*blinding_factor }.0;
+
+    let Ok(bytes) =
+        oprf::unblind_finalize::<S>(public_key, blinding_factor, input, blinded_signature)
+    else {
+        return false;
+    };
+
+    unsafe { *output = into_buffer(bytes.to_vec()) };
+
+    true
 }
 
 ///////////////////////////////////////////////////////////////////////////
@@ -251,6 +439,14 @@ pub unsafe extern "C" fn sign(
     private_key: *const PrivateKey,
     message: *const Buffer,
     signature: *mut Buffer,
+) -> bool {
+    unsafe { sign_for::<SigScheme>(private_key, message, signature) }
+}
+
+unsafe fn sign_for<S: BindingScheme>(
+    private_key: *const PrivateKey,
+    message: *const Buffer,
+    signature: *mut Buffer,
 ) -> bool {
     if private_key.is_null() || signature.is_null() {
         return false;
@@ -261,7 +457,7 @@ pub unsafe extern "C" fn sign(
 
     let private_key = unsafe { &*private_key };
 
-    let sig = match SigScheme::sign(private_key, message) {
+    let sig = match S::sign(private_key, message) {
         Ok(s) => s,
         Err(_) => return false,
     };
@@ -284,6 +480,14 @@ pub unsafe extern "C" fn sign_blinded_message(
     private_key: *const PrivateKey,
     message: *const Buffer,
     signature: *mut Buffer,
+) -> bool {
+    unsafe { sign_blinded_message_for::<SigScheme>(private_key, message, signature) }
+}
+
+unsafe fn sign_blinded_message_for<S: BindingScheme>(
+    private_key: *const PrivateKey,
+    message: *const Buffer,
+    signature: *mut Buffer,
 ) -> bool {
     if private_key.is_null() || signature.is_null() {
         return false;
@@ -294,7 +498,7 @@ pub unsafe extern "C" fn sign_blinded_message(
 
     let private_key = unsafe { &*private_key };
 
-    let sig = match SigScheme::blind_sign(private_key, message) {
+    let sig = match S::blind_sign(private_key, message) {
         Ok(s) => s,
         Err(_) => return false,
     };
@@ -321,6 +525,14 @@ pub unsafe extern "C" fn partial_sign(
     share: *const Buffer,
     message: *const Buffer,
     signature: *mut Buffer,
+) -> bool {
+    unsafe { partial_sign_for::<SigScheme>(share, message, signature) }
+}
+
+unsafe fn partial_sign_for<S: BindingScheme>(
+    share: *const Buffer,
+    message: *const Buffer,
+    signature: *mut Buffer,
 ) -> bool {
     if signature.is_null() {
         return false;
@@ -336,7 +548,7 @@ pub unsafe extern "C" fn partial_sign(
         Ok(s) => s,
         Err(_) => return false,
     };
-    let sig = match SigScheme::partial_sign(&share, message) {
+    let sig = match S::partial_sign(&share, message) {
         Ok(s) => s,
         Err(_) => return false,
     };
@@ -363,6 +575,14 @@ pub unsafe extern "C" fn partial_sign_blinded_message(
     share: *const Buffer,
     blinded_message: *const Buffer,
     signature: *mut Buffer,
+) -> bool {
+    unsafe { partial_sign_blinded_message_for::<SigScheme>(share, blinded_message, signature) }
+}
+
+unsafe fn partial_sign_blinded_message_for<S: BindingScheme>(
+    share: *const Buffer,
+    blinded_message: *const Buffer,
+    signature: *mut Buffer,
 ) -> bool {
     if signature.is_null() {
         return false;
@@ -378,7 +598,7 @@ pub unsafe extern "C" fn partial_sign_blinded_message(
         Ok(s) => s,
         Err(_) => return false,
     };
-    let sig = match SigScheme::sign_blind_partial(&share, blinded_message) {
+    let sig = match S::sign_blind_partial(&share, blinded_message) {
         Ok(s) => s,
         Err(_) => return false,
     };
@@ -409,6 +629,14 @@ pub unsafe extern "C" fn partial_verify(
     polynomial: *const Buffer,
     blinded_message: *const Buffer,
     signature: *const Buffer,
+) -> bool {
+    unsafe { partial_verify_for::<SigScheme>(polynomial, blinded_message, signature) }
+}
+
+unsafe fn partial_verify_for<S: BindingScheme>(
+    polynomial: *const Buffer,
+    blinded_message: *const Buffer,
+    signature: *const Buffer,
 ) -> bool {
     let Some(polynomial) = (unsafe { buffer_slice(polynomial) }) else {
         return false;
@@ -420,12 +648,12 @@ pub unsafe extern "C" fn partial_verify(
         return false;
     };
 
-    let polynomial: Poly<PublicKey> = match serialization::deserialize(polynomial) {
+    let polynomial: Poly<S::Public> = match serialization::deserialize(polynomial) {
         Ok(p) => p,
         Err(_) => return false,
     };
 
-    SigScheme::partial_verify(&polynomial, blinded_message, signature).is_ok()
+    S::partial_verify(&polynomial, blinded_message, signature).is_ok()
 }
 
 /// Verifies a partial *blinded* signature against the public key corresponding to the secret shared
@@ -445,6 +673,16 @@ pub unsafe extern "C" fn partial_verify_blind_signature(
     polynomial: *const Buffer,
     blinded_message: *const Buffer,
     signature: *const Buffer,
+) -> bool {
+    unsafe {
+        partial_verify_blind_signature_for::<SigScheme>(polynomial, blinded_message, signature)
+    }
+}
+
+unsafe fn partial_verify_blind_signature_for<S: BindingScheme>(
+    polynomial: *const Buffer,
+    blinded_message: *const Buffer,
+    signature: *const Buffer,
 ) -> bool {
     let Some(polynomial) = (unsafe { buffer_slice(polynomial) }) else {
         return false;
@@ -456,12 +694,12 @@ pub unsafe extern "C" fn partial_verify_blind_signature(
         return false;
     };
 
-    let polynomial: Poly<PublicKey> = match serialization::deserialize(polynomial) {
+    let polynomial: Poly<S::Public> = match serialization::deserialize(polynomial) {
         Ok(p) => p,
         Err(_) => return false,
     };
 
-    SigScheme::verify_blind_partial(&polynomial, blinded_message, signature).is_ok()
+    S::verify_blind_partial(&polynomial, blinded_message, signature).is_ok()
 }
 
 /// Combines a flattened vector of partial signatures to a single threshold signature
@@ -487,6 +725,14 @@ pub unsafe extern "C" fn combine(
     polynomial: *const Buffer,
     signatures: *const Buffer,
     asig: *mut Buffer,
+) -> bool {
+    unsafe { combine_for::<SigScheme>(polynomial, signatures, asig) }
+}
+
+unsafe fn combine_for<S: BindingScheme>(
+    polynomial: *const Buffer,
+    signatures: *const Buffer,
+    asig: *mut Buffer,
 ) -> bool {
     if asig.is_null() {
         return false;
@@ -498,7 +744,7 @@ pub unsafe extern "C" fn combine(
         return false;
     };
 
-    let polynomial: Poly<PublicKey> = match serialization::deserialize(polynomial) {
+    let polynomial: Poly<S::Public> = match serialization::deserialize(polynomial) {
         Ok(p) => p,
         Err(_) => return false,
     };
@@ -507,17 +753,17 @@ pub unsafe extern "C" fn combine(
     // them are implied by the length alone. A remainder means the flattening
     // was wrong, and every chunk after the first mistake is cut from the middle
     // of two partials.
-    if !signatures.len().is_multiple_of(PARTIAL_SIG_LENGTH) {
+    if !signatures.len().is_multiple_of(S::PARTIAL_SIG_LENGTH) {
         return false;
     }
 
     // split the flattened vector to a Vec<Vec<u8>> where each element is a serialized signature
     let sigs = signatures
-        .chunks(PARTIAL_SIG_LENGTH)
+        .chunks(S::PARTIAL_SIG_LENGTH)
         .map(|chunk| chunk.to_vec())
         .collect::<Vec<Vec<u8>>>();
 
-    let signature = match SigScheme::aggregate(&polynomial, &sigs) {
+    let signature = match S::aggregate(&polynomial, &sigs) {
         Ok(s) => s,
         Err(_) => return false,
     };
@@ -528,456 +774,1911 @@ pub unsafe extern "C" fn combine(
 }
 
 ///////////////////////////////////////////////////////////////////////////
-// Serialization
+// Batches
+//
+// Many items in one call, for callers that would otherwise cross the boundary
+// once per item and have the shared input deserialized every time. A
+// verification writes a bitmap with one bit per item: item `i` is bit `i % 8`
+// of byte `i / 8`, set if it verified. A malformed item clears its own bit; a
+// malformed shared input, or an item count that does not add up, returns false.
 ///////////////////////////////////////////////////////////////////////////
 
-#[unsafe(no_mangle)]
-/// Deserializes a public key from the provided buffer
+/// Verifies signatures on many messages against one public key
 ///
-/// * pubkey_buf: A buffer of exactly `PUBKEY_LEN` bytes
-/// * pubkey: Pointer to the memory where the public key handle will be written to
+/// * public_key: The public key every signature is checked against
+/// * messages: An array of `count` messages
+/// * count: The number of messages, and of signatures
+/// * signatures: The signatures, concatenated in the order of `messages`, each `SIGNATURE_LEN`
+///   bytes
+/// * valid_out: Pointer to the memory where the result bitmap will be written to, one bit per
+///   item: bit `i % 8` of byte `i / 8` is set if item `i` verified. Free it with `free_vector`
 ///
 /// # Safety
 /// - **This function will dereference the provided pointers. If any invalid pointers are passed
 ///   then the software will crash**.
-/// - If NULL pointers are passed, the function will return false
-/// - If the buffer does not hold exactly `PUBKEY_LEN` bytes, the function will return false
+/// - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+///   `count` is zero
+/// - If `signatures` is not exactly `count` signatures, the function will return false
 ///
-/// Returns true if successful, otherwise false.
-pub unsafe extern "C" fn deserialize_pubkey(
-    pubkey_buf: *const Buffer,
-    pubkey: *mut *mut PublicKey,
+/// Returns true if successful, otherwise false. A signature that does not verify is reported in
+/// the bitmap, not by the return value.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn batch_verify(
+    public_key: *const PublicKey,
+    messages: *const Buffer,
+    count: usize,
+    signatures: *const Buffer,
+    valid_out: *mut Buffer,
 ) -> bool {
-    unsafe { deserialize(pubkey_buf, PUBKEY_LEN, pubkey) }
+    unsafe { batch_verify_for::<SigScheme>(public_key, messages, count, signatures, valid_out) }
 }
 
-#[unsafe(no_mangle)]
-/// Deserializes a private key from the provided buffer
-///
-/// * privkey_buf: A buffer of exactly `PRIVKEY_LEN` bytes
-/// * privkey: Pointer to the memory where the private key handle will be written to
-///
-/// # Safety
-/// - **This function will dereference the provided pointers. If any invalid pointers are passed
-///   then the software will crash**.
-/// - If NULL pointers are passed, the function will return false
-/// - If the buffer does not hold exactly `PRIVKEY_LEN` bytes, the function will return false
-///
-/// Returns true if successful, otherwise false.
-pub unsafe extern "C" fn deserialize_privkey(
-    privkey_buf: *const Buffer,
-    privkey: *mut *mut PrivateKey,
+unsafe fn batch_verify_for<S: BindingScheme>(
+    public_key: *const S::Public,
+    messages: *const Buffer,
+    count: usize,
+    signatures: *const Buffer,
+    valid_out: *mut Buffer,
 ) -> bool {
-    unsafe { deserialize(privkey_buf, PRIVKEY_LEN, privkey) }
+    if public_key.is_null() || valid_out.is_null() {
+        return false;
+    }
+    let Some(messages) = (unsafe { buffer_slices(messages, count) }) else {
+        return false;
+    };
+    let Some(signatures) = (unsafe { buffer_slice(signatures) }) else {
+        return false;
+    };
+
+    let public_key = unsafe { &*public_key };
+
+    let valid = match batch::verify::<S>(public_key, &messages, signatures) {
+        Ok(valid) => valid,
+        Err(_) => return false,
+    };
+
+    unsafe { *valid_out = into_buffer(valid) };
+
+    true
 }
 
-#[unsafe(no_mangle)]
-/// Deserializes a signature from the provided buffer
-///
-/// * sig_buf: A buffer of exactly `SIGNATURE_LEN` bytes
-/// * sig: Pointer to the memory where the signature handle will be written to
+/// Verifies many partial signatures on one message against the public polynomial
 ///
-/// # Safety
-/// - **This function will dereference the provided pointers. If any invalid pointers are passed
-///   then the software will crash**.
-/// - If NULL pointers are passed, the function will return false
-/// - If the buffer does not hold exactly `SIGNATURE_LEN` bytes, the function will return false
+/// The polynomial is deserialized and the message hashed to the curve once, rather than once
+/// per partial as repeated calls to `partial_verify` would.
 ///
-/// Returns true if successful, otherwise false.
-pub unsafe extern "C" fn deserialize_sig(sig_buf: *const Buffer, sig: *mut *mut Signature) -> bool {
-    unsafe { deserialize(sig_buf, SIGNATURE_LEN, sig) }
-}
-
-#[unsafe(no_mangle)]
-/// Serializes a public key to the provided buffer
+/// * polynomial: The serialized public commitment polynomial from key generation
+/// * message: The message every partial signature is on
+/// * signatures: The partial signatures, concatenated, each `PARTIAL_SIG_LENGTH` bytes
+/// * valid_out: Pointer to the memory where the result bitmap will be written to, one bit per
+///   item: bit `i % 8` of byte `i / 8` is set if item `i` verified. Free it with `free_vector`
 ///
 /// # Safety
 /// - **This function will dereference the provided pointers. If any invalid pointers are passed
 ///   then the software will crash**.
 /// - If NULL pointers are passed, the function will return false
+/// - If the polynomial cannot be deserialized, the function will return false
+/// - If the flattened buffer is not a whole number of `PARTIAL_SIG_LENGTH` chunks, the function
+///   will return false
 ///
-/// Returns true if successful, otherwise false.
-pub unsafe extern "C" fn serialize_pubkey(
-    pubkey: *const PublicKey,
-    pubkey_buf: *mut *mut u8,
+/// Returns true if successful, otherwise false. A partial signature that does not verify is
+/// reported in the bitmap, not by the return value.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn batch_partial_verify(
+    polynomial: *const Buffer,
+    message: *const Buffer,
+    signatures: *const Buffer,
+    valid_out: *mut Buffer,
 ) -> bool {
-    unsafe { serialize(pubkey, PUBKEY_LEN, pubkey_buf) }
+    unsafe {
+        batch_partial_verify_for::<SigScheme>(polynomial, message, signatures, valid_out, false)
+    }
 }
 
-#[unsafe(no_mangle)]
-/// Serializes a private key to the provided buffer
+/// Verifies many partial *blinded* signatures on one blinded message against the public
+/// polynomial
+///
+/// * polynomial: The serialized public commitment polynomial from key generation
+/// * blinded_message: The blinded message every partial signature is on
+/// * signatures: The partial signatures, concatenated, each `PARTIAL_SIG_LENGTH` bytes
+/// * valid_out: Pointer to the memory where the result bitmap will be written to, one bit per
+///   item: bit `i % 8` of byte `i / 8` is set if item `i` verified. Free it with `free_vector`
 ///
 /// # Safety
 /// - **This function will dereference the provided pointers. If any invalid pointers are passed
 ///   then the software will crash**.
 /// - If NULL pointers are passed, the function will return false
+/// - If the polynomial cannot be deserialized, the function will return false
+/// - If the flattened buffer is not a whole number of `PARTIAL_SIG_LENGTH` chunks, the function
+///   will return false
 ///
-/// Returns true if successful, otherwise false.
-pub unsafe extern "C" fn serialize_privkey(
-    privkey: *const PrivateKey,
-    privkey_buf: *mut *mut u8,
-) -> bool {
-    unsafe { serialize(privkey, PRIVKEY_LEN, privkey_buf) }
-}
-
+/// Returns true if successful, otherwise false. A partial signature that does not verify is
+/// reported in the bitmap, not by the return value.
 #[unsafe(no_mangle)]
-/// Serializes a signature to the provided buffer
-///
-/// # Safety
-/// - **This function will dereference the provided pointers. If any invalid pointers are passed
-///   then the software will crash**.
-/// - If NULL pointers are passed, the function will return false
-///
-/// Returns true if successful, otherwise false.
-pub unsafe extern "C" fn serialize_sig(sig: *const Signature, sig_buf: *mut *mut u8) -> bool {
-    unsafe { serialize(sig, SIGNATURE_LEN, sig_buf) }
+pub unsafe extern "C" fn batch_partial_verify_blind_signature(
+    polynomial: *const Buffer,
+    blinded_message: *const Buffer,
+    signatures: *const Buffer,
+    valid_out: *mut Buffer,
+) -> bool {
+    unsafe {
+        batch_partial_verify_for::<SigScheme>(
+            polynomial,
+            blinded_message,
+            signatures,
+            valid_out,
+            true,
+        )
+    }
 }
 
-// The null and length checks live here rather than in the six exported wrappers
-// so that no call site can omit them; every wrapper documents both.
-//
-// `len` is the serialized size of `T`, and the buffer has to hold exactly that.
-// All three values are fixed-size, so a buffer of any other length is the
-// caller holding something else — and reading `len` bytes from it, which a
-// pointer without a length left no way to avoid, runs off the end of their
-// allocation.
-unsafe fn deserialize<T: DeserializeOwned>(
-    in_buf: *const Buffer,
-    len: usize,
-    out: *mut *mut T,
+unsafe fn batch_partial_verify_for<S: BindingScheme>(
+    polynomial: *const Buffer,
+    message: *const Buffer,
+    signatures: *const Buffer,
+    valid_out: *mut Buffer,
+    blinded: bool,
 ) -> bool {
-    if out.is_null() {
+    if valid_out.is_null() {
         return false;
     }
-
-    let Some(buf) = (unsafe { buffer_slice(in_buf) }) else {
+    let Some(polynomial) = (unsafe { buffer_slice(polynomial) }) else {
         return false;
     };
-    if buf.len() != len {
+    let Some(message) = (unsafe { buffer_slice(message) }) else {
         return false;
-    }
-
-    let obj = if let Ok(res) = serialization::deserialize(buf) {
-        res
-    } else {
+    };
+    let Some(signatures) = (unsafe { buffer_slice(signatures) }) else {
         return false;
     };
 
-    unsafe { *out = Box::into_raw(Box::new(obj)) };
-
-    true
-}
-
-// `len` is the size the caller will hand to `free_vector`, which it takes from
-// the constant in the header rather than from this function — nothing here
-// reports a length. Serializing to any other size would leave the caller freeing
-// an allocation of the wrong size, so refuse instead.
-unsafe fn serialize<T: Serialize>(in_obj: *const T, len: usize, out_bytes: *mut *mut u8) -> bool {
-    if in_obj.is_null() || out_bytes.is_null() {
-        return false;
-    }
+    let polynomial: Poly<S::Public> = match serialization::deserialize(polynomial) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
 
-    let obj = unsafe { &*in_obj };
-    let marshalled = if let Ok(res) = bincode::serialize(obj) {
-        res
+    let valid = if blinded {
+        batch::partial_verify_blind_signature::<S>(&polynomial, message, signatures)
     } else {
-        return false;
+        batch::partial_verify::<S>(&polynomial, message, signatures)
     };
-    if marshalled.len() != len {
+    let Ok(valid) = valid else {
         return false;
-    }
-
-    let (bytes, _) = into_raw_bytes(marshalled);
-    unsafe {
-        *out_bytes = bytes;
     };
 
+    unsafe { *valid_out = into_buffer(valid) };
+
     true
 }
 
-#[unsafe(no_mangle)]
-/// Frees the memory allocated for the blinding factor
-///
-/// # Safety
+/// Blinds many messages with randomness from one seed
 ///
-/// The pointer must be NULL, or point to a valid instance of the data type that
-/// has not already been freed. Freeing a pointer twice corrupts the heap; NULL
-/// does nothing.
-pub unsafe extern "C" fn destroy_token(token: *mut BlindingFactor) {
-    if token.is_null() {
-        return;
-    }
-    drop(unsafe { Box::from_raw(token) });
-}
-
-#[unsafe(no_mangle)]
-/// Frees the memory allocated for the keypair helper
+/// Each message gets a blinding factor of its own, drawn in turn from the RNG the seed
+/// initializes, so the factors are as independent as those from separate calls to `blind` with
+/// separate seeds.
 ///
-/// This also frees the keys behind `public_key_ptr` and `private_key_ptr`, which
-/// borrow from the keypair rather than owning their memory.
+/// * messages: An array of `count` cleartext messages
+/// * count: The number of messages
+/// * seed: A `SEED_LEN` byte seed for randomness
+/// * blinded_messages_out: Pointer to the memory where the blinded messages will be written to,
+///   concatenated in the order of `messages`, each `SIGNATURE_LEN` bytes. Free it with
+///   `free_vector`
+/// * blinding_factors_out: An array of `count` slots, each of which receives the blinding factor
+///   of the message at the same position. Destroy each with `destroy_token`
 ///
 /// # Safety
+/// - If the same seed is used twice, the blinded results WILL be the same
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**. `blinding_factors_out` must have room for `count` pointers
+/// - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+///   `count` is zero
+/// - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+/// - If any message cannot be blinded, the function will return false and write nothing
 ///
-/// The pointer must be NULL, or point to a valid instance of the data type that
-/// has not already been freed. Freeing a pointer twice corrupts the heap; NULL
-/// does nothing.
-pub unsafe extern "C" fn destroy_keypair(keypair: *mut Keypair) {
-    if keypair.is_null() {
-        return;
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn batch_blind(
+    messages: *const Buffer,
+    count: usize,
+    seed: *const Buffer,
+    blinded_messages_out: *mut Buffer,
+    blinding_factors_out: *mut *mut BlindingFactor,
+) -> bool {
+    unsafe {
+        batch_blind_for::<SigScheme>(
+            messages,
+            count,
+            seed,
+            blinded_messages_out,
+            blinding_factors_out,
+        )
     }
-    drop(unsafe { Box::from_raw(keypair) });
 }
 
-#[unsafe(no_mangle)]
-/// Frees the memory allocated for a private key
-///
-/// # Safety
-///
-/// The pointer must be NULL, or come from `deserialize_privkey` and not have
-/// been freed already. In particular it must not come from `private_key_ptr`,
-/// which borrows from a keypair instead of allocating. Freeing a pointer twice
-/// corrupts the heap; NULL does nothing.
-pub unsafe extern "C" fn destroy_privkey(private_key: *mut PrivateKey) {
-    if private_key.is_null() {
-        return;
+unsafe fn batch_blind_for<S: BindingScheme>(
+    messages: *const Buffer,
+    count: usize,
+    seed: *const Buffer,
+    blinded_messages_out: *mut Buffer,
+    blinding_factors_out: *mut *mut BlindingFactor,
+) -> bool {
+    let rng = unsafe { buffer_slice(seed) }.and_then(get_rng);
+
+    unsafe {
+        batch_blind_from::<S>(
+            messages,
+            count,
+            rng,
+            blinded_messages_out,
+            blinding_factors_out,
+        )
     }
-    drop(unsafe { Box::from_raw(private_key) });
 }
 
-#[unsafe(no_mangle)]
-/// Frees the memory allocated for a vector
-///
-/// Takes a const pointer so that a caller can pass `Buffer::ptr` straight back
-/// without casting away the qualifier to free its own buffer.
-///
-/// # Safety
-///
-/// The pointer must be NULL, or be one this library handed out together with
-/// the exact length it reported for it: the allocation is reconstructed from
-/// the two, so a different length frees a different allocation. Freeing a
-/// pointer twice corrupts the heap; NULL does nothing.
-pub unsafe extern "C" fn free_vector(bytes: *const u8, len: usize) {
-    if bytes.is_null() {
-        return;
+/// `batch_blind` with the randomness already in hand. See `blind_from`.
+unsafe fn batch_blind_from<S: BindingScheme>(
+    messages: *const Buffer,
+    count: usize,
+    rng: Option<impl FallibleRng>,
+    blinded_messages_out: *mut Buffer,
+    blinding_factors_out: *mut *mut BlindingFactor,
+) -> bool {
+    if blinded_messages_out.is_null() || blinding_factors_out.is_null() {
+        return false;
     }
-    // Reconstructed as the boxed slice `into_raw_bytes` handed out, whose
-    // allocation is exactly `len` bytes.
-    let bytes = std::ptr::slice_from_raw_parts_mut(bytes as *mut u8, len);
-    drop(unsafe { Box::from_raw(bytes) });
+    let Some(messages) = (unsafe { buffer_slices(messages, count) }) else {
+        return false;
+    };
+    let Some(mut rng) = rng else {
+        return false;
+    };
+
+    // Nothing is written until every message is blinded, so a failure leaves
+    // the caller no handles to destroy.
+    let (blinded_messages, blinding_factors) = match batch::blind::<S>(&messages, &mut rng) {
+        Ok(blinded) => blinded,
+        Err(_) => return false,
+    };
+    if rng.failed() {
+        return false;
+    }
+
+    unsafe { *blinded_messages_out = into_buffer(blinded_messages) };
+    for (i, blinding_factor) in blinding_factors.into_iter().enumerate() {
+        let handle = Box::into_raw(Box::new(BlindingFactor(blinding_factor)));
+        unsafe { *blinding_factors_out.add(i) = handle };
+    }
+
+    true
 }
 
-#[unsafe(no_mangle)]
-/// Frees the memory allocated for a public key
+/// Unblinds many blind signatures, each with its own blinding factor
+///
+/// * blinded_signatures: The blind signatures, concatenated, each `SIGNATURE_LEN` bytes
+/// * blinding_factors: An array of `count` blinding factors, the one at each position unblinding
+///   the signature at the same position
+/// * count: The number of blinding factors, and of blind signatures
+/// * signatures_out: Pointer to the memory where the unblinded signatures will be written to,
+///   concatenated in the same order, each `SIGNATURE_LEN` bytes. Free it with `free_vector`
 ///
 /// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, including any of the `count` blinding factors, the function
+///   will return false. `blinding_factors` may be NULL when `count` is zero
+/// - If `blinded_signatures` is not exactly `count` signatures, the function will return false
+/// - If any signature cannot be unblinded, the function will return false
 ///
-/// The pointer must be NULL, or come from `deserialize_pubkey` and not have
-/// been freed already. In particular it must not come from `public_key_ptr`,
-/// which borrows from a keypair instead of allocating. Freeing a pointer twice
-/// corrupts the heap; NULL does nothing.
-pub unsafe extern "C" fn destroy_pubkey(public_key: *mut PublicKey) {
-    if public_key.is_null() {
-        return;
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn batch_unblind(
+    blinded_signatures: *const Buffer,
+    blinding_factors: *const *const BlindingFactor,
+    count: usize,
+    signatures_out: *mut Buffer,
+) -> bool {
+    unsafe {
+        batch_unblind_for::<SigScheme>(blinded_signatures, blinding_factors, count, signatures_out)
     }
-    drop(unsafe { Box::from_raw(public_key) });
 }
 
-#[unsafe(no_mangle)]
-/// Frees the memory allocated for a signature
-///
-/// # Safety
-///
-/// The pointer must be NULL, or point to a valid instance of the data type that
-/// has not already been freed. Freeing a pointer twice corrupts the heap; NULL
-/// does nothing.
-pub unsafe extern "C" fn destroy_sig(signature: *mut Signature) {
-    if signature.is_null() {
-        return;
+unsafe fn batch_unblind_for<S: BindingScheme>(
+    blinded_signatures: *const Buffer,
+    blinding_factors: *const *const BlindingFactor,
+    count: usize,
+    signatures_out: *mut Buffer,
+) -> bool {
+    if signatures_out.is_null() {
+        return false;
     }
-    drop(unsafe { Box::from_raw(signature) });
+    let Some(blinded_signatures) = (unsafe { buffer_slice(blinded_signatures) }) else {
+        return false;
+    };
+    let Some(blinding_factors) = (unsafe { array_slice(blinding_factors, count) }) else {
+        return false;
+    };
+    let Some(blinding_factors) = blinding_factors
+        .iter()
+        .map(|factor| unsafe { factor.as_ref() }.map(|factor| &factor.0))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+
+    let signatures = match batch::unblind::<S>(blinded_signatures, &blinding_factors) {
+        Ok(signatures) => signatures,
+        Err(_) => return false,
+    };
+
+    unsafe { *signatures_out = into_buffer(signatures) };
+
+    true
 }
 
 ///////////////////////////////////////////////////////////////////////////
-// Helpers
+// Splitting a key
 //
-// These should be exposed behind a helper module and should not be made part
-// of the public API
+// Turns a single signer's private key into a threshold committee that signs for
+// the same public key, so its users keep verifying against the key they have.
 ///////////////////////////////////////////////////////////////////////////
 
-/// Generates a t-of-n polynomial and private key shares.
-///
-/// WARNING: Trustful central keygen — intended only for local testing. Production
-/// deployments should use a DKG protocol so no single party ever holds the master
-/// secret.
-#[cfg(test)]
-fn threshold_keygen(n: usize, t: usize, seed: &[u8]) -> Keys {
-    let mut rng = get_rng(seed).expect("the tests seed this with at least SEED_LEN bytes");
-    let private = Poly::<PrivateKey>::new_from(t - 1, &mut rng);
-    let shares = (0..n)
-        .map(|i| private.eval(i as Index))
-        .map(|e| Share {
-            index: e.index,
-            private: e.value,
-        })
-        .collect();
-    let polynomial: Poly<PublicKey> = private.commit();
-    let threshold_public_key = polynomial.public_key().clone();
-
-    Keys {
-        shares,
-        polynomial,
-        threshold_public_key,
-    }
-}
-
-/// Generates a single private key from the provided seed.
+/// Splits an existing private key into `n` shares, any `t` of which sign for it
 ///
-/// The return value should be destroyed with `destroy_keypair`.
+/// * private_key: The private key to split
+/// * n: The number of shares, at most `MAX_SHARES`
+/// * t: The threshold, between 1 and `n`
+/// * seed: A `SEED_LEN` byte seed for the polynomial's random coefficients
+/// * shares_out: Pointer to the memory where the shares will be written to, concatenated in
+///   index order, each `SHARE_LEN` bytes and accepted wherever a share is. Free it with
+///   `free_vector`
+/// * polynomial_out: Pointer to the memory where the public polynomial will be written to.
+///   Its public key is `private_key`'s own. Free it with `free_vector`
 ///
 /// # Safety
+/// - Whoever calls this holds the whole key, as they did before the split. Once the shares
+///   are handed out, the private key should be destroyed
+/// - If the same seed is used twice, the shares WILL be the same
 /// - **This function will dereference the provided pointers. If any invalid pointers are passed
 ///   then the software will crash**.
 /// - If NULL pointers are passed, the function will return false
+/// - If `n` is not between 1 and `MAX_SHARES`, or `t` not between 1 and `n`, the function
+///   will return false
 /// - If the seed is shorter than `SEED_LEN` bytes, the function will return false
 ///
 /// Returns true if successful, otherwise false.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn keygen(seed: *const Buffer, keypair: *mut *mut Keypair) -> bool {
-    if keypair.is_null() {
+pub unsafe extern "C" fn split_private_key(
+    private_key: *const PrivateKey,
+    n: usize,
+    t: usize,
+    seed: *const Buffer,
+    shares_out: *mut Buffer,
+    polynomial_out: *mut Buffer,
+) -> bool {
+    unsafe {
+        let rng = buffer_slice(seed).and_then(get_rng);
+        split_private_key_from::<SigScheme>(private_key, n, t, rng, shares_out, polynomial_out)
+    }
+}
+
+/// `split_private_key` with the randomness already in hand, whichever source it
+/// came from. `None` is a source the caller got wrong, reported as `false`.
+unsafe fn split_private_key_from<S: BindingScheme>(
+    private_key: *const PrivateKey,
+    n: usize,
+    t: usize,
+    rng: Option<impl FallibleRng>,
+    shares_out: *mut Buffer,
+    polynomial_out: *mut Buffer,
+) -> bool {
+    if shares_out.is_null() || polynomial_out.is_null() || n > MAX_SHARES {
         return false;
     }
-    let Some(seed) = (unsafe { buffer_slice(seed) }) else {
+    let Some(private_key) = (unsafe { private_key.as_ref() }) else {
         return false;
     };
-
-    let Some(mut rng) = get_rng(seed) else {
+    let Some(mut rng) = rng else {
         return false;
     };
-    let (private, public) = SigScheme::keypair(&mut rng);
-    let keypair_local = Keypair { private, public };
-    unsafe { *keypair = Box::into_raw(Box::new(keypair_local)) };
+
+    let (shares, polynomial) = match threshold_bls::sig::deal::<S, _>(private_key, n, t, &mut rng) {
+        Ok(dealt) => dealt,
+        Err(_) => return false,
+    };
+    if rng.failed() {
+        return false;
+    }
+
+    let shares = shares
+        .iter()
+        .flat_map(|share| bincode::serialize(share).expect("could not serialize share"))
+        .collect();
+    let polynomial = bincode::serialize(&polynomial).expect("could not serialize polynomial");
+
+    unsafe { *shares_out = into_buffer(shares) };
+    unsafe { *polynomial_out = into_buffer(polynomial) };
 
     true
 }
 
-/// Gets a pointer to the public key corresponding to the provided `KeyPair` pointer
+///////////////////////////////////////////////////////////////////////////
+// Caller-provided randomness
+//
+// `keygen`, `blind`, `batch_blind` and `split_private_key` expand a `SEED_LEN`
+// byte seed with ChaCha. These take a `FillRandom` callback instead and draw
+// from it as the operation needs bytes, so an integrator can hand over its
+// platform's CSPRNG directly rather than drawing a seed from it first. If the
+// callback returns false, the operation returns false and writes nothing: a
+// key, blinding factor or share drawn partly from a failed source is never
+// handed out.
+///////////////////////////////////////////////////////////////////////////
+
+/// Generates a single private key with randomness from `fill_random`.
 ///
-/// The key is **borrowed from the keypair**, not a separate allocation: it stays
-/// valid until `destroy_keypair` and must never be passed to `destroy_pubkey`,
-/// which would free memory that was never allocated on its own and leave
-/// `destroy_keypair` to free it a second time.
+/// The return value should be destroyed with `destroy_keypair`.
+///
+/// * fill_random: Fills a buffer with cryptographically secure random bytes
+/// * context: Passed to every call of `fill_random`, unread by the library
+/// * keypair: Pointer to the memory where the keypair handle will be written to
 ///
 /// # Safety
-/// The provided pointer will be dereferenced, so there must be valid data beneath it.
-/// Returns NULL if a NULL keypair is passed.
+/// - `fill_random` must be a cryptographically secure generator. The library cannot tell
+///   predictable bytes from random ones, and a key drawn from them is predictable too
+/// - `fill_random` must write `len` bytes at the pointer it is given, or return false. It is
+///   only called during this call, on the calling thread
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If `fill_random` returns false, the function will return false
+///
+/// Returns true if successful, otherwise false.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn public_key_ptr(keypair: *const Keypair) -> *const PublicKey {
-    match unsafe { keypair.as_ref() } {
-        Some(keypair) => &keypair.public,
-        None => std::ptr::null(),
+pub unsafe extern "C" fn keygen_with_rng(
+    fill_random: FillRandom,
+    context: *mut c_void,
+    keypair: *mut *mut Keypair,
+) -> bool {
+    if keypair.is_null() {
+        return false;
     }
+    let Some(rng) = (unsafe { CallbackRng::new(fill_random, context) }) else {
+        return false;
+    };
+    let Some((private, public)) = keypair_from::<SigScheme>(rng) else {
+        return false;
+    };
+    let keypair_local = Keypair { private, public };
+    unsafe { *keypair = Box::into_raw(Box::new(keypair_local)) };
+
+    true
 }
 
-/// Gets a pointer to the private key corresponding to the provided `KeyPair` pointer
+/// Blinds a message with randomness from `fill_random`. See `blind`.
 ///
-/// The key is **borrowed from the keypair**, not a separate allocation: it stays
-/// valid until `destroy_keypair` and must never be passed to `destroy_privkey`,
-/// which would free memory that was never allocated on its own and leave
-/// `destroy_keypair` to free it a second time.
+/// * message: A cleartext message which you want to blind
+/// * fill_random: Fills a buffer with cryptographically secure random bytes
+/// * context: Passed to every call of `fill_random`, unread by the library
+/// * blinded_message_out : Pointer to the memory where the blinded message will be written to
+/// * blinding_factor_out : Pointer to the object storing the blinding factor
 ///
 /// # Safety
-/// The provided pointer will be dereferenced, so there must be valid data beneath it.
-/// Returns NULL if a NULL keypair is passed.
+/// - `fill_random` must be a cryptographically secure generator. Predictable blinding factors
+///   let the signer link a blinded message to the message
+/// - `fill_random` must write `len` bytes at the pointer it is given, or return false. It is
+///   only called during this call, on the calling thread
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If `fill_random` returns false, the function will return false and write nothing
+/// - If the message cannot be blinded, the function will return false
+///
+/// Returns true if successful, otherwise false.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn private_key_ptr(keypair: *const Keypair) -> *const PrivateKey {
-    match unsafe { keypair.as_ref() } {
-        Some(keypair) => &keypair.private,
-        None => std::ptr::null(),
+pub unsafe extern "C" fn blind_with_rng(
+    message: *const Buffer,
+    fill_random: FillRandom,
+    context: *mut c_void,
+    blinded_message_out: *mut Buffer,
+    blinding_factor_out: *mut *mut BlindingFactor,
+) -> bool {
+    unsafe {
+        let rng = CallbackRng::new(fill_random, context);
+        blind_from::<SigScheme>(message, rng, blinded_message_out, blinding_factor_out)
     }
 }
 
-/// T-of-n threshold key parameters. Test-only helper produced by the central
-/// `threshold_keygen` — not exposed across the FFI boundary.
-#[cfg(test)]
-#[derive(Debug, Clone)]
-struct Keys {
-    shares: Vec<Share<PrivateKey>>,
-    polynomial: Poly<PublicKey>,
-    threshold_public_key: PublicKey,
+/// Blinds many messages with randomness from `fill_random`. See `batch_blind`.
+///
+/// * messages: An array of `count` cleartext messages
+/// * count: The number of messages
+/// * fill_random: Fills a buffer with cryptographically secure random bytes
+/// * context: Passed to every call of `fill_random`, unread by the library
+/// * blinded_messages_out: Pointer to the memory where the blinded messages will be written to,
+///   concatenated in the order of `messages`, each `SIGNATURE_LEN` bytes. Free it with
+///   `free_vector`
+/// * blinding_factors_out: An array of `count` slots, each of which receives the blinding factor
+///   of the message at the same position. Destroy each with `destroy_token`
+///
+/// # Safety
+/// - `fill_random` must be a cryptographically secure generator. Predictable blinding factors
+///   let the signer link a blinded message to the message
+/// - `fill_random` must write `len` bytes at the pointer it is given, or return false. It is
+///   only called during this call, on the calling thread
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**. `blinding_factors_out` must have room for `count` pointers
+/// - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+///   `count` is zero
+/// - If `fill_random` returns false, the function will return false and write nothing
+/// - If any message cannot be blinded, the function will return false and write nothing
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn batch_blind_with_rng(
+    messages: *const Buffer,
+    count: usize,
+    fill_random: FillRandom,
+    context: *mut c_void,
+    blinded_messages_out: *mut Buffer,
+    blinding_factors_out: *mut *mut BlindingFactor,
+) -> bool {
+    unsafe {
+        let rng = CallbackRng::new(fill_random, context);
+        batch_blind_from::<SigScheme>(
+            messages,
+            count,
+            rng,
+            blinded_messages_out,
+            blinding_factors_out,
+        )
+    }
 }
 
-#[derive(Clone)]
-// Deliberately not `repr(C)`: the fields are arkworks-backed types that are not
-// themselves `repr(C)`, and no C code is entitled to their offsets. C only ever
-// holds a `Keypair *` and reaches the keys through `public_key_ptr` /
-// `private_key_ptr`, which do Rust field projection inside this crate.
-/// A BLS12-377 Keypair
-pub struct Keypair {
-    /// The private key
-    private: PrivateKey,
-    /// The public key
-    public: PublicKey,
+/// Splits an existing private key into `n` shares with randomness from `fill_random`. See
+/// `split_private_key`.
+///
+/// * private_key: The private key to split
+/// * n: The number of shares, at most `MAX_SHARES`
+/// * t: The threshold, between 1 and `n`
+/// * fill_random: Fills a buffer with cryptographically secure random bytes
+/// * context: Passed to every call of `fill_random`, unread by the library
+/// * shares_out: Pointer to the memory where the shares will be written to, each `SHARE_LEN`
+///   bytes. Free it with `free_vector`
+/// * polynomial_out: Pointer to the memory where the public polynomial will be written to.
+///   Free it with `free_vector`
+///
+/// # Safety
+/// - `fill_random` must be a cryptographically secure generator. Predictable coefficients let
+///   fewer than `t` shareholders recover the key
+/// - `fill_random` must write `len` bytes at the pointer it is given, or return false. It is
+///   only called during this call, on the calling thread
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If `n` is not between 1 and `MAX_SHARES`, or `t` not between 1 and `n`, the function
+///   will return false
+/// - If `fill_random` returns false, the function will return false and write nothing
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn split_private_key_with_rng(
+    private_key: *const PrivateKey,
+    n: usize,
+    t: usize,
+    fill_random: FillRandom,
+    context: *mut c_void,
+    shares_out: *mut Buffer,
+    polynomial_out: *mut Buffer,
+) -> bool {
+    unsafe {
+        let rng = CallbackRng::new(fill_random, context);
+        split_private_key_from::<SigScheme>(private_key, n, t, rng, shares_out, polynomial_out)
+    }
 }
 
-/// Seeds the RNG, reporting a seed shorter than `SEED_LEN` as `None`, which the
-/// exports turn into `false`. See [`crate::seed_from_slice`].
-fn get_rng(digest: &[u8]) -> Option<impl RngCore> {
-    Some(ChaChaRng::from_seed(seed_from_slice(digest)?))
+///////////////////////////////////////////////////////////////////////////
+// Serialization
+///////////////////////////////////////////////////////////////////////////
+
+#[unsafe(no_mangle)]
+/// Deserializes a public key from the provided buffer
+///
+/// * pubkey_buf: A buffer of exactly `PUBKEY_LEN` bytes
+/// * pubkey: Pointer to the memory where the public key handle will be written to
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the buffer does not hold exactly `PUBKEY_LEN` bytes, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn deserialize_pubkey(
+    pubkey_buf: *const Buffer,
+    pubkey: *mut *mut PublicKey,
+) -> bool {
+    unsafe { deserialize(pubkey_buf, PUBKEY_LEN, pubkey) }
 }
 
-// The general pattern in these FFI tests is:
-// 1. create a MaybeUninit pointer
-// 2. pass it to the function
-// 3. assert that the function call was successful
-// 4. assume the pointer is now initialized
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::mem::MaybeUninit;
+#[unsafe(no_mangle)]
+/// Deserializes a private key from the provided buffer
+///
+/// * privkey_buf: A buffer of exactly `PRIVKEY_LEN` bytes
+/// * privkey: Pointer to the memory where the private key handle will be written to
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the buffer does not hold exactly `PRIVKEY_LEN` bytes, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn deserialize_privkey(
+    privkey_buf: *const Buffer,
+    privkey: *mut *mut PrivateKey,
+) -> bool {
+    unsafe { deserialize(privkey_buf, PRIVKEY_LEN, privkey) }
+}
 
-    #[test]
-    fn threshold_verify_ffi() {
-        threshold_verify_ffi_should_blind(true);
-        threshold_verify_ffi_should_blind(false);
-    }
+#[unsafe(no_mangle)]
+/// Deserializes a signature from the provided buffer
+///
+/// * sig_buf: A buffer of exactly `SIGNATURE_LEN` bytes
+/// * sig: Pointer to the memory where the signature handle will be written to
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the buffer does not hold exactly `SIGNATURE_LEN` bytes, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn deserialize_sig(sig_buf: *const Buffer, sig: *mut *mut Signature) -> bool {
+    unsafe { deserialize(sig_buf, SIGNATURE_LEN, sig) }
+}
 
-    fn threshold_verify_ffi_should_blind(should_blind: bool) {
-        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
-        let msg = vec![1u8, 2, 3, 4, 6];
-        let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
-        let empty_token = BlindingFactor(Token::new());
-        let partial_sign_fn = if should_blind {
-            partial_sign_blinded_message
-        } else {
-            partial_sign
-        };
-        let partial_verify_fn = if should_blind {
-            partial_verify_blind_signature
-        } else {
-            partial_verify
-        };
+#[unsafe(no_mangle)]
+/// Serializes a public key to the provided buffer
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn serialize_pubkey(
+    pubkey: *const PublicKey,
+    pubkey_buf: *mut *mut u8,
+) -> bool {
+    unsafe { serialize(pubkey, PUBKEY_LEN, pubkey_buf) }
+}
 
-        let (n, t) = (5, 3);
-        let keys = threshold_keygen(n, t, &seed[..]);
+#[unsafe(no_mangle)]
+/// Serializes a private key to the provided buffer
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn serialize_privkey(
+    privkey: *const PrivateKey,
+    privkey_buf: *mut *mut u8,
+) -> bool {
+    unsafe { serialize(privkey, PRIVKEY_LEN, privkey_buf) }
+}
 
-        let (message_to_sign, blinding_factor) = if should_blind {
-            let mut blinded_message = MaybeUninit::<Buffer>::uninit();
-            let mut blinding_factor = MaybeUninit::<*mut BlindingFactor>::uninit();
-            assert!(unsafe {
-                blind(
-                    &Buffer::from(msg.as_ref()),
-                    &Buffer::from(user_seed),
-                    blinded_message.as_mut_ptr(),
-                    blinding_factor.as_mut_ptr(),
-                )
-            });
-            let blinded_message = unsafe { blinded_message.assume_init() };
-            let blinding_factor = unsafe { &*blinding_factor.assume_init() };
+#[unsafe(no_mangle)]
+/// Serializes a signature to the provided buffer
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn serialize_sig(sig: *const Signature, sig_buf: *mut *mut u8) -> bool {
+    unsafe { serialize(sig, SIGNATURE_LEN, sig_buf) }
+}
 
-            (blinded_message, blinding_factor)
-        } else {
-            (Buffer::from(&msg[..]), &empty_token)
-        };
+// The null and length checks live here rather than in the exported wrappers
+// so that no call site can omit them; every wrapper documents both.
+//
+// `len` is the serialized size of `T`, and the buffer has to hold exactly that.
+// All three values are fixed-size, so a buffer of any other length is the
+// caller holding something else — and reading `len` bytes from it, which a
+// pointer without a length left no way to avoid, runs off the end of their
+// allocation.
+unsafe fn deserialize<T: DeserializeOwned>(
+    in_buf: *const Buffer,
+    len: usize,
+    out: *mut *mut T,
+) -> bool {
+    if out.is_null() {
+        return false;
+    }
+
+    let Some(buf) = (unsafe { buffer_slice(in_buf) }) else {
+        return false;
+    };
+    if buf.len() != len {
+        return false;
+    }
+
+    let obj = if let Ok(res) = serialization::deserialize(buf) {
+        res
+    } else {
+        return false;
+    };
+
+    unsafe { *out = Box::into_raw(Box::new(obj)) };
+
+    true
+}
+
+// `len` is the size the caller will hand to `free_vector`, which it takes from
+// the constant in the header rather than from this function — nothing here
+// reports a length. Serializing to any other size would leave the caller freeing
+// an allocation of the wrong size, so refuse instead.
+unsafe fn serialize<T: Serialize>(in_obj: *const T, len: usize, out_bytes: *mut *mut u8) -> bool {
+    if in_obj.is_null() || out_bytes.is_null() {
+        return false;
+    }
+
+    let obj = unsafe { &*in_obj };
+    let marshalled = if let Ok(res) = bincode::serialize(obj) {
+        res
+    } else {
+        return false;
+    };
+    if marshalled.len() != len {
+        return false;
+    }
+
+    let (bytes, _) = into_raw_bytes(marshalled);
+    unsafe {
+        *out_bytes = bytes;
+    };
+
+    true
+}
+
+#[unsafe(no_mangle)]
+/// Frees the memory allocated for the blinding factor
+///
+/// # Safety
+///
+/// The pointer must be NULL, or point to a valid instance of the data type that
+/// has not already been freed. Freeing a pointer twice corrupts the heap; NULL
+/// does nothing.
+pub unsafe extern "C" fn destroy_token(token: *mut BlindingFactor) {
+    if token.is_null() {
+        return;
+    }
+    drop(unsafe { Box::from_raw(token) });
+}
+
+#[unsafe(no_mangle)]
+/// Frees the memory allocated for the keypair helper
+///
+/// This also frees the keys behind `public_key_ptr` and `private_key_ptr`, which
+/// borrow from the keypair rather than owning their memory.
+///
+/// # Safety
+///
+/// The pointer must be NULL, or point to a valid instance of the data type that
+/// has not already been freed. Freeing a pointer twice corrupts the heap; NULL
+/// does nothing.
+pub unsafe extern "C" fn destroy_keypair(keypair: *mut Keypair) {
+    if keypair.is_null() {
+        return;
+    }
+    drop(unsafe { Box::from_raw(keypair) });
+}
+
+#[unsafe(no_mangle)]
+/// Frees the memory allocated for a private key
+///
+/// # Safety
+///
+/// The pointer must be NULL, or come from `deserialize_privkey` and not have
+/// been freed already. In particular it must not come from `private_key_ptr`,
+/// which borrows from a keypair instead of allocating. Freeing a pointer twice
+/// corrupts the heap; NULL does nothing.
+pub unsafe extern "C" fn destroy_privkey(private_key: *mut PrivateKey) {
+    if private_key.is_null() {
+        return;
+    }
+    drop(unsafe { Box::from_raw(private_key) });
+}
+
+#[unsafe(no_mangle)]
+/// Frees the memory allocated for a vector
+///
+/// Takes a const pointer so that a caller can pass `Buffer::ptr` straight back
+/// without casting away the qualifier to free its own buffer.
+///
+/// # Safety
+///
+/// The pointer must be NULL, or be one this library handed out together with
+/// the exact length it reported for it: the allocation is reconstructed from
+/// the two, so a different length frees a different allocation. Freeing a
+/// pointer twice corrupts the heap; NULL does nothing.
+pub unsafe extern "C" fn free_vector(bytes: *const u8, len: usize) {
+    if bytes.is_null() {
+        return;
+    }
+    // Reconstructed as the boxed slice `into_raw_bytes` handed out, whose
+    // allocation is exactly `len` bytes.
+    let bytes = std::ptr::slice_from_raw_parts_mut(bytes as *mut u8, len);
+    drop(unsafe { Box::from_raw(bytes) });
+}
+
+#[unsafe(no_mangle)]
+/// Frees the memory allocated for a public key
+///
+/// # Safety
+///
+/// The pointer must be NULL, or come from `deserialize_pubkey` and not have
+/// been freed already. In particular it must not come from `public_key_ptr`,
+/// which borrows from a keypair instead of allocating. Freeing a pointer twice
+/// corrupts the heap; NULL does nothing.
+pub unsafe extern "C" fn destroy_pubkey(public_key: *mut PublicKey) {
+    if public_key.is_null() {
+        return;
+    }
+    drop(unsafe { Box::from_raw(public_key) });
+}
+
+#[unsafe(no_mangle)]
+/// Frees the memory allocated for a signature
+///
+/// # Safety
+///
+/// The pointer must be NULL, or point to a valid instance of the data type that
+/// has not already been freed. Freeing a pointer twice corrupts the heap; NULL
+/// does nothing.
+pub unsafe extern "C" fn destroy_sig(signature: *mut Signature) {
+    if signature.is_null() {
+        return;
+    }
+    drop(unsafe { Box::from_raw(signature) });
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Keys on G1
+//
+// The same operations under the scheme that puts public keys on G1 (48 bytes)
+// and signatures on G2 (96 bytes), for callers that want compact keys. Each
+// function behaves as its unprefixed counterpart, with these lengths and
+// handles in place of the G2 ones.
+//
+// Private keys, shares and blinding factors are shared with the functions
+// above: they are the same scalars under either scheme. Everything in a group
+// is not, and mixing the two fails rather than converting — a `G1PublicKey`
+// cannot be passed where a `PublicKey` is expected, and bytes from one scheme
+// do not deserialize as the other's.
+///////////////////////////////////////////////////////////////////////////
+
+/// Blinds a message for signing under the keys-on-G1 scheme. See `blind`.
+///
+/// The blinding factor is destroyed with `destroy_token`, and signatures on the
+/// blinded message are unblinded with `g1_unblind`.
+///
+/// # Safety
+/// - If the same seed is used twice, the blinded result WILL be the same
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+/// - If the message cannot be blinded, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_blind(
+    message: *const Buffer,
+    seed: *const Buffer,
+    blinded_message_out: *mut Buffer,
+    blinding_factor_out: *mut *mut BlindingFactor,
+) -> bool {
+    unsafe { blind_for::<G1SigScheme>(message, seed, blinded_message_out, blinding_factor_out) }
+}
+
+/// Unblinds a keys-on-G1 blind signature. See `unblind`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_unblind(
+    blinded_signature: *const Buffer,
+    blinding_factor: *const BlindingFactor,
+    unblinded_signature: *mut Buffer,
+) -> bool {
+    unsafe { unblind_for::<G1SigScheme>(blinded_signature, blinding_factor, unblinded_signature) }
+}
+
+/// Verifies a `G1_SIGNATURE_LEN` byte signature against a public key on G1. See `verify`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_verify(
+    public_key: *const G1PublicKey,
+    message: *const Buffer,
+    signature: *const Buffer,
+) -> bool {
+    unsafe { verify_for::<G1SigScheme>(public_key, message, signature) }
+}
+
+/// Derives the OPRF output from a keys-on-G1 signature. See `oprf_finalize`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_oprf_finalize(
+    public_key: *const G1PublicKey,
+    input: *const Buffer,
+    signature: *const Buffer,
+    output: *mut Buffer,
+) -> bool {
+    unsafe { oprf_finalize_for::<G1SigScheme>(public_key, input, signature, output) }
+}
+
+/// Unblinds a keys-on-G1 blind signature and derives the OPRF output from it. See
+/// `oprf_unblind_finalize`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_oprf_unblind_finalize(
+    public_key: *const G1PublicKey,
+    input: *const Buffer,
+    blinded_signature: *const Buffer,
+    blinding_factor: *const BlindingFactor,
+    output: *mut Buffer,
+) -> bool {
+    unsafe {
+        oprf_unblind_finalize_for::<G1SigScheme>(
+            public_key,
+            input,
+            blinded_signature,
+            blinding_factor,
+            output,
+        )
+    }
+}
+
+/// Signs the message under the keys-on-G1 scheme. See `sign`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_sign(
+    private_key: *const PrivateKey,
+    message: *const Buffer,
+    signature: *mut Buffer,
+) -> bool {
+    unsafe { sign_for::<G1SigScheme>(private_key, message, signature) }
+}
+
+/// Signs a message blinded with `g1_blind`. See `sign_blinded_message`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_sign_blinded_message(
+    private_key: *const PrivateKey,
+    message: *const Buffer,
+    signature: *mut Buffer,
+) -> bool {
+    unsafe { sign_blinded_message_for::<G1SigScheme>(private_key, message, signature) }
+}
+
+/// Partially signs the message under the keys-on-G1 scheme. See `partial_sign`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_partial_sign(
+    share: *const Buffer,
+    message: *const Buffer,
+    signature: *mut Buffer,
+) -> bool {
+    unsafe { partial_sign_for::<G1SigScheme>(share, message, signature) }
+}
+
+/// Partially signs a message blinded with `g1_blind`. See `partial_sign_blinded_message`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_partial_sign_blinded_message(
+    share: *const Buffer,
+    blinded_message: *const Buffer,
+    signature: *mut Buffer,
+) -> bool {
+    unsafe { partial_sign_blinded_message_for::<G1SigScheme>(share, blinded_message, signature) }
+}
+
+/// Verifies a keys-on-G1 partial signature against a polynomial committed on G1. See
+/// `partial_verify`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_partial_verify(
+    polynomial: *const Buffer,
+    blinded_message: *const Buffer,
+    signature: *const Buffer,
+) -> bool {
+    unsafe { partial_verify_for::<G1SigScheme>(polynomial, blinded_message, signature) }
+}
+
+/// Verifies a keys-on-G1 partial blind signature against a polynomial committed on G1. See
+/// `partial_verify_blind_signature`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_partial_verify_blind_signature(
+    polynomial: *const Buffer,
+    blinded_message: *const Buffer,
+    signature: *const Buffer,
+) -> bool {
+    unsafe {
+        partial_verify_blind_signature_for::<G1SigScheme>(polynomial, blinded_message, signature)
+    }
+}
+
+/// Combines keys-on-G1 partial signatures, each `G1_PARTIAL_SIG_LENGTH` bytes, into a
+/// threshold signature. See `combine`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the polynomial cannot be deserialized, the function will return false
+/// - If the flattened buffer is not a whole number of `G1_PARTIAL_SIG_LENGTH` chunks, the
+///   function will return false
+/// - This function does not check if the signatures are valid!
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_combine(
+    polynomial: *const Buffer,
+    signatures: *const Buffer,
+    asig: *mut Buffer,
+) -> bool {
+    unsafe { combine_for::<G1SigScheme>(polynomial, signatures, asig) }
+}
+
+/// Verifies signatures on many messages against one public key on G1, each signature
+/// `G1_SIGNATURE_LEN` bytes. See `batch_verify`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+///   `count` is zero
+/// - If `signatures` is not exactly `count` signatures, the function will return false
+///
+/// Returns true if successful, otherwise false. A signature that does not verify is reported in
+/// the bitmap, not by the return value.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_batch_verify(
+    public_key: *const G1PublicKey,
+    messages: *const Buffer,
+    count: usize,
+    signatures: *const Buffer,
+    valid_out: *mut Buffer,
+) -> bool {
+    unsafe { batch_verify_for::<G1SigScheme>(public_key, messages, count, signatures, valid_out) }
+}
+
+/// Verifies many keys-on-G1 partial signatures on one message, each `G1_PARTIAL_SIG_LENGTH`
+/// bytes. See `batch_partial_verify`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the polynomial cannot be deserialized, the function will return false
+/// - If the flattened buffer is not a whole number of `G1_PARTIAL_SIG_LENGTH` chunks, the
+///   function will return false
+///
+/// Returns true if successful, otherwise false. A partial signature that does not verify is
+/// reported in the bitmap, not by the return value.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_batch_partial_verify(
+    polynomial: *const Buffer,
+    message: *const Buffer,
+    signatures: *const Buffer,
+    valid_out: *mut Buffer,
+) -> bool {
+    unsafe {
+        batch_partial_verify_for::<G1SigScheme>(polynomial, message, signatures, valid_out, false)
+    }
+}
+
+/// Verifies many keys-on-G1 partial blinded signatures on one blinded message, each
+/// `G1_PARTIAL_SIG_LENGTH` bytes. See `batch_partial_verify_blind_signature`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the polynomial cannot be deserialized, the function will return false
+/// - If the flattened buffer is not a whole number of `G1_PARTIAL_SIG_LENGTH` chunks, the
+///   function will return false
+///
+/// Returns true if successful, otherwise false. A partial signature that does not verify is
+/// reported in the bitmap, not by the return value.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_batch_partial_verify_blind_signature(
+    polynomial: *const Buffer,
+    blinded_message: *const Buffer,
+    signatures: *const Buffer,
+    valid_out: *mut Buffer,
+) -> bool {
+    unsafe {
+        batch_partial_verify_for::<G1SigScheme>(
+            polynomial,
+            blinded_message,
+            signatures,
+            valid_out,
+            true,
+        )
+    }
+}
+
+/// Blinds many messages for signing under the keys-on-G1 scheme, each blinded message
+/// `G1_SIGNATURE_LEN` bytes. See `batch_blind`.
+///
+/// # Safety
+/// - If the same seed is used twice, the blinded results WILL be the same
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**. `blinding_factors_out` must have room for `count` pointers
+/// - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+///   `count` is zero
+/// - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+/// - If any message cannot be blinded, the function will return false and write nothing
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_batch_blind(
+    messages: *const Buffer,
+    count: usize,
+    seed: *const Buffer,
+    blinded_messages_out: *mut Buffer,
+    blinding_factors_out: *mut *mut BlindingFactor,
+) -> bool {
+    unsafe {
+        batch_blind_for::<G1SigScheme>(
+            messages,
+            count,
+            seed,
+            blinded_messages_out,
+            blinding_factors_out,
+        )
+    }
+}
+
+/// Unblinds many keys-on-G1 blind signatures, each `G1_SIGNATURE_LEN` bytes. See
+/// `batch_unblind`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, including any of the `count` blinding factors, the function
+///   will return false. `blinding_factors` may be NULL when `count` is zero
+/// - If `blinded_signatures` is not exactly `count` signatures, the function will return false
+/// - If any signature cannot be unblinded, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_batch_unblind(
+    blinded_signatures: *const Buffer,
+    blinding_factors: *const *const BlindingFactor,
+    count: usize,
+    signatures_out: *mut Buffer,
+) -> bool {
+    unsafe {
+        batch_unblind_for::<G1SigScheme>(
+            blinded_signatures,
+            blinding_factors,
+            count,
+            signatures_out,
+        )
+    }
+}
+
+/// Splits an existing private key into `n` shares under the keys-on-G1 scheme. See
+/// `split_private_key`.
+///
+/// * shares_out: Each share is `SHARE_LEN` bytes, as under the other scheme
+/// * polynomial_out: The public polynomial, committed on G1
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If `n` is not between 1 and `MAX_SHARES`, or `t` not between 1 and `n`, the function
+///   will return false
+/// - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_split_private_key(
+    private_key: *const PrivateKey,
+    n: usize,
+    t: usize,
+    seed: *const Buffer,
+    shares_out: *mut Buffer,
+    polynomial_out: *mut Buffer,
+) -> bool {
+    unsafe {
+        let rng = buffer_slice(seed).and_then(get_rng);
+        split_private_key_from::<G1SigScheme>(private_key, n, t, rng, shares_out, polynomial_out)
+    }
+}
+
+#[unsafe(no_mangle)]
+/// Deserializes a public key on G1 from the provided buffer
+///
+/// * pubkey_buf: A buffer of exactly `G1_PUBKEY_LEN` bytes
+/// * pubkey: Pointer to the memory where the public key handle will be written to
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the buffer does not hold exactly `G1_PUBKEY_LEN` bytes, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn g1_deserialize_pubkey(
+    pubkey_buf: *const Buffer,
+    pubkey: *mut *mut G1PublicKey,
+) -> bool {
+    unsafe { deserialize(pubkey_buf, G1_PUBKEY_LEN, pubkey) }
+}
+
+#[unsafe(no_mangle)]
+/// Deserializes a signature on G2 from the provided buffer
+///
+/// * sig_buf: A buffer of exactly `G1_SIGNATURE_LEN` bytes
+/// * sig: Pointer to the memory where the signature handle will be written to
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the buffer does not hold exactly `G1_SIGNATURE_LEN` bytes, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn g1_deserialize_sig(
+    sig_buf: *const Buffer,
+    sig: *mut *mut G1Signature,
+) -> bool {
+    unsafe { deserialize(sig_buf, G1_SIGNATURE_LEN, sig) }
+}
+
+#[unsafe(no_mangle)]
+/// Serializes a public key on G1 to the provided buffer, `G1_PUBKEY_LEN` bytes
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn g1_serialize_pubkey(
+    pubkey: *const G1PublicKey,
+    pubkey_buf: *mut *mut u8,
+) -> bool {
+    unsafe { serialize(pubkey, G1_PUBKEY_LEN, pubkey_buf) }
+}
+
+#[unsafe(no_mangle)]
+/// Serializes a signature on G2 to the provided buffer, `G1_SIGNATURE_LEN` bytes
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+///
+/// Returns true if successful, otherwise false.
+pub unsafe extern "C" fn g1_serialize_sig(sig: *const G1Signature, sig_buf: *mut *mut u8) -> bool {
+    unsafe { serialize(sig, G1_SIGNATURE_LEN, sig_buf) }
+}
+
+#[unsafe(no_mangle)]
+/// Frees the memory allocated for a public key on G1
+///
+/// # Safety
+///
+/// The pointer must be NULL, or come from `g1_deserialize_pubkey` and not have
+/// been freed already. In particular it must not come from `g1_public_key_ptr`,
+/// which borrows from a keypair instead of allocating. Freeing a pointer twice
+/// corrupts the heap; NULL does nothing.
+pub unsafe extern "C" fn g1_destroy_pubkey(public_key: *mut G1PublicKey) {
+    if public_key.is_null() {
+        return;
+    }
+    drop(unsafe { Box::from_raw(public_key) });
+}
+
+#[unsafe(no_mangle)]
+/// Frees the memory allocated for a signature on G2
+///
+/// # Safety
+///
+/// The pointer must be NULL, or point to a valid instance of the data type that
+/// has not already been freed. Freeing a pointer twice corrupts the heap; NULL
+/// does nothing.
+pub unsafe extern "C" fn g1_destroy_sig(signature: *mut G1Signature) {
+    if signature.is_null() {
+        return;
+    }
+    drop(unsafe { Box::from_raw(signature) });
+}
+
+/// Generates a single private key from the provided seed, with its public key on G1.
+///
+/// The return value should be destroyed with `g1_destroy_keypair`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_keygen(seed: *const Buffer, keypair: *mut *mut G1Keypair) -> bool {
+    if keypair.is_null() {
+        return false;
+    }
+    let Some((private, public)) = (unsafe { keypair_for::<G1SigScheme>(seed) }) else {
+        return false;
+    };
+    let keypair_local = G1Keypair { private, public };
+    unsafe { *keypair = Box::into_raw(Box::new(keypair_local)) };
+
+    true
+}
+
+/// Gets a pointer to the public key of the provided `G1Keypair`
+///
+/// Borrowed from the keypair, as with `public_key_ptr`: it stays valid until
+/// `g1_destroy_keypair` and must never be passed to `g1_destroy_pubkey`.
+///
+/// # Safety
+/// The provided pointer will be dereferenced, so there must be valid data beneath it.
+/// Returns NULL if a NULL keypair is passed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_public_key_ptr(keypair: *const G1Keypair) -> *const G1PublicKey {
+    match unsafe { keypair.as_ref() } {
+        Some(keypair) => &keypair.public,
+        None => std::ptr::null(),
+    }
+}
+
+/// Gets a pointer to the private key of the provided `G1Keypair`
+///
+/// Borrowed from the keypair, as with `private_key_ptr`: it stays valid until
+/// `g1_destroy_keypair` and must never be passed to `destroy_privkey`.
+///
+/// # Safety
+/// The provided pointer will be dereferenced, so there must be valid data beneath it.
+/// Returns NULL if a NULL keypair is passed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_private_key_ptr(keypair: *const G1Keypair) -> *const PrivateKey {
+    match unsafe { keypair.as_ref() } {
+        Some(keypair) => &keypair.private,
+        None => std::ptr::null(),
+    }
+}
+
+#[unsafe(no_mangle)]
+/// Frees the memory allocated for the keys-on-G1 keypair helper
+///
+/// This also frees the keys behind `g1_public_key_ptr` and `g1_private_key_ptr`.
+///
+/// # Safety
+///
+/// The pointer must be NULL, or point to a valid instance of the data type that
+/// has not already been freed. Freeing a pointer twice corrupts the heap; NULL
+/// does nothing.
+pub unsafe extern "C" fn g1_destroy_keypair(keypair: *mut G1Keypair) {
+    if keypair.is_null() {
+        return;
+    }
+    drop(unsafe { Box::from_raw(keypair) });
+}
+
+/// Generates a single private key with its public key on G1, with randomness from
+/// `fill_random`. See `keygen_with_rng`.
+///
+/// The return value should be destroyed with `g1_destroy_keypair`.
+///
+/// # Safety
+/// - `fill_random` must be a cryptographically secure generator, and must write `len` bytes at
+///   the pointer it is given or return false
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If `fill_random` returns false, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_keygen_with_rng(
+    fill_random: FillRandom,
+    context: *mut c_void,
+    keypair: *mut *mut G1Keypair,
+) -> bool {
+    if keypair.is_null() {
+        return false;
+    }
+    let Some(rng) = (unsafe { CallbackRng::new(fill_random, context) }) else {
+        return false;
+    };
+    let Some((private, public)) = keypair_from::<G1SigScheme>(rng) else {
+        return false;
+    };
+    let keypair_local = G1Keypair { private, public };
+    unsafe { *keypair = Box::into_raw(Box::new(keypair_local)) };
+
+    true
+}
+
+/// Blinds a message for signing under the keys-on-G1 scheme, with randomness from
+/// `fill_random`. See `blind_with_rng`.
+///
+/// # Safety
+/// - `fill_random` must be a cryptographically secure generator, and must write `len` bytes at
+///   the pointer it is given or return false
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If `fill_random` returns false, the function will return false and write nothing
+/// - If the message cannot be blinded, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_blind_with_rng(
+    message: *const Buffer,
+    fill_random: FillRandom,
+    context: *mut c_void,
+    blinded_message_out: *mut Buffer,
+    blinding_factor_out: *mut *mut BlindingFactor,
+) -> bool {
+    unsafe {
+        let rng = CallbackRng::new(fill_random, context);
+        blind_from::<G1SigScheme>(message, rng, blinded_message_out, blinding_factor_out)
+    }
+}
+
+/// Blinds many messages for signing under the keys-on-G1 scheme, with randomness from
+/// `fill_random`. See `batch_blind_with_rng`.
+///
+/// # Safety
+/// - `fill_random` must be a cryptographically secure generator, and must write `len` bytes at
+///   the pointer it is given or return false
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**. `blinding_factors_out` must have room for `count` pointers
+/// - If NULL pointers are passed, the function will return false. `messages` may be NULL when
+///   `count` is zero
+/// - If `fill_random` returns false, the function will return false and write nothing
+/// - If any message cannot be blinded, the function will return false and write nothing
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_batch_blind_with_rng(
+    messages: *const Buffer,
+    count: usize,
+    fill_random: FillRandom,
+    context: *mut c_void,
+    blinded_messages_out: *mut Buffer,
+    blinding_factors_out: *mut *mut BlindingFactor,
+) -> bool {
+    unsafe {
+        let rng = CallbackRng::new(fill_random, context);
+        batch_blind_from::<G1SigScheme>(
+            messages,
+            count,
+            rng,
+            blinded_messages_out,
+            blinding_factors_out,
+        )
+    }
+}
+
+/// Splits an existing private key into `n` shares under the keys-on-G1 scheme, with
+/// randomness from `fill_random`. See `split_private_key_with_rng`.
+///
+/// # Safety
+/// - `fill_random` must be a cryptographically secure generator, and must write `len` bytes at
+///   the pointer it is given or return false
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If `n` is not between 1 and `MAX_SHARES`, or `t` not between 1 and `n`, the function
+///   will return false
+/// - If `fill_random` returns false, the function will return false and write nothing
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn g1_split_private_key_with_rng(
+    private_key: *const PrivateKey,
+    n: usize,
+    t: usize,
+    fill_random: FillRandom,
+    context: *mut c_void,
+    shares_out: *mut Buffer,
+    polynomial_out: *mut Buffer,
+) -> bool {
+    unsafe {
+        let rng = CallbackRng::new(fill_random, context);
+        split_private_key_from::<G1SigScheme>(private_key, n, t, rng, shares_out, polynomial_out)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Helpers
+//
+// These should be exposed behind a helper module and should not be made part
+// of the public API
+///////////////////////////////////////////////////////////////////////////
+
+/// Generates a t-of-n polynomial and private key shares.
+///
+/// WARNING: Trustful central keygen — intended only for local testing. Production
+/// deployments should use a DKG protocol so no single party ever holds the master
+/// secret.
+#[cfg(test)]
+fn threshold_keygen(n: usize, t: usize, seed: &[u8]) -> Keys {
+    threshold_keygen_for::<SigScheme>(n, t, seed)
+}
+
+/// `threshold_keygen` under any scheme the bindings expose. The shares are the
+/// same scalars under either; only the committed polynomial differs.
+#[cfg(test)]
+fn threshold_keygen_for<S: BindingScheme>(n: usize, t: usize, seed: &[u8]) -> Keys<S::Public> {
+    let mut rng = get_rng(seed).expect("the tests seed this with at least SEED_LEN bytes");
+    let private = Poly::<PrivateKey>::new_from(t - 1, &mut rng);
+    let shares = (0..n)
+        .map(|i| private.eval(i as Index))
+        .map(|e| Share {
+            index: e.index,
+            private: e.value,
+        })
+        .collect();
+    let polynomial: Poly<S::Public> = private.commit();
+    let threshold_public_key = polynomial.public_key().clone();
+
+    Keys {
+        shares,
+        polynomial,
+        threshold_public_key,
+    }
+}
+
+/// Generates a single private key from the provided seed.
+///
+/// The return value should be destroyed with `destroy_keypair`.
+///
+/// # Safety
+/// - **This function will dereference the provided pointers. If any invalid pointers are passed
+///   then the software will crash**.
+/// - If NULL pointers are passed, the function will return false
+/// - If the seed is shorter than `SEED_LEN` bytes, the function will return false
+///
+/// Returns true if successful, otherwise false.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn keygen(seed: *const Buffer, keypair: *mut *mut Keypair) -> bool {
+    if keypair.is_null() {
+        return false;
+    }
+    let Some((private, public)) = (unsafe { keypair_for::<SigScheme>(seed) }) else {
+        return false;
+    };
+    let keypair_local = Keypair { private, public };
+    unsafe { *keypair = Box::into_raw(Box::new(keypair_local)) };
+
+    true
+}
+
+/// Draws a keypair under `S` from the caller's seed. `None` is a seed the
+/// exports report as `false`: NULL, no memory behind it, or too short.
+unsafe fn keypair_for<S: BindingScheme>(seed: *const Buffer) -> Option<(PrivateKey, S::Public)> {
+    let seed = unsafe { buffer_slice(seed) }?;
+
+    keypair_from::<S>(get_rng(seed)?)
+}
+
+/// Draws a keypair under `S` from any source of randomness. `None` if the
+/// source failed partway, in which case the key was drawn from whatever the
+/// source left behind and must not be handed out.
+fn keypair_from<S: BindingScheme>(mut rng: impl FallibleRng) -> Option<(PrivateKey, S::Public)> {
+    let keypair = S::keypair(&mut rng);
+    if rng.failed() {
+        return None;
+    }
+
+    Some(keypair)
+}
+
+/// Gets a pointer to the public key corresponding to the provided `KeyPair` pointer
+///
+/// The key is **borrowed from the keypair**, not a separate allocation: it stays
+/// valid until `destroy_keypair` and must never be passed to `destroy_pubkey`,
+/// which would free memory that was never allocated on its own and leave
+/// `destroy_keypair` to free it a second time.
+///
+/// # Safety
+/// The provided pointer will be dereferenced, so there must be valid data beneath it.
+/// Returns NULL if a NULL keypair is passed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn public_key_ptr(keypair: *const Keypair) -> *const PublicKey {
+    match unsafe { keypair.as_ref() } {
+        Some(keypair) => &keypair.public,
+        None => std::ptr::null(),
+    }
+}
+
+/// Gets a pointer to the private key corresponding to the provided `KeyPair` pointer
+///
+/// The key is **borrowed from the keypair**, not a separate allocation: it stays
+/// valid until `destroy_keypair` and must never be passed to `destroy_privkey`,
+/// which would free memory that was never allocated on its own and leave
+/// `destroy_keypair` to free it a second time.
+///
+/// # Safety
+/// The provided pointer will be dereferenced, so there must be valid data beneath it.
+/// Returns NULL if a NULL keypair is passed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn private_key_ptr(keypair: *const Keypair) -> *const PrivateKey {
+    match unsafe { keypair.as_ref() } {
+        Some(keypair) => &keypair.private,
+        None => std::ptr::null(),
+    }
+}
+
+/// T-of-n threshold key parameters. Test-only helper produced by the central
+/// `threshold_keygen` — not exposed across the FFI boundary.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+struct Keys<P = PublicKey> {
+    shares: Vec<Share<PrivateKey>>,
+    polynomial: Poly<P>,
+    threshold_public_key: P,
+}
+
+#[derive(Clone)]
+// Deliberately not `repr(C)`: the fields are arkworks-backed types that are not
+// themselves `repr(C)`, and no C code is entitled to their offsets. C only ever
+// holds a `Keypair *` and reaches the keys through `public_key_ptr` /
+// `private_key_ptr`, which do Rust field projection inside this crate.
+/// A BLS12-377 Keypair
+pub struct Keypair {
+    /// The private key
+    private: PrivateKey,
+    /// The public key
+    public: PublicKey,
+}
+
+#[derive(Clone)]
+// Not `repr(C)`, for the reasons `Keypair` gives. A type of its own rather than
+// a generic `Keypair`, so that C sees two handles it cannot mix up.
+/// A BLS12-377 Keypair with its public key on G1
+pub struct G1Keypair {
+    /// The private key
+    private: PrivateKey,
+    /// The public key
+    public: G1PublicKey,
+}
+
+/// Seeds the RNG, reporting a seed shorter than `SEED_LEN` as `None`, which the
+/// exports turn into `false`. See [`crate::seed_from_slice`].
+fn get_rng(digest: &[u8]) -> Option<ChaChaRng> {
+    Some(ChaChaRng::from_seed(seed_from_slice(digest)?))
+}
+
+/// A source of randomness that may fail while it is being drawn from.
+///
+/// `RngCore::fill_bytes` has no way to report an error, and the core draws
+/// through it, so a source that fails records it instead and the entry point
+/// asks once the draw is over. Whatever was drawn from a failed source is
+/// discarded rather than returned.
+trait FallibleRng: RngCore + CryptoRng {
+    /// Whether any draw so far has failed.
+    fn failed(&self) -> bool {
+        false
+    }
+}
+
+impl FallibleRng for ChaChaRng {}
+
+/// Fills `len` bytes at `bytes` with random data, returning true if it did.
+///
+/// `context` is passed back unchanged from the call that took the callback, for
+/// whatever state the caller's generator needs; the library never reads it. The
+/// callback is only invoked during that call, on the calling thread.
+///
+/// Passing NULL where a callback is expected makes the call return false.
+// An `Option` because C can pass NULL, and a bare `fn` must never be null.
+pub type FillRandom =
+    Option<unsafe extern "C" fn(context: *mut c_void, bytes: *mut u8, len: usize) -> bool>;
+
+/// The caller's `FillRandom` as an RNG.
+///
+/// Once the callback reports a failure it is not called again: every later
+/// draw is zeros, and the entry point throws the result away.
+struct CallbackRng {
+    fill_random: FillRandom,
+    context: *mut c_void,
+    failed: bool,
+}
+
+impl CallbackRng {
+    /// `None` if the callback is NULL.
+    ///
+    /// # Safety
+    /// `fill_random` must be safe to call with `context` and any buffer the
+    /// library hands it, for as long as the `CallbackRng` lives.
+    unsafe fn new(fill_random: FillRandom, context: *mut c_void) -> Option<Self> {
+        fill_random?;
+        Some(Self {
+            fill_random,
+            context,
+            failed: false,
+        })
+    }
+}
+
+impl RngCore for CallbackRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if self.try_fill_bytes(dest).is_err() {
+            dest.fill(0);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        // An empty draw has nothing to fill, and a callback handed a dangling
+        // pointer with a zero length is one more thing for it to get wrong.
+        if !self.failed && !dest.is_empty() {
+            let (context, bytes, len) = (self.context, dest.as_mut_ptr(), dest.len());
+            self.failed = !self
+                .fill_random
+                .is_some_and(|fill_random| unsafe { fill_random(context, bytes, len) });
+        }
+        if self.failed {
+            return Err(rand_core::Error::from(CALLBACK_FAILED));
+        }
+
+        Ok(())
+    }
+}
+
+/// The caller's callback is its platform's CSPRNG, which is what `CryptoRng`
+/// asserts. The library cannot check that; the entry points document it.
+impl CryptoRng for CallbackRng {}
+
+impl FallibleRng for CallbackRng {
+    fn failed(&self) -> bool {
+        self.failed
+    }
+}
+
+/// The `rand_core::Error` code a failed callback reports. The callback returns
+/// a bool, so there is no code of its own to pass on.
+const CALLBACK_FAILED: std::num::NonZeroU32 =
+    match std::num::NonZeroU32::new(rand_core::Error::CUSTOM_START) {
+        Some(code) => code,
+        None => unreachable!(),
+    };
+
+// The general pattern in these FFI tests is:
+// 1. create a MaybeUninit pointer
+// 2. pass it to the function
+// 3. assert that the function call was successful
+// 4. assume the pointer is now initialized
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::MaybeUninit;
+
+    #[test]
+    fn threshold_verify_ffi() {
+        threshold_verify_ffi_should_blind(true);
+        threshold_verify_ffi_should_blind(false);
+    }
+
+    fn threshold_verify_ffi_should_blind(should_blind: bool) {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let msg = vec![1u8, 2, 3, 4, 6];
+        let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
+        let empty_token = BlindingFactor(Token::new());
+        let partial_sign_fn = if should_blind {
+            partial_sign_blinded_message
+        } else {
+            partial_sign
+        };
+        let partial_verify_fn = if should_blind {
+            partial_verify_blind_signature
+        } else {
+            partial_verify
+        };
+
+        let (n, t) = (5, 3);
+        let keys = threshold_keygen(n, t, &seed[..]);
+
+        let (message_to_sign, blinding_factor) = if should_blind {
+            let mut blinded_message = MaybeUninit::<Buffer>::uninit();
+            let mut blinding_factor = MaybeUninit::<*mut BlindingFactor>::uninit();
+            assert!(unsafe {
+                blind(
+                    &Buffer::from(msg.as_ref()),
+                    &Buffer::from(user_seed),
+                    blinded_message.as_mut_ptr(),
+                    blinding_factor.as_mut_ptr(),
+                )
+            });
+            let blinded_message = unsafe { blinded_message.assume_init() };
+            let blinding_factor = unsafe { &*blinding_factor.assume_init() };
+
+            (blinded_message, blinding_factor)
+        } else {
+            (Buffer::from(&msg[..]), &empty_token)
+        };
 
         // 2. partially sign the blinded message
         //
@@ -993,609 +2694,1632 @@ mod tests {
             let share = Buffer::from(&share[..]);
             let mut partial_sig = MaybeUninit::<Buffer>::uninit();
             let ret =
-                unsafe { partial_sign_fn(&share, &message_to_sign, partial_sig.as_mut_ptr()) };
-            assert!(ret);
+                unsafe { partial_sign_fn(&share, &message_to_sign, partial_sig.as_mut_ptr()) };
+            assert!(ret);
+
+            let partial_sig = unsafe { partial_sig.assume_init() };
+            sigs.push(partial_sig);
+        }
+
+        // 3. verify the partial signatures & concatenate them
+        let polynomial = bincode::serialize(&keys.polynomial).unwrap();
+        let public_poly = Buffer::from(&polynomial[..]);
+        let public_key = &public_poly as *const _;
+        let mut concatenated = Vec::new();
+        for sig in &sigs {
+            let sig_slice = unsafe { buffer_slice(sig) }.unwrap();
+            concatenated.extend_from_slice(sig_slice);
+            let ret = unsafe { partial_verify_fn(public_key, &message_to_sign, sig) };
+            assert!(ret);
+        }
+        let concatenated = Buffer::from(&concatenated[..]);
+
+        // 4. generate the threshold signature
+        let mut asig = MaybeUninit::<Buffer>::uninit();
+        let ret = unsafe { combine(&public_poly, &concatenated, asig.as_mut_ptr()) };
+        assert!(ret);
+        let asig = unsafe { asig.assume_init() };
+
+        // 5. unblind the threshold signature
+        let asig = if should_blind {
+            let mut unblinded = MaybeUninit::<Buffer>::uninit();
+            let ret = unsafe { unblind(&asig, blinding_factor, unblinded.as_mut_ptr()) };
+            assert!(ret);
+            unsafe { unblinded.assume_init() }
+        } else {
+            asig
+        };
+
+        // 6. verify the threshold signature against the public key
+        let ret = unsafe {
+            verify(
+                &keys.threshold_public_key as *const _,
+                &Buffer::from(&msg[..]),
+                &asig,
+            )
+        };
+        assert!(ret);
+    }
+
+    // `combine` splits its input into PARTIAL_SIG_LENGTH chunks, so the
+    // boundaries between partials are implied by the length alone. A remainder
+    // means the caller flattened them wrongly, and chunking it anyway cuts the
+    // tail from the middle of two partials.
+    //
+    // This pins the contract rather than guarding the check: the misaligned
+    // tail fails to deserialize as well, and `bool` carries no message to tell
+    // the two apart. The wasm test asserts on the error text, which does.
+    #[test]
+    fn combine_rejects_a_partial_chunk() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let msg = Buffer::from(&[1u8, 9, 6, 9][..]);
+        let (n, t) = (5, 3);
+        let keys = threshold_keygen(n, t, &seed[..]);
+
+        let mut concatenated = Vec::new();
+        for share in keys.shares.iter().take(t) {
+            let share = bincode::serialize(share).unwrap();
+            let mut partial = MaybeUninit::<Buffer>::uninit();
+            assert!(unsafe { partial_sign(&Buffer::from(&share[..]), &msg, partial.as_mut_ptr()) });
+            let partial = unsafe { partial.assume_init() };
+            concatenated.extend_from_slice(unsafe { buffer_slice(&partial) }.unwrap());
+        }
+        assert_eq!(concatenated.len(), t * PARTIAL_SIG_LENGTH);
+
+        let polynomial = bincode::serialize(&keys.polynomial).unwrap();
+        let polynomial = Buffer::from(&polynomial[..]);
+
+        let mut asig = MaybeUninit::<Buffer>::uninit();
+        assert!(unsafe {
+            combine(
+                &polynomial,
+                &Buffer::from(&concatenated[..]),
+                asig.as_mut_ptr(),
+            )
+        });
+
+        let mut one_over = concatenated.clone();
+        one_over.push(0);
+        let one_short = &concatenated[..concatenated.len() - 1];
+        let mut half_a_partial = concatenated.clone();
+        half_a_partial.extend(vec![0u8; PARTIAL_SIG_LENGTH / 2]);
+
+        for wrong in [&one_over[..], one_short, &half_a_partial[..]] {
+            let ret = unsafe { combine(&polynomial, &Buffer::from(wrong), asig.as_mut_ptr()) };
+            assert!(!ret, "combine accepted {} bytes", wrong.len());
+        }
+    }
+
+    #[test]
+    fn verify_ffi() {
+        verify_ffi_should_blind(true);
+        verify_ffi_should_blind(false);
+    }
+
+    fn verify_ffi_should_blind(should_blind: bool) {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let msg = vec![1u8, 2, 3, 4, 6];
+        let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
+        let empty_token = BlindingFactor(Token::new());
+
+        let sign_fn = if should_blind {
+            sign_blinded_message
+        } else {
+            sign
+        };
+
+        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
+        let keypair = unsafe { &*keypair.assume_init() };
+
+        let (message_to_sign, blinding_factor) = if should_blind {
+            let mut blinded_message = MaybeUninit::<Buffer>::uninit();
+            let mut blinding_factor = MaybeUninit::<*mut BlindingFactor>::uninit();
+            assert!(unsafe {
+                blind(
+                    &Buffer::from(msg.as_ref()),
+                    &Buffer::from(user_seed),
+                    blinded_message.as_mut_ptr(),
+                    blinding_factor.as_mut_ptr(),
+                )
+            });
+            let blinded_message = unsafe { blinded_message.assume_init() };
+            let blinding_factor = unsafe { &*blinding_factor.assume_init() };
+
+            (blinded_message, blinding_factor)
+        } else {
+            (Buffer::from(&msg[..]), &empty_token)
+        };
+
+        let mut sig = MaybeUninit::<Buffer>::uninit();
+        let ret = unsafe { sign_fn(private_key_ptr(keypair), &message_to_sign, sig.as_mut_ptr()) };
+        assert!(ret);
+        let sig = unsafe { sig.assume_init() };
+
+        let sig = if should_blind {
+            let mut unblinded = MaybeUninit::<Buffer>::uninit();
+            let ret = unsafe { unblind(&sig, blinding_factor, unblinded.as_mut_ptr()) };
+            assert!(ret);
+
+            unsafe { unblinded.assume_init() }
+        } else {
+            sig
+        };
+
+        let ret = unsafe { verify(public_key_ptr(keypair), &Buffer::from(&msg[..]), &sig) };
+        assert!(ret);
+    }
+
+    #[test]
+    fn private_key_serialization() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
+        let keypair = unsafe { &*keypair.assume_init() };
+
+        let private_key_ptr = unsafe { private_key_ptr(keypair) };
+        let private_key = unsafe { &*private_key_ptr };
+        let marshalled = bincode::serialize(private_key).unwrap();
+
+        let mut privkey_buf = MaybeUninit::<*mut u8>::uninit();
+
+        let ret = unsafe { serialize_privkey(private_key_ptr, privkey_buf.as_mut_ptr()) };
+        assert!(ret);
+
+        let privkey_buf = unsafe { privkey_buf.assume_init() };
+        let message = unsafe { std::slice::from_raw_parts(privkey_buf, PRIVKEY_LEN) };
+        assert_eq!(marshalled, message);
+
+        let unmarshalled: PrivateKey = serialization::deserialize(message).unwrap();
+        assert_eq!(&unmarshalled, private_key);
+
+        let mut de = MaybeUninit::<*mut PrivateKey>::uninit();
+        let ret = unsafe { deserialize_privkey(&Buffer::from(message), de.as_mut_ptr()) };
+        assert!(ret);
+        let de = unsafe { de.assume_init() };
+
+        assert_eq!(private_key, unsafe { &*de });
+    }
+
+    #[test]
+    fn public_key_serialization() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
+        let keypair = unsafe { &*keypair.assume_init() };
+
+        let public_key_ptr = unsafe { public_key_ptr(keypair) };
+        let public_key = unsafe { &*public_key_ptr };
+
+        let marshalled = bincode::serialize(public_key).unwrap();
+
+        let mut pubkey_buf = MaybeUninit::<*mut u8>::uninit();
+
+        let ret = unsafe { serialize_pubkey(public_key_ptr, pubkey_buf.as_mut_ptr()) };
+        assert!(ret);
+
+        let pubkey_buf = unsafe { pubkey_buf.assume_init() };
+        // the serialized result
+        let message = unsafe { std::slice::from_raw_parts(pubkey_buf, PUBKEY_LEN) };
+        assert_eq!(marshalled, message);
+
+        let unmarshalled: PublicKey = serialization::deserialize(message).unwrap();
+        assert_eq!(&unmarshalled, public_key);
+
+        let mut de = MaybeUninit::<*mut PublicKey>::uninit();
+        let ret = unsafe { deserialize_pubkey(&Buffer::from(message), de.as_mut_ptr()) };
+        assert!(ret);
+        let de = unsafe { de.assume_init() };
+
+        assert_eq!(public_key, unsafe { &*de });
+    }
+
+    /// The identity is a well-formed encoding, so it still deserializes — the
+    /// C ABI's existing guards only cover malformed input. Verification is what
+    /// has to reject it.
+    #[test]
+    fn identity_public_key_verifies_nothing() {
+        use threshold_bls::group::Element;
+
+        let identity = bincode::serialize(&PublicKey::zero()).unwrap();
+        assert_eq!(identity.len(), PUBKEY_LEN);
+
+        let mut pubkey = MaybeUninit::<*mut PublicKey>::uninit();
+        let ret = unsafe { deserialize_pubkey(&Buffer::from(&identity[..]), pubkey.as_mut_ptr()) };
+        assert!(ret, "the identity encoding is well-formed");
+        let pubkey = unsafe { pubkey.assume_init() };
+
+        let identity_sig = bincode::serialize(&Signature::zero()).unwrap();
+        for msg in [&b"attack at dawn"[..], b"totally different", b""] {
+            let ret =
+                unsafe { verify(pubkey, &Buffer::from(msg), &Buffer::from(&identity_sig[..])) };
+            assert!(!ret, "identity public key verified {msg:?}");
+        }
+    }
 
-            let partial_sig = unsafe { partial_sig.assume_init() };
-            sigs.push(partial_sig);
+    // The six serialize/deserialize functions document that a NULL argument
+    // returns false. Before the guards landed in the shared helpers they
+    // dereferenced it instead, so `deserialize_pubkey(NULL, &out)` read 96
+    // bytes from address zero.
+    #[test]
+    fn serialization_rejects_null() {
+        let mut pubkey_out = MaybeUninit::<*mut PublicKey>::uninit();
+        let mut privkey_out = MaybeUninit::<*mut PrivateKey>::uninit();
+        let mut sig_out = MaybeUninit::<*mut Signature>::uninit();
+        let mut bytes_out = MaybeUninit::<*mut u8>::uninit();
+
+        unsafe {
+            assert!(!deserialize_pubkey(
+                std::ptr::null(),
+                pubkey_out.as_mut_ptr()
+            ));
+            assert!(!deserialize_privkey(
+                std::ptr::null(),
+                privkey_out.as_mut_ptr()
+            ));
+            assert!(!deserialize_sig(std::ptr::null(), sig_out.as_mut_ptr()));
+
+            assert!(!serialize_pubkey(std::ptr::null(), bytes_out.as_mut_ptr()));
+            assert!(!serialize_privkey(std::ptr::null(), bytes_out.as_mut_ptr()));
+            assert!(!serialize_sig(std::ptr::null(), bytes_out.as_mut_ptr()));
         }
+    }
 
-        // 3. verify the partial signatures & concatenate them
-        let polynomial = bincode::serialize(&keys.polynomial).unwrap();
-        let public_poly = Buffer::from(&polynomial[..]);
-        let public_key = &public_poly as *const _;
-        let mut concatenated = Vec::new();
-        for sig in &sigs {
-            let sig_slice = unsafe { buffer_slice(sig) }.unwrap();
-            concatenated.extend_from_slice(sig_slice);
-            let ret = unsafe { partial_verify_fn(public_key, &message_to_sign, sig) };
-            assert!(ret);
+    #[test]
+    fn serialization_rejects_null_output() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
+        let keypair = unsafe { &*keypair.assume_init() };
+        let pubkey = unsafe { public_key_ptr(keypair) };
+        let marshalled = bincode::serialize(unsafe { &*pubkey }).unwrap();
+
+        unsafe {
+            assert!(!serialize_pubkey(pubkey, std::ptr::null_mut()));
+            assert!(!deserialize_pubkey(
+                &Buffer::from(&marshalled[..]),
+                std::ptr::null_mut()
+            ));
         }
-        let concatenated = Buffer::from(&concatenated[..]);
+    }
 
-        // 4. generate the threshold signature
-        let mut asig = MaybeUninit::<Buffer>::uninit();
-        let ret = unsafe { combine(&public_poly, &concatenated, asig.as_mut_ptr()) };
-        assert!(ret);
-        let asig = unsafe { asig.assume_init() };
+    // The three fixed-size deserializes read a constant number of bytes. While
+    // they took a bare pointer, a caller holding fewer than that had no way to
+    // say so and the read ran off the end of their allocation.
+    #[test]
+    fn deserialization_rejects_a_wrong_length_buffer() {
+        use threshold_bls::group::Element;
 
-        // 5. unblind the threshold signature
-        let asig = if should_blind {
-            let mut unblinded = MaybeUninit::<Buffer>::uninit();
-            let ret = unsafe { unblind(&asig, blinding_factor, unblinded.as_mut_ptr()) };
-            assert!(ret);
-            unsafe { unblinded.assume_init() }
-        } else {
-            asig
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
+        let keypair = unsafe { &*keypair.assume_init() };
+
+        let pubkey = bincode::serialize(unsafe { &*public_key_ptr(keypair) }).unwrap();
+        let privkey = bincode::serialize(unsafe { &*private_key_ptr(keypair) }).unwrap();
+        let sig = bincode::serialize(&Signature::zero()).unwrap();
+        let encodings = [
+            (pubkey, PUBKEY_LEN),
+            (privkey, PRIVKEY_LEN),
+            (sig, SIGNATURE_LEN),
+        ];
+
+        let mut pubkey_out = MaybeUninit::<*mut PublicKey>::uninit();
+        let mut privkey_out = MaybeUninit::<*mut PrivateKey>::uninit();
+        let mut sig_out = MaybeUninit::<*mut Signature>::uninit();
+
+        // Each encoding is exactly the length the header states, and is
+        // accepted at that length — so the rejections below are the length and
+        // nothing else.
+        for (encoding, len) in &encodings {
+            assert_eq!(encoding.len(), *len);
+        }
+        unsafe {
+            assert!(deserialize_pubkey(
+                &Buffer::from(&encodings[0].0[..]),
+                pubkey_out.as_mut_ptr()
+            ));
+            assert!(deserialize_privkey(
+                &Buffer::from(&encodings[1].0[..]),
+                privkey_out.as_mut_ptr()
+            ));
+            assert!(deserialize_sig(
+                &Buffer::from(&encodings[2].0[..]),
+                sig_out.as_mut_ptr()
+            ));
+        }
+
+        for (encoding, len) in &encodings {
+            let mut too_long = encoding.clone();
+            too_long.push(0);
+
+            for wrong in [&encoding[..0], &encoding[..len - 1], &too_long[..]] {
+                let buffer = Buffer::from(wrong);
+                unsafe {
+                    assert!(!deserialize_pubkey(&buffer, pubkey_out.as_mut_ptr()));
+                    assert!(!deserialize_privkey(&buffer, privkey_out.as_mut_ptr()));
+                    assert!(!deserialize_sig(&buffer, sig_out.as_mut_ptr()));
+                }
+            }
+        }
+    }
+
+    // Every buffer argument on the C surface reaches Rust through
+    // `buffer_slice`, so the whole surface inherits what it rejects.
+    #[test]
+    fn buffer_slice_validates_the_buffer() {
+        let bytes = [1u8, 2, 3];
+
+        assert_eq!(unsafe { buffer_slice(std::ptr::null()) }, None);
+
+        // A length behind a NULL pointer describes memory the caller does not
+        // have. Reading it as empty would sign or verify the empty message.
+        let lying = Buffer {
+            ptr: std::ptr::null(),
+            len: bytes.len(),
         };
+        assert_eq!(unsafe { buffer_slice(&lying) }, None);
 
-        // 6. verify the threshold signature against the public key
-        let ret = unsafe {
-            verify(
-                &keys.threshold_public_key as *const _,
-                &Buffer::from(&msg[..]),
-                &asig,
-            )
+        // An empty buffer, however the caller spells it.
+        let empty = Buffer {
+            ptr: std::ptr::null(),
+            len: 0,
         };
-        assert!(ret);
+        assert_eq!(unsafe { buffer_slice(&empty) }, Some(&[][..]));
+        assert_eq!(
+            unsafe { buffer_slice(&Buffer::from(&bytes[..0])) },
+            Some(&[][..])
+        );
+
+        assert_eq!(
+            unsafe { buffer_slice(&Buffer::from(&bytes[..])) },
+            Some(&bytes[..])
+        );
     }
 
-    // `combine` splits its input into PARTIAL_SIG_LENGTH chunks, so the
-    // boundaries between partials are implied by the length alone. A remainder
-    // means the caller flattened them wrongly, and chunking it anyway cuts the
-    // tail from the middle of two partials.
-    //
-    // This pins the contract rather than guarding the check: the misaligned
-    // tail fails to deserialize as well, and `bool` carries no message to tell
-    // the two apart. The wasm test asserts on the error text, which does.
-    #[test]
-    fn combine_rejects_a_partial_chunk() {
-        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
-        let msg = Buffer::from(&[1u8, 9, 6, 9][..]);
-        let (n, t) = (5, 3);
-        let keys = threshold_keygen(n, t, &seed[..]);
+    /// A buffer claiming a length behind a NULL pointer must be refused by
+    /// every entry point that takes one, rather than dereferenced.
+    #[test]
+    fn entry_points_reject_a_buffer_with_no_memory() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
+        let msg = [1u8, 2, 3, 4, 6];
+        let (n, t) = (5, 3);
+
+        // Arguments that work. Every call below is asserted to succeed with
+        // these, then repeated with one buffer swapped for one that has no
+        // memory behind it, so a `false` is attributable to that swap and not
+        // to an argument that would have been rejected anyway.
+        let keys = threshold_keygen(n, t, &seed[..]);
+        let share_bytes = bincode::serialize(&keys.shares[0]).unwrap();
+        let polynomial_bytes = bincode::serialize(&keys.polynomial).unwrap();
+        let seed_buffer = Buffer::from(&seed[..]);
+        let message = Buffer::from(&msg[..]);
+        let share = Buffer::from(&share_bytes[..]);
+        let polynomial = Buffer::from(&polynomial_bytes[..]);
+
+        let no_memory = Buffer {
+            ptr: std::ptr::null(),
+            len: msg.len(),
+        };
+        let mut out = MaybeUninit::<Buffer>::uninit();
+        let mut factor = MaybeUninit::<*mut BlindingFactor>::uninit();
+
+        unsafe {
+            let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+            assert!(keygen(&seed_buffer, keypair.as_mut_ptr()));
+            assert!(!keygen(&no_memory, keypair.as_mut_ptr()));
+            let keypair = keypair.assume_init();
+            let (privkey, pubkey) = (private_key_ptr(keypair), public_key_ptr(keypair));
+
+            assert!(sign(privkey, &message, out.as_mut_ptr()));
+            assert!(!sign(privkey, &no_memory, out.as_mut_ptr()));
+            let signature = out.assume_init_read();
+
+            assert!(verify(pubkey, &message, &signature));
+            assert!(!verify(pubkey, &no_memory, &signature));
+            assert!(!verify(pubkey, &message, &no_memory));
+
+            assert!(blind(
+                &message,
+                &Buffer::from(user_seed),
+                out.as_mut_ptr(),
+                factor.as_mut_ptr()
+            ));
+            assert!(!blind(
+                &no_memory,
+                &Buffer::from(user_seed),
+                out.as_mut_ptr(),
+                factor.as_mut_ptr()
+            ));
+            assert!(!blind(
+                &message,
+                &no_memory,
+                out.as_mut_ptr(),
+                factor.as_mut_ptr()
+            ));
+            let blinded = out.assume_init_read();
+            let blinding_factor = factor.assume_init();
+
+            assert!(sign_blinded_message(privkey, &blinded, out.as_mut_ptr()));
+            assert!(!sign_blinded_message(privkey, &no_memory, out.as_mut_ptr()));
+            let blind_signature = out.assume_init_read();
+
+            assert!(unblind(&blind_signature, blinding_factor, out.as_mut_ptr()));
+            assert!(!unblind(&no_memory, blinding_factor, out.as_mut_ptr()));
+            let unblinded = out.assume_init_read();
+
+            assert!(partial_sign(&share, &message, out.as_mut_ptr()));
+            assert!(!partial_sign(&no_memory, &message, out.as_mut_ptr()));
+            assert!(!partial_sign(&share, &no_memory, out.as_mut_ptr()));
+            let partial = out.assume_init_read();
 
-        let mut concatenated = Vec::new();
-        for share in keys.shares.iter().take(t) {
-            let share = bincode::serialize(share).unwrap();
-            let mut partial = MaybeUninit::<Buffer>::uninit();
-            assert!(unsafe { partial_sign(&Buffer::from(&share[..]), &msg, partial.as_mut_ptr()) });
-            let partial = unsafe { partial.assume_init() };
-            concatenated.extend_from_slice(unsafe { buffer_slice(&partial) }.unwrap());
-        }
-        assert_eq!(concatenated.len(), t * PARTIAL_SIG_LENGTH);
+            assert!(partial_verify(&polynomial, &message, &partial));
+            assert!(!partial_verify(&no_memory, &message, &partial));
+            assert!(!partial_verify(&polynomial, &no_memory, &partial));
+            assert!(!partial_verify(&polynomial, &message, &no_memory));
 
-        let polynomial = bincode::serialize(&keys.polynomial).unwrap();
-        let polynomial = Buffer::from(&polynomial[..]);
+            assert!(partial_sign_blinded_message(
+                &share,
+                &blinded,
+                out.as_mut_ptr()
+            ));
+            assert!(!partial_sign_blinded_message(
+                &no_memory,
+                &blinded,
+                out.as_mut_ptr()
+            ));
+            assert!(!partial_sign_blinded_message(
+                &share,
+                &no_memory,
+                out.as_mut_ptr()
+            ));
+            let blind_partial = out.assume_init_read();
 
-        let mut asig = MaybeUninit::<Buffer>::uninit();
-        assert!(unsafe {
-            combine(
+            assert!(partial_verify_blind_signature(
                 &polynomial,
-                &Buffer::from(&concatenated[..]),
-                asig.as_mut_ptr(),
-            )
-        });
+                &blinded,
+                &blind_partial
+            ));
+            assert!(!partial_verify_blind_signature(
+                &no_memory,
+                &blinded,
+                &blind_partial
+            ));
+            assert!(!partial_verify_blind_signature(
+                &polynomial,
+                &no_memory,
+                &blind_partial
+            ));
+            assert!(!partial_verify_blind_signature(
+                &polynomial,
+                &blinded,
+                &no_memory
+            ));
 
-        let mut one_over = concatenated.clone();
-        one_over.push(0);
-        let one_short = &concatenated[..concatenated.len() - 1];
-        let mut half_a_partial = concatenated.clone();
-        half_a_partial.extend(vec![0u8; PARTIAL_SIG_LENGTH / 2]);
+            // `combine` needs a threshold's worth of partials, so build them
+            // from the remaining shares.
+            let mut concatenated = Vec::new();
+            for share in keys.shares.iter().take(t) {
+                let share_bytes = bincode::serialize(share).unwrap();
+                assert!(partial_sign(
+                    &Buffer::from(&share_bytes[..]),
+                    &message,
+                    out.as_mut_ptr()
+                ));
+                let partial = out.assume_init_read();
+                concatenated.extend_from_slice(buffer_slice(&partial).unwrap());
+                free_vector(partial.ptr, partial.len);
+            }
+            let partials = Buffer::from(&concatenated[..]);
+            let polynomial_bytes = bincode::serialize(&keys.polynomial).unwrap();
+            let polynomial = Buffer::from(&polynomial_bytes[..]);
+            assert!(combine(&polynomial, &partials, out.as_mut_ptr()));
+            assert!(!combine(&polynomial, &no_memory, out.as_mut_ptr()));
+            assert!(!combine(&no_memory, &partials, out.as_mut_ptr()));
+            let combined = out.assume_init_read();
 
-        for wrong in [&one_over[..], one_short, &half_a_partial[..]] {
-            let ret = unsafe { combine(&polynomial, &Buffer::from(wrong), asig.as_mut_ptr()) };
-            assert!(!ret, "combine accepted {} bytes", wrong.len());
+            for buffer in [
+                signature,
+                blinded,
+                blind_signature,
+                unblinded,
+                partial,
+                blind_partial,
+                combined,
+            ] {
+                free_vector(buffer.ptr, buffer.len);
+            }
+            destroy_token(blinding_factor);
+            destroy_keypair(keypair);
         }
     }
 
+    // A seed too short to fill the RNG's state used to be sliced to length,
+    // which panicked — and a panic crossing `extern "C"` aborts the process the
+    // library was called from, which for the mobile consumer is the whole app.
     #[test]
-    fn verify_ffi() {
-        verify_ffi_should_blind(true);
-        verify_ffi_should_blind(false);
-    }
-
-    fn verify_ffi_should_blind(should_blind: bool) {
-        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
-        let msg = vec![1u8, 2, 3, 4, 6];
-        let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
-        let empty_token = BlindingFactor(Token::new());
-
-        let sign_fn = if should_blind {
-            sign_blinded_message
-        } else {
-            sign
-        };
-
+    fn a_short_seed_is_rejected() {
+        let short = [7u8; SEED_LEN - 1];
+        let msg = [1u8, 2, 3, 4, 6];
         let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
-        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
-        let keypair = unsafe { &*keypair.assume_init() };
-
-        let (message_to_sign, blinding_factor) = if should_blind {
-            let mut blinded_message = MaybeUninit::<Buffer>::uninit();
-            let mut blinding_factor = MaybeUninit::<*mut BlindingFactor>::uninit();
-            assert!(unsafe {
-                blind(
-                    &Buffer::from(msg.as_ref()),
-                    &Buffer::from(user_seed),
-                    blinded_message.as_mut_ptr(),
-                    blinding_factor.as_mut_ptr(),
-                )
-            });
-            let blinded_message = unsafe { blinded_message.assume_init() };
-            let blinding_factor = unsafe { &*blinding_factor.assume_init() };
-
-            (blinded_message, blinding_factor)
-        } else {
-            (Buffer::from(&msg[..]), &empty_token)
-        };
+        let mut blinded = MaybeUninit::<Buffer>::uninit();
+        let mut blinding_factor = MaybeUninit::<*mut BlindingFactor>::uninit();
 
-        let mut sig = MaybeUninit::<Buffer>::uninit();
-        let ret = unsafe { sign_fn(private_key_ptr(keypair), &message_to_sign, sig.as_mut_ptr()) };
-        assert!(ret);
-        let sig = unsafe { sig.assume_init() };
+        unsafe {
+            assert!(!keygen(&Buffer::from(&short[..]), keypair.as_mut_ptr()));
+            assert!(!keygen(&Buffer::from(&short[..0]), keypair.as_mut_ptr()));
 
-        let sig = if should_blind {
-            let mut unblinded = MaybeUninit::<Buffer>::uninit();
-            let ret = unsafe { unblind(&sig, blinding_factor, unblinded.as_mut_ptr()) };
-            assert!(ret);
+            assert!(!blind(
+                &Buffer::from(&msg[..]),
+                &Buffer::from(&short[..]),
+                blinded.as_mut_ptr(),
+                blinding_factor.as_mut_ptr()
+            ));
+        }
+    }
 
-            unsafe { unblinded.assume_init() }
-        } else {
-            sig
-        };
+    // The whole seed is consumed, so the exact length is enough: the check is
+    // not off by one in the other direction.
+    #[test]
+    fn a_seed_of_exactly_the_required_length_is_accepted() {
+        let seed = [7u8; SEED_LEN];
+        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
 
-        let ret = unsafe { verify(public_key_ptr(keypair), &Buffer::from(&msg[..]), &sig) };
-        assert!(ret);
+        unsafe {
+            assert!(keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()));
+            destroy_keypair(keypair.assume_init());
+        }
     }
 
+    // `keygen` used to return void, so a caller had no way to learn that it had
+    // written nothing.
     #[test]
-    fn private_key_serialization() {
+    fn keygen_reports_failure() {
         let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
-
         let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
-        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
-        let keypair = unsafe { &*keypair.assume_init() };
 
-        let private_key_ptr = unsafe { private_key_ptr(keypair) };
-        let private_key = unsafe { &*private_key_ptr };
-        let marshalled = bincode::serialize(private_key).unwrap();
+        unsafe {
+            assert!(!keygen(std::ptr::null(), keypair.as_mut_ptr()));
+            assert!(!keygen(&Buffer::from(&seed[..]), std::ptr::null_mut()));
+        }
+    }
 
-        let mut privkey_buf = MaybeUninit::<*mut u8>::uninit();
+    #[test]
+    fn key_accessors_reject_null() {
+        unsafe {
+            assert!(public_key_ptr(std::ptr::null()).is_null());
+            assert!(private_key_ptr(std::ptr::null()).is_null());
+            assert!(g1_public_key_ptr(std::ptr::null()).is_null());
+            assert!(g1_private_key_ptr(std::ptr::null()).is_null());
+        }
+    }
 
-        let ret = unsafe { serialize_privkey(private_key_ptr, privkey_buf.as_mut_ptr()) };
-        assert!(ret);
+    #[test]
+    fn oprf_ffi() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
+        let input = Buffer::from(&b"user@example.com"[..]);
 
-        let privkey_buf = unsafe { privkey_buf.assume_init() };
-        let message = unsafe { std::slice::from_raw_parts(privkey_buf, PRIVKEY_LEN) };
-        assert_eq!(marshalled, message);
+        unsafe {
+            let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+            assert!(keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()));
+            let keypair = keypair.assume_init();
+            let (privkey, pubkey) = (private_key_ptr(keypair), public_key_ptr(keypair));
 
-        let unmarshalled: PrivateKey = serialization::deserialize(message).unwrap();
-        assert_eq!(&unmarshalled, private_key);
+            let mut sig = MaybeUninit::<Buffer>::uninit();
+            assert!(sign(privkey, &input, sig.as_mut_ptr()));
+            let sig = sig.assume_init();
+
+            let mut output = MaybeUninit::<Buffer>::uninit();
+            assert!(oprf_finalize(pubkey, &input, &sig, output.as_mut_ptr()));
+            let output = output.assume_init();
+            assert_eq!(output.len, OPRF_OUTPUT_LEN);
+            let expected = oprf::finalize::<SigScheme>(
+                &*pubkey,
+                buffer_slice(&input).unwrap(),
+                buffer_slice(&sig).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(buffer_slice(&output).unwrap(), &expected[..]);
 
-        let mut de = MaybeUninit::<*mut PrivateKey>::uninit();
-        let ret = unsafe { deserialize_privkey(&Buffer::from(message), de.as_mut_ptr()) };
-        assert!(ret);
-        let de = unsafe { de.assume_init() };
+            let mut blinded = MaybeUninit::<Buffer>::uninit();
+            let mut factor = MaybeUninit::<*mut BlindingFactor>::uninit();
+            assert!(blind(
+                &input,
+                &Buffer::from(user_seed),
+                blinded.as_mut_ptr(),
+                factor.as_mut_ptr()
+            ));
+            let (blinded, factor) = (blinded.assume_init(), factor.assume_init());
 
-        assert_eq!(private_key, unsafe { &*de });
+            let mut blind_sig = MaybeUninit::<Buffer>::uninit();
+            assert!(sign_blinded_message(
+                privkey,
+                &blinded,
+                blind_sig.as_mut_ptr()
+            ));
+            let blind_sig = blind_sig.assume_init();
+
+            let mut unblinded_output = MaybeUninit::<Buffer>::uninit();
+            assert!(oprf_unblind_finalize(
+                pubkey,
+                &input,
+                &blind_sig,
+                factor,
+                unblinded_output.as_mut_ptr()
+            ));
+            let unblinded_output = unblinded_output.assume_init();
+            assert_eq!(buffer_slice(&unblinded_output), buffer_slice(&output));
+
+            // a signature on another input has no output for this one
+            let mut rejected = MaybeUninit::<Buffer>::uninit();
+            let other = Buffer::from(&b"other@example.com"[..]);
+            assert!(!oprf_finalize(pubkey, &other, &sig, rejected.as_mut_ptr()));
+            assert!(!oprf_unblind_finalize(
+                pubkey,
+                &other,
+                &blind_sig,
+                factor,
+                rejected.as_mut_ptr()
+            ));
+            assert!(!oprf_finalize(
+                std::ptr::null(),
+                &input,
+                &sig,
+                rejected.as_mut_ptr()
+            ));
+
+            for buffer in [sig, output, blinded, blind_sig, unblinded_output] {
+                free_vector(buffer.ptr, buffer.len);
+            }
+            destroy_token(factor);
+            destroy_keypair(keypair);
+        }
     }
 
     #[test]
-    fn public_key_serialization() {
+    fn g1_verify_ffi() {
         let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
+        let msg = Buffer::from(&[1u8, 2, 3, 4, 6][..]);
 
-        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
-        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
-        let keypair = unsafe { &*keypair.assume_init() };
-
-        let public_key_ptr = unsafe { public_key_ptr(keypair) };
-        let public_key = unsafe { &*public_key_ptr };
+        unsafe {
+            let mut keypair = MaybeUninit::<*mut G1Keypair>::uninit();
+            assert!(g1_keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()));
+            let keypair = keypair.assume_init();
+            let (privkey, pubkey) = (g1_private_key_ptr(keypair), g1_public_key_ptr(keypair));
+
+            let mut sig = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_sign(privkey, &msg, sig.as_mut_ptr()));
+            let sig = sig.assume_init();
+            assert_eq!(sig.len, G1_SIGNATURE_LEN);
+            assert!(g1_verify(pubkey, &msg, &sig));
+
+            let mut blinded = MaybeUninit::<Buffer>::uninit();
+            let mut factor = MaybeUninit::<*mut BlindingFactor>::uninit();
+            assert!(g1_blind(
+                &msg,
+                &Buffer::from(user_seed),
+                blinded.as_mut_ptr(),
+                factor.as_mut_ptr()
+            ));
+            let (blinded, factor) = (blinded.assume_init(), factor.assume_init());
 
-        let marshalled = bincode::serialize(public_key).unwrap();
+            let mut blind_sig = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_sign_blinded_message(
+                privkey,
+                &blinded,
+                blind_sig.as_mut_ptr()
+            ));
+            let blind_sig = blind_sig.assume_init();
 
-        let mut pubkey_buf = MaybeUninit::<*mut u8>::uninit();
+            let mut unblinded = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_unblind(&blind_sig, factor, unblinded.as_mut_ptr()));
+            let unblinded = unblinded.assume_init();
+            assert!(g1_verify(pubkey, &msg, &unblinded));
+            assert_eq!(buffer_slice(&unblinded), buffer_slice(&sig));
 
-        let ret = unsafe { serialize_pubkey(public_key_ptr, pubkey_buf.as_mut_ptr()) };
-        assert!(ret);
+            for buffer in [sig, blinded, blind_sig, unblinded] {
+                free_vector(buffer.ptr, buffer.len);
+            }
+            destroy_token(factor);
+            g1_destroy_keypair(keypair);
+        }
+    }
 
-        let pubkey_buf = unsafe { pubkey_buf.assume_init() };
-        // the serialized result
-        let message = unsafe { std::slice::from_raw_parts(pubkey_buf, PUBKEY_LEN) };
-        assert_eq!(marshalled, message);
+    #[test]
+    fn g1_threshold_verify_ffi() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
+        let msg = Buffer::from(&[1u8, 2, 3, 4, 6][..]);
+        let (n, t) = (5, 3);
+        let keys = threshold_keygen_for::<G1SigScheme>(n, t, &seed[..]);
+        let polynomial = bincode::serialize(&keys.polynomial).unwrap();
+        let polynomial = Buffer::from(&polynomial[..]);
 
-        let unmarshalled: PublicKey = serialization::deserialize(message).unwrap();
-        assert_eq!(&unmarshalled, public_key);
+        unsafe {
+            let mut blinded = MaybeUninit::<Buffer>::uninit();
+            let mut factor = MaybeUninit::<*mut BlindingFactor>::uninit();
+            assert!(g1_blind(
+                &msg,
+                &Buffer::from(user_seed),
+                blinded.as_mut_ptr(),
+                factor.as_mut_ptr()
+            ));
+            let (blinded, factor) = (blinded.assume_init(), factor.assume_init());
 
-        let mut de = MaybeUninit::<*mut PublicKey>::uninit();
-        let ret = unsafe { deserialize_pubkey(&Buffer::from(message), de.as_mut_ptr()) };
-        assert!(ret);
-        let de = unsafe { de.assume_init() };
+            let mut partials = Vec::new();
+            let mut blind_partials = Vec::new();
+            for share in keys.shares.iter().take(t) {
+                let share = bincode::serialize(share).unwrap();
+                let share = Buffer::from(&share[..]);
+                let mut out = MaybeUninit::<Buffer>::uninit();
 
-        assert_eq!(public_key, unsafe { &*de });
-    }
+                assert!(g1_partial_sign(&share, &msg, out.as_mut_ptr()));
+                let partial = out.assume_init_read();
+                assert_eq!(partial.len, G1_PARTIAL_SIG_LENGTH);
+                assert!(g1_partial_verify(&polynomial, &msg, &partial));
+                partials.extend_from_slice(buffer_slice(&partial).unwrap());
+                free_vector(partial.ptr, partial.len);
 
-    /// The identity is a well-formed encoding, so it still deserializes — the
-    /// C ABI's existing guards only cover malformed input. Verification is what
-    /// has to reject it.
-    #[test]
-    fn identity_public_key_verifies_nothing() {
-        use threshold_bls::group::Element;
+                assert!(g1_partial_sign_blinded_message(
+                    &share,
+                    &blinded,
+                    out.as_mut_ptr()
+                ));
+                let partial = out.assume_init_read();
+                assert!(g1_partial_verify_blind_signature(
+                    &polynomial,
+                    &blinded,
+                    &partial
+                ));
+                blind_partials.extend_from_slice(buffer_slice(&partial).unwrap());
+                free_vector(partial.ptr, partial.len);
+            }
 
-        let identity = bincode::serialize(&PublicKey::zero()).unwrap();
-        assert_eq!(identity.len(), PUBKEY_LEN);
+            let mut asig = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_combine(
+                &polynomial,
+                &Buffer::from(&partials[..]),
+                asig.as_mut_ptr()
+            ));
+            let asig = asig.assume_init();
+            assert!(g1_verify(&keys.threshold_public_key, &msg, &asig));
 
-        let mut pubkey = MaybeUninit::<*mut PublicKey>::uninit();
-        let ret = unsafe { deserialize_pubkey(&Buffer::from(&identity[..]), pubkey.as_mut_ptr()) };
-        assert!(ret, "the identity encoding is well-formed");
-        let pubkey = unsafe { pubkey.assume_init() };
+            let mut blind_asig = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_combine(
+                &polynomial,
+                &Buffer::from(&blind_partials[..]),
+                blind_asig.as_mut_ptr()
+            ));
+            let blind_asig = blind_asig.assume_init();
+            let mut unblinded = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_unblind(&blind_asig, factor, unblinded.as_mut_ptr()));
+            let unblinded = unblinded.assume_init();
+            assert!(g1_verify(&keys.threshold_public_key, &msg, &unblinded));
+
+            // The chunk size is the G1 partial's, so the G2 one misaligns it.
+            assert!(!partials.len().is_multiple_of(PARTIAL_SIG_LENGTH));
+            let mut rejected = MaybeUninit::<Buffer>::uninit();
+            assert!(!combine(
+                &polynomial,
+                &Buffer::from(&partials[..]),
+                rejected.as_mut_ptr()
+            ));
 
-        let identity_sig = bincode::serialize(&Signature::zero()).unwrap();
-        for msg in [&b"attack at dawn"[..], b"totally different", b""] {
-            let ret =
-                unsafe { verify(pubkey, &Buffer::from(msg), &Buffer::from(&identity_sig[..])) };
-            assert!(!ret, "identity public key verified {msg:?}");
+            for buffer in [blinded, asig, blind_asig, unblinded] {
+                free_vector(buffer.ptr, buffer.len);
+            }
+            destroy_token(factor);
         }
     }
 
-    // The six serialize/deserialize functions document that a NULL argument
-    // returns false. Before the guards landed in the shared helpers they
-    // dereferenced it instead, so `deserialize_pubkey(NULL, &out)` read 96
-    // bytes from address zero.
     #[test]
-    fn serialization_rejects_null() {
-        let mut pubkey_out = MaybeUninit::<*mut PublicKey>::uninit();
-        let mut privkey_out = MaybeUninit::<*mut PrivateKey>::uninit();
-        let mut sig_out = MaybeUninit::<*mut Signature>::uninit();
-        let mut bytes_out = MaybeUninit::<*mut u8>::uninit();
+    fn g1_serialization_round_trips_at_the_g1_lengths() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let msg = Buffer::from(&[1u8, 9, 6, 9][..]);
 
         unsafe {
+            let mut keypair = MaybeUninit::<*mut G1Keypair>::uninit();
+            assert!(g1_keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()));
+            let keypair = keypair.assume_init();
+
+            let mut bytes = MaybeUninit::<*mut u8>::uninit();
+            assert!(g1_serialize_pubkey(
+                g1_public_key_ptr(keypair),
+                bytes.as_mut_ptr()
+            ));
+            let bytes = bytes.assume_init();
+            let encoded = std::slice::from_raw_parts(bytes, G1_PUBKEY_LEN).to_vec();
+            free_vector(bytes, G1_PUBKEY_LEN);
+
+            let mut pubkey = MaybeUninit::<*mut G1PublicKey>::uninit();
+            assert!(g1_deserialize_pubkey(
+                &Buffer::from(&encoded[..]),
+                pubkey.as_mut_ptr()
+            ));
+            let pubkey = pubkey.assume_init();
+            assert_eq!(&*pubkey, &*g1_public_key_ptr(keypair));
+
+            let mut sig = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_sign(g1_private_key_ptr(keypair), &msg, sig.as_mut_ptr()));
+            let sig = sig.assume_init();
+            let mut handle = MaybeUninit::<*mut G1Signature>::uninit();
+            assert!(g1_deserialize_sig(&sig, handle.as_mut_ptr()));
+            let handle = handle.assume_init();
+            let mut bytes = MaybeUninit::<*mut u8>::uninit();
+            assert!(g1_serialize_sig(handle, bytes.as_mut_ptr()));
+            let bytes = bytes.assume_init();
+            assert_eq!(
+                std::slice::from_raw_parts(bytes, G1_SIGNATURE_LEN),
+                buffer_slice(&sig).unwrap()
+            );
+            free_vector(bytes, G1_SIGNATURE_LEN);
+
+            // Bytes from one scheme are never read as the other's: the lengths
+            // differ, so each deserializer refuses the other's encoding.
+            let mut g2_pubkey = MaybeUninit::<*mut PublicKey>::uninit();
+            let mut g2_sig = MaybeUninit::<*mut Signature>::uninit();
             assert!(!deserialize_pubkey(
+                &Buffer::from(&encoded[..]),
+                g2_pubkey.as_mut_ptr()
+            ));
+            assert!(!deserialize_sig(&sig, g2_sig.as_mut_ptr()));
+
+            let mut pubkey_out = MaybeUninit::<*mut G1PublicKey>::uninit();
+            let mut bytes_out = MaybeUninit::<*mut u8>::uninit();
+            assert!(!g1_deserialize_pubkey(
                 std::ptr::null(),
                 pubkey_out.as_mut_ptr()
             ));
-            assert!(!deserialize_privkey(
+            assert!(!g1_serialize_pubkey(
                 std::ptr::null(),
-                privkey_out.as_mut_ptr()
+                bytes_out.as_mut_ptr()
             ));
-            assert!(!deserialize_sig(std::ptr::null(), sig_out.as_mut_ptr()));
+            assert!(!g1_serialize_sig(handle, std::ptr::null_mut()));
 
-            assert!(!serialize_pubkey(std::ptr::null(), bytes_out.as_mut_ptr()));
-            assert!(!serialize_privkey(std::ptr::null(), bytes_out.as_mut_ptr()));
-            assert!(!serialize_sig(std::ptr::null(), bytes_out.as_mut_ptr()));
+            free_vector(sig.ptr, sig.len);
+            g1_destroy_sig(handle);
+            g1_destroy_pubkey(pubkey);
+            g1_destroy_keypair(keypair);
         }
     }
 
-    #[test]
-    fn serialization_rejects_null_output() {
-        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
-        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
-        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
-        let keypair = unsafe { &*keypair.assume_init() };
-        let pubkey = unsafe { public_key_ptr(keypair) };
-        let marshalled = bincode::serialize(unsafe { &*pubkey }).unwrap();
-
-        unsafe {
-            assert!(!serialize_pubkey(pubkey, std::ptr::null_mut()));
-            assert!(!deserialize_pubkey(
-                &Buffer::from(&marshalled[..]),
-                std::ptr::null_mut()
-            ));
-        }
+    // Freeing NULL is a no-op in C, and callers rely on it: a cleanup path that
+    // runs after a failed allocation has nothing else to pass.
+    /// Reads a result bitmap and hands its allocation back.
+    unsafe fn take_bitmap(valid: Buffer) -> Vec<u8> {
+        let bits = unsafe { buffer_slice(&valid) }.unwrap().to_vec();
+        unsafe { free_vector(valid.ptr, valid.len) };
+        bits
     }
 
-    // The three fixed-size deserializes read a constant number of bytes. While
-    // they took a bare pointer, a caller holding fewer than that had no way to
-    // say so and the read ran off the end of their allocation.
     #[test]
-    fn deserialization_rejects_a_wrong_length_buffer() {
-        use threshold_bls::group::Element;
-
+    fn batch_verify_ffi() {
         let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let messages = [&b"one"[..], b"two", b"three"];
+
         let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
         assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
-        let keypair = unsafe { &*keypair.assume_init() };
-
-        let pubkey = bincode::serialize(unsafe { &*public_key_ptr(keypair) }).unwrap();
-        let privkey = bincode::serialize(unsafe { &*private_key_ptr(keypair) }).unwrap();
-        let sig = bincode::serialize(&Signature::zero()).unwrap();
-        let encodings = [
-            (pubkey, PUBKEY_LEN),
-            (privkey, PRIVKEY_LEN),
-            (sig, SIGNATURE_LEN),
-        ];
+        let keypair = unsafe { keypair.assume_init() };
+        let (private, public) = unsafe { (private_key_ptr(keypair), public_key_ptr(keypair)) };
 
-        let mut pubkey_out = MaybeUninit::<*mut PublicKey>::uninit();
-        let mut privkey_out = MaybeUninit::<*mut PrivateKey>::uninit();
-        let mut sig_out = MaybeUninit::<*mut Signature>::uninit();
+        let mut signatures = messages
+            .iter()
+            .map(|message| SigScheme::sign(unsafe { &*private }, message).unwrap())
+            .collect::<Vec<_>>();
+        let messages = messages.map(Buffer::from);
+        let mut valid = MaybeUninit::<Buffer>::uninit();
 
-        // Each encoding is exactly the length the header states, and is
-        // accepted at that length — so the rejections below are the length and
-        // nothing else.
-        for (encoding, len) in &encodings {
-            assert_eq!(encoding.len(), *len);
-        }
         unsafe {
-            assert!(deserialize_pubkey(
-                &Buffer::from(&encodings[0].0[..]),
-                pubkey_out.as_mut_ptr()
+            let flattened = signatures.concat();
+            assert!(batch_verify(
+                public,
+                messages.as_ptr(),
+                3,
+                &Buffer::from(&flattened[..]),
+                valid.as_mut_ptr()
             ));
-            assert!(deserialize_privkey(
-                &Buffer::from(&encodings[1].0[..]),
-                privkey_out.as_mut_ptr()
+            assert_eq!(take_bitmap(valid.assume_init_read()), [0b111]);
+
+            // The middle signature is for another message now.
+            signatures[1] = SigScheme::sign(&*private, b"four").unwrap();
+            let flattened = signatures.concat();
+            assert!(batch_verify(
+                public,
+                messages.as_ptr(),
+                3,
+                &Buffer::from(&flattened[..]),
+                valid.as_mut_ptr()
             ));
-            assert!(deserialize_sig(
-                &Buffer::from(&encodings[2].0[..]),
-                sig_out.as_mut_ptr()
+            assert_eq!(take_bitmap(valid.assume_init_read()), [0b101]);
+
+            // Three signatures for two messages is a caller error, not a result.
+            assert!(!batch_verify(
+                public,
+                messages.as_ptr(),
+                2,
+                &Buffer::from(&flattened[..]),
+                valid.as_mut_ptr()
             ));
-        }
 
-        for (encoding, len) in &encodings {
-            let mut too_long = encoding.clone();
-            too_long.push(0);
+            // An empty batch needs no array at all.
+            assert!(batch_verify(
+                public,
+                std::ptr::null(),
+                0,
+                &Buffer::from(&[][..]),
+                valid.as_mut_ptr()
+            ));
+            assert_eq!(valid.assume_init_read().len, 0);
+            assert!(!batch_verify(
+                public,
+                std::ptr::null(),
+                3,
+                &Buffer::from(&flattened[..]),
+                valid.as_mut_ptr()
+            ));
 
-            for wrong in [&encoding[..0], &encoding[..len - 1], &too_long[..]] {
-                let buffer = Buffer::from(wrong);
-                unsafe {
-                    assert!(!deserialize_pubkey(&buffer, pubkey_out.as_mut_ptr()));
-                    assert!(!deserialize_privkey(&buffer, privkey_out.as_mut_ptr()));
-                    assert!(!deserialize_sig(&buffer, sig_out.as_mut_ptr()));
-                }
-            }
+            destroy_keypair(keypair);
         }
     }
 
-    // Every buffer argument on the C surface reaches Rust through
-    // `buffer_slice`, so the whole surface inherits what it rejects.
     #[test]
-    fn buffer_slice_validates_the_buffer() {
-        let bytes = [1u8, 2, 3];
+    fn batch_partial_verify_ffi() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let msg = &[1u8, 2, 3, 4, 6][..];
+        let (n, t) = (5, 3);
+        let keys = threshold_keygen(n, t, &seed[..]);
+        let polynomial = bincode::serialize(&keys.polynomial).unwrap();
+        let polynomial = Buffer::from(&polynomial[..]);
 
-        assert_eq!(unsafe { buffer_slice(std::ptr::null()) }, None);
+        let mut rng = get_rng(&seed[..]).unwrap();
+        let (_, blinded) = SigScheme::blind_msg(msg, &mut rng).unwrap();
+        let (_, other) = SigScheme::blind_msg(b"other", &mut rng).unwrap();
+        let mut partials = Vec::new();
+        let mut blind_partials = Vec::new();
+        for share in &keys.shares {
+            partials.push(SigScheme::partial_sign(share, msg).unwrap());
+            blind_partials.push(SigScheme::sign_blind_partial(share, &blinded).unwrap());
+        }
+        // Signer 3 signs something else in both batches.
+        partials[3] = SigScheme::partial_sign(&keys.shares[3], b"other").unwrap();
+        blind_partials[3] = SigScheme::sign_blind_partial(&keys.shares[3], &other).unwrap();
 
-        // A length behind a NULL pointer describes memory the caller does not
-        // have. Reading it as empty would sign or verify the empty message.
-        let lying = Buffer {
-            ptr: std::ptr::null(),
-            len: bytes.len(),
-        };
-        assert_eq!(unsafe { buffer_slice(&lying) }, None);
+        let mut valid = MaybeUninit::<Buffer>::uninit();
+        unsafe {
+            let flattened = partials.concat();
+            assert!(batch_partial_verify(
+                &polynomial,
+                &Buffer::from(msg),
+                &Buffer::from(&flattened[..]),
+                valid.as_mut_ptr()
+            ));
+            assert_eq!(take_bitmap(valid.assume_init_read()), [0b10111]);
 
-        // An empty buffer, however the caller spells it.
-        let empty = Buffer {
-            ptr: std::ptr::null(),
-            len: 0,
-        };
-        assert_eq!(unsafe { buffer_slice(&empty) }, Some(&[][..]));
-        assert_eq!(
-            unsafe { buffer_slice(&Buffer::from(&bytes[..0])) },
-            Some(&[][..])
-        );
+            let flattened = blind_partials.concat();
+            assert!(batch_partial_verify_blind_signature(
+                &polynomial,
+                &Buffer::from(&blinded[..]),
+                &Buffer::from(&flattened[..]),
+                valid.as_mut_ptr()
+            ));
+            assert_eq!(take_bitmap(valid.assume_init_read()), [0b10111]);
 
-        assert_eq!(
-            unsafe { buffer_slice(&Buffer::from(&bytes[..])) },
-            Some(&bytes[..])
-        );
+            assert!(!batch_partial_verify(
+                &polynomial,
+                &Buffer::from(msg),
+                &Buffer::from(&flattened[1..]),
+                valid.as_mut_ptr()
+            ));
+            assert!(!batch_partial_verify(
+                &Buffer::from(&polynomial_bytes_truncated(&keys)[..]),
+                &Buffer::from(msg),
+                &Buffer::from(&flattened[..]),
+                valid.as_mut_ptr()
+            ));
+        }
+    }
+
+    fn polynomial_bytes_truncated(keys: &Keys) -> Vec<u8> {
+        let mut bytes = bincode::serialize(&keys.polynomial).unwrap();
+        bytes.pop();
+        bytes
     }
 
-    /// A buffer claiming a length behind a NULL pointer must be refused by
-    /// every entry point that takes one, rather than dereferenced.
     #[test]
-    fn entry_points_reject_a_buffer_with_no_memory() {
+    fn batch_blind_and_unblind_ffi() {
         let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
         let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
-        let msg = [1u8, 2, 3, 4, 6];
-        let (n, t) = (5, 3);
-
-        // Arguments that work. Every call below is asserted to succeed with
-        // these, then repeated with one buffer swapped for one that has no
-        // memory behind it, so a `false` is attributable to that swap and not
-        // to an argument that would have been rejected anyway.
-        let keys = threshold_keygen(n, t, &seed[..]);
-        let share_bytes = bincode::serialize(&keys.shares[0]).unwrap();
-        let polynomial_bytes = bincode::serialize(&keys.polynomial).unwrap();
-        let seed_buffer = Buffer::from(&seed[..]);
-        let message = Buffer::from(&msg[..]);
-        let share = Buffer::from(&share_bytes[..]);
-        let polynomial = Buffer::from(&polynomial_bytes[..]);
+        let messages = [&b"one"[..], b"two", b"three"].map(Buffer::from);
 
-        let no_memory = Buffer {
-            ptr: std::ptr::null(),
-            len: msg.len(),
-        };
-        let mut out = MaybeUninit::<Buffer>::uninit();
-        let mut factor = MaybeUninit::<*mut BlindingFactor>::uninit();
+        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
+        let keypair = unsafe { keypair.assume_init() };
 
         unsafe {
-            let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
-            assert!(keygen(&seed_buffer, keypair.as_mut_ptr()));
-            assert!(!keygen(&no_memory, keypair.as_mut_ptr()));
-            let keypair = keypair.assume_init();
-            let (privkey, pubkey) = (private_key_ptr(keypair), public_key_ptr(keypair));
+            let mut blinded = MaybeUninit::<Buffer>::uninit();
+            let mut factors = [std::ptr::null_mut::<BlindingFactor>(); 3];
+            assert!(batch_blind(
+                messages.as_ptr(),
+                3,
+                &Buffer::from(user_seed),
+                blinded.as_mut_ptr(),
+                factors.as_mut_ptr()
+            ));
+            let blinded = blinded.assume_init();
+            assert_eq!(blinded.len, 3 * SIGNATURE_LEN);
+            assert!(factors.iter().all(|factor| !factor.is_null()));
+
+            let blind_signatures = buffer_slice(&blinded)
+                .unwrap()
+                .chunks(SIGNATURE_LEN)
+                .map(|message| SigScheme::blind_sign(&*private_key_ptr(keypair), message).unwrap())
+                .collect::<Vec<_>>()
+                .concat();
+            let blind_signatures = Buffer::from(&blind_signatures[..]);
+
+            let borrowed = factors.map(|factor| factor.cast_const());
+            let mut signatures = MaybeUninit::<Buffer>::uninit();
+            assert!(batch_unblind(
+                &blind_signatures,
+                borrowed.as_ptr(),
+                3,
+                signatures.as_mut_ptr()
+            ));
+            let signatures = signatures.assume_init();
+
+            let mut valid = MaybeUninit::<Buffer>::uninit();
+            assert!(batch_verify(
+                public_key_ptr(keypair),
+                messages.as_ptr(),
+                3,
+                &signatures,
+                valid.as_mut_ptr()
+            ));
+            assert_eq!(take_bitmap(valid.assume_init()), [0b111]);
+
+            // A missing factor refuses the batch instead of skipping the slot.
+            let mut missing = borrowed;
+            missing[1] = std::ptr::null();
+            let mut rejected = MaybeUninit::<Buffer>::uninit();
+            assert!(!batch_unblind(
+                &blind_signatures,
+                missing.as_ptr(),
+                3,
+                rejected.as_mut_ptr()
+            ));
+            assert!(!batch_unblind(
+                &blind_signatures,
+                borrowed.as_ptr(),
+                2,
+                rejected.as_mut_ptr()
+            ));
 
-            assert!(sign(privkey, &message, out.as_mut_ptr()));
-            assert!(!sign(privkey, &no_memory, out.as_mut_ptr()));
-            let signature = out.assume_init_read();
+            for buffer in [blinded, signatures] {
+                free_vector(buffer.ptr, buffer.len);
+            }
+            for factor in factors {
+                destroy_token(factor);
+            }
+            destroy_keypair(keypair);
+        }
+    }
 
-            assert!(verify(pubkey, &message, &signature));
-            assert!(!verify(pubkey, &no_memory, &signature));
-            assert!(!verify(pubkey, &message, &no_memory));
+    #[test]
+    fn g1_batches_ffi() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let user_seed = &b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"[..];
+        let messages = [&b"one"[..], b"two"].map(Buffer::from);
+        let keys = threshold_keygen_for::<G1SigScheme>(3, 2, &seed[..]);
+        let polynomial = bincode::serialize(&keys.polynomial).unwrap();
+        let polynomial = Buffer::from(&polynomial[..]);
 
-            assert!(blind(
-                &message,
+        unsafe {
+            let mut blinded = MaybeUninit::<Buffer>::uninit();
+            let mut factors = [std::ptr::null_mut::<BlindingFactor>(); 2];
+            assert!(g1_batch_blind(
+                messages.as_ptr(),
+                2,
                 &Buffer::from(user_seed),
-                out.as_mut_ptr(),
-                factor.as_mut_ptr()
+                blinded.as_mut_ptr(),
+                factors.as_mut_ptr()
             ));
-            assert!(!blind(
-                &no_memory,
-                &Buffer::from(user_seed),
-                out.as_mut_ptr(),
-                factor.as_mut_ptr()
+            let blinded = blinded.assume_init();
+            assert_eq!(blinded.len, 2 * G1_SIGNATURE_LEN);
+            let blinded_messages = buffer_slice(&blinded).unwrap().chunks(G1_SIGNATURE_LEN);
+
+            // Each blinded message is signed by the whole committee.
+            let mut blind_signatures = Vec::new();
+            for blinded_message in blinded_messages {
+                let partials = keys
+                    .shares
+                    .iter()
+                    .map(|share| G1SigScheme::sign_blind_partial(share, blinded_message).unwrap())
+                    .collect::<Vec<_>>()
+                    .concat();
+
+                let mut valid = MaybeUninit::<Buffer>::uninit();
+                assert!(g1_batch_partial_verify_blind_signature(
+                    &polynomial,
+                    &Buffer::from(blinded_message),
+                    &Buffer::from(&partials[..]),
+                    valid.as_mut_ptr()
+                ));
+                assert_eq!(take_bitmap(valid.assume_init()), [0b111]);
+
+                let chunks = partials
+                    .chunks(G1_PARTIAL_SIG_LENGTH)
+                    .map(<[u8]>::to_vec)
+                    .collect::<Vec<_>>();
+                blind_signatures.extend(G1SigScheme::aggregate(&keys.polynomial, &chunks).unwrap());
+            }
+
+            let borrowed = factors.map(|factor| factor.cast_const());
+            let mut signatures = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_batch_unblind(
+                &Buffer::from(&blind_signatures[..]),
+                borrowed.as_ptr(),
+                2,
+                signatures.as_mut_ptr()
             ));
-            assert!(!blind(
-                &message,
-                &no_memory,
-                out.as_mut_ptr(),
-                factor.as_mut_ptr()
+            let signatures = signatures.assume_init();
+
+            let mut valid = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_batch_verify(
+                &keys.threshold_public_key,
+                messages.as_ptr(),
+                2,
+                &signatures,
+                valid.as_mut_ptr()
             ));
-            let blinded = out.assume_init_read();
-            let blinding_factor = factor.assume_init();
-
-            assert!(sign_blinded_message(privkey, &blinded, out.as_mut_ptr()));
-            assert!(!sign_blinded_message(privkey, &no_memory, out.as_mut_ptr()));
-            let blind_signature = out.assume_init_read();
+            assert_eq!(take_bitmap(valid.assume_init()), [0b11]);
+
+            let partials = keys
+                .shares
+                .iter()
+                .map(|share| G1SigScheme::partial_sign(share, b"one").unwrap())
+                .collect::<Vec<_>>()
+                .concat();
+            let mut valid = MaybeUninit::<Buffer>::uninit();
+            assert!(g1_batch_partial_verify(
+                &polynomial,
+                &messages[0],
+                &Buffer::from(&partials[..]),
+                valid.as_mut_ptr()
+            ));
+            assert_eq!(take_bitmap(valid.assume_init()), [0b111]);
 
-            assert!(unblind(&blind_signature, blinding_factor, out.as_mut_ptr()));
-            assert!(!unblind(&no_memory, blinding_factor, out.as_mut_ptr()));
-            let unblinded = out.assume_init_read();
+            // G2 signature lengths do not divide G1 batches.
+            let mut rejected = MaybeUninit::<Buffer>::uninit();
+            assert!(!batch_partial_verify(
+                &polynomial,
+                &messages[0],
+                &Buffer::from(&partials[..]),
+                rejected.as_mut_ptr()
+            ));
 
-            assert!(partial_sign(&share, &message, out.as_mut_ptr()));
-            assert!(!partial_sign(&no_memory, &message, out.as_mut_ptr()));
-            assert!(!partial_sign(&share, &no_memory, out.as_mut_ptr()));
-            let partial = out.assume_init_read();
+            for buffer in [blinded, signatures] {
+                free_vector(buffer.ptr, buffer.len);
+            }
+            for factor in factors {
+                destroy_token(factor);
+            }
+        }
+    }
 
-            assert!(partial_verify(&polynomial, &message, &partial));
-            assert!(!partial_verify(&no_memory, &message, &partial));
-            assert!(!partial_verify(&polynomial, &no_memory, &partial));
-            assert!(!partial_verify(&polynomial, &message, &no_memory));
+    #[test]
+    fn batches_reject_null() {
+        let empty = Buffer::from(&[][..]);
+        let mut out = MaybeUninit::<Buffer>::uninit();
+        let mut factors = [std::ptr::null_mut::<BlindingFactor>(); 1];
+        let message = [Buffer::from(&b"one"[..])];
+        let seed = Buffer::from(&[0u8; SEED_LEN][..]);
 
-            assert!(partial_sign_blinded_message(
-                &share,
-                &blinded,
+        unsafe {
+            assert!(!batch_verify(
+                std::ptr::null(),
+                std::ptr::null(),
+                0,
+                &empty,
                 out.as_mut_ptr()
             ));
-            assert!(!partial_sign_blinded_message(
-                &no_memory,
-                &blinded,
+            assert!(!batch_partial_verify(
+                std::ptr::null(),
+                &empty,
+                &empty,
                 out.as_mut_ptr()
             ));
-            assert!(!partial_sign_blinded_message(
-                &share,
-                &no_memory,
-                out.as_mut_ptr()
+            assert!(!batch_partial_verify_blind_signature(
+                &empty,
+                &empty,
+                &empty,
+                std::ptr::null_mut()
             ));
-            let blind_partial = out.assume_init_read();
-
-            assert!(partial_verify_blind_signature(
-                &polynomial,
-                &blinded,
-                &blind_partial
+            assert!(!batch_blind(
+                message.as_ptr(),
+                1,
+                &seed,
+                out.as_mut_ptr(),
+                std::ptr::null_mut()
             ));
-            assert!(!partial_verify_blind_signature(
-                &no_memory,
-                &blinded,
-                &blind_partial
+            assert!(!batch_blind(
+                message.as_ptr(),
+                1,
+                std::ptr::null(),
+                out.as_mut_ptr(),
+                factors.as_mut_ptr()
             ));
-            assert!(!partial_verify_blind_signature(
-                &polynomial,
-                &no_memory,
-                &blind_partial
+            assert!(!batch_unblind(
+                &empty,
+                std::ptr::null(),
+                1,
+                out.as_mut_ptr()
             ));
-            assert!(!partial_verify_blind_signature(
-                &polynomial,
-                &blinded,
-                &no_memory
+            assert!(!g1_batch_verify(
+                std::ptr::null(),
+                std::ptr::null(),
+                0,
+                &empty,
+                out.as_mut_ptr()
+            ));
+            assert!(!g1_batch_unblind(
+                &empty,
+                std::ptr::null(),
+                0,
+                std::ptr::null_mut()
             ));
+        }
+        assert!(factors[0].is_null(), "a refused batch wrote a handle");
+    }
 
-            // `combine` needs a threshold's worth of partials, so build them
-            // from the remaining shares.
-            let mut concatenated = Vec::new();
-            for share in keys.shares.iter().take(t) {
-                let share_bytes = bincode::serialize(share).unwrap();
-                assert!(partial_sign(
-                    &Buffer::from(&share_bytes[..]),
-                    &message,
-                    out.as_mut_ptr()
-                ));
-                let partial = out.assume_init_read();
-                concatenated.extend_from_slice(buffer_slice(&partial).unwrap());
-                free_vector(partial.ptr, partial.len);
-            }
-            let partials = Buffer::from(&concatenated[..]);
-            let polynomial_bytes = bincode::serialize(&keys.polynomial).unwrap();
-            let polynomial = Buffer::from(&polynomial_bytes[..]);
-            assert!(combine(&polynomial, &partials, out.as_mut_ptr()));
-            assert!(!combine(&polynomial, &no_memory, out.as_mut_ptr()));
-            assert!(!combine(&no_memory, &partials, out.as_mut_ptr()));
-            let combined = out.assume_init_read();
+    /// What a `FillRandom` test callback draws from: a seeded ChaCha stream that
+    /// refuses once it has served `fail_after` calls.
+    struct TestSource {
+        rng: ChaChaRng,
+        calls: usize,
+        fail_after: usize,
+    }
 
-            for buffer in [
-                signature,
-                blinded,
-                blind_signature,
-                unblinded,
-                partial,
-                blind_partial,
-                combined,
-            ] {
-                free_vector(buffer.ptr, buffer.len);
+    impl TestSource {
+        fn new(fail_after: usize) -> Self {
+            Self {
+                rng: ChaChaRng::from_seed([9; SEED_LEN]),
+                calls: 0,
+                fail_after,
             }
-            destroy_token(blinding_factor);
-            destroy_keypair(keypair);
+        }
+
+        fn context(&mut self) -> *mut c_void {
+            (self as *mut Self).cast()
         }
     }
 
-    // A seed too short to fill the RNG's state used to be sliced to length,
-    // which panicked — and a panic crossing `extern "C"` aborts the process the
-    // library was called from, which for the mobile consumer is the whole app.
+    unsafe extern "C" fn fill_from_test_source(
+        context: *mut c_void,
+        bytes: *mut u8,
+        len: usize,
+    ) -> bool {
+        let source = unsafe { &mut *context.cast::<TestSource>() };
+        if source.calls == source.fail_after {
+            return false;
+        }
+        source.calls += 1;
+        source
+            .rng
+            .fill_bytes(unsafe { std::slice::from_raw_parts_mut(bytes, len) });
+        true
+    }
+
     #[test]
-    fn a_short_seed_is_rejected() {
-        let short = [7u8; SEED_LEN - 1];
-        let msg = [1u8, 2, 3, 4, 6];
-        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
-        let mut blinded = MaybeUninit::<Buffer>::uninit();
-        let mut blinding_factor = MaybeUninit::<*mut BlindingFactor>::uninit();
+    fn keygen_with_rng_draws_from_the_callback() {
+        let draw = || {
+            let mut source = TestSource::new(usize::MAX);
+            let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+            unsafe {
+                assert!(keygen_with_rng(
+                    Some(fill_from_test_source),
+                    source.context(),
+                    keypair.as_mut_ptr()
+                ));
+            }
+            assert!(source.calls > 0, "the callback was never asked for bytes");
+            unsafe { keypair.assume_init() }
+        };
 
+        // The same stream gives the same key, and the key signs.
+        let (first, second) = (draw(), draw());
         unsafe {
-            assert!(!keygen(&Buffer::from(&short[..]), keypair.as_mut_ptr()));
-            assert!(!keygen(&Buffer::from(&short[..0]), keypair.as_mut_ptr()));
+            assert_eq!((*first).private, (*second).private);
+            let message = b"hello";
+            let signature = SigScheme::sign(&*private_key_ptr(first), message).unwrap();
+            SigScheme::verify(&*public_key_ptr(second), message, &signature).unwrap();
+            destroy_keypair(first);
+            destroy_keypair(second);
+        }
 
-            assert!(!blind(
-                &Buffer::from(&msg[..]),
-                &Buffer::from(&short[..]),
-                blinded.as_mut_ptr(),
-                blinding_factor.as_mut_ptr()
+        let mut source = TestSource::new(usize::MAX);
+        let mut keypair = MaybeUninit::<*mut G1Keypair>::uninit();
+        unsafe {
+            assert!(g1_keygen_with_rng(
+                Some(fill_from_test_source),
+                source.context(),
+                keypair.as_mut_ptr()
             ));
+            g1_destroy_keypair(keypair.assume_init());
         }
     }
 
-    // The whole seed is consumed, so the exact length is enough: the check is
-    // not off by one in the other direction.
     #[test]
-    fn a_seed_of_exactly_the_required_length_is_accepted() {
-        let seed = [7u8; SEED_LEN];
+    fn blind_with_rng_round_trips() {
+        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let message = Buffer::from(&b"hello"[..]);
         let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+        assert!(unsafe { keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()) });
+        let keypair = unsafe { keypair.assume_init() };
 
+        let mut source = TestSource::new(usize::MAX);
         unsafe {
-            assert!(keygen(&Buffer::from(&seed[..]), keypair.as_mut_ptr()));
-            destroy_keypair(keypair.assume_init());
+            let mut blinded = MaybeUninit::<Buffer>::uninit();
+            let mut factor = MaybeUninit::<*mut BlindingFactor>::uninit();
+            assert!(blind_with_rng(
+                &message,
+                Some(fill_from_test_source),
+                source.context(),
+                blinded.as_mut_ptr(),
+                factor.as_mut_ptr()
+            ));
+            let (blinded, factor) = (blinded.assume_init(), factor.assume_init());
+
+            let blind_signature =
+                SigScheme::blind_sign(&*private_key_ptr(keypair), buffer_slice(&blinded).unwrap())
+                    .unwrap();
+            let signature = SigScheme::unblind_sig(&(*factor).0, &blind_signature).unwrap();
+            SigScheme::verify(&*public_key_ptr(keypair), b"hello", &signature).unwrap();
+
+            let messages = [&b"one"[..], b"two"].map(Buffer::from);
+            let mut batch = MaybeUninit::<Buffer>::uninit();
+            let mut factors = [std::ptr::null_mut::<BlindingFactor>(); 2];
+            assert!(batch_blind_with_rng(
+                messages.as_ptr(),
+                2,
+                Some(fill_from_test_source),
+                source.context(),
+                batch.as_mut_ptr(),
+                factors.as_mut_ptr()
+            ));
+            let batch = batch.assume_init();
+            assert_eq!(batch.len, 2 * SIGNATURE_LEN);
+
+            free_vector(blinded.ptr, blinded.len);
+            free_vector(batch.ptr, batch.len);
+            destroy_token(factor);
+            for factor in factors {
+                destroy_token(factor);
+            }
+            destroy_keypair(keypair);
         }
     }
 
-    // `keygen` used to return void, so a caller had no way to learn that it had
-    // written nothing.
+    // A key or blinding factor drawn partly from a failed source is never
+    // handed out, however far the draw got before the failure.
     #[test]
-    fn keygen_reports_failure() {
-        let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
-        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+    fn a_failing_callback_writes_nothing() {
+        let message = Buffer::from(&b"hello"[..]);
+        let messages = [&b"one"[..], b"two"].map(Buffer::from);
+
+        for fail_after in [0, 1] {
+            let mut source = TestSource::new(fail_after);
+            let mut keypair = std::ptr::null_mut::<Keypair>();
+            let mut g1_keypair = std::ptr::null_mut::<G1Keypair>();
+            let mut blinded = MaybeUninit::<Buffer>::uninit();
+            let mut factor = std::ptr::null_mut::<BlindingFactor>();
+            let mut factors = [std::ptr::null_mut::<BlindingFactor>(); 2];
+
+            unsafe {
+                assert!(!keygen_with_rng(
+                    Some(fill_from_test_source),
+                    source.context(),
+                    &mut keypair
+                ));
+                source.calls = 0;
+                assert!(!g1_keygen_with_rng(
+                    Some(fill_from_test_source),
+                    source.context(),
+                    &mut g1_keypair
+                ));
+                source.calls = 0;
+                assert!(!blind_with_rng(
+                    &message,
+                    Some(fill_from_test_source),
+                    source.context(),
+                    blinded.as_mut_ptr(),
+                    &mut factor
+                ));
+                source.calls = 0;
+                assert!(!g1_batch_blind_with_rng(
+                    messages.as_ptr(),
+                    2,
+                    Some(fill_from_test_source),
+                    source.context(),
+                    blinded.as_mut_ptr(),
+                    factors.as_mut_ptr()
+                ));
+            }
+            assert!(keypair.is_null() && g1_keypair.is_null() && factor.is_null());
+            assert!(factors.iter().all(|factor| factor.is_null()));
+        }
+    }
+
+    #[test]
+    fn a_null_callback_is_rejected() {
+        let message = Buffer::from(&b"hello"[..]);
+        let mut keypair = std::ptr::null_mut::<Keypair>();
+        let mut blinded = MaybeUninit::<Buffer>::uninit();
+        let mut factor = std::ptr::null_mut::<BlindingFactor>();
+        let mut source = TestSource::new(usize::MAX);
 
         unsafe {
-            assert!(!keygen(std::ptr::null(), keypair.as_mut_ptr()));
-            assert!(!keygen(&Buffer::from(&seed[..]), std::ptr::null_mut()));
+            assert!(!keygen_with_rng(None, source.context(), &mut keypair));
+            assert!(!keygen_with_rng(
+                Some(fill_from_test_source),
+                source.context(),
+                std::ptr::null_mut()
+            ));
+            assert!(!g1_blind_with_rng(
+                &message,
+                None,
+                source.context(),
+                blinded.as_mut_ptr(),
+                &mut factor
+            ));
+            assert!(!batch_blind_with_rng(
+                std::ptr::null(),
+                1,
+                Some(fill_from_test_source),
+                source.context(),
+                blinded.as_mut_ptr(),
+                &mut factor
+            ));
         }
+        assert!(keypair.is_null() && factor.is_null());
     }
 
     #[test]
-    fn key_accessors_reject_null() {
+    fn a_split_key_signs_for_its_own_public_key() {
+        use threshold_bls::group::Element;
+
+        let seed = Buffer::from(&[7u8; SEED_LEN][..]);
+        let msg = Buffer::from(&b"hello"[..]);
+        let mut keypair = MaybeUninit::<*mut Keypair>::uninit();
+        assert!(unsafe { keygen(&seed, keypair.as_mut_ptr()) });
+        let keypair = unsafe { keypair.assume_init() };
+        let private_key = unsafe { private_key_ptr(keypair) };
+
+        let (n, t) = (5, 3);
+        let mut shares = MaybeUninit::<Buffer>::uninit();
+        let mut polynomial = MaybeUninit::<Buffer>::uninit();
+        assert!(unsafe {
+            split_private_key(
+                private_key,
+                n,
+                t,
+                &seed,
+                shares.as_mut_ptr(),
+                polynomial.as_mut_ptr(),
+            )
+        });
+        let (shares, polynomial) = unsafe { (shares.assume_init(), polynomial.assume_init()) };
+        assert_eq!(shares.len, n * SHARE_LEN);
+
+        // any `t` of the shares, here the last ones, sign for the original key
+        let mut partials = Vec::new();
+        for share in unsafe { buffer_slice(&shares) }
+            .unwrap()
+            .chunks(SHARE_LEN)
+            .skip(n - t)
+        {
+            let mut partial = MaybeUninit::<Buffer>::uninit();
+            assert!(unsafe { partial_sign(&Buffer::from(share), &msg, partial.as_mut_ptr()) });
+            let partial = unsafe { partial.assume_init() };
+            partials.extend_from_slice(unsafe { buffer_slice(&partial) }.unwrap());
+            unsafe { free_vector(partial.ptr, partial.len) };
+        }
+        let mut signature = MaybeUninit::<Buffer>::uninit();
+        assert!(unsafe {
+            combine(
+                &polynomial,
+                &Buffer::from(&partials[..]),
+                signature.as_mut_ptr(),
+            )
+        });
+        let signature = unsafe { signature.assume_init() };
+        assert!(unsafe { verify(public_key_ptr(keypair), &msg, &signature) });
+
+        // the keys-on-G1 scheme commits the same key on G1, and the callback
+        // draws the coefficients as the seed does
+        let mut source = TestSource::new(usize::MAX);
+        let mut g1_shares = MaybeUninit::<Buffer>::uninit();
+        let mut g1_polynomial = MaybeUninit::<Buffer>::uninit();
+        assert!(unsafe {
+            g1_split_private_key_with_rng(
+                private_key,
+                n,
+                t,
+                Some(fill_from_test_source),
+                source.context(),
+                g1_shares.as_mut_ptr(),
+                g1_polynomial.as_mut_ptr(),
+            )
+        });
+        let (g1_shares, g1_polynomial) =
+            unsafe { (g1_shares.assume_init(), g1_polynomial.assume_init()) };
+        let g1_poly: Poly<G1PublicKey> =
+            bincode::deserialize(unsafe { buffer_slice(&g1_polynomial) }.unwrap()).unwrap();
+        let mut g1_public_key = G1PublicKey::one();
+        g1_public_key.mul(unsafe { &*private_key });
+        assert_eq!(*g1_poly.public_key(), g1_public_key);
+
         unsafe {
-            assert!(public_key_ptr(std::ptr::null()).is_null());
-            assert!(private_key_ptr(std::ptr::null()).is_null());
+            free_vector(shares.ptr, shares.len);
+            free_vector(polynomial.ptr, polynomial.len);
+            free_vector(signature.ptr, signature.len);
+            free_vector(g1_shares.ptr, g1_shares.len);
+            free_vector(g1_polynomial.ptr, g1_polynomial.len);
         }
+
+        // a group the bindings refuse, a short seed, a failed source or a
+        // missing key writes nothing
+        let empty = Buffer {
+            ptr: std::ptr::null(),
+            len: 0,
+        };
+        let (mut shares, mut polynomial) = (empty.clone(), empty.clone());
+        let mut source = TestSource::new(0);
+        unsafe {
+            for (n, t) in [(3, 0), (3, 4), (MAX_SHARES + 1, 1)] {
+                assert!(!split_private_key(
+                    private_key,
+                    n,
+                    t,
+                    &seed,
+                    &mut shares,
+                    &mut polynomial
+                ));
+            }
+            assert!(!g1_split_private_key(
+                private_key,
+                n,
+                t,
+                &Buffer::from(&[7u8; SEED_LEN - 1][..]),
+                &mut shares,
+                &mut polynomial
+            ));
+            assert!(!split_private_key_with_rng(
+                private_key,
+                n,
+                t,
+                Some(fill_from_test_source),
+                source.context(),
+                &mut shares,
+                &mut polynomial
+            ));
+            assert!(!split_private_key(
+                std::ptr::null(),
+                n,
+                t,
+                &seed,
+                &mut shares,
+                &mut polynomial
+            ));
+            destroy_keypair(keypair);
+        }
+        assert_eq!((shares, polynomial), (empty.clone(), empty));
     }
 
-    // Freeing NULL is a no-op in C, and callers rely on it: a cleanup path that
-    // runs after a failed allocation has nothing else to pass.
     #[test]
     fn destructors_accept_null() {
         unsafe {
@@ -1604,6 +4328,9 @@ mod tests {
             destroy_privkey(std::ptr::null_mut());
             destroy_pubkey(std::ptr::null_mut());
             destroy_sig(std::ptr::null_mut());
+            g1_destroy_keypair(std::ptr::null_mut());
+            g1_destroy_pubkey(std::ptr::null_mut());
+            g1_destroy_sig(std::ptr::null_mut());
             free_vector(std::ptr::null(), 0);
             free_vector(std::ptr::null(), 32);
         }
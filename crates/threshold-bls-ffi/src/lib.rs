@@ -12,20 +12,35 @@ pub mod jni_bridge;
 #[cfg(feature = "ffi")]
 pub mod ffi;
 
+#[cfg(any(feature = "ffi", feature = "wasm"))]
+mod batch;
+
 #[cfg(feature = "ffi")]
 pub(crate) type Signature = <SigScheme as Scheme>::Signature;
-#[cfg(feature = "ffi")]
+#[allow(dead_code)]
 pub const PUBKEY_LEN: usize = 96;
-#[cfg(feature = "ffi")]
+#[allow(dead_code)]
 pub const PRIVKEY_LEN: usize = 32;
 
-use threshold_bls::{poly::Idx, schemes::bls12_377::G2Scheme as SigScheme, sig::Scheme};
+use threshold_bls::{
+    mnemonic::MnemonicScheme,
+    poly::Idx,
+    schemes::bls12_377::{G1Scheme as G1SigScheme, G2Scheme as SigScheme},
+    sig::{BlindScheme, BlindThresholdScheme, Scheme, SignatureScheme, ThresholdScheme, Token},
+};
 
 #[allow(dead_code)]
 pub(crate) type PublicKey = <SigScheme as Scheme>::Public;
 #[allow(dead_code)]
 pub(crate) type PrivateKey = <SigScheme as Scheme>::Private;
 
+// The keys-on-G1 variant. Its private keys, shares and blinding factors are the
+// same scalars as `SigScheme`'s, so only the two group elements get names.
+#[cfg(feature = "ffi")]
+pub(crate) type G1PublicKey = <G1SigScheme as Scheme>::Public;
+#[cfg(feature = "ffi")]
+pub(crate) type G1Signature = <G1SigScheme as Scheme>::Signature;
+
 /// Bytes of seed the entry points that draw randomness require. It is the whole
 /// state of the RNG they seed, so a shorter seed is refused rather than padded.
 #[allow(dead_code)]
@@ -67,3 +82,67 @@ const _: () = assert!(IDX_LEN == std::mem::size_of::<Idx>());
 /// match.
 #[allow(dead_code)]
 pub const PARTIAL_SIG_LENGTH: usize = VEC_LENGTH + SIGNATURE_LEN + IDX_LEN;
+
+/// Bytes in one serialized share: its index followed by its private key. A
+/// split key's shares are written concatenated, each this long.
+#[allow(dead_code)]
+pub const SHARE_LEN: usize = IDX_LEN + PRIVKEY_LEN;
+
+/// The largest group the bindings deal keys for. A threshold group is a
+/// handful of signers, so the bound is far above any real one; it is here so
+/// that a mistyped `n` fails rather than asking the allocator for gigabytes,
+/// which in wasm traps and poisons the instance.
+#[allow(dead_code)]
+pub const MAX_SHARES: usize = 1024;
+
+/// Bytes in an OPRF output, a SHA-256 digest.
+#[allow(dead_code)]
+pub const OPRF_OUTPUT_LEN: usize = 32;
+
+/// Bytes in a serialized public key of the keys-on-G1 scheme, compressed G1.
+#[allow(dead_code)]
+pub const G1_PUBKEY_LEN: usize = 48;
+/// Bytes in a serialized signature of the keys-on-G1 scheme, compressed G2.
+#[allow(dead_code)]
+pub const G1_SIGNATURE_LEN: usize = 96;
+/// Bytes in one serialized partial signature of the keys-on-G1 scheme, the
+/// chunk size `g1_combine` splits its flattened input into.
+#[allow(dead_code)]
+pub const G1_PARTIAL_SIG_LENGTH: usize = VEC_LENGTH + G1_SIGNATURE_LEN + IDX_LEN;
+
+/// A scheme the bindings expose, so that each entry point is written once and
+/// instantiated per scheme rather than copied.
+///
+/// The bounds pin what the surfaces rely on being shared: both schemes sign
+/// with the same scalars, so a private key, share or blinding factor is the
+/// same value under either, and only the group elements differ.
+#[allow(dead_code)]
+pub(crate) trait BindingScheme:
+    Scheme<Private = PrivateKey>
+    + SignatureScheme
+    + BlindScheme<Token = Token<PrivateKey>>
+    + ThresholdScheme
+    + BlindThresholdScheme
+    + MnemonicScheme
+{
+    /// Bytes in one serialized signature under this scheme, and in a blinded
+    /// message, which is a point in the same group.
+    const SIGNATURE_LENGTH: usize;
+    /// Bytes in one serialized partial signature under this scheme.
+    const PARTIAL_SIG_LENGTH: usize;
+    /// Bytes in one serialized public share, a public key followed by its
+    /// index.
+    const PUBLIC_SHARE_LENGTH: usize;
+}
+
+impl BindingScheme for SigScheme {
+    const SIGNATURE_LENGTH: usize = SIGNATURE_LEN;
+    const PARTIAL_SIG_LENGTH: usize = PARTIAL_SIG_LENGTH;
+    const PUBLIC_SHARE_LENGTH: usize = PUBKEY_LEN + IDX_LEN;
+}
+
+impl BindingScheme for G1SigScheme {
+    const SIGNATURE_LENGTH: usize = G1_SIGNATURE_LEN;
+    const PARTIAL_SIG_LENGTH: usize = G1_PARTIAL_SIG_LENGTH;
+    const PUBLIC_SHARE_LENGTH: usize = G1_PUBKEY_LEN + IDX_LEN;
+}
@@ -5,11 +5,11 @@ use rand_chacha::ChaChaRng;
 use rand_core::{RngCore, SeedableRng};
 
 use threshold_bls::{
-    poly::{Idx as Index, Poly},
+    group::Element,
+    mnemonic, oprf,
+    poly::{Eval, Idx as Index, Poly},
     serialization,
-    sig::{
-        BlindScheme, BlindThresholdScheme, Scheme, Share, SignatureScheme, ThresholdScheme, Token,
-    },
+    sig::{Share, Token},
 };
 
 use crate::*;
@@ -49,15 +49,15 @@ type TryResult<T> = std::result::Result<T, String>;
 /// # Safety
 /// - If the same seed is used twice, the blinded result WILL be the same
 pub fn blind(message: Vec<u8>, seed: &[u8]) -> Result<BlindedMessage> {
-    try_blind(message, seed).map_err(|err| JsValue::from_str(&err))
+    try_blind::<SigScheme>(message, seed).map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_blind(message: Vec<u8>, seed: &[u8]) -> TryResult<BlindedMessage> {
+fn try_blind<S: BindingScheme>(message: Vec<u8>, seed: &[u8]) -> TryResult<BlindedMessage> {
     // convert the seed to randomness
     let mut rng = get_rng(seed)?;
 
     // blind the message with this randomness
-    let (blinding_factor, blinded_message) = SigScheme::blind_msg(&message, &mut rng)
+    let (blinding_factor, blinded_message) = S::blind_msg(&message, &mut rng)
         .map_err(|err| format!("could not blind message: {}", err))?;
 
     // return the message and the blinding_factor used for blinding
@@ -78,14 +78,18 @@ fn try_blind(message: Vec<u8>, seed: &[u8]) -> TryResult<BlindedMessage> {
 ///
 /// - If unblinding fails.
 pub fn unblind(blinded_signature: &[u8], blinding_factor_buf: &[u8]) -> Result<Vec<u8>> {
-    try_unblind(blinded_signature, blinding_factor_buf).map_err(|err| JsValue::from_str(&err))
+    try_unblind::<SigScheme>(blinded_signature, blinding_factor_buf)
+        .map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_unblind(blinded_signature: &[u8], blinding_factor_buf: &[u8]) -> TryResult<Vec<u8>> {
+fn try_unblind<S: BindingScheme>(
+    blinded_signature: &[u8],
+    blinding_factor_buf: &[u8],
+) -> TryResult<Vec<u8>> {
     let blinding_factor: Token<PrivateKey> = serialization::deserialize(blinding_factor_buf)
         .map_err(|err| format!("could not deserialize blinding factor {}", err))?;
 
-    SigScheme::unblind_sig(&blinding_factor, blinded_signature)
+    S::unblind_sig(&blinding_factor, blinded_signature)
         .map_err(|err| format!("could not unblind signature {}", err))
 }
 
@@ -101,14 +105,19 @@ fn try_unblind(blinded_signature: &[u8], blinding_factor_buf: &[u8]) -> TryResul
 ///
 /// - If verification fails
 pub fn verify(public_key_buf: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
-    try_verify(public_key_buf, message, signature).map_err(|err| JsValue::from_str(&err))
+    try_verify::<SigScheme>(public_key_buf, message, signature)
+        .map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_verify(public_key_buf: &[u8], message: &[u8], signature: &[u8]) -> TryResult<()> {
-    let public_key = public_key(public_key_buf)?;
+fn try_verify<S: BindingScheme>(
+    public_key_buf: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> TryResult<()> {
+    let public_key = public_key::<S>(public_key_buf)?;
 
     // checks the signature on the message hash
-    SigScheme::verify(&public_key, message, signature)
+    S::verify(&public_key, message, signature)
         .map_err(|err| format!("signature verification failed: {}", err))
 }
 
@@ -132,22 +141,98 @@ pub fn verify_blind_signature(
     message: &[u8],
     signature: &[u8],
 ) -> Result<()> {
-    try_verify_blind_signature(public_key_buf, message, signature)
+    try_verify_blind_signature::<SigScheme>(public_key_buf, message, signature)
         .map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_verify_blind_signature(
+fn try_verify_blind_signature<S: BindingScheme>(
     public_key_buf: &[u8],
     message: &[u8],
     signature: &[u8],
 ) -> TryResult<()> {
-    let public_key = public_key(public_key_buf)?;
+    let public_key = public_key::<S>(public_key_buf)?;
 
     // checks the pairing against the message point directly, without hashing
-    SigScheme::blind_verify(&public_key, message, signature)
+    S::blind_verify(&public_key, message, signature)
         .map_err(|err| format!("signature verification failed: {}", err))
 }
 
+#[wasm_bindgen(js_name = oprfFinalize)]
+/// Derives the OPRF output for an input from the group's signature on it, after verifying
+/// the signature. This is the finalize step every client has to perform identically, as
+/// defined in `threshold_bls::oprf`.
+///
+/// * public_key: The public key the signature is checked against
+/// * input: The OPRF input, the message that was blinded and signed
+/// * signature: The unblinded signature on `input`
+///
+/// Returns the 32 byte output.
+///
+/// # Throws
+///
+/// - If the signature does not verify on the input
+pub fn oprf_finalize(public_key_buf: &[u8], input: &[u8], signature: &[u8]) -> Result<Vec<u8>> {
+    try_oprf_finalize::<SigScheme>(public_key_buf, input, signature)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_oprf_finalize<S: BindingScheme>(
+    public_key_buf: &[u8],
+    input: &[u8],
+    signature: &[u8],
+) -> TryResult<Vec<u8>> {
+    let public_key = public_key::<S>(public_key_buf)?;
+
+    oprf::finalize::<S>(&public_key, input, signature)
+        .map(|output| output.to_vec())
+        .map_err(|err| format!("could not derive the OPRF output: {}", err))
+}
+
+#[wasm_bindgen(js_name = oprfUnblindFinalize)]
+/// Unblinds the group's blind signature on a blinded OPRF input and derives the output from
+/// it, as `unblind` followed by `oprfFinalize`.
+///
+/// * public_key: The public key the unblinded signature is checked against
+/// * input: The OPRF input, the message that was blinded
+/// * blinded_signature: The blind signature on the blinded input
+/// * blinding_factor: The blinding_factor used to blind the input
+///
+/// Returns the 32 byte output.
+///
+/// # Throws
+///
+/// - If the blinding factor cannot be deserialized
+/// - If the signature cannot be unblinded, or does not verify on the input once unblinded
+pub fn oprf_unblind_finalize(
+    public_key_buf: &[u8],
+    input: &[u8],
+    blinded_signature: &[u8],
+    blinding_factor_buf: &[u8],
+) -> Result<Vec<u8>> {
+    try_oprf_unblind_finalize::<SigScheme>(
+        public_key_buf,
+        input,
+        blinded_signature,
+        blinding_factor_buf,
+    )
+    .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_oprf_unblind_finalize<S: BindingScheme>(
+    public_key_buf: &[u8],
+    input: &[u8],
+    blinded_signature: &[u8],
+    blinding_factor_buf: &[u8],
+) -> TryResult<Vec<u8>> {
+    let public_key = public_key::<S>(public_key_buf)?;
+    let blinding_factor: Token<PrivateKey> = serialization::deserialize(blinding_factor_buf)
+        .map_err(|err| format!("could not deserialize blinding factor {}", err))?;
+
+    oprf::unblind_finalize::<S>(&public_key, &blinding_factor, input, blinded_signature)
+        .map(|output| output.to_vec())
+        .map_err(|err| format!("could not derive the OPRF output: {}", err))
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // Service -> Library
 ///////////////////////////////////////////////////////////////////////////
@@ -159,13 +244,13 @@ fn try_verify_blind_signature(
 ///
 /// - If signing fails
 pub fn sign(private_key_buf: &[u8], message: &[u8]) -> Result<Vec<u8>> {
-    try_sign(private_key_buf, message).map_err(|err| JsValue::from_str(&err))
+    try_sign::<SigScheme>(private_key_buf, message).map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_sign(private_key_buf: &[u8], message: &[u8]) -> TryResult<Vec<u8>> {
+fn try_sign<S: BindingScheme>(private_key_buf: &[u8], message: &[u8]) -> TryResult<Vec<u8>> {
     let private_key = private_key(private_key_buf)?;
 
-    SigScheme::sign(&private_key, message).map_err(|err| format!("could not sign message: {}", err))
+    S::sign(&private_key, message).map_err(|err| format!("could not sign message: {}", err))
 }
 
 #[wasm_bindgen(js_name = signBlindedMessage)]
@@ -175,14 +260,17 @@ fn try_sign(private_key_buf: &[u8], message: &[u8]) -> TryResult<Vec<u8>> {
 ///
 /// - If signing fails
 pub fn sign_blinded_message(private_key_buf: &[u8], message: &[u8]) -> Result<Vec<u8>> {
-    try_sign_blinded_message(private_key_buf, message).map_err(|err| JsValue::from_str(&err))
+    try_sign_blinded_message::<SigScheme>(private_key_buf, message)
+        .map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_sign_blinded_message(private_key_buf: &[u8], message: &[u8]) -> TryResult<Vec<u8>> {
+fn try_sign_blinded_message<S: BindingScheme>(
+    private_key_buf: &[u8],
+    message: &[u8],
+) -> TryResult<Vec<u8>> {
     let private_key = private_key(private_key_buf)?;
 
-    SigScheme::blind_sign(&private_key, message)
-        .map_err(|err| format!("could not sign message: {}", err))
+    S::blind_sign(&private_key, message).map_err(|err| format!("could not sign message: {}", err))
 }
 
 #[wasm_bindgen(js_name = partialSign)]
@@ -196,13 +284,13 @@ fn try_sign_blinded_message(private_key_buf: &[u8], message: &[u8]) -> TryResult
 /// NOTE: This method must NOT be called with a PrivateKey which is not generated via a
 /// secret sharing scheme.
 pub fn partial_sign(share_buf: &[u8], message: &[u8]) -> Result<Vec<u8>> {
-    try_partial_sign(share_buf, message).map_err(|err| JsValue::from_str(&err))
+    try_partial_sign::<SigScheme>(share_buf, message).map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_partial_sign(share_buf: &[u8], message: &[u8]) -> TryResult<Vec<u8>> {
+fn try_partial_sign<S: BindingScheme>(share_buf: &[u8], message: &[u8]) -> TryResult<Vec<u8>> {
     let share = share(share_buf)?;
 
-    SigScheme::partial_sign(&share, message)
+    S::partial_sign(&share, message)
         .map_err(|err| format!("could not partially sign message: {}", err))
 }
 
@@ -217,13 +305,17 @@ fn try_partial_sign(share_buf: &[u8], message: &[u8]) -> TryResult<Vec<u8>> {
 /// NOTE: This method must NOT be called with a PrivateKey which is not generated via a
 /// secret sharing scheme.
 pub fn partial_sign_blinded_message(share_buf: &[u8], message: &[u8]) -> Result<Vec<u8>> {
-    try_partial_sign_blinded_message(share_buf, message).map_err(|err| JsValue::from_str(&err))
+    try_partial_sign_blinded_message::<SigScheme>(share_buf, message)
+        .map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_partial_sign_blinded_message(share_buf: &[u8], message: &[u8]) -> TryResult<Vec<u8>> {
+fn try_partial_sign_blinded_message<S: BindingScheme>(
+    share_buf: &[u8],
+    message: &[u8],
+) -> TryResult<Vec<u8>> {
     let share = share(share_buf)?;
 
-    SigScheme::sign_blind_partial(&share, message)
+    S::sign_blind_partial(&share, message)
         .map_err(|err| format!("could not partially sign message: {}", err))
 }
 
@@ -231,108 +323,949 @@ fn try_partial_sign_blinded_message(share_buf: &[u8], message: &[u8]) -> TryResu
 // Combiner -> Library
 ///////////////////////////////////////////////////////////////////////////
 
-#[wasm_bindgen(js_name = partialVerify)]
-/// Verifies a partial signature against the public key corresponding to the secret shared
-/// polynomial.
+#[wasm_bindgen(js_name = partialVerify)]
+/// Verifies a partial signature against the public key corresponding to the secret shared
+/// polynomial.
+///
+/// # Throws
+///
+/// - If verification fails
+pub fn partial_verify(polynomial_buf: &[u8], blinded_message: &[u8], sig: &[u8]) -> Result<()> {
+    try_partial_verify::<SigScheme>(polynomial_buf, blinded_message, sig)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_partial_verify<S: BindingScheme>(
+    polynomial_buf: &[u8],
+    blinded_message: &[u8],
+    sig: &[u8],
+) -> TryResult<()> {
+    let polynomial = polynomial::<S>(polynomial_buf)?;
+
+    S::partial_verify(&polynomial, blinded_message, sig)
+        .map_err(|err| format!("could not partially verify message: {}", err))
+}
+
+#[wasm_bindgen(js_name = partialVerifyBlindSignature)]
+/// Verifies a partial *blind* signature against the public key corresponding to the secret shared
+/// polynomial.
+///
+/// # Throws
+///
+/// - If verification fails
+pub fn partial_verify_blind_signature(
+    polynomial_buf: &[u8],
+    blinded_message: &[u8],
+    sig: &[u8],
+) -> Result<()> {
+    try_partial_verify_blind_signature::<SigScheme>(polynomial_buf, blinded_message, sig)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_partial_verify_blind_signature<S: BindingScheme>(
+    polynomial_buf: &[u8],
+    blinded_message: &[u8],
+    sig: &[u8],
+) -> TryResult<()> {
+    let polynomial = polynomial::<S>(polynomial_buf)?;
+
+    S::verify_blind_partial(&polynomial, blinded_message, sig)
+        .map_err(|err| format!("could not partially verify message: {}", err))
+}
+
+#[wasm_bindgen]
+/// Combines a flattened vector of partial signatures to a single threshold signature
+///
+/// NOTE: Wasm-bindgen does not support `Vec<Vec<u8>>`, so this function accepts a flattened
+/// byte vector which it will parse in chunks for each signature.
+///
+/// NOTE: If you are working with an array of Uint8Arrays In Javascript, the simplest
+/// way to flatten them is via:
+///
+/// ```js
+/// function flatten(arr) {
+///     return Uint8Array.from(arr.reduce(function(a, b) {
+///         return Array.from(a).concat(Array.from(b));
+///     }, []));
+/// }
+/// ```
+///
+/// The threshold comes from the public polynomial rather than from the caller:
+/// it is the polynomial's degree plus one, and a smaller number would combine a
+/// subset of the partials into a different signature without failing.
+///
+/// # Throws
+///
+/// - If the polynomial cannot be deserialized
+/// - If the flattened vector is not a whole number of partial signatures
+/// - If the aggregation fails
+///
+/// # Safety
+///
+/// - This function does not check if the signatures are valid!
+pub fn combine(polynomial_buf: &[u8], signatures: Vec<u8>) -> Result<Vec<u8>> {
+    try_combine::<SigScheme>(polynomial_buf, signatures).map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_combine<S: BindingScheme>(polynomial_buf: &[u8], signatures: Vec<u8>) -> TryResult<Vec<u8>> {
+    // The caller flattens the partial signatures, so the boundaries between
+    // them are implied by the length alone. A remainder means the flattening
+    // was wrong, and every chunk after the first mistake is cut from the middle
+    // of two partials.
+    if !signatures.len().is_multiple_of(S::PARTIAL_SIG_LENGTH) {
+        return Err(format!(
+            "expected a multiple of {} bytes, one per partial signature, got {}",
+            S::PARTIAL_SIG_LENGTH,
+            signatures.len()
+        ));
+    }
+
+    // break the flattened vector to a Vec<Vec<u8>> where each element is a serialized signature
+    let sigs = signatures
+        .chunks(S::PARTIAL_SIG_LENGTH)
+        .map(|chunk| chunk.to_vec())
+        .collect::<Vec<Vec<u8>>>();
+
+    let polynomial = polynomial::<S>(polynomial_buf)?;
+
+    S::aggregate(&polynomial, &sigs).map_err(|err| format!("could not aggregate sigs: {}", err))
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Verifier -> Library
+//
+// Inspecting a committee's keys from its public polynomial alone. A signer's
+// public share is the polynomial evaluated at its index: the public key its
+// partial signatures verify against. It is serialized as the public key
+// followed by the `u32` index, 100 bytes.
+///////////////////////////////////////////////////////////////////////////
+
+#[wasm_bindgen(js_name = thresholdPublicKey)]
+/// Returns the threshold public key of a public polynomial: its constant term, which
+/// combined signatures verify against.
+///
+/// # Throws
+///
+/// - If the polynomial cannot be deserialized
+pub fn threshold_public_key(polynomial_buf: &[u8]) -> Result<Vec<u8>> {
+    try_threshold_public_key::<SigScheme>(polynomial_buf).map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_threshold_public_key<S: BindingScheme>(polynomial_buf: &[u8]) -> TryResult<Vec<u8>> {
+    let polynomial = polynomial::<S>(polynomial_buf)?;
+
+    bincode::serialize(polynomial.public_key())
+        .map_err(|err| format!("could not serialize threshold public key: {}", err))
+}
+
+#[wasm_bindgen(js_name = evaluatePolynomial)]
+/// Evaluates a public polynomial at a signer's index, returning that signer's public
+/// share.
+///
+/// # Throws
+///
+/// - If the polynomial cannot be deserialized
+pub fn evaluate_polynomial(polynomial_buf: &[u8], index: Index) -> Result<Vec<u8>> {
+    try_evaluate_polynomial::<SigScheme>(polynomial_buf, index)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_evaluate_polynomial<S: BindingScheme>(
+    polynomial_buf: &[u8],
+    index: Index,
+) -> TryResult<Vec<u8>> {
+    let polynomial = polynomial::<S>(polynomial_buf)?;
+
+    bincode::serialize(&polynomial.eval(index))
+        .map_err(|err| format!("could not serialize public share: {}", err))
+}
+
+#[wasm_bindgen(js_name = verifyShare)]
+/// Verifies a private key share against a public polynomial: that the share is the
+/// one the polynomial commits to at the share's index.
+///
+/// # Throws
+///
+/// - If the polynomial or the share cannot be deserialized
+/// - If the share does not lie on the polynomial
+pub fn verify_share(polynomial_buf: &[u8], share_buf: &[u8]) -> Result<()> {
+    try_verify_share::<SigScheme>(polynomial_buf, share_buf).map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_verify_share<S: BindingScheme>(polynomial_buf: &[u8], share_buf: &[u8]) -> TryResult<()> {
+    let polynomial = polynomial::<S>(polynomial_buf)?;
+    let share = share(share_buf)?;
+
+    let mut public = S::Public::one();
+    public.mul(&share.private);
+    if public != polynomial.eval(share.index).value {
+        return Err(format!(
+            "share {} does not match the polynomial",
+            share.index
+        ));
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen(js_name = recoverPolynomial)]
+/// Recovers the public polynomial of a t-of-n committee from its signers' public
+/// shares, flattened as `combine` takes partial signatures.
+///
+/// The polynomial is interpolated through the `t` shares with the lowest indices,
+/// and every share passed is then checked against it, so handing over more than `t`
+/// also catches a share that does not belong to the committee.
+///
+/// `t` has to be the committee's threshold. Any `t` points lie on some polynomial
+/// of degree `t - 1`, so `t` shares of a committee with a larger threshold recover
+/// a different polynomial without failing; only a surplus share can tell.
+///
+/// # Throws
+///
+/// - If the flattened vector is not a whole number of public shares
+/// - If a public share cannot be deserialized
+/// - If `t` is zero, or there are fewer than `t` distinct indices
+/// - If a share does not lie on the recovered polynomial
+pub fn recover_polynomial(t: usize, public_shares: Vec<u8>) -> Result<Vec<u8>> {
+    try_recover_polynomial::<SigScheme>(t, public_shares).map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_recover_polynomial<S: BindingScheme>(
+    t: usize,
+    public_shares: Vec<u8>,
+) -> TryResult<Vec<u8>> {
+    // Flattened for the same reason, and split the same way, as in `combine`.
+    if !public_shares.len().is_multiple_of(S::PUBLIC_SHARE_LENGTH) {
+        return Err(format!(
+            "expected a multiple of {} bytes, one per public share, got {}",
+            S::PUBLIC_SHARE_LENGTH,
+            public_shares.len()
+        ));
+    }
+
+    let shares = public_shares
+        .chunks(S::PUBLIC_SHARE_LENGTH)
+        .map(|chunk| {
+            serialization::deserialize::<Eval<S::Public>>(chunk)
+                .map_err(|err| format!("could not deserialize public share: {}", err))
+        })
+        .collect::<TryResult<Vec<_>>>()?;
+
+    let polynomial = Poly::full_recover(t, shares.clone())
+        .map_err(|err| format!("could not recover polynomial: {}", err))?;
+
+    // Recovery keeps the first `t` distinct indices and ignores the rest, so a
+    // surplus share, or a second one at an index already used, is only ever
+    // checked here.
+    if let Some(stray) = shares
+        .iter()
+        .find(|share| polynomial.eval(share.index).value != share.value)
+    {
+        return Err(format!(
+            "public share {} does not lie on the polynomial recovered from the first {}",
+            stray.index, t
+        ));
+    }
+
+    bincode::serialize(&polynomial)
+        .map_err(|err| format!("could not serialize polynomial: {}", err))
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Backups
+//
+// Private keys and shares as mnemonics an operator can write down and type
+// back in, in the encoding `threshold_bls::mnemonic` defines. A mnemonic
+// records the scheme it was written under, so the keys-on-G1 functions refuse
+// one these wrote, and the reverse.
+///////////////////////////////////////////////////////////////////////////
+
+#[wasm_bindgen(js_name = privateKeyToMnemonic)]
+/// Writes a private key as a 29-word mnemonic, for the keyholder to back up.
+///
+/// # Throws
+///
+/// - If the private key cannot be deserialized
+pub fn private_key_to_mnemonic(private_key_buf: &[u8]) -> Result<String> {
+    try_private_key_to_mnemonic::<SigScheme>(private_key_buf).map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_private_key_to_mnemonic<S: BindingScheme>(private_key_buf: &[u8]) -> TryResult<String> {
+    let private_key = private_key(private_key_buf)?;
+
+    Ok(mnemonic::private_key_to_mnemonic::<S>(&private_key))
+}
+
+#[wasm_bindgen(js_name = privateKeyFromMnemonic)]
+/// Reads back a private key from the mnemonic `privateKeyToMnemonic` wrote, returning it
+/// serialized.
+///
+/// # Throws
+///
+/// - If a word is misspelled, missing or out of place, so that the checksum fails
+/// - If the mnemonic is of a share, or was written under the keys-on-G1 scheme
+pub fn private_key_from_mnemonic(mnemonic: &str) -> Result<Vec<u8>> {
+    try_private_key_from_mnemonic::<SigScheme>(mnemonic).map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_private_key_from_mnemonic<S: BindingScheme>(words: &str) -> TryResult<Vec<u8>> {
+    let private_key = mnemonic::private_key_from_mnemonic::<S>(words)
+        .map_err(|err| format!("could not read the mnemonic: {}", err))?;
+
+    bincode::serialize(&private_key)
+        .map_err(|err| format!("could not serialize private key: {}", err))
+}
+
+#[wasm_bindgen(js_name = shareToMnemonic)]
+/// Writes a private key share, with its index, as a 32-word mnemonic, for the shareholder
+/// to back up.
+///
+/// # Throws
+///
+/// - If the share cannot be deserialized
+pub fn share_to_mnemonic(share_buf: &[u8]) -> Result<String> {
+    try_share_to_mnemonic::<SigScheme>(share_buf).map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_share_to_mnemonic<S: BindingScheme>(share_buf: &[u8]) -> TryResult<String> {
+    let share = share(share_buf)?;
+
+    Ok(mnemonic::share_to_mnemonic::<S>(&share))
+}
+
+#[wasm_bindgen(js_name = shareFromMnemonic)]
+/// Reads back a private key share from the mnemonic `shareToMnemonic` wrote, returning it
+/// serialized.
+///
+/// # Throws
+///
+/// - If a word is misspelled, missing or out of place, so that the checksum fails
+/// - If the mnemonic is of a private key, or was written under the keys-on-G1 scheme
+pub fn share_from_mnemonic(mnemonic: &str) -> Result<Vec<u8>> {
+    try_share_from_mnemonic::<SigScheme>(mnemonic).map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_share_from_mnemonic<S: BindingScheme>(words: &str) -> TryResult<Vec<u8>> {
+    let share = mnemonic::share_from_mnemonic::<S>(words)
+        .map_err(|err| format!("could not read the mnemonic: {}", err))?;
+
+    bincode::serialize(&share).map_err(|err| format!("could not serialize share: {}", err))
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Batches
+//
+// Many items in one call, so the shared input is deserialized once instead of
+// once per call. Wasm-bindgen cannot pass an array of `Uint8Array`s, so items
+// are concatenated: signatures and partials are fixed-length and split by
+// offset, as in `combine`, while messages come with a `Uint32Array` of their
+// lengths. A verification returns a bitmap with one bit per item: item `i` is
+// bit `i % 8` of byte `i / 8`, set if it verified.
+///////////////////////////////////////////////////////////////////////////
+
+#[wasm_bindgen(js_name = batchVerify)]
+/// Verifies signatures on many messages against one public key, returning the result
+/// bitmap.
+///
+/// * messages: The messages, concatenated
+/// * message_lengths: The length of each message, in order
+/// * signatures: The signatures, concatenated in the order of the messages, 48 bytes each
+///
+/// A signature that does not verify clears its bit rather than throwing.
+///
+/// # Throws
+///
+/// - If the public key cannot be deserialized
+/// - If the message lengths do not add up to the concatenated messages
+/// - If there is not exactly one signature per message
+pub fn batch_verify(
+    public_key_buf: &[u8],
+    messages: &[u8],
+    message_lengths: &[u32],
+    signatures: &[u8],
+) -> Result<Vec<u8>> {
+    try_batch_verify::<SigScheme>(public_key_buf, messages, message_lengths, signatures)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_batch_verify<S: BindingScheme>(
+    public_key_buf: &[u8],
+    messages: &[u8],
+    message_lengths: &[u32],
+    signatures: &[u8],
+) -> TryResult<Vec<u8>> {
+    let public_key = public_key::<S>(public_key_buf)?;
+    let messages = split_messages(messages, message_lengths)?;
+
+    batch::verify::<S>(&public_key, &messages, signatures)
+}
+
+#[wasm_bindgen(js_name = batchPartialVerify)]
+/// Verifies many partial signatures on one message against the public polynomial,
+/// returning the result bitmap. The message is hashed to the curve once rather than
+/// once per partial.
+///
+/// A partial signature that does not verify clears its bit rather than throwing.
+///
+/// # Throws
+///
+/// - If the polynomial cannot be deserialized
+/// - If the flattened vector is not a whole number of partial signatures
+pub fn batch_partial_verify(
+    polynomial_buf: &[u8],
+    message: &[u8],
+    signatures: &[u8],
+) -> Result<Vec<u8>> {
+    try_batch_partial_verify::<SigScheme>(polynomial_buf, message, signatures)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_batch_partial_verify<S: BindingScheme>(
+    polynomial_buf: &[u8],
+    message: &[u8],
+    signatures: &[u8],
+) -> TryResult<Vec<u8>> {
+    let polynomial = polynomial::<S>(polynomial_buf)?;
+
+    batch::partial_verify::<S>(&polynomial, message, signatures)
+}
+
+#[wasm_bindgen(js_name = batchPartialVerifyBlindSignature)]
+/// Verifies many partial *blind* signatures on one blinded message against the public
+/// polynomial, returning the result bitmap.
+///
+/// A partial signature that does not verify clears its bit rather than throwing.
+///
+/// # Throws
+///
+/// - If the polynomial cannot be deserialized
+/// - If the flattened vector is not a whole number of partial signatures
+pub fn batch_partial_verify_blind_signature(
+    polynomial_buf: &[u8],
+    blinded_message: &[u8],
+    signatures: &[u8],
+) -> Result<Vec<u8>> {
+    try_batch_partial_verify_blind_signature::<SigScheme>(
+        polynomial_buf,
+        blinded_message,
+        signatures,
+    )
+    .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_batch_partial_verify_blind_signature<S: BindingScheme>(
+    polynomial_buf: &[u8],
+    blinded_message: &[u8],
+    signatures: &[u8],
+) -> TryResult<Vec<u8>> {
+    let polynomial = polynomial::<S>(polynomial_buf)?;
+
+    batch::partial_verify_blind_signature::<S>(&polynomial, blinded_message, signatures)
+}
+
+#[wasm_bindgen(js_name = batchBlind)]
+/// Blinds many messages with randomness from one seed, giving each its own blinding
+/// factor.
+///
+/// * messages: The messages, concatenated
+/// * message_lengths: The length of each message, in order
+/// * seed: A 32 byte seed for randomness
+///
+/// # Safety
+/// - If the same seed is used twice, the blinded results WILL be the same
+///
+/// # Throws
+///
+/// - If the message lengths do not add up to the concatenated messages
+/// - If the seed is shorter than 32 bytes
+/// - If a message cannot be blinded
+pub fn batch_blind(
+    messages: &[u8],
+    message_lengths: &[u32],
+    seed: &[u8],
+) -> Result<BlindedMessages> {
+    try_batch_blind::<SigScheme>(messages, message_lengths, seed)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_batch_blind<S: BindingScheme>(
+    messages: &[u8],
+    message_lengths: &[u32],
+    seed: &[u8],
+) -> TryResult<BlindedMessages> {
+    let messages = split_messages(messages, message_lengths)?;
+    let mut rng = get_rng(seed)?;
+
+    let (messages, blinding_factors) = batch::blind::<S>(&messages, &mut rng)?;
+    let blinding_factors = blinding_factors
+        .iter()
+        .map(bincode::serialize)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|err| format!("could not serialize blinding factor: {}", err))?
+        .concat();
+
+    Ok(BlindedMessages {
+        messages,
+        blinding_factors,
+    })
+}
+
+#[wasm_bindgen(js_name = batchUnblind)]
+/// Unblinds many blind signatures, each with the blinding factor at the same position,
+/// and returns the signatures concatenated in that order.
+///
+/// * blinded_signatures: The blind signatures, concatenated, 48 bytes each
+/// * blinding_factors: The blinding factors, concatenated, 32 bytes each, as
+///   `batchBlind` returns them
+///
+/// # Throws
+///
+/// - If either vector is not a whole number of its items, or they hold different
+///   numbers of them
+/// - If a blinding factor cannot be deserialized
+/// - If a signature cannot be unblinded
+pub fn batch_unblind(blinded_signatures: &[u8], blinding_factors: &[u8]) -> Result<Vec<u8>> {
+    try_batch_unblind::<SigScheme>(blinded_signatures, blinding_factors)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_batch_unblind<S: BindingScheme>(
+    blinded_signatures: &[u8],
+    blinding_factors: &[u8],
+) -> TryResult<Vec<u8>> {
+    if !blinding_factors.len().is_multiple_of(PRIVKEY_LEN) {
+        return Err(format!(
+            "expected a multiple of {} bytes, one per blinding factor, got {}",
+            PRIVKEY_LEN,
+            blinding_factors.len()
+        ));
+    }
+    let blinding_factors = blinding_factors
+        .chunks(PRIVKEY_LEN)
+        .map(|chunk| {
+            serialization::deserialize::<Token<PrivateKey>>(chunk)
+                .map_err(|err| format!("could not deserialize blinding factor {}", err))
+        })
+        .collect::<TryResult<Vec<_>>>()?;
+
+    batch::unblind::<S>(
+        blinded_signatures,
+        &blinding_factors.iter().collect::<Vec<_>>(),
+    )
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Keys on G1
+//
+// The same operations under the scheme that puts public keys on G1 (48 bytes)
+// and signatures on G2 (96 bytes). Each behaves as its unprefixed counterpart;
+// private keys, shares and blinding factors are the same bytes under either,
+// while public keys, polynomials and signatures from one scheme are rejected
+// by the other's functions.
+///////////////////////////////////////////////////////////////////////////
+
+#[wasm_bindgen(js_name = g1Blind)]
+/// Blinds a message for signing under the keys-on-G1 scheme. See `blind`.
+///
+/// # Throws
+///
+/// - If the seed is shorter than 32 bytes
+/// - If the message cannot be blinded
+pub fn g1_blind(message: Vec<u8>, seed: &[u8]) -> Result<BlindedMessage> {
+    try_blind::<G1SigScheme>(message, seed).map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1Unblind)]
+/// Unblinds a keys-on-G1 blind signature. See `unblind`.
+///
+/// # Throws
+///
+/// - If unblinding fails.
+pub fn g1_unblind(blinded_signature: &[u8], blinding_factor_buf: &[u8]) -> Result<Vec<u8>> {
+    try_unblind::<G1SigScheme>(blinded_signature, blinding_factor_buf)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1Verify)]
+/// Verifies a signature against a public key on G1. See `verify`.
+///
+/// # Throws
+///
+/// - If verification fails
+pub fn g1_verify(public_key_buf: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+    try_verify::<G1SigScheme>(public_key_buf, message, signature)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1VerifyBlindSignature)]
+/// Verifies a signature over a message blinded with `g1Blind`. See `verifyBlindSignature`.
+///
+/// # Throws
+///
+/// - If verification fails
+pub fn g1_verify_blind_signature(
+    public_key_buf: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    try_verify_blind_signature::<G1SigScheme>(public_key_buf, message, signature)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1OprfFinalize)]
+/// Derives the OPRF output from a keys-on-G1 signature. See `oprfFinalize`.
+///
+/// # Throws
+///
+/// - If the signature does not verify on the input
+pub fn g1_oprf_finalize(public_key_buf: &[u8], input: &[u8], signature: &[u8]) -> Result<Vec<u8>> {
+    try_oprf_finalize::<G1SigScheme>(public_key_buf, input, signature)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1OprfUnblindFinalize)]
+/// Unblinds a keys-on-G1 blind signature and derives the OPRF output from it. See
+/// `oprfUnblindFinalize`.
+///
+/// # Throws
+///
+/// - If the blinding factor cannot be deserialized
+/// - If the signature cannot be unblinded, or does not verify on the input once unblinded
+pub fn g1_oprf_unblind_finalize(
+    public_key_buf: &[u8],
+    input: &[u8],
+    blinded_signature: &[u8],
+    blinding_factor_buf: &[u8],
+) -> Result<Vec<u8>> {
+    try_oprf_unblind_finalize::<G1SigScheme>(
+        public_key_buf,
+        input,
+        blinded_signature,
+        blinding_factor_buf,
+    )
+    .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1Sign)]
+/// Signs the message under the keys-on-G1 scheme. See `sign`.
+///
+/// # Throws
+///
+/// - If signing fails
+pub fn g1_sign(private_key_buf: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    try_sign::<G1SigScheme>(private_key_buf, message).map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1SignBlindedMessage)]
+/// Signs a message blinded with `g1Blind`. See `signBlindedMessage`.
+///
+/// # Throws
+///
+/// - If signing fails
+pub fn g1_sign_blinded_message(private_key_buf: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    try_sign_blinded_message::<G1SigScheme>(private_key_buf, message)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1PartialSign)]
+/// Partially signs the message under the keys-on-G1 scheme. See `partialSign`.
+///
+/// # Throws
+///
+/// - If signing fails
+pub fn g1_partial_sign(share_buf: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    try_partial_sign::<G1SigScheme>(share_buf, message).map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1PartialSignBlindedMessage)]
+/// Partially signs a message blinded with `g1Blind`. See `partialSignBlindedMessage`.
+///
+/// # Throws
+///
+/// - If signing fails
+pub fn g1_partial_sign_blinded_message(share_buf: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    try_partial_sign_blinded_message::<G1SigScheme>(share_buf, message)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1PartialVerify)]
+/// Verifies a keys-on-G1 partial signature against a polynomial committed on G1. See
+/// `partialVerify`.
+///
+/// # Throws
+///
+/// - If verification fails
+pub fn g1_partial_verify(polynomial_buf: &[u8], blinded_message: &[u8], sig: &[u8]) -> Result<()> {
+    try_partial_verify::<G1SigScheme>(polynomial_buf, blinded_message, sig)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1PartialVerifyBlindSignature)]
+/// Verifies a keys-on-G1 partial *blind* signature against a polynomial committed on G1. See
+/// `partialVerifyBlindSignature`.
+///
+/// # Throws
+///
+/// - If verification fails
+pub fn g1_partial_verify_blind_signature(
+    polynomial_buf: &[u8],
+    blinded_message: &[u8],
+    sig: &[u8],
+) -> Result<()> {
+    try_partial_verify_blind_signature::<G1SigScheme>(polynomial_buf, blinded_message, sig)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1Combine)]
+/// Combines flattened keys-on-G1 partial signatures, 108 bytes each, into a threshold
+/// signature. See `combine`.
+///
+/// # Throws
+///
+/// - If the polynomial cannot be deserialized
+/// - If the flattened vector is not a whole number of partial signatures
+/// - If the aggregation fails
+///
+/// # Safety
+///
+/// - This function does not check if the signatures are valid!
+pub fn g1_combine(polynomial_buf: &[u8], signatures: Vec<u8>) -> Result<Vec<u8>> {
+    try_combine::<G1SigScheme>(polynomial_buf, signatures).map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1ThresholdKeygen)]
+/// Generates a t-of-n polynomial committed on G1 and private key shares. See
+/// `thresholdKeygen`.
+///
+/// # Safety
+///
+/// WARNING: This is a helper function for local testing of the library. Do not use
+/// in production, unless you trust the person that generated the keys.
+///
+/// # Throws
+///
+/// - If the number of shares is not between 1 and `MAX_SHARES`
+/// - If the threshold is not between 1 and `n`
+/// - If the seed is shorter than 32 bytes
+pub fn g1_threshold_keygen(n: usize, t: usize, seed: &[u8]) -> Result<Keys> {
+    try_threshold_keygen::<G1SigScheme>(n, t, seed).map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1SplitPrivateKey)]
+/// Splits an existing private key into `n` shares under the keys-on-G1 scheme. See
+/// `splitPrivateKey`.
+///
+/// # Throws
+///
+/// - If the private key cannot be deserialized
+/// - If the number of shares is not between 1 and `MAX_SHARES`
+/// - If the threshold is not between 1 and `n`
+/// - If the seed is shorter than 32 bytes
+pub fn g1_split_private_key(
+    private_key_buf: &[u8],
+    n: usize,
+    t: usize,
+    seed: &[u8],
+) -> Result<Keys> {
+    try_split_private_key::<G1SigScheme>(private_key_buf, n, t, seed)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+/// Generates a single private key from the provided seed, with its public key on G1.
+///
+/// # Throws
+///
+/// - If the seed is shorter than 32 bytes
+#[wasm_bindgen(js_name = g1Keygen)]
+pub fn g1_keygen(seed: Vec<u8>) -> Result<WasmKeypair> {
+    try_keygen::<G1SigScheme>(seed).map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1ThresholdPublicKey)]
+/// Returns the threshold public key of a public polynomial committed on G1. See
+/// `thresholdPublicKey`.
+///
+/// # Throws
+///
+/// - If the polynomial cannot be deserialized
+pub fn g1_threshold_public_key(polynomial_buf: &[u8]) -> Result<Vec<u8>> {
+    try_threshold_public_key::<G1SigScheme>(polynomial_buf).map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1EvaluatePolynomial)]
+/// Evaluates a public polynomial committed on G1 at a signer's index, returning that
+/// signer's 52-byte public share. See `evaluatePolynomial`.
+///
+/// # Throws
+///
+/// - If the polynomial cannot be deserialized
+pub fn g1_evaluate_polynomial(polynomial_buf: &[u8], index: Index) -> Result<Vec<u8>> {
+    try_evaluate_polynomial::<G1SigScheme>(polynomial_buf, index)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1VerifyShare)]
+/// Verifies a private key share against a public polynomial committed on G1. See
+/// `verifyShare`.
 ///
 /// # Throws
 ///
-/// - If verification fails
-pub fn partial_verify(polynomial_buf: &[u8], blinded_message: &[u8], sig: &[u8]) -> Result<()> {
-    try_partial_verify(polynomial_buf, blinded_message, sig).map_err(|err| JsValue::from_str(&err))
+/// - If the polynomial or the share cannot be deserialized
+/// - If the share does not lie on the polynomial
+pub fn g1_verify_share(polynomial_buf: &[u8], share_buf: &[u8]) -> Result<()> {
+    try_verify_share::<G1SigScheme>(polynomial_buf, share_buf)
+        .map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_partial_verify(polynomial_buf: &[u8], blinded_message: &[u8], sig: &[u8]) -> TryResult<()> {
-    let polynomial = polynomial(polynomial_buf)?;
+#[wasm_bindgen(js_name = g1RecoverPolynomial)]
+/// Recovers a public polynomial committed on G1 from flattened public shares, 52
+/// bytes each. See `recoverPolynomial`.
+///
+/// # Throws
+///
+/// - If the flattened vector is not a whole number of public shares
+/// - If a public share cannot be deserialized
+/// - If `t` is zero, or there are fewer than `t` distinct indices
+/// - If a share does not lie on the recovered polynomial
+pub fn g1_recover_polynomial(t: usize, public_shares: Vec<u8>) -> Result<Vec<u8>> {
+    try_recover_polynomial::<G1SigScheme>(t, public_shares).map_err(|err| JsValue::from_str(&err))
+}
 
-    SigScheme::partial_verify(&polynomial, blinded_message, sig)
-        .map_err(|err| format!("could not partially verify message: {}", err))
+#[wasm_bindgen(js_name = g1BatchVerify)]
+/// Verifies signatures on many messages against one public key on G1, each signature
+/// 96 bytes. See `batchVerify`.
+///
+/// # Throws
+///
+/// - If the public key cannot be deserialized
+/// - If the message lengths do not add up to the concatenated messages
+/// - If there is not exactly one signature per message
+pub fn g1_batch_verify(
+    public_key_buf: &[u8],
+    messages: &[u8],
+    message_lengths: &[u32],
+    signatures: &[u8],
+) -> Result<Vec<u8>> {
+    try_batch_verify::<G1SigScheme>(public_key_buf, messages, message_lengths, signatures)
+        .map_err(|err| JsValue::from_str(&err))
 }
 
-#[wasm_bindgen(js_name = partialVerifyBlindSignature)]
-/// Verifies a partial *blind* signature against the public key corresponding to the secret shared
-/// polynomial.
+#[wasm_bindgen(js_name = g1BatchPartialVerify)]
+/// Verifies many keys-on-G1 partial signatures on one message, 108 bytes each. See
+/// `batchPartialVerify`.
 ///
 /// # Throws
 ///
-/// - If verification fails
-pub fn partial_verify_blind_signature(
+/// - If the polynomial cannot be deserialized
+/// - If the flattened vector is not a whole number of partial signatures
+pub fn g1_batch_partial_verify(
     polynomial_buf: &[u8],
-    blinded_message: &[u8],
-    sig: &[u8],
-) -> Result<()> {
-    try_partial_verify_blind_signature(polynomial_buf, blinded_message, sig)
+    message: &[u8],
+    signatures: &[u8],
+) -> Result<Vec<u8>> {
+    try_batch_partial_verify::<G1SigScheme>(polynomial_buf, message, signatures)
         .map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_partial_verify_blind_signature(
+#[wasm_bindgen(js_name = g1BatchPartialVerifyBlindSignature)]
+/// Verifies many keys-on-G1 partial blind signatures on one blinded message, 108 bytes
+/// each. See `batchPartialVerifyBlindSignature`.
+///
+/// # Throws
+///
+/// - If the polynomial cannot be deserialized
+/// - If the flattened vector is not a whole number of partial signatures
+pub fn g1_batch_partial_verify_blind_signature(
     polynomial_buf: &[u8],
     blinded_message: &[u8],
-    sig: &[u8],
-) -> TryResult<()> {
-    let polynomial = polynomial(polynomial_buf)?;
-
-    SigScheme::verify_blind_partial(&polynomial, blinded_message, sig)
-        .map_err(|err| format!("could not partially verify message: {}", err))
+    signatures: &[u8],
+) -> Result<Vec<u8>> {
+    try_batch_partial_verify_blind_signature::<G1SigScheme>(
+        polynomial_buf,
+        blinded_message,
+        signatures,
+    )
+    .map_err(|err| JsValue::from_str(&err))
 }
 
-#[wasm_bindgen]
-/// Combines a flattened vector of partial signatures to a single threshold signature
-///
-/// NOTE: Wasm-bindgen does not support `Vec<Vec<u8>>`, so this function accepts a flattened
-/// byte vector which it will parse in chunks for each signature.
+#[wasm_bindgen(js_name = g1BatchBlind)]
+/// Blinds many messages for signing under the keys-on-G1 scheme, each blinded message
+/// 96 bytes. See `batchBlind`.
 ///
-/// NOTE: If you are working with an array of Uint8Arrays In Javascript, the simplest
-/// way to flatten them is via:
+/// # Safety
+/// - If the same seed is used twice, the blinded results WILL be the same
 ///
-/// ```js
-/// function flatten(arr) {
-///     return Uint8Array.from(arr.reduce(function(a, b) {
-///         return Array.from(a).concat(Array.from(b));
-///     }, []));
-/// }
-/// ```
+/// # Throws
 ///
-/// The threshold comes from the public polynomial rather than from the caller:
-/// it is the polynomial's degree plus one, and a smaller number would combine a
-/// subset of the partials into a different signature without failing.
+/// - If the message lengths do not add up to the concatenated messages
+/// - If the seed is shorter than 32 bytes
+/// - If a message cannot be blinded
+pub fn g1_batch_blind(
+    messages: &[u8],
+    message_lengths: &[u32],
+    seed: &[u8],
+) -> Result<BlindedMessages> {
+    try_batch_blind::<G1SigScheme>(messages, message_lengths, seed)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1BatchUnblind)]
+/// Unblinds many keys-on-G1 blind signatures, 96 bytes each. See `batchUnblind`.
 ///
 /// # Throws
 ///
-/// - If the polynomial cannot be deserialized
-/// - If the flattened vector is not a whole number of partial signatures
-/// - If the aggregation fails
+/// - If either vector is not a whole number of its items, or they hold different
+///   numbers of them
+/// - If a blinding factor cannot be deserialized
+/// - If a signature cannot be unblinded
+pub fn g1_batch_unblind(blinded_signatures: &[u8], blinding_factors: &[u8]) -> Result<Vec<u8>> {
+    try_batch_unblind::<G1SigScheme>(blinded_signatures, blinding_factors)
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = g1PrivateKeyToMnemonic)]
+/// Writes a private key as a mnemonic for restoring under the keys-on-G1 scheme. See
+/// `privateKeyToMnemonic`.
 ///
-/// # Safety
+/// # Throws
 ///
-/// - This function does not check if the signatures are valid!
-pub fn combine(polynomial_buf: &[u8], signatures: Vec<u8>) -> Result<Vec<u8>> {
-    try_combine(polynomial_buf, signatures).map_err(|err| JsValue::from_str(&err))
+/// - If the private key cannot be deserialized
+pub fn g1_private_key_to_mnemonic(private_key_buf: &[u8]) -> Result<String> {
+    try_private_key_to_mnemonic::<G1SigScheme>(private_key_buf)
+        .map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_combine(polynomial_buf: &[u8], signatures: Vec<u8>) -> TryResult<Vec<u8>> {
-    // The caller flattens the partial signatures, so the boundaries between
-    // them are implied by the length alone. A remainder means the flattening
-    // was wrong, and every chunk after the first mistake is cut from the middle
-    // of two partials.
-    if !signatures.len().is_multiple_of(PARTIAL_SIG_LENGTH) {
-        return Err(format!(
-            "expected a multiple of {} bytes, one per partial signature, got {}",
-            PARTIAL_SIG_LENGTH,
-            signatures.len()
-        ));
-    }
-
-    // break the flattened vector to a Vec<Vec<u8>> where each element is a serialized signature
-    let sigs = signatures
-        .chunks(PARTIAL_SIG_LENGTH)
-        .map(|chunk| chunk.to_vec())
-        .collect::<Vec<Vec<u8>>>();
+#[wasm_bindgen(js_name = g1PrivateKeyFromMnemonic)]
+/// Reads back a private key from a keys-on-G1 mnemonic. See `privateKeyFromMnemonic`.
+///
+/// # Throws
+///
+/// - If a word is misspelled, missing or out of place, so that the checksum fails
+/// - If the mnemonic is of a share, or was not written under the keys-on-G1 scheme
+pub fn g1_private_key_from_mnemonic(mnemonic: &str) -> Result<Vec<u8>> {
+    try_private_key_from_mnemonic::<G1SigScheme>(mnemonic).map_err(|err| JsValue::from_str(&err))
+}
 
-    let polynomial = polynomial(polynomial_buf)?;
+#[wasm_bindgen(js_name = g1ShareToMnemonic)]
+/// Writes a private key share as a mnemonic for restoring under the keys-on-G1 scheme.
+/// See `shareToMnemonic`.
+///
+/// # Throws
+///
+/// - If the share cannot be deserialized
+pub fn g1_share_to_mnemonic(share_buf: &[u8]) -> Result<String> {
+    try_share_to_mnemonic::<G1SigScheme>(share_buf).map_err(|err| JsValue::from_str(&err))
+}
 
-    SigScheme::aggregate(&polynomial, &sigs)
-        .map_err(|err| format!("could not aggregate sigs: {}", err))
+#[wasm_bindgen(js_name = g1ShareFromMnemonic)]
+/// Reads back a private key share from a keys-on-G1 mnemonic. See `shareFromMnemonic`.
+///
+/// # Throws
+///
+/// - If a word is misspelled, missing or out of place, so that the checksum fails
+/// - If the mnemonic is of a private key, or was not written under the keys-on-G1 scheme
+pub fn g1_share_from_mnemonic(mnemonic: &str) -> Result<Vec<u8>> {
+    try_share_from_mnemonic::<G1SigScheme>(mnemonic).map_err(|err| JsValue::from_str(&err))
 }
 
 ///////////////////////////////////////////////////////////////////////////
@@ -342,7 +1275,7 @@ fn try_combine(polynomial_buf: &[u8], signatures: Vec<u8>) -> TryResult<Vec<u8>>
 // The four values callers hand over as bytes. Each message is written once here
 // rather than at every entry point that takes that type.
 
-fn public_key(buf: &[u8]) -> TryResult<PublicKey> {
+fn public_key<S: BindingScheme>(buf: &[u8]) -> TryResult<S::Public> {
     serialization::deserialize(buf)
         .map_err(|err| format!("could not deserialize public key {}", err))
 }
@@ -357,11 +1290,35 @@ fn share(buf: &[u8]) -> TryResult<Share<PrivateKey>> {
         .map_err(|err| format!("could not deserialize private key share {}", err))
 }
 
-fn polynomial(buf: &[u8]) -> TryResult<Poly<PublicKey>> {
+fn polynomial<S: BindingScheme>(buf: &[u8]) -> TryResult<Poly<S::Public>> {
     serialization::deserialize(buf)
         .map_err(|err| format!("could not deserialize polynomial {}", err))
 }
 
+/// Splits concatenated messages at the lengths the caller gave for them, which
+/// have to account for every byte.
+fn split_messages<'a>(messages: &'a [u8], lengths: &[u32]) -> TryResult<Vec<&'a [u8]>> {
+    let total = lengths
+        .iter()
+        .try_fold(0usize, |total, &len| total.checked_add(len as usize));
+    if total != Some(messages.len()) {
+        return Err(format!(
+            "the message lengths do not add up to the {} bytes of messages",
+            messages.len()
+        ));
+    }
+
+    let mut rest = messages;
+    Ok(lengths
+        .iter()
+        .map(|&len| {
+            let (message, tail) = rest.split_at(len as usize);
+            rest = tail;
+            message
+        })
+        .collect())
+}
+
 #[wasm_bindgen(js_name = thresholdKeygen)]
 /// Generates a t-of-n polynomial and private key shares
 ///
@@ -376,16 +1333,62 @@ fn polynomial(buf: &[u8]) -> TryResult<Poly<PublicKey>> {
 /// - If the threshold is not between 1 and `n`
 /// - If the seed is shorter than 32 bytes
 pub fn threshold_keygen(n: usize, t: usize, seed: &[u8]) -> Result<Keys> {
-    try_threshold_keygen(n, t, seed).map_err(|err| JsValue::from_str(&err))
+    try_threshold_keygen::<SigScheme>(n, t, seed).map_err(|err| JsValue::from_str(&err))
+}
+
+fn try_threshold_keygen<S: BindingScheme>(n: usize, t: usize, seed: &[u8]) -> TryResult<Keys> {
+    check_group(n, t)?;
+
+    let mut rng = get_rng(seed)?;
+    let private = Poly::<PrivateKey>::new_from(t - 1, &mut rng);
+    let shares = (0..n)
+        .map(|i| private.eval(i as Index))
+        .map(|e| Share {
+            index: e.index,
+            private: e.value,
+        })
+        .collect();
+    keys::<S>(shares, private.commit(), t, n)
+}
+
+#[wasm_bindgen(js_name = splitPrivateKey)]
+/// Splits an existing private key into `n` shares, any `t` of which sign for it. The
+/// returned polynomial's public key is the private key's own, so signatures combined from
+/// the shares verify against the key its users already have.
+///
+/// # Safety
+///
+/// Whoever calls this holds the whole key, as they did before the split. Once the shares
+/// are handed out, the private key should be destroyed.
+///
+/// # Throws
+///
+/// - If the private key cannot be deserialized
+/// - If the number of shares is not between 1 and `MAX_SHARES`
+/// - If the threshold is not between 1 and `n`
+/// - If the seed is shorter than 32 bytes
+pub fn split_private_key(private_key_buf: &[u8], n: usize, t: usize, seed: &[u8]) -> Result<Keys> {
+    try_split_private_key::<SigScheme>(private_key_buf, n, t, seed)
+        .map_err(|err| JsValue::from_str(&err))
 }
 
-/// The largest group this deals keys for. A threshold group is a handful of
-/// signers, so the bound is far above any real one; it is here so that a
-/// mistyped `n` fails with a message rather than asking the allocator for
-/// gigabytes, which in wasm traps and poisons the instance.
-const MAX_SHARES: usize = 1024;
+fn try_split_private_key<S: BindingScheme>(
+    private_key_buf: &[u8],
+    n: usize,
+    t: usize,
+    seed: &[u8],
+) -> TryResult<Keys> {
+    let private_key = private_key(private_key_buf)?;
+    check_group(n, t)?;
+
+    let mut rng = get_rng(seed)?;
+    let (shares, polynomial) = threshold_bls::sig::deal::<S, _>(&private_key, n, t, &mut rng)
+        .map_err(|err| format!("could not split the private key: {}", err))?;
+    keys::<S>(shares, polynomial, t, n)
+}
 
-fn try_threshold_keygen(n: usize, t: usize, seed: &[u8]) -> TryResult<Keys> {
+/// Refuses a group the bindings will not deal keys for.
+fn check_group(n: usize, t: usize) -> TryResult<()> {
     if !(1..=MAX_SHARES).contains(&n) {
         return Err(format!(
             "the number of shares must be between 1 and {} (got {})",
@@ -399,20 +1402,23 @@ fn try_threshold_keygen(n: usize, t: usize, seed: &[u8]) -> TryResult<Keys> {
     if !(1..=n).contains(&t) {
         return Err(format!("threshold must be between 1 and {} (got {})", n, t));
     }
+    Ok(())
+}
 
-    let mut rng = get_rng(seed)?;
-    let private = Poly::<PrivateKey>::new_from(t - 1, &mut rng);
-    let shares = (0..n)
-        .map(|i| private.eval(i as Index))
-        .map(|e| Share {
-            index: e.index,
-            private: e.value,
-        })
-        .collect();
-    let polynomial = private.commit();
+fn keys<S: BindingScheme>(
+    shares: Vec<Share<PrivateKey>>,
+    polynomial: Poly<S::Public>,
+    t: usize,
+    n: usize,
+) -> TryResult<Keys> {
+    let threshold_public_key = bincode::serialize(polynomial.public_key())
+        .map_err(|err| format!("could not serialize threshold public key: {}", err))?;
+    let polynomial = bincode::serialize(&polynomial)
+        .map_err(|err| format!("could not serialize polynomial: {}", err))?;
     Ok(Keys {
         shares,
         polynomial,
+        threshold_public_key,
         t,
         n,
     })
@@ -442,19 +1448,46 @@ impl BlindedMessage {
     }
 }
 
+#[wasm_bindgen(inspectable)]
+/// Many blinded messages along with the blinding factors used to produce them, each
+/// concatenated in the order of the messages
+pub struct BlindedMessages {
+    /// The blinded messages, each the length of a signature
+    messages: Vec<u8>,
+    /// The blinding factors, 32 bytes each
+    blinding_factors: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl BlindedMessages {
+    #[wasm_bindgen(getter)]
+    pub fn messages(&self) -> Vec<u8> {
+        self.messages.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = blindingFactors)]
+    pub fn blinding_factors(&self) -> Vec<u8> {
+        self.blinding_factors.clone()
+    }
+}
+
 // Named `WasmKeypair` rather than `Keypair` because `ffi.rs` exports a struct
 // by that name too. cbindgen does not evaluate features, so it parses both
 // modules and would emit the typedef twice — legal in C11, an error in C99.
 // `js_name` keeps the JS class called `Keypair`, which the published
 // declarations promise.
+//
+// The public key is held serialized so that one class serves both schemes:
+// `keygen` and `g1Keygen` return the same `Keypair`, and its bytes are in
+// whichever group the scheme that made it puts public keys.
 #[wasm_bindgen(js_name = Keypair)]
 #[derive(Clone)]
 /// A BLS12-377 Keypair
 pub struct WasmKeypair {
     /// The private key
     private: PrivateKey,
-    /// The public key
-    public: PublicKey,
+    /// The public key, serialized
+    public: Vec<u8>,
 }
 
 // Need to implement custom getters if we want to return more than one value
@@ -468,7 +1501,7 @@ impl WasmKeypair {
 
     #[wasm_bindgen(getter, js_name = publicKey)]
     pub fn public_key(&self) -> Vec<u8> {
-        bincode::serialize(&self.public).expect("could not serialize public key")
+        self.public.clone()
     }
 }
 
@@ -479,19 +1512,25 @@ impl WasmKeypair {
 /// - If the seed is shorter than 32 bytes
 #[wasm_bindgen]
 pub fn keygen(seed: Vec<u8>) -> Result<WasmKeypair> {
-    try_keygen(seed).map_err(|err| JsValue::from_str(&err))
+    try_keygen::<SigScheme>(seed).map_err(|err| JsValue::from_str(&err))
 }
 
-fn try_keygen(seed: Vec<u8>) -> TryResult<WasmKeypair> {
+fn try_keygen<S: BindingScheme>(seed: Vec<u8>) -> TryResult<WasmKeypair> {
     let mut rng = get_rng(&seed)?;
-    let (private, public) = SigScheme::keypair(&mut rng);
+    let (private, public) = S::keypair(&mut rng);
+    let public = bincode::serialize(&public)
+        .map_err(|err| format!("could not serialize public key: {}", err))?;
     Ok(WasmKeypair { private, public })
 }
 
+// The public half is held serialized, as in `WasmKeypair`, so that
+// `thresholdKeygen` and `g1ThresholdKeygen` return the same class. The shares
+// are scalars under either scheme.
 #[wasm_bindgen]
 pub struct Keys {
     shares: Vec<Share<PrivateKey>>,
-    polynomial: Poly<PublicKey>,
+    polynomial: Vec<u8>,
+    threshold_public_key: Vec<u8>,
     pub t: usize,
     pub n: usize,
 }
@@ -527,13 +1566,12 @@ impl Keys {
 
     #[wasm_bindgen(getter, js_name = polynomial)]
     pub fn polynomial(&self) -> Vec<u8> {
-        bincode::serialize(&self.polynomial).expect("could not serialize polynomial")
+        self.polynomial.clone()
     }
 
     #[wasm_bindgen(getter, js_name = thresholdPublicKey)]
     pub fn threshold_public_key(&self) -> Vec<u8> {
-        bincode::serialize(&self.polynomial.public_key())
-            .expect("could not serialize threshold public key")
+        self.threshold_public_key.clone()
     }
 }
 
@@ -576,10 +1614,10 @@ mod tests {
     fn a_short_seed_is_rejected() {
         let short = [7u8; SEED_LEN - 1];
 
-        assert!(try_keygen(short.to_vec()).is_err());
-        assert!(try_blind(vec![1, 2, 3], &short).is_err());
-        assert!(try_threshold_keygen(5, 3, &short).is_err());
-        assert!(try_keygen(Vec::new()).is_err());
+        assert!(try_keygen::<SigScheme>(short.to_vec()).is_err());
+        assert!(try_blind::<SigScheme>(vec![1, 2, 3], &short).is_err());
+        assert!(try_threshold_keygen::<SigScheme>(5, 3, &short).is_err());
+        assert!(try_keygen::<SigScheme>(Vec::new()).is_err());
     }
 
     // The whole seed is consumed, so the exact length is enough: the check is
@@ -588,9 +1626,9 @@ mod tests {
     fn a_seed_of_exactly_the_required_length_is_accepted() {
         let seed = [7u8; SEED_LEN];
 
-        assert!(try_keygen(seed.to_vec()).is_ok());
-        assert!(try_blind(vec![1, 2, 3], &seed).is_ok());
-        assert!(try_threshold_keygen(5, 3, &seed).is_ok());
+        assert!(try_keygen::<SigScheme>(seed.to_vec()).is_ok());
+        assert!(try_blind::<SigScheme>(vec![1, 2, 3], &seed).is_ok());
+        assert!(try_threshold_keygen::<SigScheme>(5, 3, &seed).is_ok());
     }
 
     // A threshold of zero asked for a polynomial of degree `usize::MAX`.
@@ -598,11 +1636,11 @@ mod tests {
     fn a_threshold_outside_1_to_n_is_rejected() {
         let seed = [7u8; SEED_LEN];
 
-        assert!(try_threshold_keygen(5, 0, &seed).is_err());
-        assert!(try_threshold_keygen(5, 6, &seed).is_err());
-        assert!(try_threshold_keygen(0, 0, &seed).is_err());
-        assert!(try_threshold_keygen(5, 1, &seed).is_ok());
-        assert!(try_threshold_keygen(5, 5, &seed).is_ok());
+        assert!(try_threshold_keygen::<SigScheme>(5, 0, &seed).is_err());
+        assert!(try_threshold_keygen::<SigScheme>(5, 6, &seed).is_err());
+        assert!(try_threshold_keygen::<SigScheme>(0, 0, &seed).is_err());
+        assert!(try_threshold_keygen::<SigScheme>(5, 1, &seed).is_ok());
+        assert!(try_threshold_keygen::<SigScheme>(5, 5, &seed).is_ok());
     }
 
     // A group of a billion is a plausible typo and an allocation no wasm
@@ -612,9 +1650,9 @@ mod tests {
     fn a_group_larger_than_the_maximum_is_rejected() {
         let seed = [7u8; SEED_LEN];
 
-        assert!(try_threshold_keygen(MAX_SHARES + 1, 1, &seed).is_err());
-        assert!(try_threshold_keygen(usize::MAX, 1, &seed).is_err());
-        assert!(try_threshold_keygen(0, 1, &seed).is_err());
+        assert!(try_threshold_keygen::<SigScheme>(MAX_SHARES + 1, 1, &seed).is_err());
+        assert!(try_threshold_keygen::<SigScheme>(usize::MAX, 1, &seed).is_err());
+        assert!(try_threshold_keygen::<SigScheme>(0, 1, &seed).is_err());
     }
 
     // The caller flattens the partials, so a mistake there is invisible to
@@ -623,7 +1661,7 @@ mod tests {
     // too high.
     #[test]
     fn a_flattened_vector_that_is_not_whole_partials_is_rejected() {
-        let keys = try_threshold_keygen(5, 3, &[7u8; SEED_LEN]).unwrap();
+        let keys = try_threshold_keygen::<SigScheme>(5, 3, &[7u8; SEED_LEN]).unwrap();
         let msg = vec![1, 9, 6, 9];
 
         let mut flattened = Vec::new();
@@ -631,7 +1669,7 @@ mod tests {
             flattened.extend(partial_sign(&keys.get_share(index).unwrap(), &msg).unwrap());
         }
         assert_eq!(flattened.len(), 3 * PARTIAL_SIG_LENGTH);
-        assert!(try_combine(&keys.polynomial(), flattened.clone()).is_ok());
+        assert!(try_combine::<SigScheme>(&keys.polynomial(), flattened.clone()).is_ok());
 
         // One byte over, one byte short, and a whole partial's worth of
         // padding that leaves the boundaries misaligned.
@@ -647,7 +1685,7 @@ mod tests {
         // distinguishes this check from the parser catching it by accident.
         for wrong in [one_over, one_short, half_a_partial] {
             let len = wrong.len();
-            let err = try_combine(&keys.polynomial(), wrong)
+            let err = try_combine::<SigScheme>(&keys.polynomial(), wrong)
                 .expect_err(&format!("combine accepted {len} bytes"));
             assert!(
                 err.contains("expected a multiple of"),
@@ -665,39 +1703,40 @@ mod tests {
 
         let cases: Vec<(String, &str)> = vec![
             (
-                try_verify(&garbage, b"msg", &garbage).unwrap_err(),
+                try_verify::<SigScheme>(&garbage, b"msg", &garbage).unwrap_err(),
                 "could not deserialize public key",
             ),
             (
-                try_verify_blind_signature(&garbage, b"msg", &garbage).unwrap_err(),
+                try_verify_blind_signature::<SigScheme>(&garbage, b"msg", &garbage).unwrap_err(),
                 "could not deserialize public key",
             ),
             (
-                try_sign(&garbage, b"msg").unwrap_err(),
+                try_sign::<SigScheme>(&garbage, b"msg").unwrap_err(),
                 "could not deserialize private key",
             ),
             (
-                try_sign_blinded_message(&garbage, b"msg").unwrap_err(),
+                try_sign_blinded_message::<SigScheme>(&garbage, b"msg").unwrap_err(),
                 "could not deserialize private key",
             ),
             (
-                try_partial_sign(&garbage, b"msg").unwrap_err(),
+                try_partial_sign::<SigScheme>(&garbage, b"msg").unwrap_err(),
                 "could not deserialize private key share",
             ),
             (
-                try_partial_sign_blinded_message(&garbage, b"msg").unwrap_err(),
+                try_partial_sign_blinded_message::<SigScheme>(&garbage, b"msg").unwrap_err(),
                 "could not deserialize private key share",
             ),
             (
-                try_partial_verify(&garbage, b"msg", &garbage).unwrap_err(),
+                try_partial_verify::<SigScheme>(&garbage, b"msg", &garbage).unwrap_err(),
                 "could not deserialize polynomial",
             ),
             (
-                try_partial_verify_blind_signature(&garbage, b"msg", &garbage).unwrap_err(),
+                try_partial_verify_blind_signature::<SigScheme>(&garbage, b"msg", &garbage)
+                    .unwrap_err(),
                 "could not deserialize polynomial",
             ),
             (
-                try_unblind(&garbage, &garbage).unwrap_err(),
+                try_unblind::<SigScheme>(&garbage, &garbage).unwrap_err(),
                 "could not deserialize blinding factor",
             ),
         ];
@@ -715,19 +1754,19 @@ mod tests {
     // the boundary none of them could be asserted here.
     #[test]
     fn well_formed_arguments_that_do_not_match_are_rejected() {
-        let keypair = try_keygen([7u8; SEED_LEN].to_vec()).unwrap();
-        let other = try_keygen([9u8; SEED_LEN].to_vec()).unwrap();
+        let keypair = try_keygen::<SigScheme>([7u8; SEED_LEN].to_vec()).unwrap();
+        let other = try_keygen::<SigScheme>([9u8; SEED_LEN].to_vec()).unwrap();
         let msg = b"attack at dawn";
-        let signature = try_sign(&keypair.private_key(), msg).unwrap();
+        let signature = try_sign::<SigScheme>(&keypair.private_key(), msg).unwrap();
 
-        assert!(try_verify(&keypair.public_key(), msg, &signature).is_ok());
+        assert!(try_verify::<SigScheme>(&keypair.public_key(), msg, &signature).is_ok());
         assert!(
-            try_verify(&other.public_key(), msg, &signature)
+            try_verify::<SigScheme>(&other.public_key(), msg, &signature)
                 .unwrap_err()
                 .starts_with("signature verification failed")
         );
         assert!(
-            try_verify(&keypair.public_key(), b"other message", &signature)
+            try_verify::<SigScheme>(&keypair.public_key(), b"other message", &signature)
                 .unwrap_err()
                 .starts_with("signature verification failed")
         );
@@ -735,7 +1774,7 @@ mod tests {
         // A signature is not a blinded message point, so blind verification of
         // one fails rather than passing on a technicality.
         assert!(
-            try_verify_blind_signature(&keypair.public_key(), &signature, &signature)
+            try_verify_blind_signature::<SigScheme>(&keypair.public_key(), &signature, &signature)
                 .unwrap_err()
                 .starts_with("signature verification failed")
         );
@@ -743,25 +1782,25 @@ mod tests {
         // Unblinding does not validate: any token inverts against any signature
         // point, so the wrong one yields a result that fails verification rather
         // than an error. Only bytes that are not a signature are refused.
-        let blinded = try_blind(msg.to_vec(), &[3u8; SEED_LEN]).unwrap();
-        let mismatched = try_unblind(&signature, &blinded.blinding_factor()).unwrap();
-        assert!(try_verify(&keypair.public_key(), msg, &mismatched).is_err());
+        let blinded = try_blind::<SigScheme>(msg.to_vec(), &[3u8; SEED_LEN]).unwrap();
+        let mismatched = try_unblind::<SigScheme>(&signature, &blinded.blinding_factor()).unwrap();
+        assert!(try_verify::<SigScheme>(&keypair.public_key(), msg, &mismatched).is_err());
         assert!(
-            try_unblind(&[1, 2, 3], &blinded.blinding_factor())
+            try_unblind::<SigScheme>(&[1, 2, 3], &blinded.blinding_factor())
                 .unwrap_err()
                 .starts_with("could not unblind signature")
         );
 
-        let keys = try_threshold_keygen(5, 3, &[7u8; SEED_LEN]).unwrap();
-        let partial = try_partial_sign(&keys.try_get_share(0).unwrap(), msg).unwrap();
-        assert!(try_partial_verify(&keys.polynomial(), msg, &partial).is_ok());
+        let keys = try_threshold_keygen::<SigScheme>(5, 3, &[7u8; SEED_LEN]).unwrap();
+        let partial = try_partial_sign::<SigScheme>(&keys.try_get_share(0).unwrap(), msg).unwrap();
+        assert!(try_partial_verify::<SigScheme>(&keys.polynomial(), msg, &partial).is_ok());
         assert!(
-            try_partial_verify(&keys.polynomial(), b"other message", &partial)
+            try_partial_verify::<SigScheme>(&keys.polynomial(), b"other message", &partial)
                 .unwrap_err()
                 .starts_with("could not partially verify message")
         );
         assert!(
-            try_partial_verify_blind_signature(&keys.polynomial(), msg, &partial)
+            try_partial_verify_blind_signature::<SigScheme>(&keys.polynomial(), msg, &partial)
                 .unwrap_err()
                 .starts_with("could not partially verify message")
         );
@@ -770,7 +1809,7 @@ mod tests {
     // `numShares` is the caller's only guard, and it was advisory.
     #[test]
     fn a_share_index_past_the_last_share_is_rejected() {
-        let keys = try_threshold_keygen(5, 3, &[7u8; SEED_LEN]).unwrap();
+        let keys = try_threshold_keygen::<SigScheme>(5, 3, &[7u8; SEED_LEN]).unwrap();
         assert_eq!(keys.num_shares(), 5);
 
         assert!(keys.try_get_share(4).is_ok());
@@ -778,6 +1817,425 @@ mod tests {
         assert!(keys.try_get_share(usize::MAX).is_err());
     }
 
+    #[test]
+    fn g1_signing() {
+        let keypair = g1_keygen([7u8; SEED_LEN].to_vec()).unwrap();
+        assert_eq!(keypair.public_key().len(), G1_PUBKEY_LEN);
+        let msg = vec![1, 2, 3, 4, 6];
+
+        let sig = g1_sign(&keypair.private_key(), &msg).unwrap();
+        assert_eq!(sig.len(), G1_SIGNATURE_LEN);
+        g1_verify(&keypair.public_key(), &msg, &sig).unwrap();
+
+        let blinded = g1_blind(msg.clone(), &[3u8; SEED_LEN]).unwrap();
+        let blind_sig = g1_sign_blinded_message(&keypair.private_key(), &blinded.message).unwrap();
+        g1_verify_blind_signature(&keypair.public_key(), &blinded.message, &blind_sig).unwrap();
+        let unblinded = g1_unblind(&blind_sig, &blinded.blinding_factor()).unwrap();
+        assert_eq!(unblinded, sig);
+    }
+
+    #[test]
+    fn oprf() {
+        let keypair = keygen([7u8; SEED_LEN].to_vec()).unwrap();
+        let input = b"user@example.com".to_vec();
+
+        let sig = sign(&keypair.private_key(), &input).unwrap();
+        let output = oprf_finalize(&keypair.public_key(), &input, &sig).unwrap();
+        assert_eq!(output.len(), OPRF_OUTPUT_LEN);
+
+        let blinded = blind(input.clone(), &[3u8; SEED_LEN]).unwrap();
+        let blind_sig = sign_blinded_message(&keypair.private_key(), &blinded.message).unwrap();
+        let unblinded_output = oprf_unblind_finalize(
+            &keypair.public_key(),
+            &input,
+            &blind_sig,
+            &blinded.blinding_factor(),
+        )
+        .unwrap();
+        assert_eq!(unblinded_output, output);
+
+        // the G1 scheme finalizes the same way
+        let g1_keypair = g1_keygen([7u8; SEED_LEN].to_vec()).unwrap();
+        let g1_sig = g1_sign(&g1_keypair.private_key(), &input).unwrap();
+        let g1_output = g1_oprf_finalize(&g1_keypair.public_key(), &input, &g1_sig).unwrap();
+        let g1_blinded = g1_blind(input.clone(), &[3u8; SEED_LEN]).unwrap();
+        let g1_blind_sig =
+            g1_sign_blinded_message(&g1_keypair.private_key(), &g1_blinded.message).unwrap();
+        assert_eq!(
+            g1_oprf_unblind_finalize(
+                &g1_keypair.public_key(),
+                &input,
+                &g1_blind_sig,
+                &g1_blinded.blinding_factor(),
+            )
+            .unwrap(),
+            g1_output
+        );
+
+        // a signature that does not verify has no output
+        assert!(try_oprf_finalize::<SigScheme>(&keypair.public_key(), b"other", &sig).is_err());
+        assert!(
+            try_oprf_unblind_finalize::<SigScheme>(
+                &keypair.public_key(),
+                b"other",
+                &blind_sig,
+                &blinded.blinding_factor(),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn g1_threshold() {
+        let (n, t) = (5, 3);
+        let keys = g1_threshold_keygen(n, t, &[7u8; SEED_LEN]).unwrap();
+        assert_eq!(keys.threshold_public_key().len(), G1_PUBKEY_LEN);
+        let msg = vec![1, 2, 3, 4, 6];
+        let blinded = g1_blind(msg.clone(), &[3u8; SEED_LEN]).unwrap();
+
+        let mut partials = Vec::new();
+        let mut blind_partials = Vec::new();
+        for index in 0..t {
+            let share = keys.get_share(index).unwrap();
+
+            let partial = g1_partial_sign(&share, &msg).unwrap();
+            g1_partial_verify(&keys.polynomial(), &msg, &partial).unwrap();
+            partials.extend(partial);
+
+            let partial = g1_partial_sign_blinded_message(&share, &blinded.message).unwrap();
+            g1_partial_verify_blind_signature(&keys.polynomial(), &blinded.message, &partial)
+                .unwrap();
+            blind_partials.extend(partial);
+        }
+        assert_eq!(partials.len(), t * G1_PARTIAL_SIG_LENGTH);
+
+        let asig = g1_combine(&keys.polynomial(), partials).unwrap();
+        g1_verify(&keys.threshold_public_key(), &msg, &asig).unwrap();
+
+        let blind_asig = g1_combine(&keys.polynomial(), blind_partials).unwrap();
+        let unblinded = g1_unblind(&blind_asig, &blinded.blinding_factor()).unwrap();
+        assert_eq!(unblinded, asig);
+    }
+
+    // A public key, polynomial or signature is in a different group under each
+    // scheme, so handing one to the other's functions is an error rather than
+    // a verification against a reinterpreted point.
+    #[test]
+    fn each_scheme_rejects_the_others_group_elements() {
+        let seed = [7u8; SEED_LEN];
+        let msg = b"attack at dawn";
+        let g1 = try_keygen::<G1SigScheme>(seed.to_vec()).unwrap();
+        let g2 = try_keygen::<SigScheme>(seed.to_vec()).unwrap();
+
+        // The private key is the same scalar under either.
+        assert_eq!(g1.private_key(), g2.private_key());
+
+        let g1_sig = try_sign::<G1SigScheme>(&g1.private_key(), msg).unwrap();
+        let g2_sig = try_sign::<SigScheme>(&g2.private_key(), msg).unwrap();
+        assert!(
+            try_verify::<SigScheme>(&g1.public_key(), msg, &g2_sig)
+                .unwrap_err()
+                .starts_with("could not deserialize public key")
+        );
+        assert!(
+            try_verify::<G1SigScheme>(&g2.public_key(), msg, &g1_sig)
+                .unwrap_err()
+                .starts_with("could not deserialize public key")
+        );
+        assert!(try_verify::<G1SigScheme>(&g1.public_key(), msg, &g2_sig).is_err());
+
+        let g1_keys = try_threshold_keygen::<G1SigScheme>(5, 3, &seed).unwrap();
+        let g2_keys = try_threshold_keygen::<SigScheme>(5, 3, &seed).unwrap();
+        assert_eq!(
+            g1_keys.try_get_share(0).unwrap(),
+            g2_keys.try_get_share(0).unwrap()
+        );
+        let partial =
+            try_partial_sign::<G1SigScheme>(&g1_keys.try_get_share(0).unwrap(), msg).unwrap();
+        assert!(
+            try_partial_verify::<SigScheme>(&g1_keys.polynomial(), msg, &partial)
+                .unwrap_err()
+                .starts_with("could not deserialize polynomial")
+        );
+        assert!(
+            try_combine::<SigScheme>(&g2_keys.polynomial(), partial)
+                .unwrap_err()
+                .contains("expected a multiple of")
+        );
+    }
+
+    #[test]
+    fn committee_keys_can_be_inspected_from_the_polynomial() {
+        inspect_committee::<SigScheme>();
+        inspect_committee::<G1SigScheme>();
+    }
+
+    fn inspect_committee<S: BindingScheme>() {
+        let (n, t) = (5, 3);
+        let keys = try_threshold_keygen::<S>(n, t, &[7u8; SEED_LEN]).unwrap();
+        let polynomial = keys.polynomial();
+
+        assert_eq!(
+            try_threshold_public_key::<S>(&polynomial).unwrap(),
+            keys.threshold_public_key()
+        );
+
+        let mut public_shares = Vec::new();
+        for index in 0..n {
+            let share = keys.get_share(index).unwrap();
+            try_verify_share::<S>(&polynomial, &share).unwrap();
+
+            let public_share = try_evaluate_polynomial::<S>(&polynomial, index as Index).unwrap();
+            assert_eq!(public_share.len(), S::PUBLIC_SHARE_LENGTH);
+            public_shares.push(public_share);
+        }
+
+        // Every share passed, and exactly `t` of them out of order, recover
+        // the same bytes the dealer published.
+        assert_eq!(
+            try_recover_polynomial::<S>(t, public_shares.concat()).unwrap(),
+            polynomial
+        );
+        let subset = [&public_shares[4], &public_shares[1], &public_shares[3]].map(|s| s.clone());
+        assert_eq!(
+            try_recover_polynomial::<S>(t, subset.concat()).unwrap(),
+            polynomial
+        );
+    }
+
+    #[test]
+    fn a_share_of_another_committee_does_not_verify() {
+        let keys = try_threshold_keygen::<SigScheme>(5, 3, &[7u8; SEED_LEN]).unwrap();
+        let other = try_threshold_keygen::<SigScheme>(5, 3, &[8u8; SEED_LEN]).unwrap();
+
+        let err = try_verify_share::<SigScheme>(&keys.polynomial(), &other.get_share(2).unwrap())
+            .unwrap_err();
+        assert!(err.contains("share 2 does not match"), "{err}");
+
+        // The right scalar under another holder's index is just as wrong.
+        let mut moved = keys.get_share(2).unwrap();
+        moved[..IDX_LEN].copy_from_slice(&3u32.to_le_bytes());
+        assert!(try_verify_share::<SigScheme>(&keys.polynomial(), &moved).is_err());
+    }
+
+    #[test]
+    fn recovery_rejects_public_shares_it_cannot_trust() {
+        let (n, t) = (5, 3);
+        let keys = try_threshold_keygen::<SigScheme>(n, t, &[7u8; SEED_LEN]).unwrap();
+        let other = try_threshold_keygen::<SigScheme>(n, t, &[8u8; SEED_LEN]).unwrap();
+        let public_share = |keys: &Keys, index: Index| {
+            try_evaluate_polynomial::<SigScheme>(&keys.polynomial(), index).unwrap()
+        };
+
+        // A stranger's share past the first `t` is caught by the surplus check,
+        // and so is a second, different share at an index already used.
+        let mut stray = (0..t as Index)
+            .map(|i| public_share(&keys, i))
+            .collect::<Vec<_>>();
+        stray.push(public_share(&other, 4));
+        let err = try_recover_polynomial::<SigScheme>(t, stray.concat()).unwrap_err();
+        assert!(err.contains("public share 4 does not lie"), "{err}");
+
+        let mut duplicate = (0..t as Index)
+            .map(|i| public_share(&keys, i))
+            .collect::<Vec<_>>();
+        duplicate.push(public_share(&other, 1));
+        let err = try_recover_polynomial::<SigScheme>(t, duplicate.concat()).unwrap_err();
+        assert!(err.contains("public share 1 does not lie"), "{err}");
+
+        let two = [public_share(&keys, 0), public_share(&keys, 1)].concat();
+        let err = try_recover_polynomial::<SigScheme>(t, two.clone()).unwrap_err();
+        assert!(err.contains("could not recover polynomial"), "{err}");
+        assert!(try_recover_polynomial::<SigScheme>(0, two.clone()).is_err());
+
+        let err = try_recover_polynomial::<SigScheme>(2, two[1..].to_vec()).unwrap_err();
+        assert!(err.contains("expected a multiple of"), "{err}");
+    }
+
+    #[test]
+    fn batches() {
+        batches_under::<SigScheme>();
+        batches_under::<G1SigScheme>();
+    }
+
+    fn batches_under<S: BindingScheme>() {
+        let (n, t) = (5, 3);
+        let keys = try_threshold_keygen::<S>(n, t, &[7u8; SEED_LEN]).unwrap();
+        let polynomial = keys.polynomial();
+        let messages = [&b"one"[..], b"two", b"three"];
+        let lengths = messages.map(|message| message.len() as u32);
+        let concatenated = messages.concat();
+
+        let blinded = try_batch_blind::<S>(&concatenated, &lengths, &[3u8; SEED_LEN]).unwrap();
+        assert_eq!(blinded.messages().len(), 3 * S::SIGNATURE_LENGTH);
+        assert_eq!(blinded.blinding_factors().len(), 3 * PRIVKEY_LEN);
+
+        // The committee signs each blinded message, one signer spoiling its
+        // partial on the first.
+        let mut blind_signatures = Vec::new();
+        for (i, blinded_message) in blinded.messages().chunks(S::SIGNATURE_LENGTH).enumerate() {
+            let mut partials = (0..n)
+                .map(|index| {
+                    try_partial_sign_blinded_message::<S>(
+                        &keys.get_share(index).unwrap(),
+                        blinded_message,
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>();
+            if i == 0 {
+                partials[1] = try_partial_sign_blinded_message::<S>(
+                    &keys.get_share(1).unwrap(),
+                    &blinded.messages()[S::SIGNATURE_LENGTH..2 * S::SIGNATURE_LENGTH],
+                )
+                .unwrap();
+            }
+
+            let valid = try_batch_partial_verify_blind_signature::<S>(
+                &polynomial,
+                blinded_message,
+                &partials.concat(),
+            )
+            .unwrap();
+            assert_eq!(valid, [if i == 0 { 0b11101 } else { 0b11111 }]);
+
+            let good = [0, 2, 3].map(|index| partials[index].clone()).concat();
+            blind_signatures.extend(try_combine::<S>(&polynomial, good).unwrap());
+        }
+
+        let signatures =
+            try_batch_unblind::<S>(&blind_signatures, &blinded.blinding_factors()).unwrap();
+        let public_key = keys.threshold_public_key();
+        assert_eq!(
+            try_batch_verify::<S>(&public_key, &concatenated, &lengths, &signatures).unwrap(),
+            [0b111]
+        );
+        // Shifting the lengths pairs every signature with the wrong message.
+        assert_eq!(
+            try_batch_verify::<S>(&public_key, &concatenated, &[4, 3, 4], &signatures).unwrap(),
+            [0b000]
+        );
+
+        let partials = (0..t)
+            .map(|index| try_partial_sign::<S>(&keys.get_share(index).unwrap(), b"two").unwrap())
+            .collect::<Vec<_>>()
+            .concat();
+        assert_eq!(
+            try_batch_partial_verify::<S>(&polynomial, b"two", &partials).unwrap(),
+            [0b111]
+        );
+        assert_eq!(
+            try_batch_partial_verify::<S>(&polynomial, b"one", &partials).unwrap(),
+            [0b000]
+        );
+    }
+
+    #[test]
+    fn batches_reject_inputs_that_do_not_add_up() {
+        let keys = try_keygen::<SigScheme>(vec![7u8; SEED_LEN]).unwrap();
+        let seed = [3u8; SEED_LEN];
+
+        for lengths in [&[3, 2][..], &[3, 4, 5], &[u32::MAX, 7]] {
+            let Err(err) = try_batch_blind::<SigScheme>(b"onetwo", lengths, &seed) else {
+                panic!("blinded with lengths {lengths:?}");
+            };
+            assert!(err.contains("do not add up"), "{lengths:?}: {err}");
+        }
+        assert!(
+            try_batch_blind::<SigScheme>(b"", &[], &seed)
+                .unwrap()
+                .messages()
+                .is_empty()
+        );
+
+        let signature = try_sign::<SigScheme>(&keys.private_key(), b"one").unwrap();
+        let err = try_batch_verify::<SigScheme>(&keys.public_key(), b"onetwo", &[3, 3], &signature)
+            .unwrap_err();
+        assert!(err.contains("got 2 messages but 1 signatures"), "{err}");
+
+        let blinded = try_batch_blind::<SigScheme>(b"onetwo", &[3, 3], &seed).unwrap();
+        let factors = blinded.blinding_factors();
+        let err = try_batch_unblind::<SigScheme>(&blinded.messages(), &factors[1..]).unwrap_err();
+        assert!(err.contains("one per blinding factor"), "{err}");
+        let err = try_batch_unblind::<SigScheme>(&blinded.messages(), &factors[..PRIVKEY_LEN])
+            .unwrap_err();
+        assert!(
+            err.contains("2 blinded signatures but 1 blinding factors"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn mnemonic_backups() {
+        let keypair = keygen([7u8; SEED_LEN].to_vec()).unwrap();
+        let words = private_key_to_mnemonic(&keypair.private_key()).unwrap();
+        assert_eq!(words.split(' ').count(), 29);
+        assert_eq!(
+            private_key_from_mnemonic(&words).unwrap(),
+            keypair.private_key()
+        );
+
+        let keys = threshold_keygen(5, 3, &[7u8; SEED_LEN]).unwrap();
+        let share = keys.get_share(2).unwrap();
+        let share_words = share_to_mnemonic(&share).unwrap();
+        assert_eq!(share_words.split(' ').count(), 32);
+        assert_eq!(share_from_mnemonic(&share_words).unwrap(), share);
+
+        // the same key backed up under the other scheme restores only there
+        let g1_words = g1_private_key_to_mnemonic(&keypair.private_key()).unwrap();
+        assert_eq!(
+            g1_private_key_from_mnemonic(&g1_words).unwrap(),
+            keypair.private_key()
+        );
+        let g1_share_words = g1_share_to_mnemonic(&share).unwrap();
+        assert_eq!(g1_share_from_mnemonic(&g1_share_words).unwrap(), share);
+
+        let err = try_private_key_from_mnemonic::<SigScheme>(&g1_words).unwrap_err();
+        assert!(err.contains("scheme 2, not scheme 1"), "{err}");
+        let err = try_share_from_mnemonic::<G1SigScheme>(&share_words).unwrap_err();
+        assert!(err.contains("scheme 1, not scheme 2"), "{err}");
+        let err = try_private_key_from_mnemonic::<SigScheme>(&share_words).unwrap_err();
+        assert!(err.contains("Share, not a PrivateKey"), "{err}");
+        let err =
+            try_private_key_from_mnemonic::<SigScheme>(&words.replacen(' ', " x ", 1)).unwrap_err();
+        assert!(err.contains("not in the word list"), "{err}");
+        assert!(try_private_key_to_mnemonic::<SigScheme>(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn an_existing_key_can_be_split() {
+        let msg = [1, 2, 3];
+        let keypair = keygen([7u8; SEED_LEN].to_vec()).unwrap();
+        let keys = split_private_key(&keypair.private_key(), 5, 3, &[8u8; SEED_LEN]).unwrap();
+        assert_eq!(keys.threshold_public_key(), keypair.public_key());
+        let partials = [0, 2, 4]
+            .iter()
+            .flat_map(|&i| partial_sign(&keys.get_share(i).unwrap(), &msg).unwrap())
+            .collect();
+        let sig = combine(&keys.polynomial(), partials).unwrap();
+        verify(&keypair.public_key(), &msg, &sig).unwrap();
+
+        let g1_keypair = g1_keygen([7u8; SEED_LEN].to_vec()).unwrap();
+        let keys = g1_split_private_key(&g1_keypair.private_key(), 4, 2, &[8u8; SEED_LEN]).unwrap();
+        assert_eq!(keys.threshold_public_key(), g1_keypair.public_key());
+        let partials = [1, 3]
+            .iter()
+            .flat_map(|&i| g1_partial_sign(&keys.get_share(i).unwrap(), &msg).unwrap())
+            .collect();
+        let sig = g1_combine(&keys.polynomial(), partials).unwrap();
+        g1_verify(&g1_keypair.public_key(), &msg, &sig).unwrap();
+
+        let private_key = keypair.private_key();
+        let seed = [8u8; SEED_LEN];
+        assert!(try_split_private_key::<SigScheme>(&private_key, 3, 0, &seed).is_err());
+        assert!(try_split_private_key::<SigScheme>(&private_key, 3, 4, &seed).is_err());
+        assert!(
+            try_split_private_key::<SigScheme>(&private_key, MAX_SHARES + 1, 1, &seed).is_err()
+        );
+        assert!(try_split_private_key::<SigScheme>(&private_key, 3, 2, &seed[1..]).is_err());
+        assert!(try_split_private_key::<SigScheme>(&[1, 2, 3], 3, 2, &seed).is_err());
+    }
+
     fn wasm_should_blind(should_blind: bool) {
         let seed = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
         let keypair = keygen(seed.to_vec()).unwrap();
@@ -0,0 +1,386 @@
+//! A threshold randomness beacon in the style of [drand](https://drand.love).
+//!
+//! A group holding shares of a threshold key signs one message per round, and
+//! the randomness of the round is the SHA-256 hash of the signature. Nobody
+//! learns it before a threshold of the group has signed, and nobody can bias
+//! it: BLS signatures are unique, so once the round is fixed so is its output.
+//!
+//! The round message is drand's. In [`Mode::Chained`] it is
+//! `SHA-256(previous_signature || round)` and each round commits to the one
+//! before it; in [`Mode::Unchained`] it is `SHA-256(round)`, so any round can
+//! be signed and verified on its own. The round is a big-endian `u64`.
+//!
+//! ```
+//! use threshold_bls::{
+//!     beacon::{self, Mode},
+//!     poly::{Idx, Poly},
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::{Scheme, Share},
+//! };
+//!
+//! let (n, t) = (5, 3);
+//! let private_poly = Poly::<<SigScheme as Scheme>::Private>::new(t - 1);
+//! let shares = (0..n)
+//!     .map(|i| private_poly.eval(i as Idx))
+//!     .map(|eval| Share { index: eval.index, private: eval.value })
+//!     .collect::<Vec<_>>();
+//! let public_poly = private_poly.commit();
+//!
+//! // each member signs round 1 of an unchained beacon, and anyone aggregates
+//! let partials = shares
+//!     .iter()
+//!     .map(|share| beacon::partial_sign::<SigScheme>(share, 1, None).unwrap())
+//!     .collect::<Vec<_>>();
+//! let round = beacon::aggregate::<SigScheme>(&public_poly, 1, None, &partials).unwrap();
+//!
+//! beacon::verify::<SigScheme>(public_poly.public_key(), Mode::Unchained, &round).unwrap();
+//! let randomness: [u8; 32] = round.randomness();
+//! ```
+//!
+//! The curve is BLS12-377 and the signatures hash to it as the rest of this
+//! crate does, so a beacon here produces the same round messages as drand but
+//! not signatures a drand client verifies.
+use crate::poly::Poly;
+use crate::sig::{Partial, Share, SignatureScheme, ThresholdError, ThresholdScheme};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// How a beacon's rounds relate to each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Each round signs the signature of the round before it, so a verifier
+    /// walks the chain and a round cannot be produced before its predecessor
+    Chained,
+    /// Each round signs only its number, so rounds are independent and a
+    /// verifier can check any one of them without the others
+    Unchained,
+}
+
+/// One round of a beacon
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Beacon {
+    /// The round number
+    pub round: u64,
+    /// The group's signature on the round message, serialized as by
+    /// [`SignatureScheme::sign`]
+    pub signature: Vec<u8>,
+    /// The signature of the previous round, which the round message commits
+    /// to in [`Mode::Chained`]. `None` in [`Mode::Unchained`].
+    pub previous_signature: Option<Vec<u8>>,
+}
+
+impl Beacon {
+    /// The message the group signed for this round
+    pub fn message(&self) -> [u8; 32] {
+        round_message(self.round, self.previous_signature.as_deref())
+    }
+
+    /// The randomness of this round. Only meaningful once the beacon has been
+    /// checked with [`verify`] or [`verify_chain`].
+    pub fn randomness(&self) -> [u8; 32] {
+        randomness(&self.signature)
+    }
+}
+
+/// Errors associated with producing and verifying beacon rounds
+#[derive(Debug, Error)]
+pub enum BeaconError<S: SignatureScheme> {
+    /// Raised when a chained round has no previous signature to sign
+    #[error("round {0} has no previous signature, which a chained beacon signs")]
+    MissingPreviousSignature(u64),
+
+    /// Raised when an unchained round carries a previous signature. Verifying
+    /// it would verify a chained round, which is not what the caller asked for.
+    #[error("round {0} has a previous signature, which an unchained beacon does not sign")]
+    UnexpectedPreviousSignature(u64),
+
+    /// Raised when a round in a chain is not the one after its predecessor
+    #[error("round {0} does not follow round {1}")]
+    RoundGap(u64, u64),
+
+    /// Raised when a chained round signs something other than its
+    /// predecessor's signature
+    #[error("round {0} does not chain from the signature of round {1}")]
+    BrokenChain(u64, u64),
+
+    /// Raised when the group's signature on a round does not verify
+    #[error("invalid signature for round {0}: {1}")]
+    SignatureError(u64, S::Error),
+
+    /// Raised when partial signing or aggregation fails
+    #[error(transparent)]
+    ThresholdError(ThresholdError<S>),
+}
+
+/// The message signed in `round`: `SHA-256(previous_signature || round)` if
+/// there is a previous signature, as in [`Mode::Chained`], and `SHA-256(round)`
+/// if there is not, as in [`Mode::Unchained`].
+pub fn round_message(round: u64, previous_signature: Option<&[u8]>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    if let Some(previous) = previous_signature {
+        hasher.update(previous);
+    }
+    hasher.update(round.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// The randomness a round's signature yields: its SHA-256 hash.
+pub fn randomness(signature: &[u8]) -> [u8; 32] {
+    Sha256::digest(signature).into()
+}
+
+/// Signs `round` with a share of the group key. `previous_signature` is the
+/// signature of the round before in a chained beacon, and `None` in an
+/// unchained one.
+pub fn partial_sign<S: SignatureScheme>(
+    share: &Share<S::Private>,
+    round: u64,
+    previous_signature: Option<&[u8]>,
+) -> Result<Partial, BeaconError<S>> {
+    S::partial_sign(share, &round_message(round, previous_signature))
+        .map_err(BeaconError::ThresholdError)
+}
+
+/// Aggregates partial signatures on `round` into the round's beacon, and
+/// verifies it against the group key.
+///
+/// The partials are not checked one by one unless the aggregate fails to
+/// verify; then the invalid ones are dropped and the rest aggregated again, so
+/// that a member who sends garbage, or a partial that does not even decode,
+/// delays the round rather than halting it.
+pub fn aggregate<S: SignatureScheme>(
+    public: &Poly<S::Public>,
+    round: u64,
+    previous_signature: Option<&[u8]>,
+    partials: &[Partial],
+) -> Result<Beacon, BeaconError<S>> {
+    let message = round_message(round, previous_signature);
+
+    let optimistic = S::aggregate(public, partials)
+        .ok()
+        .filter(|signature| S::verify(public.public_key(), &message, signature).is_ok());
+    let signature = match optimistic {
+        Some(signature) => signature,
+        None => {
+            let valid = partials
+                .iter()
+                .filter(|partial| S::partial_verify(public, &message, partial).is_ok())
+                .cloned()
+                .collect::<Vec<_>>();
+            let signature = S::aggregate(public, &valid).map_err(BeaconError::ThresholdError)?;
+            S::verify(public.public_key(), &message, &signature)
+                .map_err(|e| BeaconError::SignatureError(round, e))?;
+            signature
+        }
+    };
+
+    Ok(Beacon {
+        round,
+        signature,
+        previous_signature: previous_signature.map(<[u8]>::to_vec),
+    })
+}
+
+/// Verifies one round against the group public key.
+///
+/// In [`Mode::Chained`] this checks only that the group signed the round
+/// together with the previous signature the beacon carries, not that it is the
+/// signature of the round before: that takes the previous round, which
+/// [`verify_chain`] checks.
+pub fn verify<S: SignatureScheme>(
+    public: &S::Public,
+    mode: Mode,
+    beacon: &Beacon,
+) -> Result<(), BeaconError<S>> {
+    match (mode, &beacon.previous_signature) {
+        (Mode::Chained, None) => return Err(BeaconError::MissingPreviousSignature(beacon.round)),
+        (Mode::Unchained, Some(_)) => {
+            return Err(BeaconError::UnexpectedPreviousSignature(beacon.round));
+        }
+        _ => {}
+    }
+
+    S::verify(public, &beacon.message(), &beacon.signature)
+        .map_err(|e| BeaconError::SignatureError(beacon.round, e))
+}
+
+/// Verifies consecutive rounds against the group public key.
+///
+/// Every round is verified as by [`verify`], and each must be the round after
+/// the one before it. In [`Mode::Chained`] each must also sign its
+/// predecessor's signature. The first round is taken as given: its previous
+/// signature is checked against nothing, so the caller anchors the chain, to
+/// the group's genesis seed or to a round it verified earlier.
+pub fn verify_chain<S: SignatureScheme>(
+    public: &S::Public,
+    mode: Mode,
+    beacons: &[Beacon],
+) -> Result<(), BeaconError<S>> {
+    for pair in beacons.windows(2) {
+        let (previous, beacon) = (&pair[0], &pair[1]);
+        if previous.round.checked_add(1) != Some(beacon.round) {
+            return Err(BeaconError::RoundGap(beacon.round, previous.round));
+        }
+        if mode == Mode::Chained
+            && beacon.previous_signature.as_deref() != Some(&previous.signature[..])
+        {
+            return Err(BeaconError::BrokenChain(beacon.round, previous.round));
+        }
+    }
+
+    beacons
+        .iter()
+        .try_for_each(|beacon| verify::<S>(public, mode, beacon))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemes::bls12_377::{G1Scheme, G2Scheme};
+    use crate::sig::shares;
+
+    fn run<S: SignatureScheme>(
+        shares: &[Share<S::Private>],
+        public: &Poly<S::Public>,
+        round: u64,
+        previous_signature: Option<&[u8]>,
+    ) -> Beacon {
+        let partials = shares
+            .iter()
+            .map(|share| partial_sign::<S>(share, round, previous_signature).unwrap())
+            .collect::<Vec<_>>();
+        aggregate::<S>(public, round, previous_signature, &partials).unwrap()
+    }
+
+    fn chain<S: SignatureScheme>(
+        shares: &[Share<S::Private>],
+        public: &Poly<S::Public>,
+        rounds: u64,
+    ) -> Vec<Beacon> {
+        let genesis = [0x42; 32];
+        let mut beacons: Vec<Beacon> = Vec::new();
+        for round in 1..=rounds {
+            let previous = beacons.last().map_or(&genesis[..], |b| &b.signature[..]);
+            let beacon = run::<S>(shares, public, round, Some(previous));
+            beacons.push(beacon);
+        }
+        beacons
+    }
+
+    #[test]
+    fn round_messages_are_drands() {
+        // drand's unchained message for round 1
+        assert_eq!(
+            hex::encode(round_message(1, None)),
+            "cd2662154e6d76b2b2b92e70c0cac3ccf534f9b74eb5b89819ec509083d00a50"
+        );
+        assert_eq!(
+            hex::encode(round_message(2, Some(&[0xab; 48]))),
+            "564804c151647a69e4b08ea267fd3e9aeeac7659b0b29efa452b24ed497b495e"
+        );
+    }
+
+    fn chained_rounds_verify<S: SignatureScheme>() {
+        let (shares, public) = shares::<S>(5, 3);
+        let beacons = chain::<S>(&shares[..3], &public, 4);
+
+        verify_chain::<S>(public.public_key(), Mode::Chained, &beacons).unwrap();
+        // every subset of a threshold signs the same round
+        let again = chain::<S>(&shares[2..], &public, 4);
+        assert_eq!(beacons, again);
+        assert_eq!(beacons[3].randomness(), randomness(&beacons[3].signature));
+        assert_ne!(beacons[2].randomness(), beacons[3].randomness());
+    }
+
+    #[test]
+    fn chained_rounds_verify_g1() {
+        chained_rounds_verify::<G1Scheme>();
+    }
+
+    #[test]
+    fn chained_rounds_verify_g2() {
+        chained_rounds_verify::<G2Scheme>();
+    }
+
+    #[test]
+    fn unchained_rounds_verify_alone() {
+        let (shares, public) = shares::<G2Scheme>(5, 3);
+        let beacons = [7, 8, 9].map(|round| run::<G2Scheme>(&shares, &public, round, None));
+
+        for beacon in &beacons {
+            verify::<G2Scheme>(public.public_key(), Mode::Unchained, beacon).unwrap();
+        }
+        verify_chain::<G2Scheme>(public.public_key(), Mode::Unchained, &beacons).unwrap();
+    }
+
+    #[test]
+    fn a_broken_chain_is_rejected() {
+        let (shares, public) = shares::<G2Scheme>(4, 3);
+        let public_key = public.public_key();
+        let beacons = chain::<G2Scheme>(&shares, &public, 3);
+
+        // a gap
+        let gap = [beacons[0].clone(), beacons[2].clone()];
+        assert!(matches!(
+            verify_chain::<G2Scheme>(public_key, Mode::Chained, &gap),
+            Err(BeaconError::RoundGap(3, 1))
+        ));
+
+        // a valid round that signs some other predecessor
+        let fork = run::<G2Scheme>(&shares, &public, 3, Some(&[0x13; 48]));
+        verify::<G2Scheme>(public_key, Mode::Chained, &fork).unwrap();
+        let forked = [beacons[1].clone(), fork];
+        assert!(matches!(
+            verify_chain::<G2Scheme>(public_key, Mode::Chained, &forked),
+            Err(BeaconError::BrokenChain(3, 2))
+        ));
+
+        // a signature moved to another round
+        let mut moved = beacons[2].clone();
+        moved.signature = beacons[1].signature.clone();
+        assert!(matches!(
+            verify::<G2Scheme>(public_key, Mode::Chained, &moved),
+            Err(BeaconError::SignatureError(3, _))
+        ));
+    }
+
+    #[test]
+    fn the_mode_is_the_verifiers_choice() {
+        let (shares, public) = shares::<G2Scheme>(3, 2);
+        let public_key = public.public_key();
+        let chained = chain::<G2Scheme>(&shares, &public, 1).remove(0);
+        let unchained = run::<G2Scheme>(&shares, &public, 1, None);
+
+        assert!(matches!(
+            verify::<G2Scheme>(public_key, Mode::Unchained, &chained),
+            Err(BeaconError::UnexpectedPreviousSignature(1))
+        ));
+        assert!(matches!(
+            verify::<G2Scheme>(public_key, Mode::Chained, &unchained),
+            Err(BeaconError::MissingPreviousSignature(1))
+        ));
+    }
+
+    #[test]
+    fn aggregation_drops_invalid_partials() {
+        let (shares, public) = shares::<G2Scheme>(5, 3);
+        let mut partials = shares
+            .iter()
+            .map(|share| partial_sign::<G2Scheme>(share, 5, None).unwrap())
+            .collect::<Vec<_>>();
+        // the first member signs the wrong round
+        partials[0] = partial_sign::<G2Scheme>(&shares[0], 6, None).unwrap();
+        // and someone else sends bytes that are not a partial at all
+        partials.push(vec![0xff; 3]);
+
+        let beacon = aggregate::<G2Scheme>(&public, 5, None, &partials).unwrap();
+        verify::<G2Scheme>(public.public_key(), Mode::Unchained, &beacon).unwrap();
+
+        // with only two honest members left there is no threshold
+        assert!(matches!(
+            aggregate::<G2Scheme>(&public, 5, None, &partials[..3]),
+            Err(BeaconError::ThresholdError(_))
+        ));
+    }
+}
@@ -0,0 +1,619 @@
+//! Fanning a blinded message out to remote signers and combining their
+//! partial signatures.
+//!
+//! A [`Coordinator`] holds the group's public polynomial and a
+//! [`RemoteSigner`] per member. [`Coordinator::sign_blind`] asks every signer
+//! for its partial signature at once, gives each request a timeout and a
+//! number of retries under a [`Policy`], verifies each partial against the
+//! signer's public share as it arrives, and returns the blind signature as
+//! soon as a quorum of valid partials is in, abandoning the requests still
+//! outstanding. Signers that failed are reported one by one, whether or not
+//! the quorum was reached.
+//!
+//! The transport is the caller's: a [`RemoteSigner`] is whatever sends a
+//! blinded message to one member and brings back its partial. [`LocalSigner`]
+//! signs in process with a [`Share`], so a service can be tested end to end
+//! without a network.
+//!
+//! The coordinator runs its requests as tasks on the caller's Tokio runtime,
+//! so it must be awaited within one. It is behind the `coordinator` feature.
+//!
+//! ```
+//! use std::time::Duration;
+//! use threshold_bls::{
+//!     coordinator::{Coordinator, LocalSigner, Policy},
+//!     poly::{Idx, Poly},
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::{BlindScheme, Scheme, Share, SignatureScheme},
+//! };
+//!
+//! # tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap().block_on(async {
+//! let private = Poly::<<SigScheme as Scheme>::Private>::new(2);
+//! let public = private.commit::<<SigScheme as Scheme>::Public>();
+//! let signers = (0..5)
+//!     .map(|i| Share { index: i as Idx, private: private.eval(i as Idx).value })
+//!     .map(LocalSigner::<SigScheme>::new)
+//!     .collect();
+//!
+//! let policy = Policy { timeout: Duration::from_secs(2), ..Policy::default() };
+//! let coordinator = Coordinator::<SigScheme, _>::new(public.clone(), signers, policy).unwrap();
+//!
+//! let (token, blinded) = SigScheme::blind_msg(b"hello", &mut rand::thread_rng()).unwrap();
+//! let signed = coordinator.sign_blind(&blinded).await.unwrap();
+//!
+//! let signature = SigScheme::unblind_sig(&token, &signed.signature).unwrap();
+//! SigScheme::verify(public.public_key(), b"hello", &signature).unwrap();
+//! # });
+//! ```
+use crate::poly::{Eval, Idx, Poly};
+use crate::serialization;
+use crate::sig::{
+    Aggregator, AggregatorError, BlindThresholdScheme, Partial, Scheme, Share, SignatureScheme,
+    Verification,
+};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::panic;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::task::JoinSet;
+
+/// One member of the group, as the coordinator reaches it.
+///
+/// Implementations carry the transport: an HTTP or RPC client for a remote
+/// signer, or [`LocalSigner`] in process. The coordinator applies the timeout
+/// and retries itself, so an implementation makes a single attempt.
+pub trait RemoteSigner: Send + Sync + 'static {
+    /// Error raised when the request does not produce a partial
+    type Error: Error + Send + Sync + 'static;
+
+    /// The index of the member's share, which its partials must carry
+    fn index(&self) -> Idx;
+
+    /// Asks the member for its partial signature on a blinded message, as
+    /// [`BlindThresholdScheme::sign_blind_partial`] makes it.
+    fn sign_blind_partial(
+        &self,
+        blinded_msg: &[u8],
+    ) -> impl Future<Output = Result<Partial, Self::Error>> + Send;
+}
+
+/// How the coordinator treats its signers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    /// Valid partials to collect before combining. `None` is the threshold;
+    /// more lets a service insist on hearing from more members than the
+    /// signature needs.
+    pub quorum: Option<usize>,
+    /// How long one attempt at one signer may take
+    pub timeout: Duration,
+    /// Attempts after the first, for a signer that timed out or failed. A
+    /// signer that answers with a bad partial is not asked again.
+    pub retries: u32,
+    /// How long to wait before asking a signer again
+    pub backoff: Duration,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            quorum: None,
+            timeout: Duration::from_secs(5),
+            retries: 2,
+            backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Why a signer contributed no partial.
+#[derive(Debug, Error)]
+pub enum SignerError<E: Error> {
+    /// Raised when the last attempt did not finish within the policy's timeout
+    #[error("timed out")]
+    Timeout,
+
+    /// Raised when the last attempt failed in the signer or the transport
+    #[error("request failed: {0}")]
+    Transport(E),
+
+    /// Raised when the partial does not verify against the signer's public
+    /// share, or does not decode
+    #[error("invalid partial signature")]
+    InvalidPartial,
+
+    /// Raised when the partial carries an index other than the signer's
+    #[error("partial signature for index {0}")]
+    WrongIndex(Idx),
+}
+
+/// A signer that contributed no partial, and why.
+#[derive(Debug, Error)]
+#[error("signer {index} failed after {attempts} attempts: {error}")]
+pub struct SignerFailure<E: Error> {
+    /// The index of the signer's share
+    pub index: Idx,
+    /// How many times it was asked
+    pub attempts: u32,
+    /// What went wrong the last time
+    pub error: SignerError<E>,
+}
+
+/// Errors associated with coordinating signers
+#[derive(Debug, Error)]
+pub enum CoordinatorError<S: SignatureScheme, E: Error> {
+    /// Raised when the quorum is below the threshold, or above the number of
+    /// signers, who could then never reach it
+    #[error(
+        "a quorum of {quorum} is not between the threshold {threshold} and the {signers} signers"
+    )]
+    InvalidQuorum {
+        /// The quorum the policy asked for
+        quorum: usize,
+        /// The polynomial's threshold
+        threshold: usize,
+        /// The number of signers
+        signers: usize,
+    },
+
+    /// Raised when two signers claim the same share index
+    #[error("two signers for index {0}")]
+    DuplicateSigner(Idx),
+
+    /// Raised when too many signers failed for the quorum to be reached
+    #[error("{collected} of a quorum of {quorum} partial signatures collected")]
+    QuorumNotReached {
+        /// Valid partials collected
+        collected: usize,
+        /// Valid partials needed
+        quorum: usize,
+        /// The signers that failed
+        failures: Vec<SignerFailure<E>>,
+    },
+
+    /// Raised when the partials cannot be combined
+    #[error(transparent)]
+    AggregatorError(#[from] AggregatorError<S>),
+}
+
+/// A blind signature, and who contributed to it.
+#[derive(Debug)]
+pub struct Signed<E: Error> {
+    /// The group's blind signature on the blinded message
+    pub signature: Vec<u8>,
+    /// The indices of the quorum: the signers whose partials were collected
+    /// and verified. With a quorum above the threshold, the signature is
+    /// interpolated from a threshold of them, so not every one contributed
+    /// to it.
+    pub signers: Vec<Idx>,
+    /// The signers that failed before the quorum was reached. Requests still
+    /// outstanding then are abandoned, and not reported.
+    pub failures: Vec<SignerFailure<E>>,
+}
+
+/// Collects blind partial signatures from a group's signers. See the
+/// [module documentation](self).
+pub struct Coordinator<S: Scheme, R: RemoteSigner> {
+    public: Arc<Poly<S::Public>>,
+    signers: Vec<Arc<R>>,
+    policy: Policy,
+    quorum: usize,
+}
+
+impl<S, R> Coordinator<S, R>
+where
+    S: SignatureScheme + BlindThresholdScheme + 'static,
+    S::Public: Send + Sync,
+    R: RemoteSigner,
+{
+    /// A coordinator for the signers of `public`, one per share index.
+    pub fn new(
+        public: Poly<S::Public>,
+        signers: Vec<R>,
+        policy: Policy,
+    ) -> Result<Self, CoordinatorError<S, R::Error>> {
+        let threshold = public.degree() + 1;
+        let quorum = policy.quorum.unwrap_or(threshold);
+        if quorum < threshold || quorum > signers.len() {
+            return Err(CoordinatorError::InvalidQuorum {
+                quorum,
+                threshold,
+                signers: signers.len(),
+            });
+        }
+
+        let mut indices = BTreeSet::new();
+        for signer in &signers {
+            if !indices.insert(signer.index()) {
+                return Err(CoordinatorError::DuplicateSigner(signer.index()));
+            }
+        }
+
+        Ok(Self {
+            public: Arc::new(public),
+            signers: signers.into_iter().map(Arc::new).collect(),
+            policy,
+            quorum,
+        })
+    }
+
+    /// Asks every signer for its partial signature on `blinded_msg`, and
+    /// combines the first quorum of valid ones into the group's blind
+    /// signature.
+    pub async fn sign_blind(
+        &self,
+        blinded_msg: &[u8],
+    ) -> Result<Signed<R::Error>, CoordinatorError<S, R::Error>> {
+        let msg: Arc<[u8]> = Arc::from(blinded_msg);
+
+        // Dropping the set aborts the tasks in it, so returning early, or the
+        // caller dropping this future, abandons the outstanding requests.
+        let mut requests = JoinSet::new();
+        for signer in &self.signers {
+            requests.spawn(request::<S, R>(
+                signer.clone(),
+                self.public.clone(),
+                msg.clone(),
+                self.policy.clone(),
+            ));
+        }
+
+        // The partials are verified as they arrive, so the aggregate is
+        // checked once, as a safeguard, rather than each partial again.
+        let mut aggregator =
+            Aggregator::<S>::blind((*self.public).clone(), blinded_msg, Verification::Lazy);
+        let mut failures = Vec::new();
+        while let Some(joined) = requests.join_next().await {
+            match joined {
+                Ok(Ok(partial)) => {
+                    if aggregator.add(&partial)?.collected < self.quorum {
+                        continue;
+                    }
+                    if let Some(signature) = aggregator.signature()? {
+                        return Ok(Signed {
+                            signature,
+                            signers: aggregator.signers().collect(),
+                            failures,
+                        });
+                    }
+                }
+                Ok(Err(failure)) => failures.push(failure),
+                // Nothing aborts a request while the set is being drained, so
+                // the task panicked, and the panic is the caller's to see.
+                Err(err) => panic::resume_unwind(err.into_panic()),
+            }
+        }
+
+        Err(CoordinatorError::QuorumNotReached {
+            collected: aggregator.progress().collected,
+            quorum: self.quorum,
+            failures,
+        })
+    }
+}
+
+/// Asks one signer for its partial, with the policy's timeout and retries,
+/// and checks what it returns.
+async fn request<S, R>(
+    signer: Arc<R>,
+    public: Arc<Poly<S::Public>>,
+    msg: Arc<[u8]>,
+    policy: Policy,
+) -> Result<Partial, SignerFailure<R::Error>>
+where
+    S: BlindThresholdScheme,
+    R: RemoteSigner,
+{
+    let index = signer.index();
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let error =
+            match tokio::time::timeout(policy.timeout, signer.sign_blind_partial(&msg)).await {
+                Ok(Ok(partial)) => {
+                    return check::<S, R::Error>(&public, &msg, index, partial).map_err(|error| {
+                        SignerFailure {
+                            index,
+                            attempts,
+                            error,
+                        }
+                    });
+                }
+                Ok(Err(err)) => SignerError::Transport(err),
+                Err(_) => SignerError::Timeout,
+            };
+
+        if attempts > policy.retries {
+            return Err(SignerFailure {
+                index,
+                attempts,
+                error,
+            });
+        }
+        tokio::time::sleep(policy.backoff).await;
+    }
+}
+
+/// Checks that a partial is the signer's, and valid on the blinded message.
+fn check<S: BlindThresholdScheme, E: Error>(
+    public: &Poly<S::Public>,
+    msg: &[u8],
+    index: Idx,
+    partial: Partial,
+) -> Result<Partial, SignerError<E>> {
+    let claimed = serialization::deserialize::<Eval<Vec<u8>>>(&partial)
+        .map_err(|_| SignerError::InvalidPartial)?
+        .index;
+    if claimed != index {
+        return Err(SignerError::WrongIndex(claimed));
+    }
+
+    S::verify_blind_partial(public, msg, &partial).map_err(|_| SignerError::InvalidPartial)?;
+    Ok(partial)
+}
+
+/// A signer in the same process, signing with its share directly.
+pub struct LocalSigner<S: Scheme> {
+    share: Share<S::Private>,
+    m: PhantomData<fn() -> S>,
+}
+
+impl<S: Scheme> LocalSigner<S> {
+    /// A signer holding `share`
+    pub fn new(share: Share<S::Private>) -> Self {
+        Self {
+            share,
+            m: PhantomData,
+        }
+    }
+}
+
+impl<S> RemoteSigner for LocalSigner<S>
+where
+    S: BlindThresholdScheme + 'static,
+    S::Private: Send + Sync,
+    <S as BlindThresholdScheme>::Error: Send + Sync + 'static,
+{
+    type Error = <S as BlindThresholdScheme>::Error;
+
+    fn index(&self) -> Idx {
+        self.share.index
+    }
+
+    fn sign_blind_partial(
+        &self,
+        blinded_msg: &[u8],
+    ) -> impl Future<Output = Result<Partial, Self::Error>> + Send {
+        std::future::ready(S::sign_blind_partial(&self.share, blinded_msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{BlindScheme, G2Scheme, shares},
+    };
+    use rand::thread_rng;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    type S = G2Scheme<PCurve>;
+
+    #[derive(Debug, Error)]
+    #[error("unreachable")]
+    struct Unreachable;
+
+    /// A local signer that misbehaves in one way.
+    enum Behaviour {
+        Honest,
+        /// Never answers
+        Silent,
+        /// Fails this many times, then answers
+        Flaky(u32),
+        /// Answers with a partial signed under another key
+        Forger,
+        /// Answers with another member's partial
+        Impersonator(Share<<S as Scheme>::Private>),
+    }
+
+    struct TestSigner {
+        share: Share<<S as Scheme>::Private>,
+        behaviour: Behaviour,
+        calls: AtomicU32,
+    }
+
+    impl RemoteSigner for TestSigner {
+        type Error = Unreachable;
+
+        fn index(&self) -> Idx {
+            self.share.index
+        }
+
+        async fn sign_blind_partial(&self, blinded_msg: &[u8]) -> Result<Partial, Unreachable> {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst);
+            match &self.behaviour {
+                Behaviour::Honest => {}
+                Behaviour::Silent => std::future::pending().await,
+                Behaviour::Flaky(failures) if calls < *failures => return Err(Unreachable),
+                Behaviour::Flaky(_) => {}
+                Behaviour::Forger => {
+                    let forged = Share {
+                        index: self.share.index,
+                        private: S::keypair(&mut thread_rng()).0,
+                    };
+                    return Ok(S::sign_blind_partial(&forged, blinded_msg).unwrap());
+                }
+                Behaviour::Impersonator(other) => {
+                    return Ok(S::sign_blind_partial(other, blinded_msg).unwrap());
+                }
+            }
+            Ok(S::sign_blind_partial(&self.share, blinded_msg).unwrap())
+        }
+    }
+
+    fn signers(
+        shares: &[Share<<S as Scheme>::Private>],
+        behaviours: Vec<Behaviour>,
+    ) -> Vec<TestSigner> {
+        shares
+            .iter()
+            .zip(behaviours)
+            .map(|(share, behaviour)| TestSigner {
+                share: share.clone(),
+                behaviour,
+                calls: AtomicU32::new(0),
+            })
+            .collect()
+    }
+
+    fn assert_signs(
+        public: &Poly<<S as Scheme>::Public>,
+        token: &crate::sig::Token<<S as Scheme>::Private>,
+        signature: &[u8],
+    ) {
+        let signature = S::unblind_sig(token, signature).unwrap();
+        S::verify(public.public_key(), b"coordinated", &signature).unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn local_signers_sign() {
+        let (shares, public) = shares::<S>(5, 3);
+        let signers = shares.into_iter().map(LocalSigner::<S>::new).collect();
+        let coordinator =
+            Coordinator::<S, _>::new(public.clone(), signers, Policy::default()).unwrap();
+
+        let (token, blinded) = S::blind_msg(b"coordinated", &mut thread_rng()).unwrap();
+        let signed = coordinator.sign_blind(&blinded).await.unwrap();
+        assert_eq!(signed.signers.len(), 3);
+        assert!(signed.failures.is_empty());
+        assert_signs(&public, &token, &signed.signature);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn misbehaving_signers_are_reported_and_routed_around() {
+        let (shares, public) = shares::<S>(7, 3);
+        let behaviours = vec![
+            Behaviour::Silent,
+            Behaviour::Forger,
+            Behaviour::Impersonator(shares[6].clone()),
+            Behaviour::Flaky(1),
+            Behaviour::Honest,
+            Behaviour::Silent,
+            Behaviour::Honest,
+        ];
+        let policy = Policy {
+            quorum: None,
+            timeout: Duration::from_secs(1),
+            retries: 1,
+            backoff: Duration::from_secs(10),
+        };
+        let coordinator =
+            Coordinator::<S, _>::new(public.clone(), signers(&shares, behaviours), policy).unwrap();
+
+        let (token, blinded) = S::blind_msg(b"coordinated", &mut thread_rng()).unwrap();
+        let signed = coordinator.sign_blind(&blinded).await.unwrap();
+        assert_signs(&public, &token, &signed.signature);
+
+        // the flaky signer came through on its retry; the silent ones were
+        // still being retried then, and were abandoned unreported
+        let mut signers = signed.signers.clone();
+        signers.sort();
+        assert_eq!(signers, vec![3, 4, 6]);
+        let mut failures = signed
+            .failures
+            .iter()
+            .map(|failure| (failure.index, failure.attempts, failure.error.to_string()))
+            .collect::<Vec<_>>();
+        failures.sort();
+        assert_eq!(
+            failures,
+            vec![
+                (1, 1, "invalid partial signature".to_string()),
+                (2, 1, "partial signature for index 6".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn every_failure_is_reported_when_the_quorum_is_not_reached() {
+        let (shares, public) = shares::<S>(4, 3);
+        let behaviours = vec![
+            Behaviour::Silent,
+            Behaviour::Flaky(u32::MAX),
+            Behaviour::Honest,
+            Behaviour::Honest,
+        ];
+        let policy = Policy {
+            retries: 3,
+            ..Policy::default()
+        };
+        let coordinator =
+            Coordinator::<S, _>::new(public, signers(&shares, behaviours), policy).unwrap();
+
+        let (_, blinded) = S::blind_msg(b"coordinated", &mut thread_rng()).unwrap();
+        match coordinator.sign_blind(&blinded).await {
+            Err(CoordinatorError::QuorumNotReached {
+                collected,
+                quorum,
+                mut failures,
+            }) => {
+                assert_eq!((collected, quorum), (2, 3));
+                failures.sort_by_key(|failure| failure.index);
+                assert_eq!(failures.len(), 2);
+                assert_eq!((failures[0].index, failures[0].attempts), (0, 4));
+                assert!(matches!(failures[0].error, SignerError::Timeout));
+                assert_eq!((failures[1].index, failures[1].attempts), (1, 4));
+                assert!(matches!(failures[1].error, SignerError::Transport(_)));
+            }
+            other => panic!(
+                "unexpected result: {:?}",
+                other.map(|signed| signed.signers)
+            ),
+        }
+    }
+
+    #[test]
+    fn quorum_and_signers_are_checked() {
+        let (shares, public) = shares::<S>(4, 3);
+        let local = |shares: &[Share<<S as Scheme>::Private>]| {
+            shares
+                .iter()
+                .cloned()
+                .map(LocalSigner::<S>::new)
+                .collect::<Vec<_>>()
+        };
+
+        for quorum in [2, 5] {
+            let policy = Policy {
+                quorum: Some(quorum),
+                ..Policy::default()
+            };
+            assert!(matches!(
+                Coordinator::<S, _>::new(public.clone(), local(&shares), policy),
+                Err(CoordinatorError::InvalidQuorum {
+                    threshold: 3,
+                    signers: 4,
+                    ..
+                })
+            ));
+        }
+        assert!(matches!(
+            Coordinator::<S, _>::new(public.clone(), local(&shares[..2]), Policy::default()),
+            Err(CoordinatorError::InvalidQuorum {
+                quorum: 3,
+                signers: 2,
+                ..
+            })
+        ));
+
+        let mut duplicated = shares.clone();
+        duplicated[3] = shares[1].clone();
+        assert!(matches!(
+            Coordinator::<S, _>::new(public, local(&duplicated), Policy::default()),
+            Err(CoordinatorError::DuplicateSigner(1))
+        ));
+    }
+}
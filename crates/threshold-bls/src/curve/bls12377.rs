@@ -6,8 +6,9 @@ use ark_ec::{
     pairing::Pairing,
     short_weierstrass::{Affine, Projective, SWCurveConfig},
 };
-use ark_ff::{Field, One, UniformRand, Zero};
+use ark_ff::{Field, One, PrimeField, UniformRand, Zero};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress};
+use num_bigint::BigUint;
 use rand_core::RngCore;
 use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
@@ -21,6 +22,7 @@ use std::{
 };
 
 use thiserror::Error;
+use zeroize::Zeroize;
 
 /// Domain separator for signing messages
 const SIG_DOMAIN: &[u8] = b"ULforxof";
@@ -135,6 +137,25 @@ pub struct GT(
     <bls377::Bls12_377 as Pairing>::TargetField,
 );
 
+/// Conversions to and from plain integers, for the key derivation in
+/// [`crate::hd`], which is specified in terms of them.
+impl Scalar {
+    /// The group order.
+    pub(crate) fn modulus() -> BigUint {
+        bls377::Fr::MODULUS.into()
+    }
+
+    /// The integer below the group order that this scalar is.
+    pub(crate) fn to_biguint(self) -> BigUint {
+        self.0.into_bigint().into()
+    }
+
+    /// Reduces `n` modulo the group order.
+    pub(crate) fn from_biguint(n: &BigUint) -> Self {
+        Self(bls377::Fr::from(n.clone()))
+    }
+}
+
 impl Element for Scalar {
     type RHS = Scalar;
 
@@ -186,6 +207,16 @@ impl Sc for Scalar {
     }
 }
 
+/// Clears the scalar in place, so that a private key held in
+/// `zeroize::Zeroizing` does not outlive its owner in memory. `Scalar` is
+/// `Copy`, so only the copy being cleared is: the others are their holders' to
+/// clear.
+impl Zeroize for Scalar {
+    fn zeroize(&mut self) {
+        self.0.zeroize()
+    }
+}
+
 impl fmt::Display for Scalar {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{{{:?}}}", self.0)
@@ -0,0 +1,282 @@
+//! Non-interactive proofs that two points share a discrete logarithm.
+//!
+//! A [`DleqProof`] shows that `a = x·g` and `b = x·h` for one scalar `x`
+//! without revealing `x`. Its usual use here is a share holder proving that a
+//! value it computed with its share, `b = x·h`, used the same share as its
+//! public share `a = x·g` committed in the public polynomial: the proof
+//! convinces anyone holding the polynomial, where checking `b` directly would
+//! need the share.
+//!
+//! The two pairs may be in different groups of the same order, `g` in G1 and
+//! `h` in G2 say, which is what lets a signer prove its partial signature
+//! consistent with its public share without the verifier computing a pairing.
+//!
+//! The proof is Chaum–Pedersen's, made non-interactive with Fiat–Shamir. Any
+//! bytes the proof should be bound to go into the challenge as `context`, and
+//! a proof verifies only under the context it was made with.
+//!
+//! ```
+//! use threshold_bls::{
+//!     curve::bls12377::{G1, Scalar},
+//!     dleq::DleqProof,
+//!     group::Element,
+//! };
+//!
+//! let rng = &mut rand::thread_rng();
+//! let x = Scalar::rand(rng);
+//! let (g, h) = (G1::one(), G1::rand(rng));
+//! let (mut a, mut b) = (g.clone(), h.clone());
+//! a.mul(&x);
+//! b.mul(&x);
+//!
+//! let proof = DleqProof::prove(&x, &g, &h, b"context", rng).unwrap();
+//! proof.verify(&g, &a, &h, &b, b"context").unwrap();
+//! ```
+use crate::group::{Point, Scalar};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Blake2s personalization for the challenge, so that it cannot collide with a
+/// hash computed for anything else.
+const DLEQ_DOMAIN: &[u8] = b"BLS-DLEQ";
+
+/// Errors associated with DLEQ proofs
+#[derive(Debug, Error)]
+pub enum DleqError {
+    /// Raised when a proof does not verify
+    #[error("invalid DLEQ proof")]
+    InvalidProof,
+
+    /// Raised when the points cannot be serialized into the challenge
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+
+    /// Raised when no candidate hash was a valid scalar. Each candidate is one
+    /// with probability above one half, so this does not happen in practice.
+    #[error("could not hash the transcript to a scalar")]
+    HashToScalarError,
+}
+
+/// A proof that `log_g(a) == log_h(b)`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DleqProof<S> {
+    /// The Fiat–Shamir challenge
+    pub challenge: S,
+    /// The response, `k - challenge·x` for the prover's nonce `k`
+    pub response: S,
+}
+
+impl<S: Scalar<RHS = S>> DleqProof<S> {
+    /// Proves that `x·g` and `x·h` share the logarithm `x`. The nonce is drawn
+    /// from `rng`, which must be a CSPRNG: two proofs with one nonce reveal `x`.
+    pub fn prove<P: Point<RHS = S>, Q: Point<RHS = S>, R: RngCore>(
+        x: &S,
+        g: &P,
+        h: &Q,
+        context: &[u8],
+        rng: &mut R,
+    ) -> Result<Self, DleqError> {
+        let (mut a, mut b) = (g.clone(), h.clone());
+        a.mul(x);
+        b.mul(x);
+
+        let k = S::rand(rng);
+        let (mut commit_g, mut commit_h) = (g.clone(), h.clone());
+        commit_g.mul(&k);
+        commit_h.mul(&k);
+
+        let challenge = challenge(g, &a, h, &b, &commit_g, &commit_h, context)?;
+        let mut response = challenge.clone();
+        response.mul(x);
+        response.negate();
+        response.add(&k);
+
+        Ok(Self {
+            challenge,
+            response,
+        })
+    }
+
+    /// Verifies that `a` and `b` are `x·g` and `x·h` for one `x`, under the
+    /// `context` the proof was made with.
+    pub fn verify<P: Point<RHS = S>, Q: Point<RHS = S>>(
+        &self,
+        g: &P,
+        a: &P,
+        h: &Q,
+        b: &Q,
+        context: &[u8],
+    ) -> Result<(), DleqError> {
+        let (commit_g, commit_h) = (self.commitment(g, a), self.commitment(h, b));
+
+        if challenge(g, a, h, b, &commit_g, &commit_h, context)? != self.challenge {
+            return Err(DleqError::InvalidProof);
+        }
+
+        Ok(())
+    }
+
+    /// `response·base + challenge·image`, which is the prover's commitment
+    /// `k·base` exactly when the response is honest.
+    fn commitment<P: Point<RHS = S>>(&self, base: &P, image: &P) -> P {
+        let mut commitment = base.clone();
+        commitment.mul(&self.response);
+        let mut offset = image.clone();
+        offset.mul(&self.challenge);
+        commitment.add(&offset);
+        commitment
+    }
+}
+
+/// Hashes the transcript to the challenge scalar.
+fn challenge<S: Scalar, P: Point<RHS = S>, Q: Point<RHS = S>>(
+    g: &P,
+    a: &P,
+    h: &Q,
+    b: &Q,
+    commit_g: &P,
+    commit_h: &Q,
+    context: &[u8],
+) -> Result<S, DleqError> {
+    // The points go in the order they did when both pairs shared a group, so
+    // proofs made then still verify.
+    let mut transcript = Vec::new();
+    bincode::serialize_into(&mut transcript, g)?;
+    bincode::serialize_into(&mut transcript, a)?;
+    bincode::serialize_into(&mut transcript, h)?;
+    bincode::serialize_into(&mut transcript, b)?;
+    bincode::serialize_into(&mut transcript, commit_g)?;
+    bincode::serialize_into(&mut transcript, commit_h)?;
+
+    // Try-and-increment, as for a child key's tweak, so the challenge is
+    // uniform rather than biased toward small values.
+    for counter in 0u8..=255 {
+        let hash = blake2s_simd::Params::new()
+            .hash_length(32)
+            .personal(DLEQ_DOMAIN)
+            .to_state()
+            .update(&[counter])
+            .update(&transcript)
+            .update(context)
+            .finalize();
+        if let Some(challenge) = S::from_random_bytes(hash.as_bytes()) {
+            return Ok(challenge);
+        }
+    }
+
+    Err(DleqError::HashToScalarError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::bls12377::{G1, G2, Scalar as Sc};
+    use crate::group::Element;
+    use rand::thread_rng;
+
+    fn instance<P: Point<RHS = Sc>>() -> (Sc, P, P, P, P) {
+        let rng = &mut thread_rng();
+        let x = Sc::rand(rng);
+        let (g, h) = (P::one(), P::rand(rng));
+        let (mut a, mut b) = (g.clone(), h.clone());
+        a.mul(&x);
+        b.mul(&x);
+        (x, g, a, h, b)
+    }
+
+    fn proves<P: Point<RHS = Sc>>() {
+        let (x, g, a, h, b) = instance::<P>();
+        let proof = DleqProof::prove(&x, &g, &h, b"ctx", &mut thread_rng()).unwrap();
+        proof.verify(&g, &a, &h, &b, b"ctx").unwrap();
+
+        let bytes = bincode::serialize(&proof).unwrap();
+        assert_eq!(bytes.len(), 64);
+        let decoded: DleqProof<Sc> = bincode::deserialize(&bytes).unwrap();
+        decoded.verify(&g, &a, &h, &b, b"ctx").unwrap();
+    }
+
+    #[test]
+    fn proves_g1() {
+        proves::<G1>();
+    }
+
+    #[test]
+    fn proves_g2() {
+        proves::<G2>();
+    }
+
+    #[test]
+    fn proves_across_groups() {
+        let rng = &mut thread_rng();
+        let x = Sc::rand(rng);
+        let (g, h) = (G1::one(), G2::rand(rng));
+        let (mut a, mut b) = (g.clone(), h.clone());
+        a.mul(&x);
+        b.mul(&x);
+
+        let proof = DleqProof::prove(&x, &g, &h, b"", rng).unwrap();
+        proof.verify(&g, &a, &h, &b, b"").unwrap();
+
+        b.mul(&x);
+        assert!(matches!(
+            proof.verify(&g, &a, &h, &b, b""),
+            Err(DleqError::InvalidProof)
+        ));
+    }
+
+    #[test]
+    fn different_logarithms_do_not_verify() {
+        let (x, g, a, h, _) = instance::<G1>();
+        let proof = DleqProof::prove(&x, &g, &h, b"", &mut thread_rng()).unwrap();
+
+        // b under another logarithm
+        let mut b = h.clone();
+        b.mul(&Sc::rand(&mut thread_rng()));
+        assert!(matches!(
+            proof.verify(&g, &a, &h, &b, b""),
+            Err(DleqError::InvalidProof)
+        ));
+    }
+
+    #[test]
+    fn the_transcript_is_in_order() {
+        let (_, g, a, h, b) = instance::<G1>();
+        let (commit_g, commit_h) = (G1::rand(&mut thread_rng()), G1::rand(&mut thread_rng()));
+
+        let mut transcript = Vec::new();
+        for point in [&g, &a, &h, &b, &commit_g, &commit_h] {
+            bincode::serialize_into(&mut transcript, point).unwrap();
+        }
+        let expected = (0u8..=255)
+            .find_map(|counter| {
+                let hash = blake2s_simd::Params::new()
+                    .hash_length(32)
+                    .personal(DLEQ_DOMAIN)
+                    .to_state()
+                    .update(&[counter])
+                    .update(&transcript)
+                    .update(b"ctx")
+                    .finalize();
+                Sc::from_random_bytes(hash.as_bytes())
+            })
+            .unwrap();
+
+        let challenge: Sc = challenge(&g, &a, &h, &b, &commit_g, &commit_h, b"ctx").unwrap();
+        assert_eq!(challenge, expected);
+    }
+
+    #[test]
+    fn a_proof_is_bound_to_its_context() {
+        let (x, g, a, h, b) = instance::<G1>();
+        let proof = DleqProof::prove(&x, &g, &h, b"one", &mut thread_rng()).unwrap();
+        assert!(matches!(
+            proof.verify(&g, &a, &h, &b, b"two"),
+            Err(DleqError::InvalidProof)
+        ));
+
+        let mut tampered = proof;
+        tampered.response.add(&Sc::one());
+        assert!(tampered.verify(&g, &a, &h, &b, b"one").is_err());
+    }
+}
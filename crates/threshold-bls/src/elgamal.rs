@@ -0,0 +1,393 @@
+//! Threshold encryption to a group's public key, decrypted with the same
+//! [`Share`]s the group signs with.
+//!
+//! Anyone can [`encrypt`] a payload to the public key of a threshold group.
+//! Each member then computes a [`DecryptionShare`] from its share, with a
+//! [DLEQ proof](crate::dleq) that it used the share the public polynomial
+//! commits to, and any threshold of valid decryption shares [`combine`] into
+//! the payload. No member learns the payload from its own share, and a member
+//! who sends a wrong decryption share is caught rather than corrupting the
+//! result.
+//!
+//! ```
+//! use threshold_bls::{
+//!     elgamal,
+//!     poly::{Idx, Poly},
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::{Scheme, Share},
+//! };
+//!
+//! let (n, t) = (5, 3);
+//! let private_poly = Poly::<<SigScheme as Scheme>::Private>::new(t - 1);
+//! let shares = (0..n)
+//!     .map(|i| private_poly.eval(i as Idx))
+//!     .map(|eval| Share { index: eval.index, private: eval.value })
+//!     .collect::<Vec<_>>();
+//! let public_poly = private_poly.commit();
+//! let rng = &mut rand::thread_rng();
+//!
+//! // a user encrypts to the group's public key
+//! let ciphertext = elgamal::encrypt::<SigScheme, _>(
+//!     public_poly.public_key(),
+//!     b"ballot 17",
+//!     b"vote: yes",
+//!     rng,
+//! )
+//! .unwrap();
+//!
+//! // the members decrypt it together
+//! let decryption_shares = shares[..t]
+//!     .iter()
+//!     .map(|share| elgamal::decryption_share::<SigScheme, _>(share, &ciphertext, rng))
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! let payload =
+//!     elgamal::combine::<SigScheme>(&public_poly, &ciphertext, &decryption_shares).unwrap();
+//! assert_eq!(payload, b"vote: yes");
+//! ```
+//!
+//! The scheme is Shoup and Gennaro's TDH2: hashed ElGamal in the public key's
+//! group, with a proof that the encryptor knows its randomness. The proof binds
+//! the masked payload and a `label`, so a ciphertext cannot be altered, or
+//! replayed under another label, without members refusing to decrypt it.
+use crate::dleq::{DleqError, DleqProof};
+use crate::group::{Element, Point};
+use crate::ibe::{mask, xor};
+use crate::poly::{Eval, Idx, Poly, PolyError};
+use crate::sig::{Scheme, Share};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Domain separator for the hash of the shared point into the payload's mask.
+const MASK_DOMAIN: &[u8] = b"BLS-ELGAMAL-MASK";
+
+/// Hashed to the public key's group for the second generator of the
+/// ciphertext's proof, whose discrete logarithm nobody knows.
+const GENERATOR_DOMAIN: &[u8] = b"BLS-ELGAMAL-GENERATOR";
+
+/// A payload encrypted to a group's public key.
+///
+/// Serialized with bincode, the fields follow one another in order: `u` and
+/// `u_bar` as compressed points of the public key's group, the masked payload
+/// and the label each behind a `u64` length, and the proof as two scalars.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ciphertext<P, S> {
+    /// The encryption randomness times the generator
+    pub u: P,
+    /// The encryption randomness times the second generator
+    pub u_bar: P,
+    /// The payload, masked with the hash of the randomness times the public key
+    pub w: Vec<u8>,
+    /// Data the ciphertext is bound to, such as what the payload is for
+    pub label: Vec<u8>,
+    /// Proves that `u` and `u_bar` share the randomness, binding `w` and
+    /// `label`
+    pub proof: DleqProof<S>,
+}
+
+/// A member's share of the decryption of a [`Ciphertext`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecryptionShare<P, S> {
+    /// The index of the share it was computed with
+    pub index: Idx,
+    /// The share times the ciphertext's `u`
+    pub value: P,
+    /// Proves that `value` was computed with the share the public polynomial
+    /// commits to at `index`
+    pub proof: DleqProof<S>,
+}
+
+/// A ciphertext for the scheme `S`
+pub type SchemeCiphertext<S> = Ciphertext<<S as Scheme>::Public, <S as Scheme>::Private>;
+
+/// A decryption share for the scheme `S`
+pub type SchemeDecryptionShare<S> = DecryptionShare<<S as Scheme>::Public, <S as Scheme>::Private>;
+
+/// Errors associated with threshold encryption
+#[derive(Debug, Error)]
+pub enum ElGamalError {
+    /// Raised when encrypting to the identity public key, which anyone can
+    /// decrypt for
+    #[error("public key is the identity element")]
+    InvalidPublicKey,
+
+    /// Raised when a ciphertext's proof does not verify, so it was altered
+    /// after encryption or not produced by [`encrypt`]
+    #[error("invalid ciphertext")]
+    InvalidCiphertext,
+
+    /// Raised when the decryption share at an index does not verify
+    #[error("invalid decryption share at index {0}")]
+    InvalidDecryptionShare(Idx),
+
+    /// Raised when the decryption shares cannot be combined, too few of them
+    /// among the most common case
+    #[error("could not combine the decryption shares: {0}")]
+    PolyError(#[from] PolyError),
+
+    /// Raised when proving fails for a reason other than the proof not holding
+    #[error(transparent)]
+    DleqError(#[from] DleqError),
+}
+
+/// Encrypts `msg` under `label` to the group holding `public`, the public key
+/// of its polynomial. A threshold of the group's shares decrypts it.
+pub fn encrypt<S: Scheme, R: RngCore>(
+    public: &S::Public,
+    label: &[u8],
+    msg: &[u8],
+    rng: &mut R,
+) -> Result<SchemeCiphertext<S>, ElGamalError> {
+    if public == &S::Public::zero() {
+        return Err(ElGamalError::InvalidPublicKey);
+    }
+
+    let r = S::Private::rand(rng);
+    let mut shared = public.clone();
+    shared.mul(&r);
+    let w = xor(msg, &mask(MASK_DOMAIN, &serialize(&shared), msg.len()));
+
+    let (g, g_bar) = (S::Public::one(), second_generator::<S>()?);
+    let (mut u, mut u_bar) = (g.clone(), g_bar.clone());
+    u.mul(&r);
+    u_bar.mul(&r);
+    let proof = DleqProof::prove(&r, &g, &g_bar, &proof_context(&w, label), rng)?;
+
+    Ok(Ciphertext {
+        u,
+        u_bar,
+        w,
+        label: label.to_vec(),
+        proof,
+    })
+}
+
+/// Checks that `ciphertext` was produced by [`encrypt`] and has not been
+/// altered since. Anyone can check this, without a share.
+pub fn verify_ciphertext<S: Scheme>(ciphertext: &SchemeCiphertext<S>) -> Result<(), ElGamalError> {
+    let context = proof_context(&ciphertext.w, &ciphertext.label);
+    ciphertext
+        .proof
+        .verify(
+            &S::Public::one(),
+            &ciphertext.u,
+            &second_generator::<S>()?,
+            &ciphertext.u_bar,
+            &context,
+        )
+        .map_err(|_| ElGamalError::InvalidCiphertext)
+}
+
+/// Computes a member's decryption share of `ciphertext`.
+///
+/// The ciphertext is verified first, and a member refuses one that does not
+/// verify: decrypting altered ciphertexts on request would let whoever asks
+/// learn about payloads they could not otherwise read.
+pub fn decryption_share<S: Scheme, R: RngCore>(
+    share: &Share<S::Private>,
+    ciphertext: &SchemeCiphertext<S>,
+    rng: &mut R,
+) -> Result<SchemeDecryptionShare<S>, ElGamalError> {
+    verify_ciphertext::<S>(ciphertext)?;
+
+    let mut value = ciphertext.u.clone();
+    value.mul(&share.private);
+    let proof = DleqProof::prove(
+        &share.private,
+        &S::Public::one(),
+        &ciphertext.u,
+        &share.index.to_le_bytes(),
+        rng,
+    )?;
+
+    Ok(DecryptionShare {
+        index: share.index,
+        value,
+        proof,
+    })
+}
+
+/// Checks a decryption share of `ciphertext` against the public polynomial of
+/// the group.
+pub fn verify_decryption_share<S: Scheme>(
+    public: &Poly<S::Public>,
+    ciphertext: &SchemeCiphertext<S>,
+    decryption_share: &SchemeDecryptionShare<S>,
+) -> Result<(), ElGamalError> {
+    let index = decryption_share.index;
+    decryption_share
+        .proof
+        .verify(
+            &S::Public::one(),
+            &public.eval(index).value,
+            &ciphertext.u,
+            &decryption_share.value,
+            &index.to_le_bytes(),
+        )
+        .map_err(|_| ElGamalError::InvalidDecryptionShare(index))
+}
+
+/// Combines decryption shares of `ciphertext` into the payload.
+///
+/// Every decryption share is verified, and one that does not verify fails the
+/// call with its index rather than being combined into a wrong payload. At
+/// least the threshold, the polynomial's degree plus one, is needed.
+pub fn combine<S: Scheme>(
+    public: &Poly<S::Public>,
+    ciphertext: &SchemeCiphertext<S>,
+    decryption_shares: &[SchemeDecryptionShare<S>],
+) -> Result<Vec<u8>, ElGamalError> {
+    verify_ciphertext::<S>(ciphertext)?;
+    let evals = decryption_shares
+        .iter()
+        .map(|decryption_share| {
+            verify_decryption_share::<S>(public, ciphertext, decryption_share)?;
+            Ok(Eval {
+                index: decryption_share.index,
+                value: decryption_share.value.clone(),
+            })
+        })
+        .collect::<Result<Vec<_>, ElGamalError>>()?;
+
+    let shared = Poly::<S::Public>::recover(public.degree() + 1, evals)?;
+    let w = &ciphertext.w;
+    Ok(xor(w, &mask(MASK_DOMAIN, &serialize(&shared), w.len())))
+}
+
+/// The second generator of the public key's group.
+fn second_generator<S: Scheme>() -> Result<S::Public, ElGamalError> {
+    let mut g_bar = S::Public::zero();
+    // Hashing a constant cannot fail in practice; if it ever did, no
+    // ciphertext would verify, which is the safe way round.
+    g_bar
+        .map(GENERATOR_DOMAIN)
+        .map_err(|_| ElGamalError::InvalidCiphertext)?;
+    Ok(g_bar)
+}
+
+/// The masked payload and the label, length-prefixed so that moving bytes
+/// from one to the other changes the context.
+fn proof_context(w: &[u8], label: &[u8]) -> Vec<u8> {
+    let mut context = Vec::with_capacity(16 + w.len() + label.len());
+    for part in [w, label] {
+        context.extend_from_slice(&(part.len() as u64).to_le_bytes());
+        context.extend_from_slice(part);
+    }
+    context
+}
+
+fn serialize<P: Serialize>(point: &P) -> Vec<u8> {
+    bincode::serialize(point).expect("could not serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemes::bls12_377::{G1Scheme, G2Scheme};
+    use crate::sig::shares;
+    use rand::thread_rng;
+
+    fn decrypt_shares<S: Scheme>(
+        shares: &[Share<S::Private>],
+        ciphertext: &SchemeCiphertext<S>,
+    ) -> Vec<SchemeDecryptionShare<S>> {
+        shares
+            .iter()
+            .map(|share| decryption_share::<S, _>(share, ciphertext, &mut thread_rng()).unwrap())
+            .collect()
+    }
+
+    fn round_trip<S: Scheme>() {
+        let (shares, public) = shares::<S>(5, 3);
+        let ciphertext =
+            encrypt::<S, _>(public.public_key(), b"label", b"secret", &mut thread_rng()).unwrap();
+
+        // any threshold of members decrypts it
+        for members in [&shares[..3], &shares[2..], &shares[1..4]] {
+            let decryption_shares = decrypt_shares::<S>(members, &ciphertext);
+            for decryption_share in &decryption_shares {
+                verify_decryption_share::<S>(&public, &ciphertext, decryption_share).unwrap();
+            }
+            let payload = combine::<S>(&public, &ciphertext, &decryption_shares).unwrap();
+            assert_eq!(payload, b"secret");
+        }
+
+        let bytes = bincode::serialize(&ciphertext).unwrap();
+        let decoded: SchemeCiphertext<S> = crate::serialization::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, ciphertext);
+    }
+
+    #[test]
+    fn round_trip_g1() {
+        round_trip::<G1Scheme>();
+    }
+
+    #[test]
+    fn round_trip_g2() {
+        round_trip::<G2Scheme>();
+    }
+
+    #[test]
+    fn fewer_than_the_threshold_do_not_decrypt() {
+        let (shares, public) = shares::<G2Scheme>(5, 3);
+        let ciphertext =
+            encrypt::<G2Scheme, _>(public.public_key(), b"", b"secret", &mut thread_rng()).unwrap();
+        let decryption_shares = decrypt_shares::<G2Scheme>(&shares[..2], &ciphertext);
+
+        assert!(matches!(
+            combine::<G2Scheme>(&public, &ciphertext, &decryption_shares),
+            Err(ElGamalError::PolyError(PolyError::InvalidRecovery(2, 3)))
+        ));
+    }
+
+    #[test]
+    fn a_wrong_decryption_share_is_caught() {
+        let (shares, public) = shares::<G2Scheme>(5, 3);
+        let ciphertext =
+            encrypt::<G2Scheme, _>(public.public_key(), b"", b"secret", &mut thread_rng()).unwrap();
+        let mut decryption_shares = decrypt_shares::<G2Scheme>(&shares[..3], &ciphertext);
+
+        // member 1 sends member 2's decryption share as its own
+        decryption_shares[1].value = decryption_shares[2].value.clone();
+        assert!(matches!(
+            verify_decryption_share::<G2Scheme>(&public, &ciphertext, &decryption_shares[1]),
+            Err(ElGamalError::InvalidDecryptionShare(1))
+        ));
+        assert!(matches!(
+            combine::<G2Scheme>(&public, &ciphertext, &decryption_shares),
+            Err(ElGamalError::InvalidDecryptionShare(1))
+        ));
+    }
+
+    #[test]
+    fn an_altered_ciphertext_is_not_decrypted() {
+        let (shares, public) = shares::<G2Scheme>(3, 2);
+        let ciphertext =
+            encrypt::<G2Scheme, _>(public.public_key(), b"ballot 1", b"yes", &mut thread_rng())
+                .unwrap();
+
+        let mut flipped = ciphertext.clone();
+        flipped.w[0] ^= 1;
+        let mut relabelled = ciphertext.clone();
+        relabelled.label = b"ballot 2".to_vec();
+        let mut moved = ciphertext;
+        moved.u = moved.u_bar.clone();
+
+        for altered in [flipped, relabelled, moved] {
+            assert!(matches!(
+                decryption_share::<G2Scheme, _>(&shares[0], &altered, &mut thread_rng()),
+                Err(ElGamalError::InvalidCiphertext)
+            ));
+        }
+    }
+
+    #[test]
+    fn the_identity_public_key_is_rejected() {
+        let identity = <G2Scheme as Scheme>::Public::zero();
+        assert!(matches!(
+            encrypt::<G2Scheme, _>(&identity, b"", b"anyone", &mut thread_rng()),
+            Err(ElGamalError::InvalidPublicKey)
+        ));
+    }
+}
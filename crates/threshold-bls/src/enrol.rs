@@ -0,0 +1,228 @@
+//! Enrolling a new member into a group at a fresh index, without changing its
+//! key or its threshold.
+//!
+//! The group's polynomial already defines a share at every index, dealt or
+//! not, so adding a member is computing the share at an index nobody holds.
+//! That is the same computation as [repairing](crate::repair) a lost share, and
+//! runs the same protocol: a threshold of members [`contribute`] masked,
+//! Lagrange-weighted parts of their shares, each [`aggregate`]s the parts it
+//! was sent, and the new member [`recover`]s its share from the sums and checks
+//! it against the public polynomial. What enrolment adds is the membership:
+//! the new index must not be one a member holds, and only members can help.
+//!
+//! The public polynomial is unchanged, so the group's public key and threshold
+//! are too, and signatures made with the new share combine with the others'.
+//!
+//! ```
+//! use threshold_bls::{
+//!     enrol,
+//!     group::Element,
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::{self, Scheme},
+//! };
+//!
+//! let rng = &mut rand::thread_rng();
+//! let (private, _) = SigScheme::keypair(rng);
+//! let (shares, public_poly) = sig::deal::<SigScheme, _>(&private, 4, 3, rng).unwrap();
+//!
+//! // members 0, 1 and 3 enrol a new member at index 4
+//! let (members, new, helpers) = ([0, 1, 2, 3], 4, [0, 1, 3]);
+//! let contributions = helpers
+//!     .iter()
+//!     .map(|&i| {
+//!         let share = &shares[i as usize];
+//!         enrol::contribute::<SigScheme, _>(&public_poly, share, new, &members, &helpers, rng)
+//!     })
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap()
+//!     .concat();
+//!
+//! let sums = helpers
+//!     .iter()
+//!     .map(|&helper| {
+//!         let received = contributions
+//!             .iter()
+//!             .filter(|contribution| contribution.to == helper)
+//!             .cloned()
+//!             .collect::<Vec<_>>();
+//!         enrol::aggregate::<SigScheme>(helper, &helpers, &received)
+//!     })
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//!
+//! let share = enrol::recover::<SigScheme>(&public_poly, new, &members, &helpers, &sums).unwrap();
+//! let mut public_share = <SigScheme as Scheme>::Public::one();
+//! public_share.mul(&share.private);
+//! assert_eq!(public_share, public_poly.eval(new).value);
+//! ```
+use crate::poly::{Idx, Poly};
+use crate::repair::{self, Contribution, RepairError, RepairShare};
+use crate::sig::{Scheme, Share};
+use rand_core::RngCore;
+use thiserror::Error;
+
+pub use crate::repair::aggregate;
+
+/// Errors associated with enrolling a member
+#[derive(Debug, Error)]
+pub enum EnrolError {
+    /// Raised when the new member's index is already a member's
+    #[error("index {0} is already held by a member")]
+    IndexInUse(Idx),
+
+    /// Raised when a helper is not a member of the group
+    #[error("helper {0} is not a member")]
+    NotAMember(Idx),
+
+    /// Raised when the helpers cannot compute the share, or computed it
+    /// wrongly. See [`RepairError`]
+    #[error(transparent)]
+    RepairError(#[from] RepairError),
+}
+
+/// Computes a member's contributions to the share at `new`, one for each of
+/// `helpers` in the order given, which are then handled as in
+/// [`repair::contribute`].
+///
+/// `members` are the indices the group has dealt or enrolled so far.
+pub fn contribute<S: Scheme, R: RngCore>(
+    public: &Poly<S::Public>,
+    share: &Share<S::Private>,
+    new: Idx,
+    members: &[Idx],
+    helpers: &[Idx],
+    rng: &mut R,
+) -> Result<Vec<Contribution<S::Private>>, EnrolError> {
+    check_membership(new, members, helpers)?;
+
+    Ok(repair::contribute::<S, R>(
+        public, share, new, helpers, rng,
+    )?)
+}
+
+/// Sums the sums `helpers` sent into the new member's share at `new`, and
+/// checks it against `public`.
+pub fn recover<S: Scheme>(
+    public: &Poly<S::Public>,
+    new: Idx,
+    members: &[Idx],
+    helpers: &[Idx],
+    sums: &[RepairShare<S::Private>],
+) -> Result<Share<S::Private>, EnrolError> {
+    check_membership(new, members, helpers)?;
+
+    Ok(repair::recover::<S>(public, new, helpers, sums)?)
+}
+
+/// Refuses to enrol at an index a member holds, or with helpers who are not
+/// members.
+fn check_membership(new: Idx, members: &[Idx], helpers: &[Idx]) -> Result<(), EnrolError> {
+    if members.contains(&new) {
+        return Err(EnrolError::IndexInUse(new));
+    }
+    match helpers.iter().find(|helper| !members.contains(helper)) {
+        Some(&helper) => Err(EnrolError::NotAMember(helper)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{G1Scheme, G2Scheme, SignatureScheme, ThresholdError, ThresholdScheme},
+    };
+
+    fn enrol<S: Scheme>(
+        public: &Poly<S::Public>,
+        shares: &[Share<S::Private>],
+        new: Idx,
+        members: &[Idx],
+        helpers: &[Idx],
+    ) -> Result<Share<S::Private>, EnrolError> {
+        let rng = &mut rand::thread_rng();
+        let mut contributions = Vec::new();
+        for &i in helpers {
+            let share = &shares[i as usize];
+            contributions.extend(contribute::<S, _>(
+                public, share, new, members, helpers, rng,
+            )?);
+        }
+        let sums = helpers
+            .iter()
+            .map(|&helper| {
+                let received = contributions
+                    .iter()
+                    .filter(|c| c.to == helper)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                aggregate::<S>(helper, helpers, &received)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        recover::<S>(public, new, members, helpers, &sums)
+    }
+
+    fn a_new_member_signs_for_the_same_key<S>()
+    where
+        S: SignatureScheme + ThresholdScheme<Error = ThresholdError<S>>,
+    {
+        let rng = &mut rand::thread_rng();
+        let (private, public_key) = S::keypair(rng);
+        let (shares, public) = crate::sig::deal::<S, _>(&private, 4, 3, rng).unwrap();
+        let members = [0, 1, 2, 3];
+
+        // an index past the dealt ones, and one far from them
+        for new in [4, 1000] {
+            let share = enrol::<S>(&public, &shares, new, &members, &[3, 0, 2]).unwrap();
+            assert_eq!(share.index, new);
+
+            let msg = b"signed by the enlarged group";
+            let partials = [&share, &shares[1], &shares[2]]
+                .iter()
+                .map(|share| S::partial_sign(share, msg).unwrap())
+                .collect::<Vec<_>>();
+            for partial in &partials {
+                S::partial_verify(&public, msg, partial).unwrap();
+            }
+            let signature = S::aggregate(&public, &partials).unwrap();
+            S::verify(&public_key, msg, &signature).unwrap();
+            assert_eq!(public.degree() + 1, 3);
+        }
+    }
+
+    #[test]
+    fn a_new_member_signs_for_the_same_key_g1() {
+        a_new_member_signs_for_the_same_key::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn a_new_member_signs_for_the_same_key_g2() {
+        a_new_member_signs_for_the_same_key::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn membership_is_checked() {
+        type S = G2Scheme<PCurve>;
+        let rng = &mut rand::thread_rng();
+        let (private, _) = S::keypair(rng);
+        let (shares, public) = crate::sig::deal::<S, _>(&private, 4, 3, rng).unwrap();
+        let members = [0, 1, 2];
+
+        assert!(matches!(
+            enrol::<S>(&public, &shares, 2, &members, &[0, 1, 2]),
+            Err(EnrolError::IndexInUse(2))
+        ));
+        // a dealt share whose holder is not a member does not help
+        assert!(matches!(
+            enrol::<S>(&public, &shares, 4, &members, &[0, 1, 3]),
+            Err(EnrolError::NotAMember(3))
+        ));
+        assert!(matches!(
+            enrol::<S>(&public, &shares, 4, &members, &[0, 1]),
+            Err(EnrolError::RepairError(
+                RepairError::NotEnoughHelpers { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,333 @@
+//! Hierarchical deterministic key derivation, after
+//! [EIP-2333](https://eips.ethereum.org/EIPS/eip-2333).
+//!
+//! [`derive_master_sk`] turns a seed of at least 32 bytes into a master private
+//! key, and [`derive_child_sk`] turns a private key and an index into a child
+//! private key. Every child is hardened: deriving one needs the parent's private
+//! key, and a child key reveals nothing about its parent or siblings. A path
+//! such as `m/12381/3600/0/0` names the key reached by deriving one child per
+//! index from the master, and [`derive_sk_from_path`] walks it.
+//!
+//! ```
+//! use threshold_bls::{
+//!     hd,
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::SignatureScheme,
+//! };
+//!
+//! let seed = [7u8; 32];
+//! let private = hd::derive_sk_from_path(&seed, "m/12381/3600/0/0").unwrap();
+//!
+//! // the same seed and path always give the same key
+//! assert_eq!(private, hd::derive_sk_from_path(&seed, "m/12381/3600/0/0").unwrap());
+//! let signature = SigScheme::sign(&private, b"hello").unwrap();
+//! ```
+//!
+//! The algorithm is EIP-2333's, step for step, with one difference: it reduces
+//! modulo the BLS12-377 group order where EIP-2333 reduces modulo BLS12-381's.
+//! The keys are therefore **not** the keys the same seed derives in an Ethereum
+//! wallet, and the test vectors published with EIP-2333 do not apply to them.
+//! The tests here check the algorithm against those vectors with the BLS12-381
+//! order, and pin the BLS12-377 keys separately.
+//!
+//! Only private keys are derived. EIP-2333 has no public derivation, so a
+//! verifier cannot compute a child public key without the parent private key;
+//! [`HierarchicalScheme`](crate::sig::HierarchicalScheme) is the scheme for
+//! that.
+use crate::curve::bls12377::Scalar;
+use hkdf::Hkdf;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Bytes of seed [`derive_master_sk`] requires at the least.
+pub const MIN_SEED_LEN: usize = 32;
+
+/// Errors associated with deriving a key
+#[derive(Debug, Error)]
+pub enum HdError {
+    /// Raised when the seed has too little entropy to derive a master key from
+    #[error("seed must be at least {MIN_SEED_LEN} bytes (got {0})")]
+    SeedTooShort(usize),
+
+    /// Raised when a path is not `m` followed by `/`-separated `u32` indices
+    #[error("invalid derivation path {0:?}")]
+    InvalidPath(String),
+}
+
+/// Derives the master private key from a seed of at least [`MIN_SEED_LEN`]
+/// bytes. The seed is the whole secret: anyone holding it derives every key in
+/// the hierarchy.
+pub fn derive_master_sk(seed: &[u8]) -> Result<Scalar, HdError> {
+    if seed.len() < MIN_SEED_LEN {
+        return Err(HdError::SeedTooShort(seed.len()));
+    }
+
+    Ok(Scalar::from_biguint(&hkdf_mod_r(seed, &Scalar::modulus())))
+}
+
+/// Derives the hardened child of `parent` at `index`.
+pub fn derive_child_sk(parent: &Scalar, index: u32) -> Scalar {
+    let modulus = Scalar::modulus();
+    let child = derive_child(&parent.to_biguint(), index, &modulus);
+
+    Scalar::from_biguint(&child)
+}
+
+/// Derives the key at `path` below the master key of `seed`.
+///
+/// A path is `m` followed by any number of `/index` steps, each index a
+/// decimal `u32`: `m` alone is the master key, and `m/12381/3600/0/0` is the
+/// child at 0 of the child at 0 of the child at 3600 of the child at 12381.
+/// There is no hardened marker, since every step is hardened.
+pub fn derive_sk_from_path(seed: &[u8], path: &str) -> Result<Scalar, HdError> {
+    let indices = parse_path(path)?;
+    let master = derive_master_sk(seed)?;
+
+    Ok(indices
+        .into_iter()
+        .fold(master, |key, index| derive_child_sk(&key, index)))
+}
+
+/// Splits a path into its indices. See [`derive_sk_from_path`].
+pub fn parse_path(path: &str) -> Result<Vec<u32>, HdError> {
+    let invalid = || HdError::InvalidPath(path.to_string());
+
+    let mut steps = path.split('/');
+    if steps.next() != Some("m") {
+        return Err(invalid());
+    }
+    steps
+        .map(|step| {
+            // `u32::from_str` takes a leading `+`, which a path has no use for,
+            // and two spellings of one index would name one key two ways.
+            if !step.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            step.parse().map_err(|_| invalid())
+        })
+        .collect()
+}
+
+/// `derive_child_SK`: the child is the key hashed from the compressed Lamport
+/// public key of the parent at `index`.
+fn derive_child(parent: &BigUint, index: u32, modulus: &BigUint) -> BigUint {
+    hkdf_mod_r(&parent_sk_to_lamport_pk(parent, index), modulus)
+}
+
+/// `HKDF_mod_r`: hashes key material to a nonzero integer below `modulus`.
+fn hkdf_mod_r(ikm: &[u8], modulus: &BigUint) -> BigUint {
+    // ceil(3 * ceil(log2(r)) / 16), 48 bytes for both BLS12-381 and BLS12-377,
+    // so the reduction below leaves a bias of under 2^-128.
+    const L: usize = 48;
+
+    let mut salt = Sha256::digest(b"BLS-SIG-KEYGEN-SALT-");
+    let ikm = [ikm, &[0]].concat();
+    loop {
+        let mut okm = [0u8; L];
+        Hkdf::<Sha256>::new(Some(&salt), &ikm)
+            .expand(&(L as u16).to_be_bytes(), &mut okm)
+            .expect("48 bytes is well within what HKDF-SHA256 can expand to");
+
+        let sk = BigUint::from_bytes_be(&okm) % modulus;
+        if sk != BigUint::ZERO {
+            return sk;
+        }
+        salt = Sha256::digest(salt);
+    }
+}
+
+/// `parent_SK_to_lamport_PK`: the hash of the Lamport public key for the
+/// parent key and its bitwise complement, salted with the index.
+fn parent_sk_to_lamport_pk(parent: &BigUint, index: u32) -> [u8; 32] {
+    let salt = index.to_be_bytes();
+
+    // I2OSP(parent_SK, 32). The parent is below the group order, which fits.
+    let mut ikm = [0u8; 32];
+    let bytes = parent.to_bytes_be();
+    ikm[32 - bytes.len()..].copy_from_slice(&bytes);
+    let not_ikm = ikm.map(|b| !b);
+
+    let mut lamport_pk = Sha256::new();
+    for ikm in [ikm, not_ikm] {
+        for chunk in ikm_to_lamport_sk(&ikm, &salt).chunks(32) {
+            lamport_pk.update(Sha256::digest(chunk));
+        }
+    }
+    lamport_pk.finalize().into()
+}
+
+/// `IKM_to_lamport_SK`: 255 Lamport secret keys of 32 bytes, concatenated.
+fn ikm_to_lamport_sk(ikm: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut okm = vec![0u8; 255 * 32];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(&[], &mut okm)
+        .expect("8160 bytes is exactly what HKDF-SHA256 can expand to");
+    okm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group::Element;
+    use crate::schemes::bls12_377::G2Scheme;
+    use crate::sig::SignatureScheme;
+
+    /// The BLS12-381 group order, which EIP-2333 reduces modulo.
+    fn bls12_381_order() -> BigUint {
+        BigUint::parse_bytes(
+            b"73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+            16,
+        )
+        .unwrap()
+    }
+
+    fn decimal(n: &str) -> BigUint {
+        BigUint::parse_bytes(n.as_bytes(), 10).unwrap()
+    }
+
+    /// The test cases published with EIP-2333: seed, master key, child index
+    /// and child key.
+    const EIP_2333_VECTORS: [(&str, &str, u32, &str); 4] = [
+        (
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+            0,
+            "20397789859736650942317412262472558107875392172444076792671091975210932703118",
+        ),
+        (
+            "3141592653589793238462643383279502884197169399375105820974944592",
+            "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+            3141592653,
+            "25457201688850691947727629385191704516744796114925897962676248250929345014287",
+        ),
+        (
+            "0099FF991111002299DD7744EE3355BBDD8844115566CC55663355668888CC00",
+            "27580842291869792442942448775674722299803720648445448686099262467207037398656",
+            4294967295,
+            "29358610794459428860402234341874281240803786294062035874021252734817515685787",
+        ),
+        (
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            "19022158461524446591288038168518313374041767046816487870552872741050760015818",
+            42,
+            "31372231650479070279774297061823572166496564838472787488249775572789064611981",
+        ),
+    ];
+
+    #[test]
+    fn the_algorithm_matches_eip_2333() {
+        let order = bls12_381_order();
+        for (seed, master, index, child) in EIP_2333_VECTORS {
+            let seed = hex::decode(seed).unwrap();
+            let derived_master = hkdf_mod_r(&seed, &order);
+            assert_eq!(derived_master, decimal(master));
+            assert_eq!(derive_child(&derived_master, index, &order), decimal(child));
+        }
+    }
+
+    /// The same seeds and indices under the BLS12-377 order, which is what
+    /// this module derives. `docs/key-derivation.md` publishes them.
+    const BLS12_377_VECTORS: [(&str, &str, u32, &str); 4] = [
+        (
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            "149578432920073668634485734793641253128295133252519738491387990947771028269",
+            0,
+            "2426279411894516673746970959776790083851304606714102642536553102048873584068",
+        ),
+        (
+            "3141592653589793238462643383279502884197169399375105820974944592",
+            "5929869835894768236784671036391600268658788569008944105123833972465447990450",
+            3141592653,
+            "4342390277684999024512275408409435319586746386460534657690850306828203433142",
+        ),
+        (
+            "0099FF991111002299DD7744EE3355BBDD8844115566CC55663355668888CC00",
+            "6681946343240689778051976836498434314698838288532380447141920416689365683027",
+            4294967295,
+            "6409675001386639851943882240467160468748328824634452812722643256742265126280",
+        ),
+        (
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            "3246582025977177491325844402802945199230393440188287116894151084346323923037",
+            42,
+            "3809683432208182946118417827817387690707578737850483414137001893845729134323",
+        ),
+    ];
+
+    #[test]
+    fn bls12_377_keys_are_pinned() {
+        for (seed, master, index, child) in BLS12_377_VECTORS {
+            let seed = hex::decode(seed).unwrap();
+            let derived_master = derive_master_sk(&seed).unwrap();
+            assert_eq!(derived_master.to_biguint(), decimal(master));
+            let derived_child = derive_child_sk(&derived_master, index);
+            assert_eq!(derived_child.to_biguint(), decimal(child));
+        }
+    }
+
+    #[test]
+    fn a_path_walks_one_child_per_index() {
+        let seed = [7u8; MIN_SEED_LEN];
+        let master = derive_master_sk(&seed).unwrap();
+        assert_eq!(derive_sk_from_path(&seed, "m").unwrap(), master);
+
+        let by_hand = [12381, 3600, 0, 0]
+            .into_iter()
+            .fold(master, |key, index| derive_child_sk(&key, index));
+        let derived = derive_sk_from_path(&seed, "m/12381/3600/0/0").unwrap();
+        assert_eq!(derived, by_hand);
+        assert_ne!(
+            derived,
+            derive_sk_from_path(&seed, "m/12381/3600/1/0").unwrap()
+        );
+        assert_ne!(derived, Scalar::zero());
+
+        // The derived key is an ordinary private key.
+        let signature = G2Scheme::sign(&derived, b"hello").unwrap();
+        let mut public = <G2Scheme as crate::sig::Scheme>::Public::one();
+        public.mul(&derived);
+        G2Scheme::verify(&public, b"hello", &signature).unwrap();
+    }
+
+    #[test]
+    fn malformed_paths_are_rejected() {
+        assert_eq!(parse_path("m").unwrap(), Vec::<u32>::new());
+        assert_eq!(parse_path("m/0/4294967295").unwrap(), [0, u32::MAX]);
+
+        for path in [
+            "",
+            "M",
+            "/0",
+            "0/1",
+            "m/",
+            "m//1",
+            "m/1/",
+            "m/-1",
+            "m/+1",
+            "m/1'",
+            "m/ 1",
+            "m/4294967296",
+            "n/1",
+        ] {
+            assert!(
+                matches!(parse_path(path), Err(HdError::InvalidPath(_))),
+                "{path:?} parsed"
+            );
+        }
+    }
+
+    #[test]
+    fn a_short_seed_is_rejected() {
+        let seed = [7u8; MIN_SEED_LEN - 1];
+        assert!(matches!(
+            derive_master_sk(&seed),
+            Err(HdError::SeedTooShort(31))
+        ));
+        assert!(matches!(
+            derive_sk_from_path(&seed, "m/0"),
+            Err(HdError::SeedTooShort(31))
+        ));
+        assert!(derive_master_sk(&[7u8; MIN_SEED_LEN]).is_ok());
+    }
+}
@@ -0,0 +1,377 @@
+//! Timelock encryption: identity-based encryption to the rounds of a
+//! [beacon](crate::beacon).
+//!
+//! A beacon's signature on round `r` is the group private key times the hash
+//! of the round message, which in Boneh–Franklin IBE is exactly the decryption
+//! key for the identity `r`. So anyone can [`encrypt`] a payload to a group
+//! public key and a future round, and nobody, the group included, can
+//! [`decrypt`] it until a threshold of the group signs that round.
+//!
+//! ```
+//! use threshold_bls::{
+//!     beacon, ibe,
+//!     poly::{Idx, Poly},
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::{Scheme, Share},
+//! };
+//!
+//! let private_poly = Poly::<<SigScheme as Scheme>::Private>::new(1);
+//! let public_poly = private_poly.commit();
+//!
+//! // encrypt to round 10 of the group's beacon
+//! let ciphertext = ibe::encrypt::<SigScheme, _>(
+//!     public_poly.public_key(),
+//!     10,
+//!     b"sealed bid",
+//!     &mut rand::thread_rng(),
+//! )
+//! .unwrap();
+//!
+//! // ... and once the group has signed round 10, its signature decrypts it
+//! let partials = (0..2)
+//!     .map(|i| private_poly.eval(i as Idx))
+//!     .map(|eval| Share { index: eval.index, private: eval.value })
+//!     .map(|share| beacon::partial_sign::<SigScheme>(&share, 10, None).unwrap())
+//!     .collect::<Vec<_>>();
+//! let round = beacon::aggregate::<SigScheme>(&public_poly, 10, None, &partials).unwrap();
+//!
+//! let payload = ibe::decrypt::<SigScheme>(&round.signature, &ciphertext).unwrap();
+//! assert_eq!(payload, b"sealed bid");
+//! ```
+//!
+//! The scheme is Boneh–Franklin's `FullIdent`, with the Fujisaki–Okamoto
+//! transform making the ciphertext non-malleable: decryption recomputes the
+//! encryption randomness from the recovered payload and rejects a ciphertext
+//! that was not built from it. Rounds are identities only in
+//! [`Mode::Unchained`](crate::beacon::Mode::Unchained), since a chained round
+//! message depends on a signature nobody has yet.
+use crate::beacon;
+use crate::group::{Element, PairingCurve, Point, Scalar};
+use crate::serialization;
+use crate::sig::{G1Scheme, G2Scheme, SignatureScheme};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Domain separators for the hashes of the scheme, so that none of them can
+/// collide with another or with a hash computed for anything else.
+const KEY_DOMAIN: &[u8] = b"BLS-IBE-H2";
+const NONCE_DOMAIN: &[u8] = b"BLS-IBE-H3";
+const PAYLOAD_DOMAIN: &[u8] = b"BLS-IBE-H4";
+
+/// Bytes of the random value a ciphertext hides the payload's key in.
+pub const SIGMA_LEN: usize = 32;
+
+/// A scheme whose keys can encrypt to the messages its signatures sign.
+pub trait IbeScheme: SignatureScheme {
+    /// Pairs a point of the public key's group with one of the signature's,
+    /// in whichever order the curve's pairing takes them, and serializes the
+    /// result.
+    fn pair(public: &Self::Public, signature: &Self::Signature) -> Vec<u8>;
+}
+
+impl<C> IbeScheme for G1Scheme<C>
+where
+    C: PairingCurve,
+    C::GT: Serialize,
+{
+    fn pair(public: &C::G1, signature: &C::G2) -> Vec<u8> {
+        bincode::serialize(&C::pair(public, signature)).expect("could not serialize")
+    }
+}
+
+impl<C> IbeScheme for G2Scheme<C>
+where
+    C: PairingCurve,
+    C::GT: Serialize,
+{
+    fn pair(public: &C::G2, signature: &C::G1) -> Vec<u8> {
+        bincode::serialize(&C::pair(signature, public)).expect("could not serialize")
+    }
+}
+
+/// A payload encrypted to a round.
+///
+/// Serialized with bincode, as everything else in this crate is, the fields
+/// follow one another in order: the round as a `u64`, `u` as a compressed
+/// point of the public key's group, the 32 bytes of `v`, and `w` behind a
+/// `u64` length. [`Ciphertext::to_bytes`] and [`Ciphertext::from_bytes`] do
+/// this.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ciphertext<P> {
+    /// The round whose signature decrypts this
+    pub round: u64,
+    /// The encryption randomness times the generator
+    pub u: P,
+    /// The random value `sigma`, masked with the hash of the pairing
+    pub v: [u8; SIGMA_LEN],
+    /// The payload, masked with the hash of `sigma`
+    pub w: Vec<u8>,
+}
+
+impl<P: Serialize + for<'a> Deserialize<'a>> Ciphertext<P> {
+    /// Serializes the ciphertext.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("could not serialize")
+    }
+
+    /// Deserializes a ciphertext produced by [`Ciphertext::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IbeError> {
+        Ok(serialization::deserialize(bytes)?)
+    }
+}
+
+/// Errors associated with timelock encryption
+#[derive(Debug, Error)]
+pub enum IbeError {
+    /// Raised when encrypting to the identity public key, for which the
+    /// pairing is 1 whatever the round and anyone could decrypt
+    #[error("public key is the identity element")]
+    InvalidPublicKey,
+
+    /// Raised when the round message cannot be hashed to the curve
+    #[error("could not hash to curve")]
+    HashingError,
+
+    /// Raised when no candidate hash was a valid nonzero scalar. Each
+    /// candidate is one with probability above one half, so this does not
+    /// happen in practice.
+    #[error("could not hash to a scalar")]
+    HashToScalarError,
+
+    /// Raised when decryption fails: the signature is not the round's, or the
+    /// ciphertext was not produced by [`encrypt`]. The two cannot be told
+    /// apart, and nothing of the payload is returned in either case.
+    #[error("could not decrypt the ciphertext")]
+    DecryptionError,
+
+    /// Raised when a ciphertext or signature cannot be deserialized
+    #[error("could not deserialize: {0}")]
+    BincodeError(#[from] bincode::Error),
+}
+
+/// Encrypts `msg` to `round` of the beacon run by the group holding `public`.
+/// The round's signature is needed to decrypt it; see [`decrypt`].
+pub fn encrypt<S: IbeScheme, R: RngCore>(
+    public: &S::Public,
+    round: u64,
+    msg: &[u8],
+    rng: &mut R,
+) -> Result<Ciphertext<S::Public>, IbeError> {
+    if public == &S::Public::zero() {
+        return Err(IbeError::InvalidPublicKey);
+    }
+    let identity = identity_point::<S>(round)?;
+
+    let mut sigma = [0u8; SIGMA_LEN];
+    rng.fill_bytes(&mut sigma);
+    let r = nonce::<S>(&sigma, msg)?;
+
+    let mut u = S::Public::one();
+    u.mul(&r);
+
+    // e(pub, Q)^r, computed as e(r·pub, Q) since the trait has no
+    // exponentiation in the target group
+    let mut shared = public.clone();
+    shared.mul(&r);
+    let v = xor(
+        &sigma,
+        &mask(KEY_DOMAIN, &S::pair(&shared, &identity), SIGMA_LEN),
+    )
+    .try_into()
+    .expect("mask is SIGMA_LEN bytes");
+    let w = xor(msg, &mask(PAYLOAD_DOMAIN, &sigma, msg.len()));
+
+    Ok(Ciphertext { round, u, v, w })
+}
+
+/// Decrypts `ciphertext` with the group's signature on its round, serialized
+/// as by [`SignatureScheme::sign`]: the `signature` of the round's
+/// [`Beacon`](crate::beacon::Beacon).
+///
+/// The signature is not verified first, and need not be: a signature that is
+/// not the round's fails decryption as a tampered ciphertext does.
+pub fn decrypt<S: IbeScheme>(
+    signature: &[u8],
+    ciphertext: &Ciphertext<S::Public>,
+) -> Result<Vec<u8>, IbeError> {
+    let signature: S::Signature = serialization::deserialize(signature)?;
+
+    let shared = S::pair(&ciphertext.u, &signature);
+    let sigma = xor(&ciphertext.v, &mask(KEY_DOMAIN, &shared, SIGMA_LEN));
+    let msg = xor(
+        &ciphertext.w,
+        &mask(PAYLOAD_DOMAIN, &sigma, ciphertext.w.len()),
+    );
+
+    // Fujisaki–Okamoto: only the sigma and payload `u` was built from decrypt
+    // it, so a ciphertext cannot be altered into a related one.
+    let r = nonce::<S>(&sigma, &msg)?;
+    let mut u = S::Public::one();
+    u.mul(&r);
+    if u != ciphertext.u {
+        return Err(IbeError::DecryptionError);
+    }
+
+    Ok(msg)
+}
+
+/// The identity of `round`: the point its signature signs.
+fn identity_point<S: IbeScheme>(round: u64) -> Result<S::Signature, IbeError> {
+    let mut point = S::Signature::zero();
+    point
+        .map(&beacon::round_message(round, None))
+        .map_err(|_| IbeError::HashingError)?;
+    Ok(point)
+}
+
+/// `H3`: the encryption randomness, derived from sigma and the payload so
+/// that decryption can recompute it.
+fn nonce<S: IbeScheme>(sigma: &[u8], msg: &[u8]) -> Result<S::Private, IbeError> {
+    // Try-and-increment, as for a child key's tweak: rejecting rather than
+    // reducing keeps the scalar uniform, and zero would make `u` the identity.
+    for counter in 0u8..=255 {
+        let hash = Sha256::new()
+            .chain_update(NONCE_DOMAIN)
+            .chain_update([counter])
+            .chain_update(sigma)
+            .chain_update(msg)
+            .finalize();
+        match S::Private::from_random_bytes(&hash) {
+            Some(r) if r != S::Private::zero() => return Ok(r),
+            _ => {}
+        }
+    }
+
+    Err(IbeError::HashToScalarError)
+}
+
+/// Expands `input` to `len` bytes with SHA-256 in counter mode.
+pub(crate) fn mask(domain: &[u8], input: &[u8], len: usize) -> Vec<u8> {
+    (0u64..)
+        .flat_map(|block| {
+            Sha256::new()
+                .chain_update(domain)
+                .chain_update(block.to_be_bytes())
+                .chain_update(input)
+                .finalize()
+        })
+        .take(len)
+        .collect()
+}
+
+pub(crate) fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(a, b)| a ^ b).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beacon::{aggregate, partial_sign};
+    use crate::poly::{Idx, Poly};
+    use crate::schemes::bls12_377::{G1Scheme, G2Scheme};
+    use crate::sig::{Scheme, Share};
+    use rand::thread_rng;
+
+    /// A 3-of-5 group's public polynomial and its signature on `round`.
+    fn beacon_signature<S: IbeScheme>(round: u64) -> (Poly<S::Public>, Vec<u8>) {
+        let private = Poly::<S::Private>::new(2);
+        let public = private.commit();
+        let partials = (0..5)
+            .map(|i| private.eval(i as Idx))
+            .map(|eval| Share {
+                index: eval.index,
+                private: eval.value,
+            })
+            .map(|share| partial_sign::<S>(&share, round, None).unwrap())
+            .collect::<Vec<_>>();
+        let beacon = aggregate::<S>(&public, round, None, &partials).unwrap();
+        (public, beacon.signature)
+    }
+
+    fn round_trip<S: IbeScheme>() {
+        let (public, signature) = beacon_signature::<S>(42);
+        for msg in [&b""[..], b"a sealed bid", &[0x5a; 100]] {
+            let ciphertext =
+                encrypt::<S, _>(public.public_key(), 42, msg, &mut thread_rng()).unwrap();
+            assert_eq!(ciphertext.w.len(), msg.len());
+
+            let bytes = ciphertext.to_bytes();
+            let decoded = Ciphertext::<S::Public>::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded, ciphertext);
+            assert_eq!(decrypt::<S>(&signature, &decoded).unwrap(), msg);
+        }
+    }
+
+    #[test]
+    fn round_trip_g1() {
+        round_trip::<G1Scheme>();
+    }
+
+    #[test]
+    fn round_trip_g2() {
+        round_trip::<G2Scheme>();
+    }
+
+    #[test]
+    fn another_rounds_signature_does_not_decrypt() {
+        let (public, _) = beacon_signature::<G2Scheme>(1);
+        let ciphertext =
+            encrypt::<G2Scheme, _>(public.public_key(), 2, b"not yet", &mut thread_rng()).unwrap();
+
+        let (_, other_group) = beacon_signature::<G2Scheme>(2);
+        assert!(matches!(
+            decrypt::<G2Scheme>(&other_group, &ciphertext),
+            Err(IbeError::DecryptionError)
+        ));
+    }
+
+    #[test]
+    fn a_tampered_ciphertext_is_rejected() {
+        let (public, signature) = beacon_signature::<G2Scheme>(7);
+        let ciphertext =
+            encrypt::<G2Scheme, _>(public.public_key(), 7, b"pay alice 10", &mut thread_rng())
+                .unwrap();
+
+        // flipping a payload bit flips the same bit of the plaintext, which
+        // is what the Fujisaki–Okamoto check is there to catch
+        let mut flipped = ciphertext.clone();
+        flipped.w[0] ^= 1;
+        assert!(matches!(
+            decrypt::<G2Scheme>(&signature, &flipped),
+            Err(IbeError::DecryptionError)
+        ));
+
+        let mut flipped = ciphertext;
+        flipped.v[0] ^= 1;
+        assert!(matches!(
+            decrypt::<G2Scheme>(&signature, &flipped),
+            Err(IbeError::DecryptionError)
+        ));
+    }
+
+    #[test]
+    fn the_identity_public_key_is_rejected() {
+        let identity = <G2Scheme as Scheme>::Public::zero();
+        assert!(matches!(
+            encrypt::<G2Scheme, _>(&identity, 1, b"anyone", &mut thread_rng()),
+            Err(IbeError::InvalidPublicKey)
+        ));
+    }
+
+    #[test]
+    fn the_serialized_layout_is_fixed() {
+        let (public, _) = beacon_signature::<G2Scheme>(3);
+        let ciphertext =
+            encrypt::<G2Scheme, _>(public.public_key(), 3, b"hello", &mut thread_rng()).unwrap();
+        let bytes = ciphertext.to_bytes();
+
+        // round ‖ 96-byte G2 point ‖ v ‖ u64 length ‖ payload
+        assert_eq!(bytes.len(), 8 + 96 + SIGMA_LEN + 8 + 5);
+        assert_eq!(bytes[..8], 3u64.to_le_bytes());
+        assert_eq!(bytes[8 + 96..8 + 96 + SIGMA_LEN], ciphertext.v);
+        assert_eq!(bytes[8 + 96 + SIGMA_LEN..][..8], 5u64.to_le_bytes());
+        assert!(Ciphertext::<<G2Scheme as Scheme>::Public>::from_bytes(&bytes[..50]).is_err());
+    }
+}
@@ -0,0 +1,746 @@
+//! Storing key shares, public polynomials and public keys under an id.
+//!
+//! [`KeyStore`] is what a signer needs from its storage: put and get a
+//! [`Share`], the public [`Poly`] of the group it belongs to, and plain public
+//! keys, each under a caller-chosen id, and list what is stored.
+//! [`FileKeyStore`] keeps them as JSON files in a directory, one per entry, and
+//! encrypts every share under a password.
+//!
+//! A share file follows [EIP-2335](https://eips.ethereum.org/EIPS/eip-2335):
+//! the password is stretched with scrypt into a 32-byte key, whose first half
+//! is an AES-128-CTR key for the share's secret scalar and whose second half,
+//! hashed with the ciphertext under SHA-256, is a checksum that tells a wrong
+//! password from a right one. Alongside the `crypto` section it records, in the
+//! clear, the share's index, the group's threshold, the share's public key and
+//! the [`fingerprint`] of the group's public polynomial, so that a share can be
+//! matched to its group without the password. The checksum covers those fields
+//! too, so that a file whose index or group was edited is refused when the
+//! share is read. It differs from EIP-2335 in those fields, and in using the
+//! password's bytes as given, without normalizing them. Public polynomials and
+//! keys are not secret, and are stored in the clear.
+//!
+//! ```
+//! use threshold_bls::{
+//!     keystore::{FileKeyStore, Kdf, KeyStore},
+//!     poly::{Idx, Poly},
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::{Scheme, Share},
+//! };
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! // a weak KDF keeps the example fast; the default is EIP-2335's
+//! let store = FileKeyStore::<SigScheme>::open(dir.path(), "correct horse")
+//!     .unwrap()
+//!     .with_kdf(Kdf { log_n: 10, r: 8, p: 1 });
+//!
+//! let private = Poly::<<SigScheme as Scheme>::Private>::new(2);
+//! let public = private.commit::<<SigScheme as Scheme>::Public>();
+//! let share = Share { index: 3, private: private.eval(3 as Idx).value };
+//!
+//! store.put_share("validator-3", &share, &public).unwrap();
+//! store.put_public_poly("validator-3", &public).unwrap();
+//! assert_eq!(store.get_share("validator-3").unwrap(), Some(share));
+//! ```
+use crate::group::Element;
+use crate::poly::{Idx, Poly};
+use crate::serialization;
+use crate::sig::{Scheme, Share};
+use aes::cipher::{KeyIvInit, StreamCipher};
+use rand::RngCore;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+use std::{
+    error::Error,
+    fs, io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// The `version` of the share files [`FileKeyStore`] writes and reads, that of
+/// the EIP-2335 format they follow.
+pub const VERSION: u32 = 4;
+
+/// Most memory, in bytes, a share file's scrypt parameters may ask for. A file
+/// asking for more is rejected before deriving anything from it, so that a
+/// planted file cannot exhaust the reader's memory.
+pub const MAX_KDF_MEMORY: u64 = 1 << 30;
+
+/// Most work a share file's scrypt parameters may ask for, as the bytes scrypt
+/// mixes, `128 * r * n * p`: sixteen times the default [`Kdf`]'s. The memory
+/// cap alone does not bound `p`, with which the time to derive grows.
+pub const MAX_KDF_WORK: u64 = 1 << 32;
+
+/// Storage for a signer's key material, by id.
+///
+/// A share, a public polynomial and a public key are separate entries, so one
+/// id can name all three of a group. Putting an entry replaces the entry of
+/// the same kind already under the id.
+pub trait KeyStore<S: Scheme> {
+    /// Error raised when the storage fails
+    type Error: Error + Send + Sync + 'static;
+
+    /// Stores a share of the group whose public polynomial is `public`
+    fn put_share(
+        &self,
+        id: &str,
+        share: &Share<S::Private>,
+        public: &Poly<S::Public>,
+    ) -> Result<(), Self::Error>;
+
+    /// The share stored under `id`, if any
+    fn get_share(&self, id: &str) -> Result<Option<Share<S::Private>>, Self::Error>;
+
+    /// Stores a group's public polynomial
+    fn put_public_poly(&self, id: &str, public: &Poly<S::Public>) -> Result<(), Self::Error>;
+
+    /// The public polynomial stored under `id`, if any
+    fn get_public_poly(&self, id: &str) -> Result<Option<Poly<S::Public>>, Self::Error>;
+
+    /// Stores a public key
+    fn put_public_key(&self, id: &str, public: &S::Public) -> Result<(), Self::Error>;
+
+    /// The public key stored under `id`, if any
+    fn get_public_key(&self, id: &str) -> Result<Option<S::Public>, Self::Error>;
+
+    /// Every entry stored, ordered by id and then kind
+    fn list(&self) -> Result<Vec<(String, Kind)>, Self::Error>;
+}
+
+/// The kinds of entry in a [`KeyStore`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Kind {
+    /// A [`Share`]
+    Share,
+    /// A public [`Poly`]
+    PublicPoly,
+    /// A public key
+    PublicKey,
+}
+
+impl Kind {
+    const ALL: [Kind; 3] = [Kind::Share, Kind::PublicPoly, Kind::PublicKey];
+
+    fn suffix(self) -> &'static str {
+        match self {
+            Kind::Share => ".share.json",
+            Kind::PublicPoly => ".poly.json",
+            Kind::PublicKey => ".key.json",
+        }
+    }
+}
+
+/// Errors associated with storing keys
+#[derive(Debug, Error)]
+pub enum KeystoreError {
+    /// Raised when an id is empty, starts with a dot, or has characters other
+    /// than ASCII letters, digits, `-`, `_` and `.`
+    #[error("invalid id {0:?}")]
+    InvalidId(String),
+
+    /// Raised when a share does not lie on the public polynomial it is stored
+    /// with, or a share file's secret does not match its public key
+    #[error("share {0} does not match its public polynomial")]
+    ShareMismatch(Idx),
+
+    /// Raised when a share file's checksum does not match: the password is
+    /// wrong, or the file was altered, its clear fields included
+    #[error("wrong password, or a corrupted keystore")]
+    WrongPassword,
+
+    /// Raised when a file is in a format or version this store does not read,
+    /// or asks for scrypt parameters out of bounds
+    #[error("unsupported keystore: {0}")]
+    Unsupported(String),
+
+    /// Raised when the file system fails
+    #[error(transparent)]
+    IoError(#[from] io::Error),
+
+    /// Raised when a file is not valid JSON for its kind
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+
+    /// Raised when a field is not valid hex
+    #[error(transparent)]
+    HexError(#[from] hex::FromHexError),
+
+    /// Raised when (de)serialization fails
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+}
+
+/// The scrypt parameters a [`FileKeyStore`] encrypts shares with. The default
+/// is EIP-2335's: `n = 2^18`, `r = 8`, `p = 1`, which takes 256 MiB and about a
+/// second to derive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Kdf {
+    /// The base-2 logarithm of the cost parameter `n`
+    pub log_n: u8,
+    /// The block size
+    pub r: u32,
+    /// The parallelization parameter
+    pub p: u32,
+}
+
+impl Default for Kdf {
+    fn default() -> Self {
+        Self {
+            log_n: 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl Kdf {
+    fn params(&self) -> Result<scrypt::Params, KeystoreError> {
+        let unsupported = || KeystoreError::Unsupported(format!("scrypt parameters {self:?}"));
+        let memory = (128 * self.r as u128) << self.log_n.min(64);
+        if self.log_n >= 64
+            || memory > MAX_KDF_MEMORY as u128
+            || memory * self.p as u128 > MAX_KDF_WORK as u128
+        {
+            return Err(unsupported());
+        }
+        scrypt::Params::new(self.log_n, self.r, self.p, 32).map_err(|_| unsupported())
+    }
+}
+
+/// What a share file records in the clear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareInfo<P> {
+    /// The share's index
+    pub index: Idx,
+    /// The group's threshold
+    pub threshold: usize,
+    /// The [`fingerprint`] of the group's public polynomial
+    pub fingerprint: [u8; 32],
+    /// The share's public key, the public polynomial's evaluation at its index
+    pub public: P,
+}
+
+/// Identifies a public polynomial: the SHA-256 hash of its serialization.
+pub fn fingerprint<P: Element + Serialize>(public: &Poly<P>) -> [u8; 32] {
+    Sha256::digest(bincode::serialize(public).expect("could not serialize")).into()
+}
+
+/// A [`KeyStore`] in a directory, which encrypts shares under a password. See
+/// the [module documentation](self).
+pub struct FileKeyStore<S> {
+    dir: PathBuf,
+    password: Zeroizing<Vec<u8>>,
+    kdf: Kdf,
+    m: PhantomData<fn() -> S>,
+}
+
+impl<S: Scheme> FileKeyStore<S> {
+    /// A store in `dir`, which is created if it does not exist, encrypting
+    /// shares under `password` with the default [`Kdf`].
+    pub fn open(dir: impl AsRef<Path>, password: impl AsRef<[u8]>) -> Result<Self, KeystoreError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            password: Zeroizing::new(password.as_ref().to_vec()),
+            kdf: Kdf::default(),
+            m: PhantomData,
+        })
+    }
+
+    /// Encrypts the shares put from now on with `kdf`. Shares are read with
+    /// the parameters recorded in their files, whatever these are.
+    pub fn with_kdf(mut self, kdf: Kdf) -> Self {
+        self.kdf = kdf;
+        self
+    }
+
+    /// What the share file under `id` records in the clear, read without the
+    /// password.
+    pub fn share_info(&self, id: &str) -> Result<Option<ShareInfo<S::Public>>, KeystoreError> {
+        let Some(file) = self.read::<ShareFile>(id, Kind::Share)? else {
+            return Ok(None);
+        };
+        Ok(Some(file.info()?))
+    }
+
+    fn path(&self, id: &str, kind: Kind) -> Result<PathBuf, KeystoreError> {
+        let valid = !id.is_empty()
+            && !id.starts_with('.')
+            && id
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"-_.".contains(&b));
+        if !valid {
+            return Err(KeystoreError::InvalidId(id.to_string()));
+        }
+        Ok(self.dir.join(format!("{id}{}", kind.suffix())))
+    }
+
+    fn read<T: DeserializeOwned>(&self, id: &str, kind: Kind) -> Result<Option<T>, KeystoreError> {
+        match fs::read(self.path(id, kind)?) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Writes to a temporary file of its own and renames it over the entry, so
+    /// that a crash leaves the old entry or the new one, never half of one,
+    /// and concurrent writers do not share a temporary file. The temporary
+    /// file is readable only by its owner on Unix, and the directory is synced
+    /// after the rename so that the rename itself survives a crash.
+    fn write<T: Serialize>(&self, id: &str, kind: Kind, value: &T) -> Result<(), KeystoreError> {
+        use io::Write;
+
+        let path = self.path(id, kind)?;
+        let mut file = tempfile::NamedTempFile::new_in(&self.dir)?;
+        file.write_all(&serde_json::to_vec_pretty(value)?)?;
+        file.as_file().sync_all()?;
+        file.persist(&path).map_err(|err| err.error)?;
+        #[cfg(unix)]
+        fs::File::open(&self.dir)?.sync_all()?;
+        Ok(())
+    }
+}
+
+impl<S: Scheme> KeyStore<S> for FileKeyStore<S> {
+    type Error = KeystoreError;
+
+    fn put_share(
+        &self,
+        id: &str,
+        share: &Share<S::Private>,
+        public: &Poly<S::Public>,
+    ) -> Result<(), KeystoreError> {
+        let mut share_public = S::Public::one();
+        share_public.mul(&share.private);
+        if public.eval(share.index).value != share_public {
+            return Err(KeystoreError::ShareMismatch(share.index));
+        }
+
+        let mut salt = [0u8; 32];
+        let mut iv = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut iv);
+
+        let key = derive_key(&self.password, &self.kdf, &salt)?;
+        let mut message = Zeroizing::new(bincode::serialize(&share.private)?);
+        Aes128Ctr::new(key[..16].into(), (&iv).into()).apply_keystream(&mut message);
+
+        let mut file = ShareFile {
+            crypto: Crypto {
+                kdf: Module {
+                    function: "scrypt".to_string(),
+                    params: ScryptParams {
+                        dklen: 32,
+                        n: 1 << self.kdf.log_n,
+                        r: self.kdf.r,
+                        p: self.kdf.p,
+                        salt: hex::encode(salt),
+                    },
+                    message: String::new(),
+                },
+                checksum: Module {
+                    function: "sha256".to_string(),
+                    params: Empty {},
+                    message: String::new(),
+                },
+                cipher: Module {
+                    function: "aes-128-ctr".to_string(),
+                    params: CipherParams {
+                        iv: hex::encode(iv),
+                    },
+                    message: hex::encode(&message),
+                },
+            },
+            index: share.index,
+            threshold: public.degree() + 1,
+            public_fingerprint: hex::encode(fingerprint(public)),
+            pubkey: hex::encode(bincode::serialize(&share_public)?),
+            version: VERSION,
+        };
+        file.crypto.checksum.message = hex::encode(file.checksum(&key)?);
+        self.write(id, Kind::Share, &file)
+    }
+
+    fn get_share(&self, id: &str) -> Result<Option<Share<S::Private>>, KeystoreError> {
+        let Some(file) = self.read::<ShareFile>(id, Kind::Share)? else {
+            return Ok(None);
+        };
+        let info = file.info::<S::Public>()?;
+        let crypto = &file.crypto;
+        if crypto.kdf.function != "scrypt"
+            || crypto.checksum.function != "sha256"
+            || crypto.cipher.function != "aes-128-ctr"
+        {
+            return Err(KeystoreError::Unsupported(format!(
+                "{} with {}",
+                crypto.kdf.function, crypto.cipher.function
+            )));
+        }
+
+        let params = &crypto.kdf.params;
+        if params.dklen != 32 || !params.n.is_power_of_two() {
+            return Err(KeystoreError::Unsupported(format!(
+                "scrypt with n = {} and dklen = {}",
+                params.n, params.dklen
+            )));
+        }
+        let kdf = Kdf {
+            log_n: params.n.trailing_zeros() as u8,
+            r: params.r,
+            p: params.p,
+        };
+        let iv: [u8; 16] = hex::decode(&crypto.cipher.params.iv)?
+            .try_into()
+            .map_err(|_| KeystoreError::Unsupported("an IV that is not 16 bytes".to_string()))?;
+
+        let key = derive_key(&self.password, &kdf, &hex::decode(&params.salt)?)?;
+        let mut message = Zeroizing::new(hex::decode(&crypto.cipher.message)?);
+        let expected = hex::decode(&crypto.checksum.message)?;
+        if !bool::from(file.checksum(&key)?[..].ct_eq(&expected)) {
+            return Err(KeystoreError::WrongPassword);
+        }
+        Aes128Ctr::new(key[..16].into(), (&iv).into()).apply_keystream(&mut message);
+
+        let private: S::Private = serialization::deserialize(&message)?;
+        let mut public = S::Public::one();
+        public.mul(&private);
+        if public != info.public {
+            return Err(KeystoreError::ShareMismatch(info.index));
+        }
+        Ok(Some(Share {
+            index: info.index,
+            private,
+        }))
+    }
+
+    fn put_public_poly(&self, id: &str, public: &Poly<S::Public>) -> Result<(), KeystoreError> {
+        let file = PublicFile {
+            threshold: Some(public.degree() + 1),
+            fingerprint: Some(hex::encode(fingerprint(public))),
+            public: hex::encode(bincode::serialize(public)?),
+        };
+        self.write(id, Kind::PublicPoly, &file)
+    }
+
+    fn get_public_poly(&self, id: &str) -> Result<Option<Poly<S::Public>>, KeystoreError> {
+        self.read::<PublicFile>(id, Kind::PublicPoly)?
+            .map(|file| Ok(serialization::deserialize(&hex::decode(file.public)?)?))
+            .transpose()
+    }
+
+    fn put_public_key(&self, id: &str, public: &S::Public) -> Result<(), KeystoreError> {
+        let file = PublicFile {
+            threshold: None,
+            fingerprint: None,
+            public: hex::encode(bincode::serialize(public)?),
+        };
+        self.write(id, Kind::PublicKey, &file)
+    }
+
+    fn get_public_key(&self, id: &str) -> Result<Option<S::Public>, KeystoreError> {
+        self.read::<PublicFile>(id, Kind::PublicKey)?
+            .map(|file| Ok(serialization::deserialize(&hex::decode(file.public)?)?))
+            .transpose()
+    }
+
+    fn list(&self) -> Result<Vec<(String, Kind)>, KeystoreError> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let name = entry?.file_name();
+            let Some(name) = name.to_str() else {
+                continue;
+            };
+            for kind in Kind::ALL {
+                if let Some(id) = name.strip_suffix(kind.suffix())
+                    && self.path(id, kind).is_ok()
+                {
+                    entries.push((id.to_string(), kind));
+                }
+            }
+        }
+        entries.sort();
+        Ok(entries)
+    }
+}
+
+fn derive_key(
+    password: &[u8],
+    kdf: &Kdf,
+    salt: &[u8],
+) -> Result<Zeroizing<[u8; 32]>, KeystoreError> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    scrypt::scrypt(password, salt, &kdf.params()?, key.as_mut())
+        .expect("the output length is valid");
+    Ok(key)
+}
+
+#[derive(Serialize, Deserialize)]
+struct ShareFile {
+    crypto: Crypto,
+    index: Idx,
+    threshold: usize,
+    public_fingerprint: String,
+    pubkey: String,
+    version: u32,
+}
+
+impl ShareFile {
+    /// EIP-2335's checksum, SHA-256 of the key's second half and the
+    /// ciphertext, followed by the fields recorded in the clear
+    fn checksum(&self, key: &[u8; 32]) -> Result<[u8; 32], KeystoreError> {
+        let clear = (
+            self.index,
+            self.threshold as u64,
+            &self.public_fingerprint,
+            &self.pubkey,
+            self.version,
+        );
+        let mut hasher = Sha256::new();
+        hasher.update(&key[16..]);
+        hasher.update(hex::decode(&self.crypto.cipher.message)?);
+        hasher.update(bincode::serialize(&clear)?);
+        Ok(hasher.finalize().into())
+    }
+
+    fn info<P: DeserializeOwned>(&self) -> Result<ShareInfo<P>, KeystoreError> {
+        if self.version != VERSION {
+            return Err(KeystoreError::Unsupported(format!(
+                "version {}",
+                self.version
+            )));
+        }
+        Ok(ShareInfo {
+            index: self.index,
+            threshold: self.threshold,
+            fingerprint: hex::decode(&self.public_fingerprint)?
+                .try_into()
+                .map_err(|_| {
+                    KeystoreError::Unsupported("a fingerprint that is not 32 bytes".to_string())
+                })?,
+            public: serialization::deserialize(&hex::decode(&self.pubkey)?)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Crypto {
+    kdf: Module<ScryptParams>,
+    checksum: Module<Empty>,
+    cipher: Module<CipherParams>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Module<P> {
+    function: String,
+    params: P,
+    message: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ScryptParams {
+    dklen: usize,
+    n: u64,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Empty {}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PublicFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    threshold: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+    public: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{G1Scheme, G2Scheme, SignatureScheme},
+    };
+    use rand::thread_rng;
+
+    const FAST: Kdf = Kdf {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    fn group<S: Scheme>() -> (Share<S::Private>, Poly<S::Public>) {
+        let private = Poly::<S::Private>::new(2);
+        let share = Share {
+            index: 4,
+            private: private.eval(4).value,
+        };
+        (share, private.commit())
+    }
+
+    fn round_trip<S: SignatureScheme>() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileKeyStore::<S>::open(dir.path(), "password")
+            .unwrap()
+            .with_kdf(FAST);
+        let (share, public) = group::<S>();
+        let (_, key) = S::keypair(&mut thread_rng());
+
+        store.put_share("group", &share, &public).unwrap();
+        store.put_public_poly("group", &public).unwrap();
+        store.put_public_key("other", &key).unwrap();
+
+        assert_eq!(store.get_share("group").unwrap(), Some(share.clone()));
+        assert_eq!(
+            store.get_public_poly("group").unwrap(),
+            Some(public.clone())
+        );
+        assert_eq!(store.get_public_key("other").unwrap(), Some(key));
+        assert_eq!(store.get_share("other").unwrap(), None);
+        assert_eq!(store.get_public_key("group").unwrap(), None);
+        assert_eq!(
+            store.list().unwrap(),
+            vec![
+                ("group".to_string(), Kind::Share),
+                ("group".to_string(), Kind::PublicPoly),
+                ("other".to_string(), Kind::PublicKey),
+            ]
+        );
+
+        // no temporary file is left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+
+        let info = store.share_info("group").unwrap().unwrap();
+        assert_eq!((info.index, info.threshold), (4, 3));
+        assert_eq!(info.fingerprint, fingerprint(&public));
+        assert_eq!(info.public, public.eval(4).value);
+
+        // another store over the same directory reads them back
+        let reopened = FileKeyStore::<S>::open(dir.path(), "password").unwrap();
+        assert_eq!(reopened.get_share("group").unwrap(), Some(share));
+    }
+
+    #[test]
+    fn round_trip_g1() {
+        round_trip::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn round_trip_g2() {
+        round_trip::<G2Scheme<PCurve>>();
+    }
+
+    type S = G2Scheme<PCurve>;
+
+    #[test]
+    fn a_wrong_password_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let (share, public) = group::<S>();
+        FileKeyStore::<S>::open(dir.path(), "password")
+            .unwrap()
+            .with_kdf(FAST)
+            .put_share("group", &share, &public)
+            .unwrap();
+
+        let store = FileKeyStore::<S>::open(dir.path(), "passw0rd").unwrap();
+        assert!(matches!(
+            store.get_share("group"),
+            Err(KeystoreError::WrongPassword)
+        ));
+        // the clear fields need no password
+        assert_eq!(store.share_info("group").unwrap().unwrap().index, 4);
+    }
+
+    #[test]
+    fn a_share_off_its_polynomial_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileKeyStore::<S>::open(dir.path(), "password")
+            .unwrap()
+            .with_kdf(FAST);
+        let (mut share, public) = group::<S>();
+        share.index = 5;
+        assert!(matches!(
+            store.put_share("group", &share, &public),
+            Err(KeystoreError::ShareMismatch(5))
+        ));
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn ids_cannot_escape_the_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileKeyStore::<S>::open(dir.path(), "password").unwrap();
+        let (_, public) = group::<S>();
+        for id in ["", "../group", "a/b", ".hidden", "a b"] {
+            assert!(matches!(
+                store.put_public_poly(id, &public),
+                Err(KeystoreError::InvalidId(_))
+            ));
+        }
+        store.put_public_poly("v1.group-a_3", &public).unwrap();
+    }
+
+    #[test]
+    fn the_file_is_eip2335_shaped() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileKeyStore::<S>::open(dir.path(), "password")
+            .unwrap()
+            .with_kdf(FAST);
+        let (share, public) = group::<S>();
+        store.put_share("group", &share, &public).unwrap();
+
+        let path = dir.path().join("group.share.json");
+        let json: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(json["version"], 4);
+        assert_eq!(json["index"], 4);
+        assert_eq!(json["threshold"], 3);
+        assert_eq!(json["crypto"]["kdf"]["function"], "scrypt");
+        assert_eq!(json["crypto"]["kdf"]["params"]["n"], 16);
+        assert_eq!(json["crypto"]["checksum"]["function"], "sha256");
+        assert_eq!(json["crypto"]["cipher"]["function"], "aes-128-ctr");
+        let secret = hex::encode(bincode::serialize(&share.private).unwrap());
+        assert!(!fs::read_to_string(&path).unwrap().contains(&secret));
+
+        // the clear fields are covered by the checksum
+        let other = hex::encode(fingerprint(&group::<S>().1));
+        for (field, value) in [
+            ("index", 5.into()),
+            ("threshold", 2.into()),
+            ("public_fingerprint", other.into()),
+        ] {
+            let mut edited = json.clone();
+            edited[field] = value;
+            fs::write(&path, serde_json::to_vec(&edited).unwrap()).unwrap();
+            assert!(matches!(
+                store.get_share("group"),
+                Err(KeystoreError::WrongPassword)
+            ));
+        }
+
+        // a file asking for more memory or work than allowed is not derived
+        // from
+        for (param, value) in [("n", 1u64 << 40), ("p", u32::MAX as u64)] {
+            let mut edited = json.clone();
+            edited["crypto"]["kdf"]["params"][param] = value.into();
+            fs::write(&path, serde_json::to_vec(&edited).unwrap()).unwrap();
+            assert!(matches!(
+                store.get_share("group"),
+                Err(KeystoreError::Unsupported(_))
+            ));
+        }
+    }
+}
@@ -149,21 +149,61 @@
 //! ## Curves
 //!
 //! `BLS12-377` is the only curve implemented, in [`curve::bls12377`]; the schemes
-//! instantiated over it are in [`schemes::bls12_377`]. The crate has no Cargo
-//! features and is not published to crates.io, so consumers depend on it by git
-//! revision.
+//! instantiated over it are in [`schemes::bls12_377`]. The crate is not published
+//! to crates.io, so consumers depend on it by git revision.
+//!
+//! ## Features
+//!
+//! `coordinator` adds the [`coordinator`](coordinator/index.html) module, which
+//! collects partial signatures from remote signers on a Tokio runtime. It is off
+//! by default, so the crate does not otherwise depend on an async runtime.
+
+/// A drand-style threshold randomness beacon.
+pub mod beacon;
+
+/// Collecting blind partial signatures from remote signers, with timeouts and
+/// retries.
+#[cfg(feature = "coordinator")]
+pub mod coordinator;
 
 /// Curve implementations for the traits defined in the [`group`](group/index.html) module.
 pub mod curve;
 
+/// Non-interactive proofs that two points share a discrete logarithm.
+pub mod dleq;
+
+/// Threshold encryption to a group's public key, decrypted with its signing shares.
+pub mod elgamal;
+
+/// Enrolling a new member at a fresh index without changing the group's key.
+pub mod enrol;
+
 /// Definitions of generic traits with scalars of prime fields and points on elliptic curves.
 pub mod group;
 
+/// Hierarchical deterministic derivation of BLS12-377 private keys from a seed.
+pub mod hd;
+
+/// Timelock encryption to the rounds of a beacon, by identity-based encryption.
+pub mod ibe;
+
+/// Storing key shares and public polynomials, with shares encrypted on disk.
+pub mod keystore;
+
+/// Backing up private keys and shares as checksummed word lists.
+pub mod mnemonic;
+
+/// An oblivious pseudorandom function from blind threshold signatures.
+pub mod oprf;
+
 /// Implementation of a polynomial suitable to be used for secret sharing schemes.
 /// It can evaluate and interpolate private and public shares to their
 /// corresponding polynomial.
 pub mod poly;
 
+/// Repairing a member's lost share with the help of a threshold of the others.
+pub mod repair;
+
 /// Bounded bincode (de)serialization helpers that cap input size to prevent
 /// OOM from attacker-crafted length prefixes.
 pub mod serialization;
@@ -171,6 +211,9 @@ pub mod serialization;
 /// BLS Signature implementations. Supports blind and threshold signatures.
 pub mod sig;
 
+/// Unlinkable single-use tokens issued with blind signatures.
+pub mod token;
+
 /// Pre-instantiated signature schemes for each curve
 pub mod schemes {
     use crate::sig::{G1Scheme, G2Scheme};
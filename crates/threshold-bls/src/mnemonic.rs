@@ -0,0 +1,388 @@
+//! Backing up a private key or a share as a list of words.
+//!
+//! [`private_key_to_mnemonic`] and [`share_to_mnemonic`] write a key or a share
+//! as words from the BIP-39 English word list, for an operator to copy onto
+//! paper, and [`private_key_from_mnemonic`] and [`share_from_mnemonic`] read
+//! them back. A private key takes 29 words, a share 32.
+//!
+//! The words encode a version byte, the scheme's [`MnemonicScheme::SCHEME_ID`],
+//! a byte telling a key from a share, the share's big-endian `u32` index if it
+//! is one, and the secret scalar, followed by a checksum: the leading bits of
+//! `SHA-256(DST || payload)`, with [`CHECKSUM_DST`] as the domain tag, as many
+//! as fill the last word, and at least 32. Each word carries 11 bits. A word
+//! copied wrong or out of order fails the checksum, and a key backed up under
+//! one scheme is refused by another, rather than restored as a key whose public
+//! key and signatures are in the wrong group. It is not a BIP-39 mnemonic, and a
+//! BIP-39 wallet will not read it.
+//!
+//! ```
+//! use threshold_bls::{
+//!     mnemonic,
+//!     schemes::bls12_377::{G1Scheme, G2Scheme},
+//!     sig::Scheme,
+//! };
+//!
+//! let (private, _) = G2Scheme::keypair(&mut rand::thread_rng());
+//! let words = mnemonic::private_key_to_mnemonic::<G2Scheme>(&private);
+//! assert_eq!(words.split(' ').count(), 29);
+//!
+//! assert_eq!(mnemonic::private_key_from_mnemonic::<G2Scheme>(&words).unwrap(), private);
+//! assert!(mnemonic::private_key_from_mnemonic::<G1Scheme>(&words).is_err());
+//! ```
+use crate::curve::bls12377::PairingCurve;
+use crate::group::{Element, Scalar};
+use crate::poly::Idx;
+use crate::serialization;
+use crate::sig::{G1Scheme, G2Scheme, Scheme, Share};
+use bip39::Language;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// The version of the encoding the mnemonics are written in.
+pub const VERSION: u8 = 1;
+
+/// Domain tag hashed ahead of the payload into the checksum.
+pub const CHECKSUM_DST: &[u8] = b"threshold-bls-mnemonic-v1";
+
+/// Fewest checksum bits a mnemonic carries.
+const MIN_CHECKSUM_BITS: usize = 32;
+
+/// Bits each word carries, its index in a list of 2048.
+const BITS_PER_WORD: usize = 11;
+
+/// A scheme whose keys can be backed up as a mnemonic.
+///
+/// Its id is written into every mnemonic, so that a key is only restored under
+/// the scheme it was backed up from. It has to be unique among the schemes a
+/// deployment uses, and can never change once mnemonics are written with it.
+pub trait MnemonicScheme: Scheme {
+    /// The scheme's id in a mnemonic
+    const SCHEME_ID: u8;
+}
+
+impl MnemonicScheme for G2Scheme<PairingCurve> {
+    const SCHEME_ID: u8 = 1;
+}
+
+impl MnemonicScheme for G1Scheme<PairingCurve> {
+    const SCHEME_ID: u8 = 2;
+}
+
+/// What a mnemonic backs up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A private key
+    PrivateKey = 0,
+    /// A share, with its index
+    Share = 1,
+}
+
+/// Errors associated with reading a mnemonic
+#[derive(Debug, Error)]
+pub enum MnemonicError {
+    /// Raised when a word is not in the word list
+    #[error("{0:?} is not in the word list")]
+    UnknownWord(String),
+
+    /// Raised when the number of words is not that of any mnemonic
+    #[error("no mnemonic is {0} words long")]
+    WrongLength(usize),
+
+    /// Raised when the checksum does not match, as when a word was copied
+    /// wrong or two were swapped
+    #[error("the checksum does not match")]
+    InvalidChecksum,
+
+    /// Raised when the mnemonic was written by another version of the encoding
+    #[error("unsupported mnemonic version {0}")]
+    UnsupportedVersion(u8),
+
+    /// Raised when the mnemonic was written for another scheme
+    #[error("the mnemonic is for scheme {found}, not scheme {expected}")]
+    WrongScheme {
+        /// The id of the scheme it was read as
+        expected: u8,
+        /// The id of the scheme it was written for
+        found: u8,
+    },
+
+    /// Raised when the mnemonic backs up a share where a private key was
+    /// expected, or the reverse
+    #[error("the mnemonic is for a {found:?}, not a {expected:?}")]
+    WrongKind {
+        /// What it was read as
+        expected: Kind,
+        /// What it backs up
+        found: Kind,
+    },
+
+    /// Raised when the secret does not decode as a scalar
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+}
+
+/// Writes a private key as a mnemonic.
+pub fn private_key_to_mnemonic<S: MnemonicScheme>(private: &S::Private) -> String {
+    encode(&payload::<S>(Kind::PrivateKey, None, private))
+}
+
+/// Reads back a private key written by [`private_key_to_mnemonic`] under the
+/// same scheme.
+pub fn private_key_from_mnemonic<S: MnemonicScheme>(
+    mnemonic: &str,
+) -> Result<S::Private, MnemonicError> {
+    let (_, private) = decode::<S>(mnemonic, Kind::PrivateKey)?;
+    Ok(private)
+}
+
+/// Writes a share, index included, as a mnemonic.
+pub fn share_to_mnemonic<S: MnemonicScheme>(share: &Share<S::Private>) -> String {
+    encode(&payload::<S>(
+        Kind::Share,
+        Some(share.index),
+        &share.private,
+    ))
+}
+
+/// Reads back a share written by [`share_to_mnemonic`] under the same scheme.
+pub fn share_from_mnemonic<S: MnemonicScheme>(
+    mnemonic: &str,
+) -> Result<Share<S::Private>, MnemonicError> {
+    let (index, private) = decode::<S>(mnemonic, Kind::Share)?;
+    Ok(Share {
+        index: index.expect("a share's payload has an index"),
+        private,
+    })
+}
+
+fn payload<S: MnemonicScheme>(kind: Kind, index: Option<Idx>, private: &S::Private) -> Vec<u8> {
+    let mut payload = vec![VERSION, S::SCHEME_ID, kind as u8];
+    if let Some(index) = index {
+        payload.extend_from_slice(&index.to_be_bytes());
+    }
+    payload.extend(bincode::serialize(private).expect("could not serialize"));
+    payload
+}
+
+/// Bytes of payload a mnemonic of `kind` under `S` carries.
+fn payload_len<S: Scheme>(kind: Kind) -> usize {
+    let index = match kind {
+        Kind::PrivateKey => 0,
+        Kind::Share => std::mem::size_of::<Idx>(),
+    };
+    3 + index + S::Private::zero().serialized_size()
+}
+
+/// Words in the mnemonic of a payload of `len` bytes: enough for the payload
+/// and the shortest checksum, the checksum growing to fill the last word.
+fn word_count(len: usize) -> usize {
+    (len * 8 + MIN_CHECKSUM_BITS).div_ceil(BITS_PER_WORD)
+}
+
+fn checksum(payload: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(CHECKSUM_DST);
+    hasher.update(payload);
+    hasher.finalize().into()
+}
+
+fn bits(bytes: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| byte >> i & 1 == 1))
+}
+
+fn encode(payload: &[u8]) -> String {
+    let words = Language::English.word_list();
+    let bits = bits(payload)
+        .chain(bits(&checksum(payload)))
+        .take(word_count(payload.len()) * BITS_PER_WORD)
+        .collect::<Vec<_>>();
+    bits.chunks(BITS_PER_WORD)
+        .map(|chunk| words[chunk.iter().fold(0, |acc, &bit| acc << 1 | bit as usize)])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decode<S: MnemonicScheme>(
+    mnemonic: &str,
+    expected: Kind,
+) -> Result<(Option<Idx>, S::Private), MnemonicError> {
+    let indices = mnemonic
+        .split_whitespace()
+        .map(|word| {
+            Language::English
+                .find_word(&word.to_lowercase())
+                .ok_or_else(|| MnemonicError::UnknownWord(word.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // the length tells the kinds apart before anything is decoded, so that a
+    // share read as a key is reported as such rather than as a bad checksum
+    let kind = [Kind::PrivateKey, Kind::Share]
+        .into_iter()
+        .find(|&kind| word_count(payload_len::<S>(kind)) == indices.len())
+        .ok_or(MnemonicError::WrongLength(indices.len()))?;
+
+    let bits = indices
+        .iter()
+        .flat_map(|index| (0..BITS_PER_WORD).rev().map(move |i| index >> i & 1 == 1))
+        .collect::<Vec<_>>();
+    let (payload, sum) = bits.split_at(payload_len::<S>(kind) * 8);
+    let payload = payload
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, &bit| acc << 1 | bit as u8))
+        .collect::<Vec<_>>();
+    if !self::bits(&checksum(&payload))
+        .zip(sum)
+        .all(|(a, &b)| a == b)
+    {
+        return Err(MnemonicError::InvalidChecksum);
+    }
+
+    let (header, body) = payload.split_at(3);
+    if header[0] != VERSION {
+        return Err(MnemonicError::UnsupportedVersion(header[0]));
+    }
+    if header[1] != S::SCHEME_ID {
+        return Err(MnemonicError::WrongScheme {
+            expected: S::SCHEME_ID,
+            found: header[1],
+        });
+    }
+    if header[2] != kind as u8 {
+        // a payload that checksums but whose kind does not match its length
+        // was not written by this version of the encoding
+        return Err(MnemonicError::UnsupportedVersion(header[0]));
+    }
+    if kind != expected {
+        return Err(MnemonicError::WrongKind {
+            expected,
+            found: kind,
+        });
+    }
+
+    let (index, secret) = match kind {
+        Kind::PrivateKey => (None, body),
+        Kind::Share => {
+            let (index, secret) = body.split_at(std::mem::size_of::<Idx>());
+            (
+                Some(Idx::from_be_bytes(index.try_into().expect("4 bytes"))),
+                secret,
+            )
+        }
+    };
+    Ok((index, serialization::deserialize(secret)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poly::Poly;
+    use rand::thread_rng;
+
+    type G2 = G2Scheme<PairingCurve>;
+    type G1 = G1Scheme<PairingCurve>;
+
+    #[test]
+    fn keys_and_shares_round_trip() {
+        let (private, _) = G2::keypair(&mut thread_rng());
+        let words = private_key_to_mnemonic::<G2>(&private);
+        assert_eq!(words.split(' ').count(), 29);
+        assert_eq!(private_key_from_mnemonic::<G2>(&words).unwrap(), private);
+
+        let poly = Poly::<<G1 as Scheme>::Private>::new(2);
+        let share = Share {
+            index: 0xdead_beef,
+            private: poly.eval(7).value,
+        };
+        let words = share_to_mnemonic::<G1>(&share);
+        assert_eq!(words.split(' ').count(), 32);
+        assert_eq!(share_from_mnemonic::<G1>(&words).unwrap(), share);
+
+        // case and spacing do not matter
+        let sloppy = format!("  {}\n", words.to_uppercase().replace(' ', "\t "));
+        assert_eq!(share_from_mnemonic::<G1>(&sloppy).unwrap(), share);
+    }
+
+    // The private key 1 under `G2Scheme`, computed independently from the
+    // format described in the module documentation.
+    const EXPECTED_KEY_MNEMONIC: &str = "absurd amount able abandon abandon abandon abandon \
+        abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon abandon abandon abandon abandon affair draw public nothing";
+
+    #[test]
+    fn the_encoding_is_pinned() {
+        let mut private = <G2 as Scheme>::Private::zero();
+        private.set_int(1);
+        let words = private_key_to_mnemonic::<G2>(&private);
+        assert_eq!(
+            words, EXPECTED_KEY_MNEMONIC,
+            "the mnemonic encoding changed, and old backups would no longer restore"
+        );
+    }
+
+    #[test]
+    fn a_mistyped_or_swapped_word_is_caught() {
+        let (private, _) = G2::keypair(&mut thread_rng());
+        let words = private_key_to_mnemonic::<G2>(&private);
+        let mut list = words.split(' ').collect::<Vec<_>>();
+
+        let original = list[10];
+        list[10] = if original == "abandon" {
+            "ability"
+        } else {
+            "abandon"
+        };
+        assert!(matches!(
+            private_key_from_mnemonic::<G2>(&list.join(" ")),
+            Err(MnemonicError::InvalidChecksum)
+        ));
+
+        list[10] = original;
+        let j = (1..list.len()).find(|&j| list[j] != list[0]).unwrap();
+        list.swap(0, j);
+        assert!(matches!(
+            private_key_from_mnemonic::<G2>(&list.join(" ")),
+            Err(MnemonicError::InvalidChecksum)
+        ));
+
+        assert!(matches!(
+            private_key_from_mnemonic::<G2>(&words.replacen(list[1], "bitcoinz", 1)),
+            Err(MnemonicError::UnknownWord(word)) if word == "bitcoinz"
+        ));
+        assert!(matches!(
+            private_key_from_mnemonic::<G2>(&list[1..].join(" ")),
+            Err(MnemonicError::WrongLength(28))
+        ));
+    }
+
+    #[test]
+    fn the_scheme_and_kind_are_checked() {
+        let (private, _) = G2::keypair(&mut thread_rng());
+        let key = private_key_to_mnemonic::<G2>(&private);
+        assert!(matches!(
+            private_key_from_mnemonic::<G1>(&key),
+            Err(MnemonicError::WrongScheme {
+                expected: 2,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            share_from_mnemonic::<G2>(&key),
+            Err(MnemonicError::WrongKind {
+                expected: Kind::Share,
+                found: Kind::PrivateKey
+            })
+        ));
+
+        let share = share_to_mnemonic::<G2>(&Share { index: 1, private });
+        assert!(matches!(
+            private_key_from_mnemonic::<G2>(&share),
+            Err(MnemonicError::WrongKind {
+                expected: Kind::PrivateKey,
+                found: Kind::Share
+            })
+        ));
+    }
+}
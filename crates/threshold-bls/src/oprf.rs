@@ -0,0 +1,215 @@
+//! An oblivious pseudorandom function from blind threshold signatures.
+//!
+//! A client blinds its input with [`BlindScheme::blind_msg`], the signers
+//! evaluate it with [`BlindThresholdScheme::sign_blind_partial`], and the
+//! client unblinds the aggregated signature. The signature is a deterministic
+//! function of the group key and the input that the signers never saw, so it
+//! is already a PRF output; [`finalize`] turns it into bytes, and is the one
+//! definition of that step, so that every client derives the same output from
+//! the same signature.
+//!
+//! The output is `SHA-256(DST || len(input) || input || len(signature) ||
+//! signature)`, with each length a big-endian `u64` and [`FINALIZE_DST`] as the
+//! domain tag. The signature is checked against the group's public key first:
+//! an output from a signature that does not verify is not the PRF's output, and
+//! a misbehaving signer could otherwise steer it. What is hashed is the
+//! signature point's canonical encoding, not the bytes it was given as, so
+//! bytes trailing a signature change neither whether it verifies nor the
+//! output.
+//!
+//! ```
+//! use threshold_bls::{
+//!     oprf,
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::{BlindScheme, Scheme},
+//! };
+//!
+//! let rng = &mut rand::thread_rng();
+//! let (private, public) = SigScheme::keypair(rng);
+//!
+//! // the client blinds its input; the server signs without learning it
+//! let (token, blinded) = SigScheme::blind_msg(b"password", rng).unwrap();
+//! let blind_signature = SigScheme::blind_sign(&private, &blinded).unwrap();
+//!
+//! let pepper = oprf::unblind_finalize::<SigScheme>(&public, &token, b"password", &blind_signature)
+//!     .unwrap();
+//! assert_eq!(pepper.len(), 32);
+//! ```
+//!
+//! [`BlindThresholdScheme::sign_blind_partial`]: crate::sig::BlindThresholdScheme::sign_blind_partial
+use crate::serialization;
+use crate::sig::{BlindScheme, SignatureScheme};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Domain tag hashed ahead of the input and signature in [`finalize`], so that
+/// the output cannot collide with a hash computed for anything else.
+pub const FINALIZE_DST: &[u8] = b"threshold-bls-OPRF-v1-Finalize";
+
+/// Errors associated with deriving an OPRF output
+#[derive(Debug, Error)]
+pub enum OprfError<S: BlindScheme + SignatureScheme> {
+    /// Raised when the blind signature cannot be unblinded
+    #[error("could not unblind the signature: {0}")]
+    UnblindError(<S as BlindScheme>::Error),
+
+    /// Raised when the signature does not verify on the input under the
+    /// group's public key
+    #[error("invalid signature on the input: {0}")]
+    SignatureError(<S as SignatureScheme>::Error),
+
+    /// Raised when the signature cannot be deserialized
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+}
+
+/// Derives the OPRF output for `input` from the group's unblinded `signature`
+/// on it, after verifying the signature under `public`.
+pub fn finalize<S: BlindScheme + SignatureScheme>(
+    public: &S::Public,
+    input: &[u8],
+    signature: &[u8],
+) -> Result<[u8; 32], OprfError<S>> {
+    let signature: S::Signature = serialization::deserialize(signature)?;
+    let signature = bincode::serialize(&signature)?;
+    S::verify(public, input, &signature).map_err(OprfError::SignatureError)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(FINALIZE_DST);
+    hasher.update((input.len() as u64).to_be_bytes());
+    hasher.update(input);
+    hasher.update((signature.len() as u64).to_be_bytes());
+    hasher.update(signature);
+    Ok(hasher.finalize().into())
+}
+
+/// Unblinds the group's blind signature on the blinded `input` with the token
+/// from blinding it, and derives the output from it as [`finalize`] does.
+pub fn unblind_finalize<S: BlindScheme + SignatureScheme>(
+    public: &S::Public,
+    token: &S::Token,
+    input: &[u8],
+    blinded_signature: &[u8],
+) -> Result<[u8; 32], OprfError<S>> {
+    let signature = S::unblind_sig(token, blinded_signature).map_err(OprfError::UnblindError)?;
+    finalize::<S>(public, input, &signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{
+            BlindThresholdScheme, Scheme, Share, ThresholdScheme,
+            bls::{G1Scheme, G2Scheme},
+            shares,
+        },
+    };
+    use rand::thread_rng;
+
+    fn threshold_evaluation<S>()
+    where
+        S: BlindThresholdScheme + ThresholdScheme + SignatureScheme,
+    {
+        let (shares, public) = shares::<S>(5, 3);
+
+        let input = b"user@example.com";
+        let evaluate = |shares: &[Share<S::Private>]| {
+            let (token, blinded) = S::blind_msg(input, &mut thread_rng()).unwrap();
+            let partials = shares
+                .iter()
+                .map(|share| S::sign_blind_partial(share, &blinded).unwrap())
+                .collect::<Vec<_>>();
+            let blind_signature = S::aggregate(&public, &partials).unwrap();
+            unblind_finalize::<S>(public.public_key(), &token, input, &blind_signature).unwrap()
+        };
+
+        // fresh blinding and another quorum, same output
+        let output = evaluate(&shares[..3]);
+        assert_eq!(output, evaluate(&shares[2..]));
+
+        // and the same output as finalizing the plain signature
+        let signature = S::aggregate(
+            &public,
+            &shares
+                .iter()
+                .map(|share| S::partial_sign(share, input).unwrap())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        assert_eq!(
+            output,
+            finalize::<S>(public.public_key(), input, &signature).unwrap()
+        );
+        assert_ne!(
+            output,
+            finalize::<S>(
+                public.public_key(),
+                b"other",
+                &S::aggregate(
+                    &public,
+                    &shares
+                        .iter()
+                        .map(|share| S::partial_sign(share, b"other").unwrap())
+                        .collect::<Vec<_>>(),
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn threshold_evaluation_g1() {
+        threshold_evaluation::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn threshold_evaluation_g2() {
+        threshold_evaluation::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn an_invalid_signature_has_no_output() {
+        type S = G2Scheme<PCurve>;
+        let rng = &mut thread_rng();
+        let (private, public) = S::keypair(rng);
+        let (_, other) = S::keypair(rng);
+
+        let signature = S::sign(&private, b"input").unwrap();
+        assert!(matches!(
+            finalize::<S>(&other, b"input", &signature),
+            Err(OprfError::SignatureError(_))
+        ));
+        assert!(matches!(
+            finalize::<S>(&public, b"another input", &signature),
+            Err(OprfError::SignatureError(_))
+        ));
+
+        // trailing bytes are not part of the signature, or of the output
+        let mut padded = signature.clone();
+        padded.push(0);
+        assert_eq!(
+            finalize::<S>(&public, b"input", &padded).unwrap(),
+            finalize::<S>(&public, b"input", &signature).unwrap()
+        );
+        assert!(matches!(
+            finalize::<S>(&public, b"input", &signature[..signature.len() - 1]),
+            Err(OprfError::BincodeError(_))
+        ));
+
+        // a token that did not blind the input unblinds to a wrong signature
+        let (_, blinded) = S::blind_msg(b"input", rng).unwrap();
+        let (wrong_token, _) = S::blind_msg(b"input", rng).unwrap();
+        let blind_signature = S::blind_sign(&private, &blinded).unwrap();
+        assert!(matches!(
+            unblind_finalize::<S>(&public, &wrong_token, b"input", &blind_signature),
+            Err(OprfError::SignatureError(_))
+        ));
+        assert!(matches!(
+            unblind_finalize::<S>(&public, &wrong_token, b"input", b"not a point"),
+            Err(OprfError::UnblindError(_))
+        ));
+    }
+}
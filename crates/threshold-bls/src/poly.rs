@@ -53,6 +53,20 @@ impl<C: Element> Poly<C> {
         Self(coeffs)
     }
 
+    /// Returns a new polynomial of the given degree whose free coefficient is
+    /// `secret` and whose other coefficients are sampled at random from the
+    /// given RNG.
+    ///
+    /// Sharing it splits an existing secret rather than a fresh one: the
+    /// polynomial evaluates to `secret` at zero, so a threshold of its shares
+    /// recovers it, while fewer reveal nothing about it.
+    pub fn new_with_secret_from<R: RngCore>(degree: usize, secret: C, rng: &mut R) -> Self {
+        let coeffs: Vec<C> = std::iter::once(secret)
+            .chain((0..degree).map(|_| C::rand(rng)))
+            .collect();
+        Self(coeffs)
+    }
+
     /// get returns the given coefficient at the requested index. It will panic
     /// if the index is out of range,i.e. `if i > self.degree()`.
     pub fn get(&self, i: Idx) -> C {
@@ -74,6 +88,14 @@ impl<C: Element> Poly<C> {
         Self::new_from(degree, &mut thread_rng())
     }
 
+    /// Returns a new polynomial of the given degree with `secret` as its free
+    /// coefficient and the others sampled at random. See
+    /// [`new_with_secret_from`](Self::new_with_secret_from).
+    pub fn new_with_secret(degree: usize, secret: C) -> Self {
+        use rand::prelude::*;
+        Self::new_with_secret_from(degree, secret, &mut thread_rng())
+    }
+
     /// Returns the zero polynomial: one coefficient, holding the group's zero
     /// element. A polynomial always has at least one coefficient, so this is the
     /// smallest one there is.
@@ -393,6 +415,20 @@ pub mod tests {
         assert_eq!(p.degree(), s);
     }
 
+    #[test]
+    fn poly_with_secret_recovers_it() {
+        let secret = Sc::rand(&mut thread_rng());
+        let p = Poly::<Sc>::new_with_secret(2, secret);
+        assert_eq!(p.degree(), 2);
+        assert_eq!(p.public_key(), &secret);
+
+        let shares = (0..3).map(|i| p.eval(i)).collect();
+        assert_eq!(Poly::recover(3, shares).unwrap(), secret);
+
+        // the higher coefficients are fresh each time
+        assert_ne!(Poly::<Sc>::new_with_secret(2, secret), p);
+    }
+
     /// Evaluating at the largest index must not collapse onto the constant
     /// term. `eval` shifts the index by one to avoid handing out the secret at
     /// x = 0, and that shift must hold across the whole index range.
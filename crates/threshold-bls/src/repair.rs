@@ -0,0 +1,499 @@
+//! Repairing a lost [`Share`] with the help of the rest of the group, without
+//! reconstructing the secret or resharing.
+//!
+//! A member who loses its share keeps its index, and the group's public
+//! polynomial still commits to the share at that index. Any threshold of the
+//! other members, the helpers, can recompute it for them:
+//!
+//! 1. Each helper weighs its share by its Lagrange coefficient at the lost
+//!    index and splits the result into random [`Contribution`]s, one for every
+//!    helper, itself included, with [`contribute`].
+//! 2. Each helper sums the contributions addressed to it into a
+//!    [`RepairShare`] with [`aggregate`], and sends it to the member being
+//!    repaired.
+//! 3. The member sums the repair shares into its share with [`recover`],
+//!    which checks it against the public polynomial.
+//!
+//! A contribution is uniformly random on its own, and a repair share is the
+//! sum of contributions from every helper, so no helper learns another's
+//! weighted share, and the repaired member learns only its own share. The
+//! contributions travel between helpers and the repair shares to the member
+//! over private channels; the protocol is Laing and Stinson's enrollment
+//! procedure for repairable threshold schemes. Run for an index nobody was
+//! dealt, it adds a member instead, which [`enrol`](crate::enrol) wraps.
+//!
+//! ```
+//! use threshold_bls::{
+//!     repair,
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::{self, Scheme},
+//! };
+//!
+//! let rng = &mut rand::thread_rng();
+//! let (private, _) = SigScheme::keypair(rng);
+//! let (shares, public_poly) = sig::deal::<SigScheme, _>(&private, 5, 3, rng).unwrap();
+//!
+//! // the member with index 1 loses its share, and members 0, 3 and 4 help
+//! let (lost, helpers) = (1, [0, 3, 4]);
+//! let contributions = helpers
+//!     .iter()
+//!     .map(|&i| {
+//!         repair::contribute::<SigScheme, _>(&public_poly, &shares[i as usize], lost, &helpers, rng)
+//!     })
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap()
+//!     .concat();
+//!
+//! // each helper sums what it was sent
+//! let repair_shares = helpers
+//!     .iter()
+//!     .map(|&helper| {
+//!         let received = contributions
+//!             .iter()
+//!             .filter(|contribution| contribution.to == helper)
+//!             .cloned()
+//!             .collect::<Vec<_>>();
+//!         repair::aggregate::<SigScheme>(helper, &helpers, &received)
+//!     })
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//!
+//! let share = repair::recover::<SigScheme>(&public_poly, lost, &helpers, &repair_shares).unwrap();
+//! assert_eq!(share, shares[1]);
+//! ```
+use crate::group::{Element, Scalar};
+use crate::poly::{Idx, Poly, PolyError};
+use crate::sig::{Scheme, Share, lagrange_at};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// A random part of a helper's weighted share, sent to one helper
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contribution<C> {
+    /// The index of the helper it is from
+    pub from: Idx,
+    /// The index of the helper it is for
+    pub to: Idx,
+    /// The part. The parts a helper sends sum to its share weighted by its
+    /// Lagrange coefficient at the lost index
+    pub value: C,
+}
+
+/// The sum of the contributions a helper received, sent to the member being
+/// repaired
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairShare<C> {
+    /// The index of the helper it is from
+    pub from: Idx,
+    /// The sum
+    pub value: C,
+}
+
+/// Errors associated with repairing a share
+#[derive(Debug, Error)]
+pub enum RepairError {
+    /// Raised when fewer helpers than the threshold are named. Fewer shares
+    /// than the threshold say nothing about the lost one
+    #[error("{helpers} helpers cannot repair a share of a threshold of {threshold}")]
+    NotEnoughHelpers {
+        /// The number of distinct helpers named
+        helpers: usize,
+        /// The threshold of the public polynomial
+        threshold: usize,
+    },
+
+    /// Raised when a helper is named twice
+    #[error("helper {0} is named more than once")]
+    DuplicateHelper(Idx),
+
+    /// Raised when the index being repaired is among the helpers
+    #[error("share {0} is the one being repaired and cannot help repair it")]
+    LostShareIsHelper(Idx),
+
+    /// Raised when a share, or a contribution or repair share, is from an
+    /// index that is not among the helpers
+    #[error("{0} is not among the helpers")]
+    NotAHelper(Idx),
+
+    /// Raised when a helper is handed a contribution addressed to another
+    #[error("the contribution from helper {from} is addressed to helper {to}")]
+    Misaddressed {
+        /// The helper it is from
+        from: Idx,
+        /// The helper it is for
+        to: Idx,
+    },
+
+    /// Raised when two contributions or repair shares are from the same helper
+    #[error("more than one contribution from helper {0}")]
+    DuplicateContribution(Idx),
+
+    /// Raised when a helper's contribution or repair share is missing
+    #[error("no contribution from helper {0}")]
+    MissingContribution(Idx),
+
+    /// Raised when a share is not the one the public polynomial commits to at
+    /// its index: a helper's own, or the repaired one, which means a helper
+    /// contributed wrongly
+    #[error("share {0} does not match the public polynomial")]
+    InvalidShare(Idx),
+
+    /// Raised when the Lagrange coefficient cannot be computed
+    #[error(transparent)]
+    PolyError(#[from] PolyError),
+}
+
+/// Computes a helper's contributions to repairing the share at `lost`, one for
+/// each of `helpers` in the order given. The one addressed to the helper
+/// itself is kept for [`aggregate`]; the others are sent to their helpers.
+///
+/// The helper's share is checked against `public` first, so that a helper
+/// whose own share is corrupt fails here rather than corrupting the repair.
+pub fn contribute<S: Scheme, R: RngCore>(
+    public: &Poly<S::Public>,
+    share: &Share<S::Private>,
+    lost: Idx,
+    helpers: &[Idx],
+    rng: &mut R,
+) -> Result<Vec<Contribution<S::Private>>, RepairError> {
+    check_helpers(public, lost, helpers)?;
+    if !helpers.contains(&share.index) {
+        return Err(RepairError::NotAHelper(share.index));
+    }
+    if !is_committed::<S>(public, share) {
+        return Err(RepairError::InvalidShare(share.index));
+    }
+
+    let mut weighted = lagrange_at_lost::<S::Private>(share.index, lost, helpers)?;
+    weighted.mul(&share.private);
+
+    // Every part but the last is random and the last makes up the sum, so
+    // any parts short of all of them are random.
+    let (&last, others) = helpers.split_last().expect("there is at least one helper");
+    let mut contributions = others
+        .iter()
+        .map(|&to| {
+            let value = S::Private::rand(rng);
+            weighted.sub(&value);
+            Contribution {
+                from: share.index,
+                to,
+                value,
+            }
+        })
+        .collect::<Vec<_>>();
+    contributions.push(Contribution {
+        from: share.index,
+        to: last,
+        value: weighted,
+    });
+
+    Ok(contributions)
+}
+
+/// Sums the contributions `helper` received, one from each of `helpers`, its
+/// own included, into its repair share.
+pub fn aggregate<S: Scheme>(
+    helper: Idx,
+    helpers: &[Idx],
+    contributions: &[Contribution<S::Private>],
+) -> Result<RepairShare<S::Private>, RepairError> {
+    if !helpers.contains(&helper) {
+        return Err(RepairError::NotAHelper(helper));
+    }
+    if let Some(contribution) = contributions.iter().find(|c| c.to != helper) {
+        return Err(RepairError::Misaddressed {
+            from: contribution.from,
+            to: contribution.to,
+        });
+    }
+    one_from_each(helpers, contributions.iter().map(|c| c.from))?;
+
+    let value = contributions
+        .iter()
+        .fold(S::Private::zero(), |mut sum, contribution| {
+            sum.add(&contribution.value);
+            sum
+        });
+
+    Ok(RepairShare {
+        from: helper,
+        value,
+    })
+}
+
+/// Sums the repair shares from each of `helpers` into the share at `lost`,
+/// and checks it against `public`.
+pub fn recover<S: Scheme>(
+    public: &Poly<S::Public>,
+    lost: Idx,
+    helpers: &[Idx],
+    repair_shares: &[RepairShare<S::Private>],
+) -> Result<Share<S::Private>, RepairError> {
+    check_helpers(public, lost, helpers)?;
+    one_from_each(helpers, repair_shares.iter().map(|r| r.from))?;
+
+    let private = repair_shares
+        .iter()
+        .fold(S::Private::zero(), |mut sum, repair_share| {
+            sum.add(&repair_share.value);
+            sum
+        });
+    let share = Share {
+        index: lost,
+        private,
+    };
+    if !is_committed::<S>(public, &share) {
+        return Err(RepairError::InvalidShare(lost));
+    }
+
+    Ok(share)
+}
+
+/// Refuses a set of helpers that cannot repair the share at `lost`.
+fn check_helpers<P: Element>(
+    public: &Poly<P>,
+    lost: Idx,
+    helpers: &[Idx],
+) -> Result<(), RepairError> {
+    let mut seen = BTreeSet::new();
+    for &helper in helpers {
+        if helper == lost {
+            return Err(RepairError::LostShareIsHelper(lost));
+        }
+        if !seen.insert(helper) {
+            return Err(RepairError::DuplicateHelper(helper));
+        }
+    }
+
+    let threshold = public.degree() + 1;
+    if helpers.len() < threshold {
+        return Err(RepairError::NotEnoughHelpers {
+            helpers: helpers.len(),
+            threshold,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that `from` names each of `helpers` exactly once.
+fn one_from_each(helpers: &[Idx], from: impl Iterator<Item = Idx>) -> Result<(), RepairError> {
+    let mut seen = BTreeSet::new();
+    for from in from {
+        if !helpers.contains(&from) {
+            return Err(RepairError::NotAHelper(from));
+        }
+        if !seen.insert(from) {
+            return Err(RepairError::DuplicateContribution(from));
+        }
+    }
+    match helpers.iter().find(|helper| !seen.contains(*helper)) {
+        Some(&missing) => Err(RepairError::MissingContribution(missing)),
+        None => Ok(()),
+    }
+}
+
+/// Whether `share` is the one `public` commits to at its index.
+fn is_committed<S: Scheme>(public: &Poly<S::Public>, share: &Share<S::Private>) -> bool {
+    let mut expected = S::Public::one();
+    expected.mul(&share.private);
+    expected == public.eval(share.index).value
+}
+
+/// The Lagrange coefficient of the helper at `index` over `helpers`, evaluated
+/// at the lost index rather than at zero, where it would weigh the helper's
+/// share towards the secret instead.
+fn lagrange_at_lost<C: Scalar<RHS = C>>(
+    index: Idx,
+    lost: Idx,
+    helpers: &[Idx],
+) -> Result<C, PolyError> {
+    let mut at = C::zero();
+    at.set_int(u64::from(lost) + 1);
+    lagrange_at(index, &at, helpers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{G1Scheme, G2Scheme, SignatureScheme},
+    };
+
+    type Contributions<S> = Vec<Contribution<<S as Scheme>::Private>>;
+
+    fn setup<S: SignatureScheme>(n: usize, t: usize) -> (Vec<Share<S::Private>>, Poly<S::Public>) {
+        let rng = &mut rand::thread_rng();
+        let (private, _) = S::keypair(rng);
+        crate::sig::deal::<S, _>(&private, n, t, rng).unwrap()
+    }
+
+    /// Every helper's contributions, flattened.
+    fn contributions<S: Scheme>(
+        public: &Poly<S::Public>,
+        shares: &[Share<S::Private>],
+        lost: Idx,
+        helpers: &[Idx],
+    ) -> Contributions<S> {
+        let rng = &mut rand::thread_rng();
+        helpers
+            .iter()
+            .flat_map(|&i| {
+                contribute::<S, _>(public, &shares[i as usize], lost, helpers, rng).unwrap()
+            })
+            .collect()
+    }
+
+    fn addressed_to<S: Scheme>(contributions: &Contributions<S>, helper: Idx) -> Contributions<S> {
+        contributions
+            .iter()
+            .filter(|c| c.to == helper)
+            .cloned()
+            .collect()
+    }
+
+    fn repair_shares<S: Scheme>(
+        contributions: &Contributions<S>,
+        helpers: &[Idx],
+    ) -> Vec<RepairShare<S::Private>> {
+        helpers
+            .iter()
+            .map(|&helper| {
+                aggregate::<S>(helper, helpers, &addressed_to::<S>(contributions, helper)).unwrap()
+            })
+            .collect()
+    }
+
+    fn repairs_the_lost_share<S: SignatureScheme>() {
+        let (shares, public) = setup::<S>(6, 3);
+
+        // exactly the threshold of helpers, and more than it
+        for (lost, helpers) in [(1, vec![0, 3, 5]), (4, vec![5, 2, 0, 1])] {
+            let contributions = contributions::<S>(&public, &shares, lost, &helpers);
+            assert_eq!(contributions.len(), helpers.len() * helpers.len());
+            let repair_shares = repair_shares::<S>(&contributions, &helpers);
+            let share = recover::<S>(&public, lost, &helpers, &repair_shares).unwrap();
+            assert_eq!(share, shares[lost as usize]);
+        }
+    }
+
+    #[test]
+    fn repairs_the_lost_share_g1() {
+        repairs_the_lost_share::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn repairs_the_lost_share_g2() {
+        repairs_the_lost_share::<G2Scheme<PCurve>>();
+    }
+
+    // No single contribution or repair share is the helper's weighted share:
+    // they are masked, so what a helper is sent does not depend on what it
+    // already knows.
+    #[test]
+    fn contributions_are_masked() {
+        type S = G2Scheme<PCurve>;
+        let (shares, public) = setup::<S>(5, 3);
+        let (lost, helpers) = (2, [0, 1, 3]);
+
+        let first = contributions::<S>(&public, &shares, lost, &helpers);
+        let second = contributions::<S>(&public, &shares, lost, &helpers);
+        assert!(first.iter().zip(&second).all(|(a, b)| a.value != b.value));
+
+        let mut weighted = lagrange_at_lost::<<S as Scheme>::Private>(0, lost, &helpers).unwrap();
+        weighted.mul(&shares[0].private);
+        assert!(first.iter().all(|c| c.value != weighted));
+    }
+
+    #[test]
+    fn a_wrong_contribution_is_caught() {
+        type S = G2Scheme<PCurve>;
+        let (shares, public) = setup::<S>(5, 3);
+        let (lost, helpers) = (2, [0, 1, 3]);
+        let mut contributions = contributions::<S>(&public, &shares, lost, &helpers);
+        contributions[4].value.add(&<S as Scheme>::Private::one());
+
+        let repair_shares = repair_shares::<S>(&contributions, &helpers);
+        assert!(matches!(
+            recover::<S>(&public, lost, &helpers, &repair_shares),
+            Err(RepairError::InvalidShare(2))
+        ));
+    }
+
+    #[test]
+    fn the_helpers_are_checked() {
+        type S = G2Scheme<PCurve>;
+        let (mut shares, public) = setup::<S>(5, 3);
+        let rng = &mut rand::thread_rng();
+        let try_contribute = |share: &Share<_>, lost, helpers: &[Idx]| {
+            contribute::<S, _>(&public, share, lost, helpers, &mut rand::thread_rng())
+        };
+
+        assert!(matches!(
+            try_contribute(&shares[0], 2, &[0, 1]),
+            Err(RepairError::NotEnoughHelpers {
+                helpers: 2,
+                threshold: 3
+            })
+        ));
+        assert!(matches!(
+            try_contribute(&shares[0], 2, &[0, 1, 1]),
+            Err(RepairError::DuplicateHelper(1))
+        ));
+        assert!(matches!(
+            try_contribute(&shares[0], 2, &[0, 1, 2]),
+            Err(RepairError::LostShareIsHelper(2))
+        ));
+        assert!(matches!(
+            try_contribute(&shares[4], 2, &[0, 1, 3]),
+            Err(RepairError::NotAHelper(4))
+        ));
+
+        shares[1].private.add(&<S as Scheme>::Private::one());
+        assert!(matches!(
+            contribute::<S, _>(&public, &shares[1], 2, &[0, 1, 3], rng),
+            Err(RepairError::InvalidShare(1))
+        ));
+    }
+
+    #[test]
+    fn contributions_are_counted_once_each() {
+        type S = G2Scheme<PCurve>;
+        let (shares, public) = setup::<S>(5, 3);
+        let (lost, helpers) = (2, [0, 1, 3]);
+        let contributions = contributions::<S>(&public, &shares, lost, &helpers);
+        let received = addressed_to::<S>(&contributions, 1);
+
+        assert!(matches!(
+            aggregate::<S>(1, &helpers, &received[..2]),
+            Err(RepairError::MissingContribution(3))
+        ));
+        let twice = [
+            received[0].clone(),
+            received[0].clone(),
+            received[2].clone(),
+        ];
+        assert!(matches!(
+            aggregate::<S>(1, &helpers, &twice),
+            Err(RepairError::DuplicateContribution(0))
+        ));
+        assert!(matches!(
+            aggregate::<S>(3, &helpers, &received),
+            Err(RepairError::Misaddressed { from: 0, to: 1 })
+        ));
+        assert!(matches!(
+            aggregate::<S>(4, &helpers, &received),
+            Err(RepairError::NotAHelper(4))
+        ));
+
+        let repair_shares = repair_shares::<S>(&contributions, &helpers);
+        assert!(matches!(
+            recover::<S>(&public, lost, &helpers, &repair_shares[1..]),
+            Err(RepairError::MissingContribution(0))
+        ));
+    }
+}
@@ -0,0 +1,376 @@
+//! Incremental aggregation of partial signatures as they arrive.
+use crate::poly::{Eval, Idx, Poly};
+use crate::serialization;
+use crate::sig::{BlindThresholdScheme, Partial, SignatureScheme, ThresholdError, ThresholdScheme};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Errors associated with incremental aggregation
+#[derive(Debug, Error)]
+pub enum AggregatorError<S: SignatureScheme> {
+    /// Raised when a partial does not verify against its signer's public share
+    #[error("invalid partial signature at index {0}")]
+    InvalidPartial(Idx),
+
+    /// Raised when a partial cannot be deserialized
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+
+    /// Raised when the partials cannot be aggregated
+    #[error(transparent)]
+    ThresholdError(#[from] ThresholdError<S>),
+}
+
+/// When an [`Aggregator`] verifies the partials it is handed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    /// Each partial as it arrives, so that a bad one is rejected by
+    /// [`Aggregator::add`] and never counts toward the threshold. Costs a
+    /// pairing check per partial.
+    Eager,
+    /// Only the aggregated signature, once enough partials are in, and the
+    /// partials one by one only if it does not verify. Costs one pairing check
+    /// when every signer is honest.
+    Lazy,
+}
+
+/// How far an [`Aggregator`] is from a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Distinct signers whose partials are held: verified ones under
+    /// [`Verification::Eager`], not yet verified ones under
+    /// [`Verification::Lazy`]
+    pub collected: usize,
+    /// Partials the signature needs
+    pub threshold: usize,
+}
+
+impl Progress {
+    /// Whether enough partials are held to attempt the signature
+    pub fn is_complete(&self) -> bool {
+        self.collected >= self.threshold
+    }
+}
+
+/// Collects the partial signatures on one message as they arrive, and yields
+/// the group's signature as soon as a threshold of valid ones are in.
+///
+/// [`ThresholdScheme::aggregate`] takes every partial at once, which makes a
+/// combiner wait for the slowest signer it asked. An aggregator is handed
+/// partials one at a time instead: it keeps one per signer index, ignoring
+/// repeats, and [`Aggregator::signature`] returns the signature once the
+/// threshold is met. A partial from a signer already heard from is ignored
+/// rather than replacing the first, so a signer cannot swap a verified partial
+/// for a bad one.
+///
+/// ```
+/// use threshold_bls::{
+///     poly::{Idx, Poly},
+///     schemes::bls12_377::G2Scheme as SigScheme,
+///     sig::{Aggregator, Scheme, Share, SignatureScheme, ThresholdScheme, Verification},
+/// };
+///
+/// let private = Poly::<<SigScheme as Scheme>::Private>::new(2);
+/// let public = private.commit::<<SigScheme as Scheme>::Public>();
+/// let msg = b"hello";
+///
+/// let mut aggregator = Aggregator::<SigScheme>::new(public.clone(), msg, Verification::Eager);
+/// for i in 0..5 {
+///     let share = Share { index: i as Idx, private: private.eval(i as Idx).value };
+///     let progress = aggregator.add(&SigScheme::partial_sign(&share, msg).unwrap()).unwrap();
+///     if progress.is_complete() {
+///         break;
+///     }
+/// }
+///
+/// let signature = aggregator.signature().unwrap().expect("three partials are in");
+/// SigScheme::verify(public.public_key(), msg, &signature).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Aggregator<S: SignatureScheme> {
+    public: Poly<S::Public>,
+    msg: Vec<u8>,
+    blinded: bool,
+    verification: Verification,
+    partials: BTreeMap<Idx, Partial>,
+    signature: Option<Vec<u8>>,
+}
+
+impl<S: SignatureScheme + BlindThresholdScheme> Aggregator<S> {
+    /// An aggregator of partials on `msg` from the signers of `public`.
+    pub fn new(public: Poly<S::Public>, msg: &[u8], verification: Verification) -> Self {
+        Self::with(public, msg, false, verification)
+    }
+
+    /// An aggregator of blind partials, from
+    /// [`BlindThresholdScheme::sign_blind_partial`], on a blinded message. The
+    /// signature it yields is blind too, and is unblinded as usual.
+    pub fn blind(public: Poly<S::Public>, blinded_msg: &[u8], verification: Verification) -> Self {
+        Self::with(public, blinded_msg, true, verification)
+    }
+
+    fn with(
+        public: Poly<S::Public>,
+        msg: &[u8],
+        blinded: bool,
+        verification: Verification,
+    ) -> Self {
+        Self {
+            public,
+            msg: msg.to_vec(),
+            blinded,
+            verification,
+            partials: BTreeMap::new(),
+            signature: None,
+        }
+    }
+
+    /// Hands the aggregator a partial. A partial from a signer already heard
+    /// from is ignored, and under [`Verification::Eager`] a partial that does
+    /// not verify is rejected and its signer may send another.
+    pub fn add(&mut self, partial: &[u8]) -> Result<Progress, AggregatorError<S>> {
+        let index = serialization::deserialize::<Eval<Vec<u8>>>(partial)?.index;
+        if self.signature.is_some() || self.partials.contains_key(&index) {
+            return Ok(self.progress());
+        }
+
+        if self.verification == Verification::Eager && !self.verify_partial(partial) {
+            return Err(AggregatorError::InvalidPartial(index));
+        }
+
+        self.partials.insert(index, partial.to_vec());
+        Ok(self.progress())
+    }
+
+    /// How many partials are held, and how many the signature needs.
+    pub fn progress(&self) -> Progress {
+        Progress {
+            collected: self.partials.len(),
+            threshold: self.public.degree() + 1,
+        }
+    }
+
+    /// The indices of the signers whose partials are held.
+    pub fn signers(&self) -> impl Iterator<Item = Idx> + '_ {
+        self.partials.keys().copied()
+    }
+
+    /// The group's signature, if a threshold of valid partials are in, and
+    /// `None` if more are needed.
+    ///
+    /// Under [`Verification::Lazy`], an aggregate that does not verify makes
+    /// the aggregator check the partials one by one and drop the bad ones,
+    /// whose signers may then send again; it returns `None` if too few good
+    /// ones are left.
+    pub fn signature(&mut self) -> Result<Option<Vec<u8>>, AggregatorError<S>> {
+        if let Some(signature) = &self.signature {
+            return Ok(Some(signature.clone()));
+        }
+        if !self.progress().is_complete() {
+            return Ok(None);
+        }
+
+        let partials = self.partials.values().cloned().collect::<Vec<_>>();
+        let aggregated = S::aggregate(&self.public, &partials);
+        // A partial whose signature does not decode fails aggregation rather
+        // than verification, and is looked for the same way.
+        if self.verification == Verification::Lazy
+            && !aggregated
+                .as_ref()
+                .is_ok_and(|signature| self.verify_signature(signature))
+        {
+            let bad = self
+                .partials
+                .iter()
+                .filter(|(_, partial)| !self.verify_partial(partial))
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>();
+            if bad.is_empty() {
+                // Partials that each verify aggregate to a signature that
+                // verifies, so this is unreachable; do not loop on it regardless.
+                return Ok(None);
+            }
+            for index in bad {
+                self.partials.remove(&index);
+            }
+            return self.signature();
+        }
+
+        let signature = aggregated?;
+        self.signature = Some(signature.clone());
+        Ok(Some(signature))
+    }
+
+    fn verify_partial(&self, partial: &[u8]) -> bool {
+        if self.blinded {
+            S::verify_blind_partial(&self.public, &self.msg, partial).is_ok()
+        } else {
+            S::partial_verify(&self.public, &self.msg, partial).is_ok()
+        }
+    }
+
+    fn verify_signature(&self, signature: &[u8]) -> bool {
+        let public = self.public.public_key();
+        if self.blinded {
+            S::blind_verify(public, &self.msg, signature).is_ok()
+        } else {
+            S::verify(public, &self.msg, signature).is_ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{
+            BlindScheme, Share,
+            bls::{G1Scheme, G2Scheme},
+            shares,
+        },
+    };
+    use rand::thread_rng;
+
+    /// A partial from `share`'s index, signed with another key.
+    fn forged<T: SignatureScheme>(share: &Share<T::Private>, msg: &[u8]) -> Partial {
+        let impostor = Share {
+            index: share.index,
+            private: T::keypair(&mut thread_rng()).0,
+        };
+        T::partial_sign(&impostor, msg).unwrap()
+    }
+
+    fn aggregates_incrementally<T>(verification: Verification)
+    where
+        T: SignatureScheme + BlindThresholdScheme,
+    {
+        let (shares, public) = shares::<T>(5, 3);
+        let msg = b"incremental";
+        let partials = shares
+            .iter()
+            .map(|share| T::partial_sign(share, msg).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut aggregator = Aggregator::<T>::new(public.clone(), msg, verification);
+        assert_eq!(aggregator.add(&partials[4]).unwrap().collected, 1);
+        // a repeat does not count twice
+        assert_eq!(aggregator.add(&partials[4]).unwrap().collected, 1);
+        assert!(aggregator.signature().unwrap().is_none());
+
+        let progress = aggregator.add(&partials[1]).unwrap();
+        assert_eq!(progress.collected, 2);
+        assert!(!progress.is_complete());
+        assert!(aggregator.add(&partials[2]).unwrap().is_complete());
+        assert_eq!(aggregator.signers().collect::<Vec<_>>(), vec![1, 2, 4]);
+
+        let signature = aggregator.signature().unwrap().unwrap();
+        T::verify(public.public_key(), msg, &signature).unwrap();
+        assert_eq!(signature, T::aggregate(&public, &partials).unwrap());
+
+        // late partials change nothing
+        aggregator.add(&partials[0]).unwrap();
+        assert_eq!(aggregator.signature().unwrap().unwrap(), signature);
+    }
+
+    #[test]
+    fn aggregates_incrementally_g1() {
+        aggregates_incrementally::<G1Scheme<PCurve>>(Verification::Eager);
+        aggregates_incrementally::<G1Scheme<PCurve>>(Verification::Lazy);
+    }
+
+    #[test]
+    fn aggregates_incrementally_g2() {
+        aggregates_incrementally::<G2Scheme<PCurve>>(Verification::Eager);
+        aggregates_incrementally::<G2Scheme<PCurve>>(Verification::Lazy);
+    }
+
+    #[test]
+    fn eager_verification_rejects_a_bad_partial_on_arrival() {
+        type T = G2Scheme<PCurve>;
+        let (shares, public) = shares::<T>(4, 2);
+        let msg = b"eager";
+
+        let mut aggregator = Aggregator::<T>::new(public.clone(), msg, Verification::Eager);
+        assert!(matches!(
+            aggregator.add(&forged::<T>(&shares[0], msg)),
+            Err(AggregatorError::InvalidPartial(0))
+        ));
+        assert_eq!(aggregator.progress().collected, 0);
+
+        // the signer may send again, and then cannot replace its partial
+        aggregator
+            .add(&T::partial_sign(&shares[0], msg).unwrap())
+            .unwrap();
+        assert_eq!(
+            aggregator
+                .add(&forged::<T>(&shares[0], msg))
+                .unwrap()
+                .collected,
+            1
+        );
+        aggregator
+            .add(&T::partial_sign(&shares[3], msg).unwrap())
+            .unwrap();
+        let signature = aggregator.signature().unwrap().unwrap();
+        T::verify(public.public_key(), msg, &signature).unwrap();
+    }
+
+    #[test]
+    fn lazy_verification_drops_bad_partials_and_waits_for_more() {
+        type T = G2Scheme<PCurve>;
+        let (shares, public) = shares::<T>(5, 3);
+        let msg = b"lazy";
+
+        let mut aggregator = Aggregator::<T>::new(public.clone(), msg, Verification::Lazy);
+        aggregator.add(&forged::<T>(&shares[0], msg)).unwrap();
+        aggregator
+            .add(&T::partial_sign(&shares[1], msg).unwrap())
+            .unwrap();
+        aggregator
+            .add(&T::partial_sign(&shares[2], msg).unwrap())
+            .unwrap();
+        assert!(aggregator.progress().is_complete());
+
+        // the aggregate does not verify, so the forgery is found and dropped
+        assert!(aggregator.signature().unwrap().is_none());
+        assert_eq!(aggregator.signers().collect::<Vec<_>>(), vec![1, 2]);
+
+        aggregator
+            .add(&T::partial_sign(&shares[3], msg).unwrap())
+            .unwrap();
+        let signature = aggregator.signature().unwrap().unwrap();
+        T::verify(public.public_key(), msg, &signature).unwrap();
+    }
+
+    #[test]
+    fn aggregates_blind_partials() {
+        type T = G1Scheme<PCurve>;
+        let (shares, public) = shares::<T>(4, 3);
+        let msg = b"blind";
+        let (token, blinded) = T::blind_msg(msg, &mut thread_rng()).unwrap();
+
+        for verification in [Verification::Eager, Verification::Lazy] {
+            let mut aggregator = Aggregator::<T>::blind(public.clone(), &blinded, verification);
+            for share in &shares[1..] {
+                aggregator
+                    .add(&T::sign_blind_partial(share, &blinded).unwrap())
+                    .unwrap();
+            }
+            let blind_signature = aggregator.signature().unwrap().unwrap();
+            let signature = T::unblind_sig(&token, &blind_signature).unwrap();
+            T::verify(public.public_key(), msg, &signature).unwrap();
+        }
+    }
+
+    #[test]
+    fn a_partial_that_does_not_decode_is_rejected() {
+        type T = G2Scheme<PCurve>;
+        let (_, public) = shares::<T>(3, 2);
+        let mut aggregator = Aggregator::<T>::new(public, b"msg", Verification::Lazy);
+        assert!(matches!(
+            aggregator.add(&[1, 2, 3]),
+            Err(AggregatorError::BincodeError(_))
+        ));
+    }
+}
@@ -43,6 +43,11 @@ impl<S: Scalar> Token<S> {
     pub fn new() -> Self {
         Self(S::zero())
     }
+
+    /// The blinding factor, for proving what the blinded message is.
+    pub(crate) fn scalar(&self) -> &S {
+        &self.0
+    }
 }
 
 /// The blinder follows the protocol described
@@ -0,0 +1,344 @@
+//! Aggregate signatures that record who signed, for epoch certificates that a
+//! light client checks against a known validator set.
+//!
+//! Every validator signs the same message with its own key, and a
+//! [`Certificate`] is the sum of some of those signatures with a bitmap of the
+//! validators they came from, by position in an ordered list of their public
+//! keys. Verifying it sums the public keys the bitmap selects and checks the
+//! signature against that, so a client learns both that enough validators
+//! signed and which ones, from one signature and `n / 8` bytes.
+//!
+//! Summing public keys is only sound if every key in the list was registered
+//! with a proof that its owner knows the private key. Otherwise a validator
+//! can register a key crafted from the others' and forge a certificate the
+//! others appear to have signed. This module does not check that; the
+//! validator list has to.
+//!
+//! The bitmap is laid out as the bindings' batch results are: validator `i` is
+//! bit `i % 8` of byte `i / 8`.
+use crate::group::Element;
+use crate::serialization;
+use crate::sig::SignatureScheme;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A signature aggregated from some validators, with a bitmap of which
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Certificate<P> {
+    /// The sum of the signers' signatures
+    pub signature: P,
+    /// The number of validators in the list the bitmap is over
+    pub validators: usize,
+    /// The signers, by position in the validator list
+    pub bitmap: Vec<u8>,
+}
+
+/// Errors associated with creating, merging and verifying certificates
+#[derive(Debug, Error)]
+pub enum CertificateError {
+    /// Raised when creating a certificate from no signatures, which would
+    /// certify nothing
+    #[error("a certificate needs at least one signature")]
+    NoSignatures,
+
+    /// Raised when a signature is attributed to a position past the end of
+    /// the validator list
+    #[error("there is no validator {index} among {validators}")]
+    UnknownValidator {
+        /// The position given
+        index: usize,
+        /// The number of validators
+        validators: usize,
+    },
+
+    /// Raised when two signatures are attributed to the same validator
+    #[error("validator {0} signed more than once")]
+    DuplicateSigner(usize),
+
+    /// Raised when merging certificates that a validator signed both of, whose
+    /// signature would be counted twice
+    #[error("validator {0} signed both certificates")]
+    Overlap(usize),
+
+    /// Raised when a certificate is not for a validator list of the given
+    /// length, the verifier's or the other certificate's, or its bitmap is not
+    /// sized for its list or marks validators past the list's end
+    #[error("the bitmap does not fit a list of {0} validators")]
+    WrongValidatorCount(usize),
+
+    /// Raised when fewer validators signed than the verifier requires
+    #[error("not enough signers: {0}/{1}")]
+    NotEnoughSigners(usize, usize),
+
+    /// Raised when the signature does not verify against the signers' summed
+    /// public keys
+    #[error("the signature does not verify against the signers' public keys")]
+    InvalidSignature,
+
+    /// Raised when a signature cannot be deserialized
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+}
+
+impl<P: Element> Certificate<P> {
+    /// The positions of the validators who signed, in ascending order
+    pub fn signers(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.validators).filter(|&i| self.has_signed(i))
+    }
+
+    /// Whether the validator at `index` signed
+    pub fn has_signed(&self, index: usize) -> bool {
+        index < self.validators
+            && self
+                .bitmap
+                .get(index / 8)
+                .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// The number of validators who signed
+    pub fn count(&self) -> usize {
+        self.signers().count()
+    }
+
+    /// Whether the bitmap is sized for the validator list and marks no
+    /// validator past its end
+    fn fits(&self) -> bool {
+        let marked = self
+            .bitmap
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum::<usize>();
+        self.bitmap.len() == self.validators.div_ceil(8) && marked == self.count()
+    }
+
+    /// Combines two certificates on the same message from the same validator
+    /// list, signed by different validators, into one signed by both sets
+    pub fn merge(&self, other: &Self) -> Result<Self, CertificateError> {
+        if other.validators != self.validators || !self.fits() || !other.fits() {
+            return Err(CertificateError::WrongValidatorCount(self.validators));
+        }
+        if let Some(index) = self.signers().find(|&i| other.has_signed(i)) {
+            return Err(CertificateError::Overlap(index));
+        }
+
+        let mut signature = self.signature.clone();
+        signature.add(&other.signature);
+        let bitmap = self
+            .bitmap
+            .iter()
+            .zip(&other.bitmap)
+            .map(|(a, b)| a | b)
+            .collect();
+
+        Ok(Self {
+            signature,
+            validators: self.validators,
+            bitmap,
+        })
+    }
+}
+
+/// Aggregates validators' signatures on one message into a certificate.
+/// Each signature is given with the position of its validator among
+/// `validators` validators.
+///
+/// The signatures are not verified here: a wrong one makes the certificate
+/// fail [`verify`].
+pub fn create<S: SignatureScheme>(
+    validators: usize,
+    signatures: &[(usize, &[u8])],
+) -> Result<Certificate<S::Signature>, CertificateError> {
+    if signatures.is_empty() {
+        return Err(CertificateError::NoSignatures);
+    }
+
+    let mut certificate = Certificate {
+        signature: S::Signature::zero(),
+        validators,
+        bitmap: vec![0; validators.div_ceil(8)],
+    };
+    for &(index, signature) in signatures {
+        if index >= validators {
+            return Err(CertificateError::UnknownValidator { index, validators });
+        }
+        if certificate.has_signed(index) {
+            return Err(CertificateError::DuplicateSigner(index));
+        }
+
+        let signature: S::Signature = serialization::deserialize(signature)?;
+        certificate.signature.add(&signature);
+        certificate.bitmap[index / 8] |= 1 << (index % 8);
+    }
+
+    Ok(certificate)
+}
+
+/// Verifies that at least `min_signers` of `validators` signed `msg`, and that
+/// the certificate's signature is the sum of theirs.
+pub fn verify<S: SignatureScheme>(
+    certificate: &Certificate<S::Signature>,
+    validators: &[S::Public],
+    msg: &[u8],
+    min_signers: usize,
+) -> Result<(), CertificateError> {
+    let n = validators.len();
+    if certificate.validators != n || !certificate.fits() {
+        return Err(CertificateError::WrongValidatorCount(n));
+    }
+    let count = certificate.count();
+    if count < min_signers || count == 0 {
+        return Err(CertificateError::NotEnoughSigners(
+            count,
+            min_signers.max(1),
+        ));
+    }
+
+    let public = certificate.signers().fold(S::Public::zero(), |mut sum, i| {
+        sum.add(&validators[i]);
+        sum
+    });
+    let signature = bincode::serialize(&certificate.signature)?;
+    S::verify(&public, msg, &signature).map_err(|_| CertificateError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{G1Scheme, G2Scheme, Scheme},
+    };
+
+    type Validators<S> = (Vec<<S as Scheme>::Private>, Vec<<S as Scheme>::Public>);
+
+    fn validators<S: SignatureScheme>(n: usize) -> Validators<S> {
+        let rng = &mut rand::thread_rng();
+        (0..n).map(|_| S::keypair(rng)).unzip()
+    }
+
+    fn certify<S: SignatureScheme>(
+        privates: &[S::Private],
+        signers: &[usize],
+        msg: &[u8],
+    ) -> Certificate<S::Signature> {
+        let signatures = signers
+            .iter()
+            .map(|&i| (i, S::sign(&privates[i], msg).unwrap()))
+            .collect::<Vec<_>>();
+        let signatures = signatures
+            .iter()
+            .map(|(i, signature)| (*i, &signature[..]))
+            .collect::<Vec<_>>();
+        create::<S>(privates.len(), &signatures).unwrap()
+    }
+
+    fn certificates_record_and_check_their_signers<S: SignatureScheme>() {
+        let msg = b"epoch 42";
+        let (privates, publics) = validators::<S>(10);
+
+        let first = certify::<S>(&privates, &[0, 2, 3], msg);
+        let second = certify::<S>(&privates, &[9, 5], msg);
+        assert_eq!(first.bitmap, [0b1101, 0]);
+        verify::<S>(&first, &publics, msg, 3).unwrap();
+
+        let merged = first.merge(&second).unwrap();
+        assert_eq!(merged.signers().collect::<Vec<_>>(), [0, 2, 3, 5, 9]);
+        assert_eq!(merged.count(), 5);
+        assert_eq!(merged, certify::<S>(&privates, &[0, 2, 3, 5, 9], msg));
+        verify::<S>(&merged, &publics, msg, 5).unwrap();
+
+        assert!(matches!(
+            verify::<S>(&merged, &publics, msg, 6),
+            Err(CertificateError::NotEnoughSigners(5, 6))
+        ));
+        assert!(matches!(
+            verify::<S>(&merged, &publics, b"epoch 43", 5),
+            Err(CertificateError::InvalidSignature)
+        ));
+
+        // claiming a validator who did not sign, or hiding one who did
+        let mut claimed = merged.clone();
+        claimed.bitmap[0] |= 0b10;
+        assert!(matches!(
+            verify::<S>(&claimed, &publics, msg, 1),
+            Err(CertificateError::InvalidSignature)
+        ));
+        let mut hidden = merged.clone();
+        hidden.bitmap[1] = 0;
+        assert!(matches!(
+            verify::<S>(&hidden, &publics, msg, 1),
+            Err(CertificateError::InvalidSignature)
+        ));
+
+        assert!(matches!(
+            merged.merge(&certify::<S>(&privates, &[1, 5], msg)),
+            Err(CertificateError::Overlap(5))
+        ));
+    }
+
+    #[test]
+    fn certificates_record_and_check_their_signers_g1() {
+        certificates_record_and_check_their_signers::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn certificates_record_and_check_their_signers_g2() {
+        certificates_record_and_check_their_signers::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn the_validator_list_is_checked() {
+        type S = G2Scheme<PCurve>;
+        let msg = b"epoch 7";
+        let (privates, publics) = validators::<S>(10);
+        let certificate = certify::<S>(&privates, &[1, 4], msg);
+
+        assert!(matches!(
+            verify::<S>(&certificate, &publics[..8], msg, 1),
+            Err(CertificateError::WrongValidatorCount(8))
+        ));
+        // the bitmap's spare bits stay clear
+        let mut stray = certificate.clone();
+        stray.bitmap[1] |= 0b100;
+        assert!(matches!(
+            verify::<S>(&stray, &publics, msg, 1),
+            Err(CertificateError::WrongValidatorCount(10))
+        ));
+        let empty = Certificate {
+            signature: <S as Scheme>::Signature::zero(),
+            validators: 10,
+            bitmap: vec![0; 2],
+        };
+        assert!(matches!(
+            verify::<S>(&empty, &publics, msg, 0),
+            Err(CertificateError::NotEnoughSigners(0, 1))
+        ));
+        assert!(matches!(
+            certificate.merge(&certify::<S>(&privates[..8], &[0], msg)),
+            Err(CertificateError::WrongValidatorCount(10))
+        ));
+        // a list of 12 has a bitmap as long as a list of 10
+        let (more, _) = validators::<S>(12);
+        assert!(matches!(
+            certificate.merge(&certify::<S>(&more, &[0], msg)),
+            Err(CertificateError::WrongValidatorCount(10))
+        ));
+
+        let signature = S::sign(&privates[0], msg).unwrap();
+        assert!(matches!(
+            create::<S>(10, &[(10, &signature[..])]),
+            Err(CertificateError::UnknownValidator {
+                index: 10,
+                validators: 10
+            })
+        ));
+        assert!(matches!(
+            create::<S>(10, &[(3, &signature[..]), (3, &signature[..])]),
+            Err(CertificateError::DuplicateSigner(3))
+        ));
+        assert!(matches!(
+            create::<S>(10, &[]),
+            Err(CertificateError::NoSignatures)
+        ));
+    }
+}
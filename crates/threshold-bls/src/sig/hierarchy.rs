@@ -0,0 +1,222 @@
+//! Child keys for any [`Scheme`](crate::sig::Scheme), offset from their parent
+//! by a tweak hashed from the parent public key and a context string.
+use crate::group::{Element, Scalar};
+use crate::poly::Poly;
+use crate::sig::tbls::Share;
+use crate::sig::{HierarchicalScheme, Scheme};
+use thiserror::Error;
+
+/// Blake2s personalization for the tweak, so that it cannot collide with a
+/// hash computed for anything else.
+const CHILD_DOMAIN: &[u8] = b"BLSchild";
+
+/// Errors associated with deriving a child key
+#[derive(Debug, Error)]
+pub enum HierarchyError {
+    /// Raised when the parent public key cannot be serialized into the hash
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+
+    /// Raised when no candidate hash was a valid scalar. Each candidate is one
+    /// with probability above one half, so this does not happen in practice.
+    #[error("could not hash the context to a scalar")]
+    HashToScalarError,
+}
+
+impl<T: Scheme> HierarchicalScheme for T {
+    type Error = HierarchyError;
+
+    fn child_tweak(parent: &Self::Public, context: &[u8]) -> Result<Self::Private, HierarchyError> {
+        let parent = bincode::serialize(parent)?;
+
+        // Try-and-increment, as hashing to the curve does: a candidate that
+        // is not below the group order is rejected rather than reduced, so
+        // the tweak is uniform rather than biased toward small values.
+        for counter in 0u8..=255 {
+            let hash = blake2s_simd::Params::new()
+                .hash_length(32)
+                .personal(CHILD_DOMAIN)
+                .to_state()
+                .update(&[counter])
+                .update(&parent)
+                .update(context)
+                .finalize();
+            if let Some(tweak) = Self::Private::from_random_bytes(hash.as_bytes()) {
+                return Ok(tweak);
+            }
+        }
+
+        Err(HierarchyError::HashToScalarError)
+    }
+
+    fn child_private(
+        parent: &Self::Public,
+        private: &Self::Private,
+        context: &[u8],
+    ) -> Result<Self::Private, HierarchyError> {
+        let mut child = private.clone();
+        child.add(&Self::child_tweak(parent, context)?);
+        Ok(child)
+    }
+
+    fn child_public(parent: &Self::Public, context: &[u8]) -> Result<Self::Public, HierarchyError> {
+        let mut offset = Self::Public::one();
+        offset.mul(&Self::child_tweak(parent, context)?);
+
+        let mut child = parent.clone();
+        child.add(&offset);
+        Ok(child)
+    }
+
+    fn child_polynomial(
+        parent: &Poly<Self::Public>,
+        context: &[u8],
+    ) -> Result<Poly<Self::Public>, HierarchyError> {
+        // Adding the tweak to the secret adds it to the constant term of the
+        // private polynomial and so to every share; the other coefficients,
+        // and with them the threshold, are untouched.
+        let mut child = parent.clone();
+        child.set(0, Self::child_public(parent.public_key(), context)?);
+        Ok(child)
+    }
+
+    fn child_share(
+        parent: &Poly<Self::Public>,
+        share: &Share<Self::Private>,
+        context: &[u8],
+    ) -> Result<Share<Self::Private>, HierarchyError> {
+        Ok(Share {
+            index: share.index,
+            private: Self::child_private(parent.public_key(), &share.private, context)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{
+            BlindScheme, BlindThresholdScheme, SignatureScheme, ThresholdScheme,
+            bls::{G1Scheme, G2Scheme},
+            shares,
+        },
+    };
+
+    #[test]
+    fn child_partials_aggregate_under_the_child_g1() {
+        child_partials_aggregate_under_the_child::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn child_partials_aggregate_under_the_child_g2() {
+        child_partials_aggregate_under_the_child::<G2Scheme<PCurve>>();
+    }
+
+    fn child_partials_aggregate_under_the_child<T>()
+    where
+        T: HierarchicalScheme + ThresholdScheme + SignatureScheme,
+    {
+        let (shares, parent) = shares::<T>(5, 3);
+        let msg = b"hello";
+        let context = b"app:payments";
+
+        let child = T::child_polynomial(&parent, context).unwrap();
+        assert_eq!(child.degree(), parent.degree());
+        assert_eq!(
+            child.public_key(),
+            &T::child_public(parent.public_key(), context).unwrap()
+        );
+
+        let partials = shares
+            .iter()
+            .map(|share| T::child_share(&parent, share, context).unwrap())
+            .map(|share| T::partial_sign(&share, msg).unwrap())
+            .collect::<Vec<_>>();
+        for partial in &partials {
+            T::partial_verify(&child, msg, partial).unwrap();
+            assert!(T::partial_verify(&parent, msg, partial).is_err());
+        }
+
+        // Any three of the five aggregate to the one child signature.
+        let signature = T::aggregate(&child, &partials[..3]).unwrap();
+        assert_eq!(signature, T::aggregate(&child, &partials[2..]).unwrap());
+        T::verify(child.public_key(), msg, &signature).unwrap();
+        assert!(T::verify(parent.public_key(), msg, &signature).is_err());
+    }
+
+    #[test]
+    fn child_private_matches_child_public() {
+        type T = G2Scheme<PCurve>;
+        let (private, public) = T::keypair(&mut rand::thread_rng());
+        let child_private = T::child_private(&public, &private, b"user:42").unwrap();
+
+        let signature = T::sign(&child_private, b"hello").unwrap();
+        let child_public = T::child_public(&public, b"user:42").unwrap();
+        T::verify(&child_public, b"hello", &signature).unwrap();
+    }
+
+    #[test]
+    fn contexts_name_distinct_children() {
+        type T = G1Scheme<PCurve>;
+        let (_, parent) = T::keypair(&mut rand::thread_rng());
+        let (_, other_parent) = T::keypair(&mut rand::thread_rng());
+
+        let child = |parent, context: &[u8]| T::child_public(parent, context).unwrap();
+        assert_eq!(child(&parent, b"a"), child(&parent, b"a"));
+        assert_ne!(child(&parent, b"a"), child(&parent, b"b"));
+        assert_ne!(child(&parent, b"a"), parent);
+        assert_ne!(child(&parent, b""), parent);
+
+        // The tweak is bound to the parent, so one context names unrelated
+        // children under different parents.
+        let tweak = |parent| T::child_tweak(parent, b"a").unwrap();
+        assert_ne!(tweak(&parent), tweak(&other_parent));
+    }
+
+    // A child is a parent like any other, and its children are derived from
+    // its own public key, so the levels compose.
+    #[test]
+    fn grandchildren_derive_from_the_child() {
+        type T = G2Scheme<PCurve>;
+        let (shares, parent) = shares::<T>(3, 2);
+
+        let child = T::child_polynomial(&parent, b"app").unwrap();
+        let grandchild = T::child_polynomial(&child, b"user:42").unwrap();
+        assert_ne!(
+            grandchild,
+            T::child_polynomial(&parent, b"user:42").unwrap()
+        );
+
+        let partials = shares
+            .iter()
+            .map(|share| T::child_share(&parent, share, b"app").unwrap())
+            .map(|share| T::child_share(&child, &share, b"user:42").unwrap())
+            .map(|share| T::partial_sign(&share, b"hello").unwrap())
+            .collect::<Vec<_>>();
+        let signature = T::aggregate(&grandchild, &partials).unwrap();
+        T::verify(grandchild.public_key(), b"hello", &signature).unwrap();
+    }
+
+    #[test]
+    fn child_shares_sign_blindly() {
+        type T = G2Scheme<PCurve>;
+        let (shares, parent) = shares::<T>(3, 2);
+        let child = T::child_polynomial(&parent, b"app").unwrap();
+
+        let (token, blinded) = T::blind_msg(b"hello", &mut rand::thread_rng()).unwrap();
+        let partials = shares
+            .iter()
+            .map(|share| T::child_share(&parent, share, b"app").unwrap())
+            .map(|share| T::sign_blind_partial(&share, &blinded).unwrap())
+            .collect::<Vec<_>>();
+        for partial in &partials {
+            T::verify_blind_partial(&child, &blinded, partial).unwrap();
+        }
+
+        let blinded_signature = T::aggregate(&child, &partials).unwrap();
+        let signature = T::unblind_sig(&token, &blinded_signature).unwrap();
+        T::verify(child.public_key(), b"hello", &signature).unwrap();
+    }
+}
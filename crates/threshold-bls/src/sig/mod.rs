@@ -1,3 +1,6 @@
+mod aggregator;
+pub use aggregator::{Aggregator, AggregatorError, Progress, Verification};
+
 mod blind;
 pub use blind::{BlindError, Token};
 
@@ -9,12 +12,52 @@ pub use blind::{BlindError, Token};
 pub mod bls;
 pub use bls::{BLSError, G1Scheme, G2Scheme};
 
+pub mod certificate;
+pub use certificate::{Certificate, CertificateError};
+
+mod hierarchy;
+pub use hierarchy::HierarchyError;
+
+mod pblind;
+pub use pblind::PartiallyBlindError;
+
+mod proven;
+pub use proven::PartialProofError;
+
 mod tblind;
 pub use tblind::BlindThresholdError;
 
 mod tbls;
-pub use tbls::{Share, ThresholdError};
+pub(crate) use tbls::lagrange_at;
+pub use tbls::{Share, ThresholdError, deal, reconstruct};
+
+pub mod tree;
+pub use tree::TreeError;
+
+mod vblind;
+pub use vblind::VerifiableBlindError;
+
+pub mod weighted;
+pub use weighted::WeightedError;
 
 #[allow(clippy::module_inception)]
 mod sig;
 pub use sig::*;
+
+/// A `t`-of-`n` group's shares and public polynomial, dealt from a fresh
+/// random polynomial, for tests.
+#[cfg(test)]
+pub(crate) fn shares<S: Scheme>(
+    n: usize,
+    t: usize,
+) -> (Vec<Share<S::Private>>, crate::poly::Poly<S::Public>) {
+    let private = crate::poly::Poly::<S::Private>::new(t - 1);
+    let shares = (0..n)
+        .map(|i| private.eval(i as crate::poly::Idx))
+        .map(|eval| Share {
+            index: eval.index,
+            private: eval.value,
+        })
+        .collect();
+    (shares, private.commit())
+}
@@ -0,0 +1,318 @@
+//! Partially blind signatures for the BLS blind threshold schemes: blind
+//! messages signed under a key offset from the group key by a secret tweak
+//! per metadata value.
+//!
+//! The tweak is hashed from the group's threshold signature on the metadata,
+//! framed under its own prefix, so the group key should not also serve blind
+//! requests directly: a blind signature under it on that framed metadata
+//! would be the tweak's preimage.
+use crate::group::{Element, Scalar};
+use crate::poly::{Eval, Idx, Poly};
+use crate::serialization;
+use crate::sig::blind::BlindError;
+use crate::sig::tbls::Share;
+use crate::sig::{
+    BlindScheme, BlindThresholdError, BlindThresholdScheme, Partial, PartiallyBlindScheme,
+    SignatureScheme, ThresholdError, ThresholdScheme,
+};
+use rand_core::RngCore;
+use thiserror::Error;
+
+/// Blake2s personalization for the tweak, so that it cannot collide with a
+/// hash computed for anything else.
+const METADATA_DOMAIN: &[u8] = b"BLSmetad";
+
+/// Prefix of the message the members sign to derive a metadata value's tweak,
+/// so that the signature cannot be asked for as an ordinary one.
+const METADATA_PREFIX: &[u8] = b"BLS-PBLIND-METADATA:";
+
+/// Errors associated with partially blind signatures
+#[derive(Debug, Error)]
+pub enum PartiallyBlindError<S: SignatureScheme> {
+    /// Raised when a member's partial on the metadata does not verify
+    #[error("invalid metadata partial at index {0}")]
+    InvalidPartial(Idx),
+
+    /// Raised when a signature does not verify under the metadata's key, or
+    /// the aggregated signature on the metadata does not verify under the
+    /// group key
+    #[error("invalid signature: {0}")]
+    SignatureError(S::Error),
+
+    /// Raised when signing or aggregating the metadata fails, too few partials
+    /// among the most common case
+    #[error(transparent)]
+    ThresholdError(#[from] ThresholdError<S>),
+
+    /// Raised when blinding or unblinding fails
+    #[error(transparent)]
+    BlindError(#[from] BlindError),
+
+    /// Raised when partially signing a blinded message fails
+    #[error(transparent)]
+    BlindThresholdError(#[from] BlindThresholdError<BlindError>),
+
+    /// Raised when a partial cannot be deserialized
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+
+    /// Raised when no candidate hash was a valid scalar. Each candidate is one
+    /// with probability above one half, so this does not happen in practice.
+    #[error("could not hash the metadata signature to a scalar")]
+    HashToScalarError,
+}
+
+impl<T> PartiallyBlindScheme for T
+where
+    T: SignatureScheme
+        + BlindScheme<Error = BlindError>
+        + BlindThresholdScheme<Error = BlindThresholdError<BlindError>>,
+{
+    type Error = PartiallyBlindError<T>;
+
+    fn metadata_partial(
+        private: &Share<Self::Private>,
+        metadata: &[u8],
+    ) -> Result<Partial, PartiallyBlindError<T>> {
+        Ok(T::partial_sign(private, &metadata_message(metadata))?)
+    }
+
+    fn metadata_tweak(
+        public: &Poly<Self::Public>,
+        metadata: &[u8],
+        partials: &[Partial],
+    ) -> Result<Self::Private, PartiallyBlindError<T>> {
+        let msg = metadata_message(metadata);
+        for partial in partials {
+            if T::partial_verify(public, &msg, partial).is_err() {
+                let index = serialization::deserialize::<Eval<Vec<u8>>>(partial)?;
+                return Err(PartiallyBlindError::InvalidPartial(index.index));
+            }
+        }
+
+        // Every threshold of valid partials recovers the same signature, so
+        // every member hashes the same tweak.
+        let signature = T::aggregate(public, partials)?;
+        T::verify(public.public_key(), &msg, &signature)
+            .map_err(PartiallyBlindError::SignatureError)?;
+
+        // Try-and-increment, as for a child key's tweak, so the tweak is
+        // uniform rather than biased toward small values.
+        for counter in 0u8..=255 {
+            let hash = blake2s_simd::Params::new()
+                .hash_length(32)
+                .personal(METADATA_DOMAIN)
+                .to_state()
+                .update(&[counter])
+                .update(&signature)
+                .finalize();
+            if let Some(tweak) = Self::Private::from_random_bytes(hash.as_bytes()) {
+                return Ok(tweak);
+            }
+        }
+
+        Err(PartiallyBlindError::HashToScalarError)
+    }
+
+    fn metadata_polynomial(
+        public: &Poly<Self::Public>,
+        tweak: &Self::Private,
+    ) -> Poly<Self::Public> {
+        // As for a child key, the tweak moves the constant term only, and the
+        // threshold is untouched.
+        let mut offset = Self::Public::one();
+        offset.mul(tweak);
+        let mut key = public.public_key().clone();
+        key.add(&offset);
+
+        let mut polynomial = public.clone();
+        polynomial.set(0, key);
+        polynomial
+    }
+
+    fn blind_msg_with_metadata<R: RngCore>(
+        msg: &[u8],
+        metadata: &[u8],
+        rng: &mut R,
+    ) -> Result<(Self::Token, Vec<u8>), PartiallyBlindError<T>> {
+        Ok(T::blind_msg(&framed(msg, metadata), rng)?)
+    }
+
+    fn sign_blind_partial_with_metadata(
+        private: &Share<Self::Private>,
+        tweak: &Self::Private,
+        blinded_msg: &[u8],
+    ) -> Result<Partial, PartiallyBlindError<T>> {
+        let mut tweaked = private.private.clone();
+        tweaked.add(tweak);
+        let share = Share {
+            index: private.index,
+            private: tweaked,
+        };
+        Ok(T::sign_blind_partial(&share, blinded_msg)?)
+    }
+
+    fn unblind_sig_with_metadata(
+        public: &Self::Public,
+        token: &Self::Token,
+        msg: &[u8],
+        metadata: &[u8],
+        blinded_signature: &[u8],
+    ) -> Result<Vec<u8>, PartiallyBlindError<T>> {
+        let signature = T::unblind_sig(token, blinded_signature)?;
+        Self::verify_with_metadata(public, msg, metadata, &signature)?;
+        Ok(signature)
+    }
+
+    fn verify_with_metadata(
+        public: &Self::Public,
+        msg: &[u8],
+        metadata: &[u8],
+        signature: &[u8],
+    ) -> Result<(), PartiallyBlindError<T>> {
+        T::verify(public, &framed(msg, metadata), signature)
+            .map_err(PartiallyBlindError::SignatureError)
+    }
+}
+
+/// The message the members sign to derive the metadata's tweak.
+fn metadata_message(metadata: &[u8]) -> Vec<u8> {
+    [METADATA_PREFIX, metadata].concat()
+}
+
+/// The message signed under a metadata key: the metadata, prefixed with its
+/// length so that no other split of the bytes frames the same message, and
+/// then the message itself.
+fn framed(msg: &[u8], metadata: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(8 + metadata.len() + msg.len());
+    framed.extend_from_slice(&(metadata.len() as u64).to_le_bytes());
+    framed.extend_from_slice(metadata);
+    framed.extend_from_slice(msg);
+    framed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{
+            bls::{G1Scheme, G2Scheme},
+            shares,
+        },
+    };
+    use rand::thread_rng;
+
+    fn tweak<T>(
+        shares: &[Share<T::Private>],
+        public: &Poly<T::Public>,
+        metadata: &[u8],
+    ) -> T::Private
+    where
+        T: PartiallyBlindScheme<Error = PartiallyBlindError<T>> + SignatureScheme,
+    {
+        let partials = shares
+            .iter()
+            .map(|share| T::metadata_partial(share, metadata).unwrap())
+            .collect::<Vec<_>>();
+        T::metadata_tweak(public, metadata, &partials).unwrap()
+    }
+
+    fn signs_under_metadata<T>()
+    where
+        T: PartiallyBlindScheme<Error = PartiallyBlindError<T>> + SignatureScheme,
+    {
+        let (shares, public) = shares::<T>(5, 3);
+        let (msg, metadata) = (b"token".as_slice(), b"epoch:1".as_slice());
+        let tweak = tweak::<T>(&shares, &public, metadata);
+        let key = T::metadata_polynomial(&public, &tweak);
+
+        let (token, blinded) =
+            T::blind_msg_with_metadata(msg, metadata, &mut thread_rng()).unwrap();
+        let partials = shares
+            .iter()
+            .map(|share| T::sign_blind_partial_with_metadata(share, &tweak, &blinded).unwrap())
+            .collect::<Vec<_>>();
+        for partial in &partials {
+            T::verify_blind_partial(&key, &blinded, partial).unwrap();
+        }
+
+        let blind_signature = T::aggregate(&key, &partials[2..]).unwrap();
+        let signature =
+            T::unblind_sig_with_metadata(key.public_key(), &token, msg, metadata, &blind_signature)
+                .unwrap();
+        T::verify_with_metadata(key.public_key(), msg, metadata, &signature).unwrap();
+
+        // neither the group key nor another metadata value accepts it
+        assert!(T::verify_with_metadata(public.public_key(), msg, metadata, &signature).is_err());
+        let other =
+            T::metadata_polynomial(&public, &self::tweak::<T>(&shares, &public, b"epoch:2"));
+        assert!(T::verify_with_metadata(other.public_key(), msg, b"epoch:2", &signature).is_err());
+        assert!(T::verify_with_metadata(key.public_key(), msg, b"epoch:2", &signature).is_err());
+    }
+
+    #[test]
+    fn signs_under_metadata_g1() {
+        signs_under_metadata::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn signs_under_metadata_g2() {
+        signs_under_metadata::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn every_threshold_of_members_derives_the_same_tweak() {
+        type T = G2Scheme<PCurve>;
+        let (shares, public) = shares::<T>(5, 3);
+        let partials = shares
+            .iter()
+            .map(|share| T::metadata_partial(share, b"epoch:1").unwrap())
+            .collect::<Vec<_>>();
+
+        let tweak = T::metadata_tweak(&public, b"epoch:1", &partials[..3]).unwrap();
+        assert_eq!(
+            tweak,
+            T::metadata_tweak(&public, b"epoch:1", &partials[2..]).unwrap()
+        );
+        assert_ne!(tweak, self::tweak::<T>(&shares, &public, b"epoch:2"));
+
+        assert!(matches!(
+            T::metadata_tweak(&public, b"epoch:1", &partials[..2]),
+            Err(PartiallyBlindError::ThresholdError(
+                ThresholdError::NotEnoughPartialSignatures(2, 3)
+            ))
+        ));
+        assert!(matches!(
+            T::metadata_tweak(&public, b"epoch:2", &partials[..3]),
+            Err(PartiallyBlindError::InvalidPartial(0))
+        ));
+    }
+
+    #[test]
+    fn a_signature_under_other_metadata_is_caught_when_unblinding() {
+        type T = G2Scheme<PCurve>;
+        let (shares, public) = shares::<T>(3, 2);
+        let key = T::metadata_polynomial(&public, &tweak::<T>(&shares, &public, b"tier:pro"));
+        let wrong = tweak::<T>(&shares, &public, b"tier:free");
+
+        let (token, blinded) =
+            T::blind_msg_with_metadata(b"msg", b"tier:pro", &mut thread_rng()).unwrap();
+        let partials = shares
+            .iter()
+            .map(|share| T::sign_blind_partial_with_metadata(share, &wrong, &blinded).unwrap())
+            .collect::<Vec<_>>();
+        let blind_signature = T::aggregate(&key, &partials).unwrap();
+
+        assert!(matches!(
+            T::unblind_sig_with_metadata(
+                key.public_key(),
+                &token,
+                b"msg",
+                b"tier:pro",
+                &blind_signature
+            ),
+            Err(PartiallyBlindError::SignatureError(_))
+        ));
+    }
+}
@@ -0,0 +1,293 @@
+//! Partial signatures carrying a DLEQ proof that they were made with the
+//! signer's share, for any [`Scheme`](crate::sig::Scheme).
+use crate::dleq::{DleqError, DleqProof};
+use crate::group::{Element, Point};
+use crate::poly::{Eval, Idx, Poly, PolyError};
+use crate::serialization;
+use crate::sig::tbls::Share;
+use crate::sig::{Partial, ProvenThresholdScheme, Scheme};
+use rand_core::RngCore;
+use thiserror::Error;
+
+/// Errors associated with proven partial signatures
+#[derive(Debug, Error)]
+pub enum PartialProofError {
+    /// Raised when a partial, its proof or a blinded message cannot be
+    /// (de)serialized. A plain partial without a proof is one.
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+
+    /// Raised when the message cannot be hashed to the curve
+    #[error("could not hash to curve")]
+    HashingError,
+
+    /// Raised when the message point is the identity element, which every
+    /// share signs to the identity
+    #[error("message point is the identity element")]
+    InvalidMessagePoint,
+
+    /// Raised when the proof of the partial at an index does not verify
+    #[error("invalid proof for the partial signature at index {0}")]
+    InvalidProof(Idx),
+
+    /// Raised when the partials cannot be aggregated, too few of them among
+    /// the most common case
+    #[error("could not aggregate the partial signatures: {0}")]
+    PolyError(#[from] PolyError),
+
+    /// Raised when proving fails for a reason other than the proof not holding
+    #[error(transparent)]
+    DleqError(#[from] DleqError),
+}
+
+/// A proven partial: the plain partial, with the signature serialized inside
+/// it as [`ThresholdScheme::partial_sign`](crate::sig::ThresholdScheme::partial_sign)
+/// does, and the proof after it.
+type ProvenPartial<S> = (Eval<Vec<u8>>, DleqProof<S>);
+
+impl<T: Scheme> ProvenThresholdScheme for T {
+    type Error = PartialProofError;
+
+    fn partial_sign_proven<R: RngCore>(
+        private: &Share<Self::Private>,
+        msg: &[u8],
+        rng: &mut R,
+    ) -> Result<Partial, PartialProofError> {
+        sign::<T, R>(private, &hash::<T>(msg)?, rng)
+    }
+
+    fn sign_blind_partial_proven<R: RngCore>(
+        private: &Share<Self::Private>,
+        blinded_msg: &[u8],
+        rng: &mut R,
+    ) -> Result<Partial, PartialProofError> {
+        sign::<T, R>(private, &blinded_point::<T>(blinded_msg)?, rng)
+    }
+
+    fn partial_verify_proof(
+        public: &Poly<Self::Public>,
+        msg: &[u8],
+        partial: &[u8],
+    ) -> Result<(), PartialProofError> {
+        check::<T>(public, &hash::<T>(msg)?, partial).map(|_| ())
+    }
+
+    fn verify_blind_partial_proof(
+        public: &Poly<Self::Public>,
+        blinded_msg: &[u8],
+        partial: &[u8],
+    ) -> Result<(), PartialProofError> {
+        check::<T>(public, &blinded_point::<T>(blinded_msg)?, partial).map(|_| ())
+    }
+
+    fn aggregate_proven(
+        public: &Poly<Self::Public>,
+        msg: &[u8],
+        partials: &[Partial],
+    ) -> Result<Vec<u8>, PartialProofError> {
+        aggregate::<T>(public, &hash::<T>(msg)?, partials)
+    }
+
+    fn aggregate_blind_proven(
+        public: &Poly<Self::Public>,
+        blinded_msg: &[u8],
+        partials: &[Partial],
+    ) -> Result<Vec<u8>, PartialProofError> {
+        aggregate::<T>(public, &blinded_point::<T>(blinded_msg)?, partials)
+    }
+}
+
+/// Hashes a message to the signature group, as signing does.
+fn hash<T: Scheme>(msg: &[u8]) -> Result<T::Signature, PartialProofError> {
+    let mut point = T::Signature::zero();
+    point
+        .map(msg)
+        .map_err(|_| PartialProofError::HashingError)?;
+    Ok(point)
+}
+
+/// Deserializes a blinded message, which is already a signature-group point.
+fn blinded_point<T: Scheme>(blinded_msg: &[u8]) -> Result<T::Signature, PartialProofError> {
+    let point: T::Signature = serialization::deserialize(blinded_msg)?;
+    if point == T::Signature::zero() {
+        return Err(PartialProofError::InvalidMessagePoint);
+    }
+    Ok(point)
+}
+
+fn sign<T: Scheme, R: RngCore>(
+    private: &Share<T::Private>,
+    point: &T::Signature,
+    rng: &mut R,
+) -> Result<Partial, PartialProofError> {
+    let mut signature = point.clone();
+    signature.mul(&private.private);
+    let proof = DleqProof::prove(
+        &private.private,
+        &T::Public::one(),
+        point,
+        &private.index.to_le_bytes(),
+        rng,
+    )?;
+
+    let partial = Eval {
+        value: bincode::serialize(&signature)?,
+        index: private.index,
+    };
+    Ok(bincode::serialize(&(partial, proof))?)
+}
+
+/// Checks a proven partial's proof and returns its signature.
+fn check<T: Scheme>(
+    public: &Poly<T::Public>,
+    point: &T::Signature,
+    partial: &[u8],
+) -> Result<Eval<T::Signature>, PartialProofError> {
+    let (partial, proof): ProvenPartial<T::Private> = serialization::deserialize(partial)?;
+    let signature: T::Signature = serialization::deserialize(&partial.value)?;
+
+    proof
+        .verify(
+            &T::Public::one(),
+            &public.eval(partial.index).value,
+            point,
+            &signature,
+            &partial.index.to_le_bytes(),
+        )
+        .map_err(|_| PartialProofError::InvalidProof(partial.index))?;
+
+    Ok(Eval {
+        index: partial.index,
+        value: signature,
+    })
+}
+
+fn aggregate<T: Scheme>(
+    public: &Poly<T::Public>,
+    point: &T::Signature,
+    partials: &[Partial],
+) -> Result<Vec<u8>, PartialProofError> {
+    let signatures = partials
+        .iter()
+        .map(|partial| check::<T>(public, point, partial))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let signature = Poly::<T::Signature>::recover(public.degree() + 1, signatures)?;
+    Ok(bincode::serialize(&signature)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{
+            BlindScheme, BlindThresholdScheme, SignatureScheme, ThresholdScheme,
+            bls::{G1Scheme, G2Scheme},
+            shares,
+        },
+    };
+    use rand::thread_rng;
+
+    fn proven_partials_aggregate<T>()
+    where
+        T: ProvenThresholdScheme<Error = PartialProofError> + ThresholdScheme + SignatureScheme,
+    {
+        let (shares, public) = shares::<T>(5, 3);
+        let msg = b"proven";
+        let partials = shares
+            .iter()
+            .map(|share| T::partial_sign_proven(share, msg, &mut thread_rng()).unwrap())
+            .collect::<Vec<_>>();
+
+        for (share, partial) in shares.iter().zip(&partials) {
+            T::partial_verify_proof(&public, msg, partial).unwrap();
+            // the proof rides after a plain partial, which the existing
+            // methods read unchanged
+            T::partial_verify(&public, msg, partial).unwrap();
+            let plain = T::partial_sign(share, msg).unwrap();
+            assert_eq!(partial[..plain.len()], plain[..]);
+            assert_eq!(partial.len(), plain.len() + 64);
+        }
+
+        let signature = T::aggregate_proven(&public, msg, &partials[1..4]).unwrap();
+        T::verify(public.public_key(), msg, &signature).unwrap();
+        assert_eq!(signature, T::aggregate(&public, &partials[..3]).unwrap());
+    }
+
+    #[test]
+    fn proven_partials_aggregate_g1() {
+        proven_partials_aggregate::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn proven_partials_aggregate_g2() {
+        proven_partials_aggregate::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn proven_blind_partials_aggregate() {
+        type T = G2Scheme<PCurve>;
+        let (shares, public) = shares::<T>(4, 3);
+        let msg = b"blind and proven";
+        let (token, blinded) = T::blind_msg(msg, &mut thread_rng()).unwrap();
+
+        let partials = shares
+            .iter()
+            .map(|share| T::sign_blind_partial_proven(share, &blinded, &mut thread_rng()).unwrap())
+            .collect::<Vec<_>>();
+        for partial in &partials {
+            T::verify_blind_partial_proof(&public, &blinded, partial).unwrap();
+            T::verify_blind_partial(&public, &blinded, partial).unwrap();
+        }
+
+        let blind_signature = T::aggregate_blind_proven(&public, &blinded, &partials).unwrap();
+        let signature = T::unblind_sig(&token, &blind_signature).unwrap();
+        T::verify(public.public_key(), msg, &signature).unwrap();
+    }
+
+    #[test]
+    fn a_partial_made_with_another_share_is_caught() {
+        type T = G2Scheme<PCurve>;
+        let (shares, public) = shares::<T>(4, 3);
+        let msg = b"proven";
+
+        // share 0's key, claiming index 1, proves against share 1's public share
+        let impostor = Share {
+            index: 1,
+            private: shares[0].private,
+        };
+        let forged = T::partial_sign_proven(&impostor, msg, &mut thread_rng()).unwrap();
+        assert!(matches!(
+            T::partial_verify_proof(&public, msg, &forged),
+            Err(PartialProofError::InvalidProof(1))
+        ));
+
+        let partials = [
+            T::partial_sign_proven(&shares[0], msg, &mut thread_rng()).unwrap(),
+            forged,
+            T::partial_sign_proven(&shares[2], msg, &mut thread_rng()).unwrap(),
+        ];
+        assert!(matches!(
+            T::aggregate_proven(&public, msg, &partials),
+            Err(PartialProofError::InvalidProof(1))
+        ));
+
+        // a proof for one message does not carry over to another
+        assert!(matches!(
+            T::partial_verify_proof(&public, b"other", &partials[0]),
+            Err(PartialProofError::InvalidProof(0))
+        ));
+    }
+
+    #[test]
+    fn a_plain_partial_has_no_proof_to_check() {
+        type T = G2Scheme<PCurve>;
+        let (shares, public) = shares::<T>(3, 2);
+        let plain = T::partial_sign(&shares[0], b"msg").unwrap();
+        assert!(matches!(
+            T::partial_verify_proof(&public, b"msg", &plain),
+            Err(PartialProofError::BincodeError(_))
+        ));
+    }
+}
@@ -198,3 +198,370 @@ pub trait BlindThresholdScheme: BlindScheme {
         blind_partial: &[u8],
     ) -> Result<(), <Self as BlindThresholdScheme>::Error>;
 }
+
+/// VerifiableBlindScheme lets a requester prove to threshold signers what it
+/// is asking them to sign, without saying which message it is.
+///
+/// [`BlindThresholdScheme::sign_blind_partial`] signs any point it is sent.
+/// The signer cannot tell a blinded message from an arbitrary point, so the
+/// service doubles as an oracle that multiplies any point by the share. Here
+/// the requester proves in zero knowledge that the blinded message is `r·H(m)`
+/// for a blinding factor `r` it knows and a message `m` from a public list of
+/// allowed messages, and the signer checks the proof before signing.
+///
+/// The predicate is membership in that list, and the list is also the
+/// anonymity set: the signer learns that the message is one of them and
+/// nothing about which. A list of one message hides nothing about the message,
+/// but still keeps the signature unlinkable to the request. Proving anything
+/// about a message outside a list needs the hash to the curve inside the
+/// proof, which this does not attempt.
+///
+/// ```
+/// use threshold_bls::{
+///     poly::{Idx, Poly},
+///     schemes::bls12_377::G2Scheme as SigScheme,
+///     sig::{Scheme, Share, VerifiableBlindScheme},
+/// };
+///
+/// let private = Poly::<<SigScheme as Scheme>::Private>::new(0);
+/// let share = Share { index: 0, private: private.eval(0).value };
+/// let allowed: [&[u8]; 3] = [b"tier:free", b"tier:pro", b"tier:team"];
+///
+/// // the token unblinds the signature afterwards, as with `blind_msg`
+/// let (token, blinded, proof) =
+///     SigScheme::blind_msg_with_proof(b"tier:pro", &allowed, &mut rand::thread_rng()).unwrap();
+///
+/// // the signer learns only that the request is for one of the tiers
+/// let partial =
+///     SigScheme::sign_blind_partial_verified(&share, &blinded, &allowed, &proof).unwrap();
+/// ```
+pub trait VerifiableBlindScheme: BlindThresholdScheme {
+    /// Error produced when proving, verifying or signing
+    type Error: Error;
+
+    /// Blinds `msg` like [`BlindScheme::blind_msg`], and proves that the
+    /// blinded message is a blinding of one of `allowed`, which must contain
+    /// `msg`. Returns the token, the blinded message and the proof.
+    #[allow(clippy::type_complexity)]
+    fn blind_msg_with_proof<R: RngCore>(
+        msg: &[u8],
+        allowed: &[&[u8]],
+        rng: &mut R,
+    ) -> Result<(Self::Token, Vec<u8>, Vec<u8>), <Self as VerifiableBlindScheme>::Error>;
+
+    /// Checks that `blinded_msg` is a blinding of one of `allowed`.
+    fn verify_blinding(
+        blinded_msg: &[u8],
+        allowed: &[&[u8]],
+        proof: &[u8],
+    ) -> Result<(), <Self as VerifiableBlindScheme>::Error>;
+
+    /// Checks the proof like [`VerifiableBlindScheme::verify_blinding`], and
+    /// only if it holds partially signs the blinded message like
+    /// [`BlindThresholdScheme::sign_blind_partial`].
+    fn sign_blind_partial_verified(
+        private: &Share<Self::Private>,
+        blinded_msg: &[u8],
+        allowed: &[&[u8]],
+        proof: &[u8],
+    ) -> Result<Partial, <Self as VerifiableBlindScheme>::Error>;
+}
+
+/// PartiallyBlindScheme binds public metadata, such as an epoch or a quota
+/// tier, into blind threshold signatures while the message stays blind.
+///
+/// Signing for some metadata uses the group key offset by a tweak, so each
+/// metadata value has its own public key, and a signature verifies only under
+/// the key for the metadata it was issued with. The tweak is not public: it
+/// is hashed from the group's threshold signature on the metadata, which the
+/// members compute among themselves with
+/// [`PartiallyBlindScheme::metadata_partial`] and never publish. That is what
+/// keeps signatures for different metadata apart. A public tweak, as
+/// [`HierarchicalScheme`] uses, would let anyone who knows the message shift
+/// a signature from one metadata value's key to another's, since BLS
+/// signatures are linear in the key.
+///
+/// Verifiers cannot derive a metadata public key themselves; the group
+/// publishes [`PartiallyBlindScheme::metadata_polynomial`] for each value, as
+/// it publishes its polynomial. Every member learns the tweaks, so a single
+/// dishonest member can move signatures between metadata values, though not
+/// sign anything new.
+///
+/// ```
+/// use threshold_bls::{
+///     poly::{Idx, Poly},
+///     schemes::bls12_377::G2Scheme as SigScheme,
+///     sig::{BlindThresholdScheme, PartiallyBlindScheme, Scheme, Share, ThresholdScheme},
+/// };
+///
+/// let private = Poly::<<SigScheme as Scheme>::Private>::new(1);
+/// let polynomial = private.commit::<<SigScheme as Scheme>::Public>();
+/// let shares = (0..3)
+///     .map(|i| private.eval(i as Idx))
+///     .map(|eval| Share { index: eval.index, private: eval.value })
+///     .collect::<Vec<_>>();
+///
+/// // the members derive the tweak for an epoch, and publish its key
+/// let metadata = b"epoch:2026-10";
+/// let partials = shares
+///     .iter()
+///     .map(|share| SigScheme::metadata_partial(share, metadata).unwrap())
+///     .collect::<Vec<_>>();
+/// let tweak = SigScheme::metadata_tweak(&polynomial, metadata, &partials).unwrap();
+/// let epoch_key = SigScheme::metadata_polynomial(&polynomial, &tweak);
+///
+/// // a requester blinds a message for the epoch, and the members sign it
+/// let rng = &mut rand::thread_rng();
+/// let (token, blinded) = SigScheme::blind_msg_with_metadata(b"token", metadata, rng).unwrap();
+/// let partials = shares
+///     .iter()
+///     .map(|share| SigScheme::sign_blind_partial_with_metadata(share, &tweak, &blinded).unwrap())
+///     .collect::<Vec<_>>();
+/// let blind_signature = SigScheme::aggregate(&epoch_key, &partials).unwrap();
+///
+/// let signature = SigScheme::unblind_sig_with_metadata(
+///     epoch_key.public_key(),
+///     &token,
+///     b"token",
+///     metadata,
+///     &blind_signature,
+/// )
+/// .unwrap();
+/// SigScheme::verify_with_metadata(epoch_key.public_key(), b"token", metadata, &signature).unwrap();
+/// ```
+pub trait PartiallyBlindScheme: BlindThresholdScheme {
+    /// Error produced when deriving a metadata key, signing or verifying
+    type Error: Error;
+
+    /// A member's partial signature on the metadata, which the members send
+    /// one another to derive its tweak. Not to be sent anywhere else: a
+    /// threshold of them gives the tweak to whoever holds them.
+    fn metadata_partial(
+        private: &Share<Self::Private>,
+        metadata: &[u8],
+    ) -> Result<Partial, <Self as PartiallyBlindScheme>::Error>;
+
+    /// Verifies a threshold of partials on the metadata, aggregates them and
+    /// hashes the result to the metadata's tweak. Every member derives the
+    /// same tweak, whichever partials it uses.
+    fn metadata_tweak(
+        public: &Poly<Self::Public>,
+        metadata: &[u8],
+        partials: &[Partial],
+    ) -> Result<Self::Private, <Self as PartiallyBlindScheme>::Error>;
+
+    /// The public polynomial of the metadata's key. Its partials verify and
+    /// aggregate against it, and its public key verifies the signatures.
+    fn metadata_polynomial(
+        public: &Poly<Self::Public>,
+        tweak: &Self::Private,
+    ) -> Poly<Self::Public>;
+
+    /// Blinds `msg` for signing under `metadata`, like [`BlindScheme::blind_msg`].
+    fn blind_msg_with_metadata<R: RngCore>(
+        msg: &[u8],
+        metadata: &[u8],
+        rng: &mut R,
+    ) -> Result<(Self::Token, Vec<u8>), <Self as PartiallyBlindScheme>::Error>;
+
+    /// Partially signs a blinded message with a member's share of the key for
+    /// the metadata whose tweak is given.
+    fn sign_blind_partial_with_metadata(
+        private: &Share<Self::Private>,
+        tweak: &Self::Private,
+        blinded_msg: &[u8],
+    ) -> Result<Partial, <Self as PartiallyBlindScheme>::Error>;
+
+    /// Unblinds an aggregated blind signature like [`BlindScheme::unblind_sig`],
+    /// and checks that it is a signature on `msg` under `metadata`'s public key,
+    /// so that a signer who used other metadata is caught by the requester.
+    fn unblind_sig_with_metadata(
+        public: &Self::Public,
+        token: &Self::Token,
+        msg: &[u8],
+        metadata: &[u8],
+        blinded_signature: &[u8],
+    ) -> Result<Vec<u8>, <Self as PartiallyBlindScheme>::Error>;
+
+    /// Verifies a signature on `msg` under `metadata`, with the public key of
+    /// the metadata's polynomial.
+    fn verify_with_metadata(
+        public: &Self::Public,
+        msg: &[u8],
+        metadata: &[u8],
+        signature: &[u8],
+    ) -> Result<(), <Self as PartiallyBlindScheme>::Error>;
+}
+
+/// HierarchicalScheme derives child keys from a parent key without running key
+/// generation again, so that one threshold master key can serve many
+/// applications or users.
+///
+/// A child key is the parent offset by a tweak: a scalar hashed from the parent
+/// public key and a context string. Everything needed to derive a child is
+/// either public or already held by the party deriving it — a verifier derives
+/// the child public key and polynomial from the parent polynomial alone, and
+/// each signer derives its child share from its own share — so no party has to
+/// talk to another. Child shares partially sign, and their partials verify
+/// against the child polynomial and aggregate under it, with the existing
+/// [`ThresholdScheme`] methods.
+///
+/// ```
+/// use threshold_bls::{
+///     poly::{Idx, Poly},
+///     schemes::bls12_377::G2Scheme as SigScheme,
+///     sig::{HierarchicalScheme, Scheme, Share, SignatureScheme, ThresholdScheme},
+/// };
+///
+/// let private = Poly::<<SigScheme as Scheme>::Private>::new(2);
+/// let polynomial = private.commit::<<SigScheme as Scheme>::Public>();
+/// let shares = (0..3)
+///     .map(|i| private.eval(i as Idx))
+///     .map(|eval| Share { index: eval.index, private: eval.value })
+///     .collect::<Vec<_>>();
+///
+/// // anyone derives the child polynomial, each signer its own child share
+/// let child = SigScheme::child_polynomial(&polynomial, b"app:payments").unwrap();
+/// let partials = shares
+///     .iter()
+///     .map(|share| SigScheme::child_share(&polynomial, share, b"app:payments").unwrap())
+///     .map(|share| SigScheme::partial_sign(&share, b"hello").unwrap())
+///     .collect::<Vec<_>>();
+///
+/// let signature = SigScheme::aggregate(&child, &partials).unwrap();
+/// SigScheme::verify(child.public_key(), b"hello", &signature).unwrap();
+/// ```
+///
+/// The offset is additive, and so related keys are related signatures: given
+/// a signature on a message under one key in a hierarchy, anyone who knows the
+/// message can compute a signature on the same message under the parent and
+/// every sibling, because the tweaks are public. The children separate keys,
+/// not signing domains. A caller that needs a signature under one child to mean
+/// nothing under another has to put the context into the message it signs.
+pub trait HierarchicalScheme: Scheme {
+    /// Error produced when deriving a child key
+    type Error: Error;
+
+    /// Returns the scalar that separates the child named by `context` from the
+    /// parent with the given public key.
+    fn child_tweak(parent: &Self::Public, context: &[u8]) -> Result<Self::Private, Self::Error>;
+
+    /// Derives the child private key of a single, non-threshold keypair.
+    fn child_private(
+        parent: &Self::Public,
+        private: &Self::Private,
+        context: &[u8],
+    ) -> Result<Self::Private, Self::Error>;
+
+    /// Derives the child public key. The result is the public key of
+    /// [`HierarchicalScheme::child_private`], and the constant term of
+    /// [`HierarchicalScheme::child_polynomial`].
+    fn child_public(parent: &Self::Public, context: &[u8]) -> Result<Self::Public, Self::Error>;
+
+    /// Derives the child public polynomial from the parent's. It has the
+    /// parent's degree, so the child keeps the parent's threshold.
+    fn child_polynomial(
+        parent: &Poly<Self::Public>,
+        context: &[u8],
+    ) -> Result<Poly<Self::Public>, Self::Error>;
+
+    /// Derives a signer's child share from its share of the parent, keeping
+    /// the index. The tweak comes from the parent polynomial's public key, so
+    /// every signer derives the same child.
+    fn child_share(
+        parent: &Poly<Self::Public>,
+        share: &Share<Self::Private>,
+        context: &[u8],
+    ) -> Result<Share<Self::Private>, Self::Error>;
+}
+
+/// ProvenThresholdScheme attaches a proof to a partial signature that it was
+/// made with the share the public polynomial commits to, so that a combiner can
+/// check partials with a few scalar multiplications instead of the two pairings
+/// [`ThresholdScheme::partial_verify`] and
+/// [`BlindThresholdScheme::verify_blind_partial`] each cost.
+///
+/// The proof is a Chaum–Pedersen [`DleqProof`](crate::dleq::DleqProof) that
+/// the signature is the message point times the same scalar as the public
+/// share is the generator. A proven partial is the plain partial with the
+/// 64-byte proof appended. Trailing bytes are ignored when a partial is
+/// deserialized, so a proven partial is also a valid plain one: the existing
+/// methods verify and aggregate it unchanged, and a signer can send proofs to
+/// combiners that do not check them.
+///
+/// ```
+/// use threshold_bls::{
+///     poly::{Idx, Poly},
+///     schemes::bls12_377::G2Scheme as SigScheme,
+///     sig::{ProvenThresholdScheme, Scheme, Share, SignatureScheme},
+/// };
+///
+/// let private = Poly::<<SigScheme as Scheme>::Private>::new(2);
+/// let polynomial = private.commit::<<SigScheme as Scheme>::Public>();
+/// let partials = (0..3)
+///     .map(|i| private.eval(i as Idx))
+///     .map(|eval| Share { index: eval.index, private: eval.value })
+///     .map(|share| SigScheme::partial_sign_proven(&share, b"hello", &mut rand::thread_rng()))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// // the combiner checks every proof, hashing the message once, and aggregates
+/// let signature = SigScheme::aggregate_proven(&polynomial, b"hello", &partials).unwrap();
+/// SigScheme::verify(polynomial.public_key(), b"hello", &signature).unwrap();
+/// ```
+pub trait ProvenThresholdScheme: Scheme {
+    /// Error produced when proving, checking or aggregating proven partials
+    type Error: Error;
+
+    /// Partially signs a message like [`ThresholdScheme::partial_sign`], and
+    /// attaches a proof drawn with `rng`.
+    fn partial_sign_proven<R: RngCore>(
+        private: &Share<Self::Private>,
+        msg: &[u8],
+        rng: &mut R,
+    ) -> Result<Partial, Self::Error>;
+
+    /// Partially signs a blinded message like
+    /// [`BlindThresholdScheme::sign_blind_partial`], and attaches a proof drawn
+    /// with `rng`.
+    fn sign_blind_partial_proven<R: RngCore>(
+        private: &Share<Self::Private>,
+        blinded_msg: &[u8],
+        rng: &mut R,
+    ) -> Result<Partial, Self::Error>;
+
+    /// Checks the proof of a proven partial signature on a message against the
+    /// public polynomial.
+    fn partial_verify_proof(
+        public: &Poly<Self::Public>,
+        msg: &[u8],
+        partial: &[u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Checks the proof of a proven partial signature on a blinded message
+    /// against the public polynomial.
+    fn verify_blind_partial_proof(
+        public: &Poly<Self::Public>,
+        blinded_msg: &[u8],
+        partial: &[u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Checks the proof of every partial on a message and aggregates them.
+    /// Unlike [`ThresholdScheme::aggregate`], a partial that was not made
+    /// with its share fails the call, naming its index, instead of producing
+    /// a signature that does not verify.
+    fn aggregate_proven(
+        public: &Poly<Self::Public>,
+        msg: &[u8],
+        partials: &[Partial],
+    ) -> Result<Vec<u8>, Self::Error>;
+
+    /// Checks the proof of every partial on a blinded message and aggregates
+    /// them into a blind signature, as [`ProvenThresholdScheme::aggregate_proven`]
+    /// does for a message in the clear.
+    fn aggregate_blind_proven(
+        public: &Poly<Self::Public>,
+        blinded_msg: &[u8],
+        partials: &[Partial],
+    ) -> Result<Vec<u8>, Self::Error>;
+}
@@ -1,10 +1,13 @@
 //! Threshold Signatures implementation for any type which implements
 //! [`SignatureScheme`](../trait.SignatureScheme.html)
+use crate::group::{Element, Scalar};
 use crate::poly::{Eval, Idx, Poly, PolyError};
 use crate::serialization;
 use crate::sig::{Partial, SignatureScheme, ThresholdScheme};
+use rand_core::RngCore;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use zeroize::{Zeroize, Zeroizing};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 /// A private share which is part of the threshold signing key
@@ -36,6 +39,151 @@ pub enum ThresholdError<I: SignatureScheme> {
     /// were fewer than the threshold
     #[error("not enough partial signatures: {0}/{1}")]
     NotEnoughPartialSignatures(usize, usize),
+
+    /// InvalidDealing is raised when a key is split with a threshold that is not
+    /// between 1 and the number of shares, or into more shares than there are
+    /// indices
+    #[error("cannot deal {n} shares with a threshold of {t}")]
+    InvalidDealing {
+        /// The number of shares asked for
+        n: usize,
+        /// The threshold asked for
+        t: usize,
+    },
+
+    /// InvalidShare is raised when a share handed to `reconstruct` is not the
+    /// one the public polynomial commits to at its index
+    #[error("share {0} does not match the public polynomial")]
+    InvalidShare(Idx),
+
+    /// KeyMismatch is raised when the key `reconstruct` recovered is not the
+    /// polynomial's public key, and is withheld
+    #[error("the reconstructed private key does not match the public key")]
+    KeyMismatch,
+}
+
+/// Splits an existing private key into `n` shares, any `t` of which sign for
+/// it, and returns them with the public polynomial they verify against.
+///
+/// The polynomial's free coefficient is `private`, so the committee's public
+/// key is the key's own, and the signatures it aggregates verify wherever the
+/// key's did. This is how a single signer becomes a threshold committee
+/// without its users changing keys. The other coefficients are drawn from
+/// `rng`. Whoever runs this holds the whole key, as they did before the split;
+/// once the shares are handed out, the key should be destroyed.
+#[allow(clippy::type_complexity)]
+pub fn deal<S: SignatureScheme, R: RngCore>(
+    private: &S::Private,
+    n: usize,
+    t: usize,
+    rng: &mut R,
+) -> Result<(Vec<Share<S::Private>>, Poly<S::Public>), ThresholdError<S>> {
+    if t == 0 || t > n || n - 1 > Idx::MAX as usize {
+        return Err(ThresholdError::InvalidDealing { n, t });
+    }
+
+    let poly = Poly::new_with_secret_from(t - 1, private.clone(), rng);
+    let shares = (0..n)
+        .map(|i| poly.eval(i as Idx))
+        .map(|eval| Share {
+            index: eval.index,
+            private: eval.value,
+        })
+        .collect();
+    Ok((shares, poly.commit()))
+}
+
+/// Recombines `shares` into the private key of the committee `public` belongs
+/// to, for disaster recovery.
+///
+/// Unlike interpolating `Eval`s with `Poly::recover`, this refuses shares it
+/// cannot vouch for: every share is checked against `public` before any is
+/// used, the threshold is the polynomial's own rather than the caller's, and
+/// the key is only returned if it is the polynomial's public key. A wrong
+/// share therefore fails with its index instead of yielding a key that signs
+/// for nobody.
+///
+/// The key is returned in a `Zeroizing` wrapper, cleared when it is dropped,
+/// and the copies of the shares made while interpolating are cleared too.
+/// Anything the caller copies out of it is theirs to clear.
+pub fn reconstruct<S: SignatureScheme>(
+    public: &Poly<S::Public>,
+    shares: &[Share<S::Private>],
+) -> Result<Zeroizing<S::Private>, ThresholdError<S>>
+where
+    S::Private: Zeroize,
+{
+    for share in shares {
+        let mut expected = S::Public::one();
+        expected.mul(&share.private);
+        if expected != public.eval(share.index).value {
+            return Err(ThresholdError::InvalidShare(share.index));
+        }
+    }
+
+    // Interpolated here rather than by `Poly::recover`, which would leave
+    // copies of the shares behind. Only the indices and Lagrange coefficients,
+    // which are public, are not cleared.
+    let threshold = public.degree() + 1;
+    let mut indices = shares.iter().map(|share| share.index).collect::<Vec<_>>();
+    indices.sort_unstable();
+    indices.dedup();
+    if indices.len() < threshold {
+        return Err(PolyError::InvalidRecovery(indices.len(), threshold).into());
+    }
+    indices.truncate(threshold);
+
+    let mut private = Zeroizing::new(S::Private::zero());
+    for &index in &indices {
+        let share = shares
+            .iter()
+            .find(|share| share.index == index)
+            .expect("the indices are the shares'");
+        let mut term = Zeroizing::new(share.private.clone());
+        term.mul(&lagrange_at(index, &S::Private::zero(), &indices)?);
+        private.add(&term);
+    }
+
+    let mut recovered = S::Public::one();
+    recovered.mul(&private);
+    if &recovered != public.public_key() {
+        return Err(ThresholdError::KeyMismatch);
+    }
+
+    Ok(private)
+}
+
+/// The Lagrange coefficient of `index` among `indices` for interpolating at
+/// the x-coordinate `at`, which is zero for the secret. Indices are evaluated
+/// at `index + 1`, as in `Poly::recover`.
+pub(crate) fn lagrange_at<C: Scalar<RHS = C>>(
+    index: Idx,
+    at: &C,
+    indices: &[Idx],
+) -> Result<C, PolyError> {
+    let x = |i: Idx| {
+        let mut x = C::zero();
+        x.set_int(u64::from(i) + 1);
+        x
+    };
+    let xi = x(index);
+
+    let mut num = C::one();
+    let mut den = C::one();
+    for &j in indices.iter().filter(|&&j| j != index) {
+        let xj = x(j);
+
+        let mut tmp = at.clone();
+        tmp.sub(&xj);
+        num.mul(&tmp);
+
+        let mut tmp = xi.clone();
+        tmp.sub(&xj);
+        den.mul(&tmp);
+    }
+    num.mul(&den.inverse().ok_or(PolyError::NoInverse)?);
+
+    Ok(num)
 }
 
 impl<I: SignatureScheme> ThresholdScheme for I {
@@ -318,4 +466,95 @@ mod tests {
         let empty = serialization::deserialize::<Poly<T::Public>>(&[0u8; 8]);
         assert!(empty.is_err(), "a polynomial with no coefficients parsed");
     }
+
+    fn an_existing_key_keeps_signing_after_the_split<T>()
+    where
+        T: SignatureScheme + ThresholdScheme<Error = ThresholdError<T>>,
+    {
+        let rng = &mut rand::thread_rng();
+        let (private, public) = T::keypair(rng);
+        let msg = b"signed before and after";
+        let before = T::sign(&private, msg).unwrap();
+
+        let (shares, poly) = deal::<T, _>(&private, 5, 3, rng).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert_eq!(poly.public_key(), &public);
+
+        let partials = shares[2..]
+            .iter()
+            .map(|share| T::partial_sign(share, msg).unwrap())
+            .collect::<Vec<_>>();
+        let after = T::aggregate(&poly, &partials).unwrap();
+        T::verify(&public, msg, &after).unwrap();
+        // BLS is deterministic, so it is the very signature the key made
+        assert_eq!(after, before);
+
+        for (n, t) in [(5, 0), (3, 4), (0, 0)] {
+            assert!(matches!(
+                deal::<T, _>(&private, n, t, rng),
+                Err(ThresholdError::InvalidDealing { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn an_existing_key_keeps_signing_after_the_split_g1() {
+        an_existing_key_keeps_signing_after_the_split::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn an_existing_key_keeps_signing_after_the_split_g2() {
+        an_existing_key_keeps_signing_after_the_split::<G2Scheme<PCurve>>();
+    }
+
+    fn reconstruction_is_guarded<T>()
+    where
+        T: SignatureScheme,
+        T::Private: Zeroize,
+    {
+        let rng = &mut rand::thread_rng();
+        let (private, _) = T::keypair(rng);
+        let (mut shares, poly) = deal::<T, _>(&private, 5, 3, rng).unwrap();
+
+        // any `t` shares, in any order, give the key back
+        let picked = [shares[4].clone(), shares[0].clone(), shares[2].clone()];
+        assert_eq!(*reconstruct::<T>(&poly, &picked).unwrap(), private);
+        assert_eq!(*reconstruct::<T>(&poly, &shares).unwrap(), private);
+
+        // a repeated share does not count twice towards the threshold
+        let repeated = [shares[1].clone(), shares[1].clone(), shares[3].clone()];
+        assert!(matches!(
+            reconstruct::<T>(&poly, &repeated),
+            Err(ThresholdError::PolyError(PolyError::InvalidRecovery(2, 3)))
+        ));
+        assert!(matches!(
+            reconstruct::<T>(&poly, &shares[..2]),
+            Err(ThresholdError::PolyError(PolyError::InvalidRecovery(2, 3)))
+        ));
+
+        // a share that is not the polynomial's is named, wherever it sits
+        shares[3].private.add(&<T as Scheme>::Private::one());
+        assert!(matches!(
+            reconstruct::<T>(&poly, &shares),
+            Err(ThresholdError::InvalidShare(3))
+        ));
+
+        // shares of another committee do not reconstruct this one's key
+        let (other, _) = T::keypair(rng);
+        let (other_shares, _) = deal::<T, _>(&other, 5, 3, rng).unwrap();
+        assert!(matches!(
+            reconstruct::<T>(&poly, &other_shares[..3]),
+            Err(ThresholdError::InvalidShare(0))
+        ));
+    }
+
+    #[test]
+    fn reconstruction_is_guarded_g1() {
+        reconstruction_is_guarded::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn reconstruction_is_guarded_g2() {
+        reconstruction_is_guarded::<G2Scheme<PCurve>>();
+    }
 }
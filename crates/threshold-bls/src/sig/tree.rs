@@ -0,0 +1,476 @@
+//! Aggregating [`Certificate`]s up a tree of validators, so that no single
+//! aggregator has to collect every signature, in the manner of Handel.
+//!
+//! The validators are the leaves of a binary tree, by position in their list.
+//! At level `l`, a validator's peers are the validators of the sibling of its
+//! subtree of height `l - 1`: at level 1 its neighbour, at level 2 the pair
+//! next to its pair, and so on up to the root, `Tree::levels` levels. Each
+//! validator runs a [`Node`]: it sends each level's peers the aggregate of
+//! everything it holds below that level, verifies what its peers send it, and
+//! keeps the best combination it can make at each level. Once every level is
+//! complete, its [`Node::aggregate`] covers the whole list, having received
+//! `log2(n)` aggregates rather than `n` signatures.
+//!
+//! Everything here is data: moving certificates between nodes, and deciding
+//! when to, is the caller's.
+use crate::group::Element;
+use crate::sig::SignatureScheme;
+use crate::sig::certificate::{self, Certificate, CertificateError};
+use std::ops::Range;
+use thiserror::Error;
+
+/// The shape of the aggregation tree over a list of validators
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tree {
+    validators: usize,
+}
+
+/// Errors associated with tree aggregation
+#[derive(Debug, Error)]
+pub enum TreeError {
+    /// Raised when a node is asked for at a position past the end of the
+    /// validator list
+    #[error("there is no validator {0} in the tree")]
+    UnknownValidator(usize),
+
+    /// Raised when a level is zero, which is the node's own signature, or
+    /// above the root
+    #[error("the tree has no level {0}")]
+    NoSuchLevel(usize),
+
+    /// Raised when a certificate received at a level is signed by a validator
+    /// who is not among the node's peers at that level
+    #[error("validator {validator} is not a peer at level {level}")]
+    NotAPeer {
+        /// The level the certificate was received at
+        level: usize,
+        /// The first signer outside the level's peers
+        validator: usize,
+    },
+
+    /// Raised when a certificate is verified against a validator list of
+    /// another length than the tree's
+    #[error("{0} public keys for a tree of {1} validators")]
+    WrongValidatorCount(usize, usize),
+
+    /// Raised when a certificate does not verify, or cannot be created
+    #[error(transparent)]
+    CertificateError(#[from] CertificateError),
+}
+
+impl Tree {
+    /// The tree over a list of `validators` validators
+    pub fn new(validators: usize) -> Self {
+        Self { validators }
+    }
+
+    /// The number of validators
+    pub fn validators(&self) -> usize {
+        self.validators
+    }
+
+    /// The number of levels above the leaves, the height of the tree
+    pub fn levels(&self) -> usize {
+        match self.validators {
+            0 => 0,
+            n => (usize::BITS - (n - 1).leading_zeros()) as usize,
+        }
+    }
+
+    /// The positions of the peers of `validator` at `level`. When the list's
+    /// length is not a power of two, the ranges near its end are short, or
+    /// empty.
+    pub fn peers(&self, validator: usize, level: usize) -> Result<Range<usize>, TreeError> {
+        if validator >= self.validators {
+            return Err(TreeError::UnknownValidator(validator));
+        }
+        if level == 0 || level > self.levels() {
+            return Err(TreeError::NoSuchLevel(level));
+        }
+
+        let half = level - 1;
+        let start = ((validator >> half) ^ 1) << half;
+        let end = (start + (1 << half)).min(self.validators);
+        Ok(start.min(end)..end)
+    }
+}
+
+/// A validator's state while aggregating: what it has verified at each level
+#[derive(Debug, Clone)]
+pub struct Node<S: SignatureScheme> {
+    tree: Tree,
+    index: usize,
+    /// The verified certificates received at each level, the node's own
+    /// signature at level 0
+    levels: Vec<Vec<Certificate<S::Signature>>>,
+}
+
+impl<S: SignatureScheme> Node<S> {
+    /// Starts aggregating as the validator at `index`, with its own
+    /// signature on the message
+    pub fn new(tree: Tree, index: usize, signature: &[u8]) -> Result<Self, TreeError> {
+        if index >= tree.validators() {
+            return Err(TreeError::UnknownValidator(index));
+        }
+        let own = certificate::create::<S>(tree.validators(), &[(index, signature)])?;
+
+        let mut levels = vec![Vec::new(); tree.levels() + 1];
+        levels[0].push(own);
+        Ok(Self {
+            tree,
+            index,
+            levels,
+        })
+    }
+
+    /// The validator's position in the list
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// What to send the peers at `level`: the best the node holds from below
+    /// that level, which is its half of the subtree the peers share with it
+    pub fn outgoing(&self, level: usize) -> Result<Certificate<S::Signature>, TreeError> {
+        if level == 0 || level > self.tree.levels() {
+            return Err(TreeError::NoSuchLevel(level));
+        }
+
+        Ok(self.combine(0..level))
+    }
+
+    /// Takes a certificate a peer sent at `level`, after checking that only
+    /// that level's peers signed it and verifying it on `msg` against
+    /// `validators`, the tree's list of public keys. Returns whether the best
+    /// combination at that level now has more signers than before.
+    ///
+    /// A certificate whose signers are all in one the node already holds at
+    /// that level is dropped without being verified, and one that holds all
+    /// the signers of others replaces them, so that a peer cannot grow the
+    /// level's candidates by sending subsets of what it sent before.
+    pub fn receive(
+        &mut self,
+        level: usize,
+        received: Certificate<S::Signature>,
+        validators: &[S::Public],
+        msg: &[u8],
+    ) -> Result<bool, TreeError> {
+        if validators.len() != self.tree.validators() {
+            return Err(TreeError::WrongValidatorCount(
+                validators.len(),
+                self.tree.validators(),
+            ));
+        }
+        let peers = self.tree.peers(self.index, level)?;
+        if let Some(validator) = received.signers().find(|i| !peers.contains(i)) {
+            return Err(TreeError::NotAPeer { level, validator });
+        }
+        let pool = &mut self.levels[level];
+        if pool.iter().any(|held| covers(held, &received)) {
+            return Ok(false);
+        }
+        certificate::verify::<S>(&received, validators, msg, 1)?;
+
+        let before = best_combination(pool).map_or(0, |best| best.count());
+        pool.retain(|held| !covers(&received, held));
+        pool.push(received);
+        let after = best_combination(pool).map_or(0, |best| best.count());
+        Ok(after > before)
+    }
+
+    /// Whether the node holds every peer at `level`, so it need not wait for
+    /// more there
+    pub fn is_complete(&self, level: usize) -> Result<bool, TreeError> {
+        let peers = self.tree.peers(self.index, level)?;
+        let best = best_combination(&self.levels[level]).map_or(0, |best| best.count());
+        Ok(best == peers.len())
+    }
+
+    /// The best aggregate the node can make from everything it holds
+    pub fn aggregate(&self) -> Certificate<S::Signature> {
+        self.combine(0..self.tree.levels() + 1)
+    }
+
+    /// Merges the best of each of `levels`. The levels' peers are disjoint, so
+    /// the bests are too.
+    fn combine(&self, levels: Range<usize>) -> Certificate<S::Signature> {
+        self.levels[levels]
+            .iter()
+            .filter_map(|pool| best_combination(pool))
+            .reduce(|merged, best| merged.merge(&best).expect("the levels are disjoint"))
+            .expect("level 0 holds the node's own signature")
+    }
+}
+
+/// Whether every signer of `inner` signed `outer`
+fn covers<P: Element>(outer: &Certificate<P>, inner: &Certificate<P>) -> bool {
+    inner.signers().all(|i| outer.has_signed(i))
+}
+
+/// Merges as many signers as it can from `candidates` without counting any
+/// twice, or `None` if there are none.
+///
+/// Finding the largest disjoint combination is set packing, which is hard in
+/// general, so this is greedy: starting from each candidate in turn, it adds
+/// every other that does not overlap, largest first, and keeps the best
+/// result. That is at least as many signers as the largest candidate has, but
+/// not always the most any combination has: a peer may send any subset of the
+/// level's peers, and finding the best for arbitrary subsets could take trying
+/// every combination of them.
+pub fn best_combination<P: Element>(candidates: &[Certificate<P>]) -> Option<Certificate<P>> {
+    let mut by_size = candidates.iter().collect::<Vec<_>>();
+    by_size.sort_by_key(|candidate| std::cmp::Reverse(candidate.count()));
+
+    by_size
+        .iter()
+        .map(|start| {
+            by_size.iter().fold((*start).clone(), |merged, candidate| {
+                merged.merge(candidate).unwrap_or(merged)
+            })
+        })
+        .max_by_key(|merged| merged.count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{G1Scheme, G2Scheme, Scheme},
+    };
+
+    #[test]
+    fn the_tree_pairs_off_subtrees() {
+        let tree = Tree::new(6);
+        assert_eq!(tree.levels(), 3);
+        assert_eq!(tree.peers(0, 1).unwrap(), 1..2);
+        assert_eq!(tree.peers(0, 2).unwrap(), 2..4);
+        assert_eq!(tree.peers(3, 2).unwrap(), 0..2);
+        assert_eq!(tree.peers(0, 3).unwrap(), 4..6);
+        assert_eq!(tree.peers(5, 3).unwrap(), 0..4);
+        // past the end of a list that is not a power of two
+        assert_eq!(tree.peers(5, 2).unwrap(), 6..6);
+
+        assert_eq!(Tree::new(1).levels(), 0);
+        assert_eq!(Tree::new(2).levels(), 1);
+        assert_eq!(Tree::new(1024).levels(), 10);
+        assert_eq!(Tree::new(1025).levels(), 11);
+        assert!(matches!(
+            tree.peers(6, 1),
+            Err(TreeError::UnknownValidator(6))
+        ));
+        assert!(matches!(tree.peers(0, 0), Err(TreeError::NoSuchLevel(0))));
+        assert!(matches!(tree.peers(0, 4), Err(TreeError::NoSuchLevel(4))));
+    }
+
+    struct Network<S: SignatureScheme> {
+        publics: Vec<S::Public>,
+        nodes: Vec<Option<Node<S>>>,
+    }
+
+    /// A node for every validator, but those that are `offline` neither sign
+    /// nor send.
+    fn network<S: SignatureScheme>(n: usize, offline: &[usize], msg: &[u8]) -> Network<S> {
+        let rng = &mut rand::thread_rng();
+        let tree = Tree::new(n);
+        let (privates, publics): (Vec<_>, Vec<_>) = (0..n).map(|_| S::keypair(rng)).unzip();
+        let nodes = (0..n)
+            .map(|i| {
+                let signature = S::sign(&privates[i], msg).unwrap();
+                (!offline.contains(&i)).then(|| Node::new(tree, i, &signature).unwrap())
+            })
+            .collect();
+        Network { publics, nodes }
+    }
+
+    /// Runs each level in turn: every online node sends its outgoing aggregate
+    /// to each of its peers at that level.
+    fn simulate<S: SignatureScheme>(network: &mut Network<S>, msg: &[u8]) {
+        let n = network.nodes.len();
+        let tree = Tree::new(n);
+        for level in 1..=tree.levels() {
+            let sent = network
+                .nodes
+                .iter()
+                .flatten()
+                .map(|node| (node.index(), node.outgoing(level).unwrap()))
+                .collect::<Vec<_>>();
+            for (from, certificate) in sent {
+                for to in tree.peers(from, level).unwrap() {
+                    if let Some(node) = &mut network.nodes[to] {
+                        node.receive(level, certificate.clone(), &network.publics, msg)
+                            .unwrap();
+                    }
+                }
+            }
+        }
+    }
+
+    fn every_node_ends_with_every_signer<S: SignatureScheme>() {
+        let msg = b"epoch 9000";
+        for n in [1, 2, 7, 16, 21] {
+            let mut network = network::<S>(n, &[], msg);
+            simulate(&mut network, msg);
+
+            for node in network.nodes.iter().flatten() {
+                let aggregate = node.aggregate();
+                assert_eq!(aggregate.count(), n);
+                certificate::verify::<S>(&aggregate, &network.publics, msg, n).unwrap();
+                for level in 1..=Tree::new(n).levels() {
+                    assert!(node.is_complete(level).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_node_ends_with_every_signer_g1() {
+        every_node_ends_with_every_signer::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn every_node_ends_with_every_signer_g2() {
+        every_node_ends_with_every_signer::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn offline_validators_are_routed_around() {
+        type S = G2Scheme<PCurve>;
+        let msg = b"epoch 9001";
+        let offline = [2, 3, 9];
+        let mut network = network::<S>(12, &offline, msg);
+        simulate(&mut network, msg);
+
+        for node in network.nodes.iter().flatten() {
+            let aggregate = node.aggregate();
+            assert_eq!(aggregate.count(), 9);
+            assert!(offline.iter().all(|&i| !aggregate.has_signed(i)));
+            certificate::verify::<S>(&aggregate, &network.publics, msg, 9).unwrap();
+        }
+        // node 0's level 2 peers, 2 and 3, never sent anything
+        let node = network.nodes[0].as_ref().unwrap();
+        assert!(!node.is_complete(2).unwrap());
+    }
+
+    #[test]
+    fn bad_contributions_are_refused() {
+        type S = G1Scheme<PCurve>;
+        let msg = b"epoch 9002";
+        let mut network = network::<S>(8, &[], msg);
+        let publics = network.publics.clone();
+        let from_4 = network.nodes[4].as_ref().unwrap().outgoing(1).unwrap();
+        let from_1 = network.nodes[1].as_ref().unwrap().outgoing(1).unwrap();
+        let node = network.nodes[0].as_mut().unwrap();
+
+        // validator 4 is a peer of 0 at level 3, not level 1
+        assert!(matches!(
+            node.receive(1, from_4.clone(), &publics, msg),
+            Err(TreeError::NotAPeer {
+                level: 1,
+                validator: 4
+            })
+        ));
+        assert!(matches!(
+            node.receive(3, from_4.clone(), &publics, b"another epoch"),
+            Err(TreeError::CertificateError(
+                CertificateError::InvalidSignature
+            ))
+        ));
+        assert!(node.receive(3, from_4.clone(), &publics, msg).unwrap());
+        // the same signers again do not improve anything
+        assert!(!node.receive(3, from_4, &publics, msg).unwrap());
+        assert!(node.receive(1, from_1, &publics, msg).unwrap());
+        assert_eq!(node.aggregate().signers().collect::<Vec<_>>(), [0, 1, 4]);
+        assert!(matches!(node.outgoing(4), Err(TreeError::NoSuchLevel(4))));
+    }
+
+    #[test]
+    fn subsets_of_held_certificates_are_not_kept() {
+        type S = G1Scheme<PCurve>;
+        let msg = b"epoch 9005";
+        let rng = &mut rand::thread_rng();
+        let (privates, publics): (Vec<_>, Vec<_>) = (0..8).map(|_| S::keypair(rng)).unzip();
+        let certify = |signers: &[usize], msg: &[u8]| {
+            let signatures = signers
+                .iter()
+                .map(|&i| (i, S::sign(&privates[i], msg).unwrap()))
+                .collect::<Vec<_>>();
+            let signatures = signatures
+                .iter()
+                .map(|(i, signature)| (*i, &signature[..]))
+                .collect::<Vec<_>>();
+            certificate::create::<S>(8, &signatures).unwrap()
+        };
+        let own = S::sign(&privates[0], msg).unwrap();
+        let mut node = Node::<S>::new(Tree::new(8), 0, &own).unwrap();
+
+        // level 3's peers are 4 to 7
+        assert!(
+            node.receive(3, certify(&[4, 5], msg), &publics, msg)
+                .unwrap()
+        );
+        assert!(node.receive(3, certify(&[7], msg), &publics, msg).unwrap());
+        assert!(
+            node.receive(3, certify(&[4, 5, 6], msg), &publics, msg)
+                .unwrap()
+        );
+        // {4, 5} is covered and dropped, {7} is not
+        assert_eq!(node.levels[3].len(), 2);
+
+        // a subset is not even verified: this one is signed on another message
+        let subset = certify(&[5, 6], b"another epoch");
+        assert!(!node.receive(3, subset, &publics, msg).unwrap());
+        assert_eq!(node.levels[3].len(), 2);
+        assert!(node.is_complete(3).unwrap());
+    }
+
+    #[test]
+    fn the_validator_list_must_be_the_trees() {
+        type S = G2Scheme<PCurve>;
+        let msg = b"epoch 9004";
+        // a list of 16 keys, and a certificate sized for it from validator 4,
+        // who is a peer of 0 at level 3 of a tree of 6
+        let longer = network::<S>(16, &[], msg);
+        let from_4 = longer.nodes[4].as_ref().unwrap().outgoing(1).unwrap();
+
+        let rng = &mut rand::thread_rng();
+        let (private, _) = S::keypair(rng);
+        let signature = S::sign(&private, msg).unwrap();
+        let mut node = Node::<S>::new(Tree::new(6), 0, &signature).unwrap();
+        assert!(matches!(
+            node.receive(3, from_4, &longer.publics, msg),
+            Err(TreeError::WrongValidatorCount(16, 6))
+        ));
+    }
+
+    #[test]
+    fn the_best_combination_does_not_count_anyone_twice() {
+        type S = G2Scheme<PCurve>;
+        let msg = b"epoch 9003";
+        let rng = &mut rand::thread_rng();
+        let (privates, publics): (Vec<_>, Vec<_>) = (0..8).map(|_| S::keypair(rng)).unzip();
+        let certify = |signers: &[usize]| {
+            let signatures = signers
+                .iter()
+                .map(|&i| (i, S::sign(&privates[i], msg).unwrap()))
+                .collect::<Vec<_>>();
+            let signatures = signatures
+                .iter()
+                .map(|(i, signature)| (*i, &signature[..]))
+                .collect::<Vec<_>>();
+            certificate::create::<S>(8, &signatures).unwrap()
+        };
+
+        // the largest candidate overlaps both of the others, which together
+        // are larger still
+        let candidates = [
+            certify(&[1, 2, 3, 4]),
+            certify(&[0, 1, 2]),
+            certify(&[3, 4, 5]),
+        ];
+        let best = best_combination(&candidates).unwrap();
+        assert_eq!(best.signers().collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+        certificate::verify::<S>(&best, &publics, msg, 6).unwrap();
+
+        assert!(best_combination::<<S as Scheme>::Signature>(&[]).is_none());
+    }
+}
@@ -0,0 +1,337 @@
+//! Proofs that a blinded message blinds one of a list of allowed messages, for
+//! the BLS blind threshold schemes.
+//!
+//! The proof is a Cramer–Damgård–Schoenmakers OR of Schnorr proofs: for the
+//! blinded message `B` and each allowed message `m_j`, that the requester
+//! knows `r` with `B = r·H(m_j)`. The branch for the real message is proven and
+//! the others simulated, and the challenges must add up to the Fiat–Shamir
+//! hash, so at most one branch can be simulated away and the verifier cannot
+//! tell which one was not.
+use crate::group::{Element, Point, Scalar};
+use crate::serialization;
+use crate::sig::blind::{BlindError, Token};
+use crate::sig::tbls::Share;
+use crate::sig::{
+    BlindScheme, BlindThresholdError, BlindThresholdScheme, Partial, Scheme, VerifiableBlindScheme,
+};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Blake2s personalization for the challenge, so that it cannot collide with a
+/// hash computed for anything else.
+const BLINDING_DOMAIN: &[u8] = b"BLSvblnd";
+
+/// Errors associated with proving and checking what a blinded message blinds
+#[derive(Debug, Error)]
+pub enum VerifiableBlindError {
+    /// Raised when blinding a message that is not in the allowed list
+    #[error("the message is not one of the allowed messages")]
+    MessageNotAllowed,
+
+    /// Raised when the blinded message is the identity element, which is
+    /// `0·H(m)` for every message
+    #[error("blinded message is the identity element")]
+    InvalidMessagePoint,
+
+    /// Raised when the proof does not show that the blinded message blinds
+    /// one of the allowed messages
+    #[error("invalid blinding proof")]
+    InvalidProof,
+
+    /// Raised when an allowed message cannot be hashed to the curve
+    #[error("could not hash to curve")]
+    HashingError,
+
+    /// Raised when no candidate hash was a valid scalar. Each candidate is one
+    /// with probability above one half, so this does not happen in practice.
+    #[error("could not hash the transcript to a scalar")]
+    HashToScalarError,
+
+    /// Raised when the proof or the blinded message cannot be (de)serialized
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+
+    /// Raised when blinding fails
+    #[error(transparent)]
+    BlindError(#[from] BlindError),
+
+    /// Raised when signing fails
+    #[error(transparent)]
+    BlindThresholdError(#[from] BlindThresholdError<BlindError>),
+}
+
+/// One challenge and one response per allowed message, in the list's order.
+#[derive(Serialize, Deserialize)]
+struct BlindingProof<S> {
+    challenges: Vec<S>,
+    responses: Vec<S>,
+}
+
+impl<T> VerifiableBlindScheme for T
+where
+    T: BlindScheme<Token = Token<<T as Scheme>::Private>, Error = BlindError>
+        + BlindThresholdScheme<Error = BlindThresholdError<BlindError>>,
+{
+    type Error = VerifiableBlindError;
+
+    fn blind_msg_with_proof<R: RngCore>(
+        msg: &[u8],
+        allowed: &[&[u8]],
+        rng: &mut R,
+    ) -> Result<(Self::Token, Vec<u8>, Vec<u8>), VerifiableBlindError> {
+        let position = allowed
+            .iter()
+            .position(|candidate| *candidate == msg)
+            .ok_or(VerifiableBlindError::MessageNotAllowed)?;
+        let points = hash_all::<T>(allowed)?;
+
+        let (token, blinded_msg) = T::blind_msg(msg, rng)?;
+        let blinded: T::Signature = serialization::deserialize(&blinded_msg)?;
+        let r = token.scalar();
+
+        // Simulate every branch but the real one: pick its challenge and
+        // response, and solve for the commitment that makes them verify.
+        let mut challenges = Vec::with_capacity(points.len());
+        let mut responses = Vec::with_capacity(points.len());
+        let mut commitments = Vec::with_capacity(points.len());
+        let nonce = T::Private::rand(rng);
+        for (j, point) in points.iter().enumerate() {
+            if j == position {
+                let mut commitment = point.clone();
+                commitment.mul(&nonce);
+                challenges.push(T::Private::zero());
+                responses.push(T::Private::zero());
+                commitments.push(commitment);
+            } else {
+                let (challenge, response) = (T::Private::rand(rng), T::Private::rand(rng));
+                commitments.push(commitment::<T>(point, &blinded, &challenge, &response));
+                challenges.push(challenge);
+                responses.push(response);
+            }
+        }
+
+        // The real branch takes whatever challenge is left over, and answers
+        // it with the blinding factor.
+        let mut challenge = challenge::<T>(&blinded, &points, &commitments)?;
+        for simulated in &challenges {
+            challenge.sub(simulated);
+        }
+        let mut response = challenge.clone();
+        response.mul(r);
+        response.negate();
+        response.add(&nonce);
+        challenges[position] = challenge;
+        responses[position] = response;
+
+        let proof = bincode::serialize(&BlindingProof {
+            challenges,
+            responses,
+        })?;
+        Ok((token, blinded_msg, proof))
+    }
+
+    fn verify_blinding(
+        blinded_msg: &[u8],
+        allowed: &[&[u8]],
+        proof: &[u8],
+    ) -> Result<(), VerifiableBlindError> {
+        let blinded: T::Signature = serialization::deserialize(blinded_msg)?;
+        if blinded == T::Signature::zero() {
+            return Err(VerifiableBlindError::InvalidMessagePoint);
+        }
+        let proof: BlindingProof<T::Private> = serialization::deserialize(proof)?;
+        if proof.challenges.len() != allowed.len() || proof.responses.len() != allowed.len() {
+            return Err(VerifiableBlindError::InvalidProof);
+        }
+
+        let points = hash_all::<T>(allowed)?;
+        let commitments = points
+            .iter()
+            .zip(proof.challenges.iter().zip(&proof.responses))
+            .map(|(point, (challenge, response))| {
+                commitment::<T>(point, &blinded, challenge, response)
+            })
+            .collect::<Vec<_>>();
+
+        let mut sum = T::Private::zero();
+        for challenge in &proof.challenges {
+            sum.add(challenge);
+        }
+        if challenge::<T>(&blinded, &points, &commitments)? != sum {
+            return Err(VerifiableBlindError::InvalidProof);
+        }
+
+        Ok(())
+    }
+
+    fn sign_blind_partial_verified(
+        private: &Share<Self::Private>,
+        blinded_msg: &[u8],
+        allowed: &[&[u8]],
+        proof: &[u8],
+    ) -> Result<Partial, VerifiableBlindError> {
+        T::verify_blinding(blinded_msg, allowed, proof)?;
+        Ok(T::sign_blind_partial(private, blinded_msg)?)
+    }
+}
+
+fn hash_all<T: Scheme>(allowed: &[&[u8]]) -> Result<Vec<T::Signature>, VerifiableBlindError> {
+    allowed
+        .iter()
+        .map(|msg| {
+            let mut point = T::Signature::zero();
+            point
+                .map(msg)
+                .map_err(|_| VerifiableBlindError::HashingError)?;
+            Ok(point)
+        })
+        .collect()
+}
+
+/// `response·H(m) + challenge·B`, the commitment a branch verifies against.
+fn commitment<T: Scheme>(
+    point: &T::Signature,
+    blinded: &T::Signature,
+    challenge: &T::Private,
+    response: &T::Private,
+) -> T::Signature {
+    let mut commitment = point.clone();
+    commitment.mul(response);
+    let mut offset = blinded.clone();
+    offset.mul(challenge);
+    commitment.add(&offset);
+    commitment
+}
+
+/// Hashes the blinded message, the allowed messages' points and the
+/// commitments to the overall challenge.
+fn challenge<T: Scheme>(
+    blinded: &T::Signature,
+    points: &[T::Signature],
+    commitments: &[T::Signature],
+) -> Result<T::Private, VerifiableBlindError> {
+    let transcript = bincode::serialize(&(blinded, points, commitments))?;
+
+    // Try-and-increment, as for a child key's tweak, so the challenge is
+    // uniform rather than biased toward small values.
+    for counter in 0u8..=255 {
+        let hash = blake2s_simd::Params::new()
+            .hash_length(32)
+            .personal(BLINDING_DOMAIN)
+            .to_state()
+            .update(&[counter])
+            .update(&transcript)
+            .finalize();
+        if let Some(challenge) = T::Private::from_random_bytes(hash.as_bytes()) {
+            return Ok(challenge);
+        }
+    }
+
+    Err(VerifiableBlindError::HashToScalarError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::bls12377::PairingCurve as PCurve;
+    use crate::sig::{
+        SignatureScheme, ThresholdScheme,
+        bls::{G1Scheme, G2Scheme},
+        shares,
+    };
+    use rand::thread_rng;
+
+    const ALLOWED: [&[u8]; 3] = [b"tier:free", b"tier:pro", b"tier:team"];
+
+    fn verified_blind_signing<T>()
+    where
+        T: VerifiableBlindScheme<Error = VerifiableBlindError> + ThresholdScheme + SignatureScheme,
+    {
+        let (shares, public) = shares::<T>(3, 2);
+
+        for msg in ALLOWED {
+            let (token, blinded, proof) =
+                T::blind_msg_with_proof(msg, &ALLOWED, &mut thread_rng()).unwrap();
+            let partials = shares
+                .iter()
+                .map(|share| T::sign_blind_partial_verified(share, &blinded, &ALLOWED, &proof))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+
+            let partials = partials
+                .iter()
+                .map(|partial| T::unblind_partial_sig(&token, partial).unwrap())
+                .collect::<Vec<_>>();
+            let signature = T::aggregate(&public, &partials).unwrap();
+            T::verify(public.public_key(), msg, &signature).unwrap();
+        }
+    }
+
+    #[test]
+    fn verified_blind_signing_g1() {
+        verified_blind_signing::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn verified_blind_signing_g2() {
+        verified_blind_signing::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn a_message_outside_the_list_cannot_be_proven() {
+        type T = G2Scheme<PCurve>;
+        assert!(matches!(
+            T::blind_msg_with_proof(b"tier:enterprise", &ALLOWED, &mut thread_rng()),
+            Err(VerifiableBlindError::MessageNotAllowed)
+        ));
+
+        // nor does a proof over the list carry over to another blinding
+        let (_, _, proof) =
+            T::blind_msg_with_proof(b"tier:pro", &ALLOWED, &mut thread_rng()).unwrap();
+        let (_, other) = T::blind_msg(b"tier:enterprise", &mut thread_rng()).unwrap();
+        assert!(matches!(
+            T::verify_blinding(&other, &ALLOWED, &proof),
+            Err(VerifiableBlindError::InvalidProof)
+        ));
+    }
+
+    #[test]
+    fn a_proof_holds_only_for_its_list() {
+        type T = G2Scheme<PCurve>;
+        let (shares, _) = shares::<T>(1, 1);
+        let (_, blinded, proof) =
+            T::blind_msg_with_proof(b"tier:pro", &ALLOWED, &mut thread_rng()).unwrap();
+
+        // reordered, shortened or extended lists all fail
+        let reordered: [&[u8]; 3] = [ALLOWED[1], ALLOWED[0], ALLOWED[2]];
+        let extended: [&[u8]; 4] = [ALLOWED[0], ALLOWED[1], ALLOWED[2], b"tier:enterprise"];
+        for list in [&reordered[..], &ALLOWED[..2], &extended[..]] {
+            assert!(matches!(
+                T::sign_blind_partial_verified(&shares[0], &blinded, list, &proof),
+                Err(VerifiableBlindError::InvalidProof)
+            ));
+        }
+    }
+
+    #[test]
+    fn an_arbitrary_point_is_not_signed() {
+        type T = G2Scheme<PCurve>;
+        let (shares, _) = shares::<T>(1, 1);
+        let (_, _, proof) =
+            T::blind_msg_with_proof(b"tier:free", &ALLOWED, &mut thread_rng()).unwrap();
+
+        let arbitrary =
+            bincode::serialize(&<T as Scheme>::Signature::rand(&mut thread_rng())).unwrap();
+        assert!(matches!(
+            T::sign_blind_partial_verified(&shares[0], &arbitrary, &ALLOWED, &proof),
+            Err(VerifiableBlindError::InvalidProof)
+        ));
+
+        let identity = bincode::serialize(&<T as Scheme>::Signature::zero()).unwrap();
+        assert!(matches!(
+            T::verify_blinding(&identity, &ALLOWED, &proof),
+            Err(VerifiableBlindError::InvalidMessagePoint)
+        ));
+    }
+}
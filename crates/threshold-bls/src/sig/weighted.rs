@@ -0,0 +1,722 @@
+//! Weighted threshold signatures, for committees whose members do not count
+//! equally, such as stake-weighted validator sets.
+//!
+//! A member of weight `w` holds `w` virtual shares of the committee's
+//! polynomial, at consecutive indices, and the threshold is a total weight:
+//! any members whose weights add up to it sign for the committee's public key,
+//! which is an ordinary BLS public key. Nothing about the polynomial changes,
+//! so the public polynomial, its public key and its verification are the same
+//! as for an unweighted committee with one share per unit of weight.
+//!
+//! Members sign in one of two ways:
+//!
+//! - In one round, with [`partial_sign`]. The combiner weighs each virtual
+//!   share's signature by a Lagrange coefficient that depends on which other
+//!   members signed, which a member does not know when it signs, so its
+//!   partial carries a signature per virtual share: `16 + w * SIG_LEN` bytes,
+//!   with the member's index, its first virtual index and the count written
+//!   once. This suits a combiner that takes whichever members answer first.
+//! - In two rounds, with [`partial_sign_compact`]. The combiner first
+//!   announces the [`Signer`]s it will combine, whose weights reach the
+//!   threshold. Knowing them, a member weighs its virtual shares itself and
+//!   returns a single signature with the sum, `4 + SIG_LEN` bytes whatever its
+//!   weight, which [`partial_verify_compact`] checks against the same sum of
+//!   its public shares. The signature is the sum of every announced member's
+//!   partial, so all of them must answer: if one does not, the combiner
+//!   announces a new set without it.
+use super::tbls::lagrange_at;
+use crate::group::Element;
+use crate::poly::{Eval, Idx, Poly, PolyError};
+use crate::serialization;
+use crate::sig::{Partial, Share, SignatureScheme, ThresholdError};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A member's virtual shares of a weighted committee's polynomial
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeightedShare<S> {
+    /// Identifies the member among the committee
+    pub member: Idx,
+    /// The index of the member's first virtual share. The others follow it
+    pub first: Idx,
+    /// The virtual shares' scalars, as many as the member's weight
+    pub private: Vec<S>,
+}
+
+impl<S: Clone> WeightedShare<S> {
+    /// The member's weight, the number of virtual shares it holds
+    pub fn weight(&self) -> usize {
+        self.private.len()
+    }
+
+    /// The virtual shares as plain shares, which sign and verify as any other
+    /// share of the polynomial does
+    pub fn shares(&self) -> impl Iterator<Item = Share<S>> + '_ {
+        (self.first..)
+            .zip(&self.private)
+            .map(|(index, private)| Share {
+                index,
+                private: private.clone(),
+            })
+    }
+
+    /// The member as the combiner announces it for two-round signing
+    pub fn signer(&self) -> Signer {
+        Signer {
+            member: self.member,
+            first: self.first,
+            weight: self.private.len() as u32,
+        }
+    }
+}
+
+/// A member the combiner will combine in two-round signing, with the virtual
+/// shares it holds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Signer {
+    /// Identifies the member among the committee
+    pub member: Idx,
+    /// The index of the member's first virtual share
+    pub first: Idx,
+    /// The member's weight, the number of virtual shares it holds
+    pub weight: u32,
+}
+
+/// A member's partial signature, with one signature per virtual share.
+///
+/// Serialized with bincode, `member` and `first` are `u32`s and `signatures`
+/// a `u64` count followed by the compressed points.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeightedPartial<P> {
+    /// The member who signed
+    pub member: Idx,
+    /// The index of the virtual share the first signature was made with. The
+    /// others follow it
+    pub first: Idx,
+    /// The signatures, one per virtual share
+    pub signatures: Vec<P>,
+}
+
+/// A member's partial signature in two-round signing: the sum of its virtual
+/// shares' signatures, each weighed by its Lagrange coefficient among the
+/// announced signers.
+///
+/// Serialized with bincode, `member` is a `u32` followed by the compressed
+/// point.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CompactPartial<P> {
+    /// The member who signed
+    pub member: Idx,
+    /// The weighted sum of the member's signatures
+    pub signature: P,
+}
+
+/// Errors associated with weighted threshold signing, verification and
+/// aggregation
+#[derive(Debug, Error)]
+pub enum WeightedError<S: SignatureScheme> {
+    /// Raised when dealing to a member with no weight, who would hold no share
+    #[error("member {0} has a weight of zero")]
+    ZeroWeight(Idx),
+
+    /// Raised when the threshold is zero or more than the committee's total
+    /// weight, or the weights add up to more virtual shares than there are
+    /// indices
+    #[error("cannot deal a total weight of {total} with a threshold of {threshold}")]
+    InvalidDealing {
+        /// The sum of the weights
+        total: u64,
+        /// The threshold asked for
+        threshold: usize,
+    },
+
+    /// Raised when a partial signature carries no signatures, or more than fit
+    /// after its first index
+    #[error("partial signature of member {0} has an invalid range of virtual shares")]
+    InvalidRange(Idx),
+
+    /// Raised when the members whose partials were given do not reach the
+    /// threshold weight
+    #[error("not enough weight: {0}/{1}")]
+    NotEnoughWeight(usize, usize),
+
+    /// Raised when a member's virtual shares overlap another announced
+    /// signer's, or the member is announced twice
+    #[error("the virtual shares of member {0} overlap another signer's")]
+    OverlappingSigners(Idx),
+
+    /// Raised when a member who is not among the announced signers, or whose
+    /// virtual shares are not as announced, signs or is verified
+    #[error("member {0} is not an announced signer")]
+    NotASigner(Idx),
+
+    /// Raised when combining without a partial from an announced signer, whose
+    /// share of the signature is then missing
+    #[error("no partial signature from announced signer {0}")]
+    MissingSigner(Idx),
+
+    /// Raised when signing fails, or a signature of a partial does not verify
+    /// against the public polynomial
+    #[error(transparent)]
+    ThresholdError(#[from] ThresholdError<S>),
+}
+
+impl<S: SignatureScheme> From<bincode::Error> for WeightedError<S> {
+    fn from(err: bincode::Error) -> Self {
+        ThresholdError::BincodeError(err).into()
+    }
+}
+
+impl<S: SignatureScheme> From<PolyError> for WeightedError<S> {
+    fn from(err: PolyError) -> Self {
+        ThresholdError::PolyError(err).into()
+    }
+}
+
+/// Splits `private` among members of the given `weights`, so that any members
+/// whose weights add up to `threshold` sign for it, and returns their shares,
+/// in the order of `weights`, with the public polynomial.
+///
+/// Member `i` holds the virtual shares after those of members `0..i`. As with
+/// [`deal`](crate::sig::deal), whoever runs this holds the whole key.
+#[allow(clippy::type_complexity)]
+pub fn deal_weighted<S: SignatureScheme, R: RngCore>(
+    private: &S::Private,
+    weights: &[u32],
+    threshold: usize,
+    rng: &mut R,
+) -> Result<(Vec<WeightedShare<S::Private>>, Poly<S::Public>), WeightedError<S>> {
+    if let Some(member) = weights.iter().position(|&weight| weight == 0) {
+        return Err(WeightedError::ZeroWeight(member as Idx));
+    }
+    let total = weights.iter().map(|&weight| u64::from(weight)).sum::<u64>();
+    let invalid = WeightedError::InvalidDealing { total, threshold };
+    let Ok(n) = usize::try_from(total) else {
+        return Err(invalid);
+    };
+    let (shares, public) = match crate::sig::deal::<S, _>(private, n, threshold, rng) {
+        Ok(dealt) => dealt,
+        Err(ThresholdError::InvalidDealing { .. }) => return Err(invalid),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut shares = shares.into_iter();
+    let weighted = weights
+        .iter()
+        .enumerate()
+        .map(|(member, &weight)| {
+            let virtual_shares = shares.by_ref().take(weight as usize).collect::<Vec<_>>();
+            WeightedShare {
+                member: member as Idx,
+                first: virtual_shares[0].index,
+                private: virtual_shares
+                    .into_iter()
+                    .map(|share| share.private)
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok((weighted, public))
+}
+
+/// Signs `msg` with each of a member's virtual shares, into one partial
+pub fn partial_sign<S: SignatureScheme>(
+    share: &WeightedShare<S::Private>,
+    msg: &[u8],
+) -> Result<Partial, WeightedError<S>> {
+    let signatures = share
+        .private
+        .iter()
+        .map(|private| {
+            let signature = S::sign(private, msg).map_err(ThresholdError::SignatureError)?;
+            Ok(serialization::deserialize(&signature)?)
+        })
+        .collect::<Result<Vec<S::Signature>, WeightedError<S>>>()?;
+    let partial = WeightedPartial {
+        member: share.member,
+        first: share.first,
+        signatures,
+    };
+
+    Ok(bincode::serialize(&partial)?)
+}
+
+/// Verifies every signature of a member's partial against the public
+/// polynomial at its virtual share's index.
+///
+/// The signatures are checked together, hashing the message once: a random
+/// linear combination of them is verified against the same combination of the
+/// public shares, with coefficients drawn from `rng`, so a member who does not
+/// know them cannot make a bad signature cancel out.
+pub fn partial_verify<S: SignatureScheme, R: RngCore>(
+    public: &Poly<S::Public>,
+    msg: &[u8],
+    partial: &[u8],
+    rng: &mut R,
+) -> Result<(), WeightedError<S>> {
+    let (mut key, mut signature) = (S::Public::zero(), S::Signature::zero());
+    for eval in virtual_evals::<S>(partial)? {
+        let coefficient = S::Private::rand(rng);
+
+        let mut public_share = public.eval(eval.index).value;
+        public_share.mul(&coefficient);
+        key.add(&public_share);
+
+        let mut term = eval.value;
+        term.mul(&coefficient);
+        signature.add(&term);
+    }
+
+    let signature = bincode::serialize(&signature)?;
+    S::verify(&key, msg, &signature).map_err(ThresholdError::SignatureError)?;
+    Ok(())
+}
+
+/// Combines members' partials into the committee's signature, once the
+/// members' weights add up to the threshold. A member's partial counts once,
+/// however often it is given.
+///
+/// The partials are not verified here; see [`partial_verify`].
+pub fn aggregate<S: SignatureScheme>(
+    public: &Poly<S::Public>,
+    partials: &[Partial],
+) -> Result<Vec<u8>, WeightedError<S>> {
+    let threshold = public.degree() + 1;
+
+    let mut evals = Vec::new();
+    for partial in partials {
+        evals.extend(virtual_evals::<S>(partial)?);
+    }
+    // Repeated partials are the same virtual indices, counted once.
+    evals.sort_by_key(|eval| eval.index);
+    evals.dedup_by_key(|eval| eval.index);
+    if evals.len() < threshold {
+        return Err(WeightedError::NotEnoughWeight(evals.len(), threshold));
+    }
+
+    let signature = Poly::<S::Signature>::recover(threshold, evals)?;
+    Ok(bincode::serialize(&signature).expect("could not serialize"))
+}
+
+/// Signs `msg` with a member's virtual shares, weighed by their Lagrange
+/// coefficients among the announced `signers`, into one signature whatever
+/// the member's weight.
+pub fn partial_sign_compact<S: SignatureScheme>(
+    public: &Poly<S::Public>,
+    share: &WeightedShare<S::Private>,
+    signers: &[Signer],
+    msg: &[u8],
+) -> Result<Partial, WeightedError<S>> {
+    let indices = announced_indices::<S>(public, signers)?;
+    if !signers.contains(&share.signer()) {
+        return Err(WeightedError::NotASigner(share.member));
+    }
+
+    let mut private = S::Private::zero();
+    for share in share.shares() {
+        let mut term = lagrange_at(share.index, &S::Private::zero(), &indices)?;
+        term.mul(&share.private);
+        private.add(&term);
+    }
+    let signature = S::sign(&private, msg).map_err(ThresholdError::SignatureError)?;
+    let partial = CompactPartial {
+        member: share.member,
+        signature: serialization::deserialize::<S::Signature>(&signature)?,
+    };
+
+    Ok(bincode::serialize(&partial)?)
+}
+
+/// Verifies a member's two-round partial against the sum of its public
+/// shares, weighed as it weighed its private ones among `signers`
+pub fn partial_verify_compact<S: SignatureScheme>(
+    public: &Poly<S::Public>,
+    signers: &[Signer],
+    msg: &[u8],
+    partial: &[u8],
+) -> Result<(), WeightedError<S>> {
+    let indices = announced_indices::<S>(public, signers)?;
+    let partial: CompactPartial<S::Signature> = serialization::deserialize(partial)?;
+    let signer = signers
+        .iter()
+        .find(|signer| signer.member == partial.member)
+        .ok_or(WeightedError::NotASigner(partial.member))?;
+
+    let mut key = S::Public::zero();
+    // The range was checked with the other signers'.
+    for index in signer.first..=signer.first + (signer.weight - 1) {
+        let mut public_share = public.eval(index).value;
+        public_share.mul(&lagrange_at(index, &S::Private::zero(), &indices)?);
+        key.add(&public_share);
+    }
+
+    let signature = bincode::serialize(&partial.signature)?;
+    S::verify(&key, msg, &signature).map_err(ThresholdError::SignatureError)?;
+    Ok(())
+}
+
+/// Combines the announced `signers`' two-round partials into the committee's
+/// signature, which needs a partial from every one of them. A member's partial
+/// counts once, however often it is given.
+///
+/// The partials are not verified here; see [`partial_verify_compact`].
+pub fn aggregate_compact<S: SignatureScheme>(
+    public: &Poly<S::Public>,
+    signers: &[Signer],
+    partials: &[Partial],
+) -> Result<Vec<u8>, WeightedError<S>> {
+    announced_indices::<S>(public, signers)?;
+    let mut partials = partials
+        .iter()
+        .map(|partial| serialization::deserialize(partial))
+        .collect::<Result<Vec<CompactPartial<S::Signature>>, _>>()?;
+    if let Some(partial) = partials
+        .iter()
+        .find(|partial| !signers.iter().any(|signer| signer.member == partial.member))
+    {
+        return Err(WeightedError::NotASigner(partial.member));
+    }
+    partials.sort_by_key(|partial| partial.member);
+    partials.dedup_by_key(|partial| partial.member);
+    if let Some(signer) = signers.iter().find(|signer| {
+        !partials
+            .iter()
+            .any(|partial| partial.member == signer.member)
+    }) {
+        return Err(WeightedError::MissingSigner(signer.member));
+    }
+
+    let signature = partials
+        .iter()
+        .fold(S::Signature::zero(), |mut signature, partial| {
+            signature.add(&partial.signature);
+            signature
+        });
+    Ok(bincode::serialize(&signature).expect("could not serialize"))
+}
+
+/// The virtual indices of the announced `signers`, after checking that no
+/// two overlap and that together they reach the threshold weight
+fn announced_indices<S: SignatureScheme>(
+    public: &Poly<S::Public>,
+    signers: &[Signer],
+) -> Result<Vec<Idx>, WeightedError<S>> {
+    let mut ranges = Vec::with_capacity(signers.len());
+    for signer in signers {
+        let last = signer
+            .weight
+            .checked_sub(1)
+            .and_then(|len| signer.first.checked_add(len))
+            .ok_or(WeightedError::InvalidRange(signer.member))?;
+        ranges.push((signer.first, last, signer.member));
+    }
+    ranges.sort_unstable();
+    if let Some(pair) = ranges.windows(2).find(|pair| pair[1].0 <= pair[0].1) {
+        return Err(WeightedError::OverlappingSigners(pair[1].2));
+    }
+    let mut members = signers
+        .iter()
+        .map(|signer| signer.member)
+        .collect::<Vec<_>>();
+    members.sort_unstable();
+    if let Some(pair) = members.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(WeightedError::OverlappingSigners(pair[0]));
+    }
+
+    let indices = ranges
+        .iter()
+        .flat_map(|&(first, last, _)| first..=last)
+        .collect::<Vec<_>>();
+    let threshold = public.degree() + 1;
+    if indices.len() < threshold {
+        return Err(WeightedError::NotEnoughWeight(indices.len(), threshold));
+    }
+    Ok(indices)
+}
+
+/// A partial's signatures, each at the index of its virtual share
+fn virtual_evals<S: SignatureScheme>(
+    partial: &[u8],
+) -> Result<Vec<Eval<S::Signature>>, WeightedError<S>> {
+    let partial: WeightedPartial<S::Signature> = serialization::deserialize(partial)?;
+    let last = Idx::try_from(partial.signatures.len())
+        .ok()
+        .and_then(|len| len.checked_sub(1))
+        .and_then(|len| partial.first.checked_add(len));
+    if last.is_none() {
+        return Err(WeightedError::InvalidRange(partial.member));
+    }
+
+    Ok((partial.first..)
+        .zip(partial.signatures)
+        .map(|(index, value)| Eval { index, value })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        group::Element,
+        sig::{G1Scheme, G2Scheme, Scheme, ThresholdScheme},
+    };
+
+    fn reaching_the_threshold_weight_signs<S: SignatureScheme>() {
+        let rng = &mut rand::thread_rng();
+        let (private, public_key) = S::keypair(rng);
+        let (shares, public) = deal_weighted::<S, _>(&private, &[5, 1, 3, 2], 7, rng).unwrap();
+        assert_eq!(
+            shares.iter().map(|s| s.weight()).collect::<Vec<_>>(),
+            [5, 1, 3, 2]
+        );
+        assert_eq!(public.public_key(), &public_key);
+
+        let msg = b"stake-weighted";
+        let partials = shares
+            .iter()
+            .map(|share| partial_sign::<S>(share, msg).unwrap())
+            .collect::<Vec<_>>();
+        for partial in &partials {
+            partial_verify::<S, _>(&public, msg, partial, rng).unwrap();
+        }
+
+        // 5 + 2 reaches 7, and the signature is the key's own
+        let signature =
+            aggregate::<S>(&public, &[partials[0].clone(), partials[3].clone()]).unwrap();
+        S::verify(&public_key, msg, &signature).unwrap();
+        assert_eq!(signature, S::sign(&private, msg).unwrap());
+
+        // 1 + 3 + 2 does not, whatever the number of members, nor does a
+        // member counted twice
+        let light = [
+            partials[1].clone(),
+            partials[2].clone(),
+            partials[3].clone(),
+        ];
+        assert!(matches!(
+            aggregate::<S>(&public, &light),
+            Err(WeightedError::NotEnoughWeight(6, 7))
+        ));
+        let twice = [
+            partials[2].clone(),
+            partials[2].clone(),
+            partials[3].clone(),
+        ];
+        assert!(matches!(
+            aggregate::<S>(&public, &twice),
+            Err(WeightedError::NotEnoughWeight(5, 7))
+        ));
+
+        // the header is written once, whatever the weight
+        let len = bincode::serialize(&<S as Scheme>::Signature::one())
+            .unwrap()
+            .len();
+        assert_eq!(partials[0].len(), 16 + 5 * len);
+    }
+
+    #[test]
+    fn reaching_the_threshold_weight_signs_g1() {
+        reaching_the_threshold_weight_signs::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn reaching_the_threshold_weight_signs_g2() {
+        reaching_the_threshold_weight_signs::<G2Scheme<PCurve>>();
+    }
+
+    // A member's virtual shares are shares of the polynomial like any other,
+    // so they sign and combine with the unweighted scheme too.
+    #[test]
+    fn virtual_shares_are_ordinary_shares() {
+        type S = G2Scheme<PCurve>;
+        let rng = &mut rand::thread_rng();
+        let (private, public_key) = S::keypair(rng);
+        let (shares, public) = deal_weighted::<S, _>(&private, &[2, 2], 3, rng).unwrap();
+        assert_eq!((shares[0].first, shares[1].first), (0, 2));
+
+        let msg = b"unweighted";
+        let partials = shares
+            .iter()
+            .flat_map(|share| share.shares())
+            .skip(1)
+            .map(|share| S::partial_sign(&share, msg).unwrap())
+            .collect::<Vec<_>>();
+        let signature = S::aggregate(&public, &partials).unwrap();
+        S::verify(&public_key, msg, &signature).unwrap();
+    }
+
+    #[test]
+    fn a_forged_signature_is_caught() {
+        type S = G1Scheme<PCurve>;
+        let rng = &mut rand::thread_rng();
+        let (private, _) = S::keypair(rng);
+        let (shares, public) = deal_weighted::<S, _>(&private, &[3, 3], 4, rng).unwrap();
+
+        let msg = b"forged";
+        let partial = partial_sign::<S>(&shares[1], msg).unwrap();
+        let mut forged: WeightedPartial<<S as Scheme>::Signature> =
+            bincode::deserialize(&partial).unwrap();
+        forged.signatures[2].add(&<S as Scheme>::Signature::one());
+        assert!(matches!(
+            partial_verify::<S, _>(&public, msg, &bincode::serialize(&forged).unwrap(), rng),
+            Err(WeightedError::ThresholdError(
+                ThresholdError::SignatureError(_)
+            ))
+        ));
+
+        // a range that runs past the last index
+        forged.first = Idx::MAX;
+        assert!(matches!(
+            partial_verify::<S, _>(&public, msg, &bincode::serialize(&forged).unwrap(), rng),
+            Err(WeightedError::InvalidRange(1))
+        ));
+        forged.signatures.clear();
+        assert!(matches!(
+            aggregate::<S>(&public, &[bincode::serialize(&forged).unwrap()]),
+            Err(WeightedError::InvalidRange(1))
+        ));
+    }
+
+    fn two_round_partials_do_not_grow_with_weight<S: SignatureScheme>() {
+        let rng = &mut rand::thread_rng();
+        let (private, public_key) = S::keypair(rng);
+        let (shares, public) = deal_weighted::<S, _>(&private, &[5, 1, 3, 2], 7, rng).unwrap();
+        let msg = b"announced";
+
+        // 5 + 2 reaches 7, and so does 5 + 3 + 2, with more weight than needed
+        for signing in [&[0, 3][..], &[0, 2, 3]] {
+            let signers = signing
+                .iter()
+                .map(|&i| shares[i].signer())
+                .collect::<Vec<_>>();
+            let partials = signing
+                .iter()
+                .map(|&i| partial_sign_compact::<S>(&public, &shares[i], &signers, msg).unwrap())
+                .collect::<Vec<_>>();
+            let len = bincode::serialize(&<S as Scheme>::Signature::one())
+                .unwrap()
+                .len();
+            for partial in &partials {
+                assert_eq!(partial.len(), 4 + len);
+                partial_verify_compact::<S>(&public, &signers, msg, partial).unwrap();
+            }
+
+            let signature = aggregate_compact::<S>(&public, &signers, &partials).unwrap();
+            S::verify(&public_key, msg, &signature).unwrap();
+            assert_eq!(signature, S::sign(&private, msg).unwrap());
+
+            assert!(matches!(
+                aggregate_compact::<S>(&public, &signers, &partials[1..]),
+                Err(WeightedError::MissingSigner(0))
+            ));
+        }
+
+        // a partial is weighed for the signers it was made for, and no others
+        let signers = [shares[0].signer(), shares[3].signer()];
+        let partial = partial_sign_compact::<S>(&public, &shares[0], &signers, msg).unwrap();
+        let more = [signers[0], signers[1], shares[2].signer()];
+        assert!(matches!(
+            partial_verify_compact::<S>(&public, &more, msg, &partial),
+            Err(WeightedError::ThresholdError(
+                ThresholdError::SignatureError(_)
+            ))
+        ));
+        let mut forged: CompactPartial<<S as Scheme>::Signature> =
+            bincode::deserialize(&partial).unwrap();
+        forged.signature.add(&<S as Scheme>::Signature::one());
+        assert!(matches!(
+            partial_verify_compact::<S>(
+                &public,
+                &signers,
+                msg,
+                &bincode::serialize(&forged).unwrap()
+            ),
+            Err(WeightedError::ThresholdError(
+                ThresholdError::SignatureError(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn two_round_partials_do_not_grow_with_weight_g1() {
+        two_round_partials_do_not_grow_with_weight::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn two_round_partials_do_not_grow_with_weight_g2() {
+        two_round_partials_do_not_grow_with_weight::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn announced_signers_are_checked() {
+        type S = G2Scheme<PCurve>;
+        let rng = &mut rand::thread_rng();
+        let (private, _) = S::keypair(rng);
+        let (shares, public) = deal_weighted::<S, _>(&private, &[5, 1, 3, 2], 7, rng).unwrap();
+        let msg = b"announced";
+        let sign = |share: &WeightedShare<_>, signers: &[Signer]| {
+            partial_sign_compact::<S>(&public, share, signers, msg)
+        };
+        let (heavy, light) = (shares[0].signer(), shares[3].signer());
+
+        assert!(matches!(
+            sign(&shares[1], &[shares[1].signer(), light]),
+            Err(WeightedError::NotEnoughWeight(3, 7))
+        ));
+        assert!(matches!(
+            sign(&shares[0], &[heavy, light]).and(sign(&shares[1], &[heavy, light])),
+            Err(WeightedError::NotASigner(1))
+        ));
+        // claiming another member's virtual shares, or counting one twice
+        let claimed = Signer {
+            member: 9,
+            first: 4,
+            weight: 2,
+        };
+        assert!(matches!(
+            sign(&shares[0], &[heavy, claimed]),
+            Err(WeightedError::OverlappingSigners(9))
+        ));
+        assert!(matches!(
+            sign(&shares[0], &[heavy, light, heavy]),
+            Err(WeightedError::OverlappingSigners(0))
+        ));
+        // a share announced with other virtual shares than it holds
+        let moved = Signer { first: 20, ..heavy };
+        assert!(matches!(
+            sign(&shares[0], &[moved, light]),
+            Err(WeightedError::NotASigner(0))
+        ));
+        let empty = Signer { weight: 0, ..light };
+        assert!(matches!(
+            sign(&shares[0], &[heavy, empty]),
+            Err(WeightedError::InvalidRange(3))
+        ));
+    }
+
+    #[test]
+    fn dealing_is_checked() {
+        type S = G2Scheme<PCurve>;
+        let rng = &mut rand::thread_rng();
+        let (private, _) = S::keypair(rng);
+
+        assert!(matches!(
+            deal_weighted::<S, _>(&private, &[2, 0, 1], 2, rng),
+            Err(WeightedError::ZeroWeight(1))
+        ));
+        assert!(matches!(
+            deal_weighted::<S, _>(&private, &[2, 1], 4, rng),
+            Err(WeightedError::InvalidDealing {
+                total: 3,
+                threshold: 4
+            })
+        ));
+        assert!(matches!(
+            deal_weighted::<S, _>(&private, &[2, 1], 0, rng),
+            Err(WeightedError::InvalidDealing { .. })
+        ));
+    }
+}
@@ -14,10 +14,11 @@
 mod tests {
     #[cfg(test)]
     mod bls12_377_vectors {
+        use crate::oprf;
         use crate::poly::{Eval, Idx, Poly};
         use crate::schemes::bls12_377::G2Scheme;
         use crate::serialization;
-        use crate::sig::{Scheme, Share, SignatureScheme, ThresholdScheme};
+        use crate::sig::{HierarchicalScheme, Scheme, Share, SignatureScheme, ThresholdScheme};
         use rand_chacha::ChaChaRng;
         use rand_chacha::rand_core::SeedableRng;
 
@@ -94,6 +95,25 @@ mod tests {
             "300000000000000019d05eff2062b7266f803c83b84321ad92c34aa794b69c3f31e75e8f3e33c2925929a7f926f32c97a2276c1541735e0102000000",
         ];
 
+        // Expected child keys derived from the public polynomial above with
+        // `CHILD_CONTEXT`: the tweak, the child public key, and the child of
+        // the share at index 1. Derivation is part of the format — every
+        // signer and verifier has to arrive at the same child independently —
+        // so a change to the hash behind the tweak breaks them as surely as a
+        // change to an encoding.
+        const CHILD_CONTEXT: &[u8] = b"app:payments";
+        const EXPECTED_CHILD_TWEAK: &str =
+            "20c45b57f11bffde9e7182562a41ca08843ecbb403306be363aec3569ea05c10";
+        const EXPECTED_CHILD_PUBLIC_KEY: &str = "009d59f48825669c297ae6d9579fb517f772ceaee4e5d0ed310ac11c828e8c2b7e7e845acb1d62ff1c71a3cfc8379001a62b5c6336a3844923a6cfe6c5ac2b4084f7cac2ad9fdc9a9ed13bb6fa64d534c2fde1fe2e13e37269125559f30f5a00";
+        const EXPECTED_CHILD_SHARE: &str =
+            "01000000300797be3daafad0ff0bf5373390645b4abc4f71706c24f096f0c30b19879008";
+
+        // The OPRF output of the first message under the first key, finalized
+        // from the first pinned signature. Every client hashes the unblinded
+        // signature itself, so the finalize step is an encoding like any other.
+        const EXPECTED_OPRF_OUTPUT: &str =
+            "c903baf80d3e6c5ccb78505a518b92eed2bab60135a32f435f797f2c370d1c48";
+
         // Create a deterministic RNG for reproducible key generation
         fn get_deterministic_rng() -> ChaChaRng {
             ChaChaRng::from_seed(SEED)
@@ -258,6 +278,32 @@ mod tests {
             }
         }
 
+        #[test]
+        fn test_child_key_derivation() {
+            let (shares, public_poly) = fixed_threshold_setup();
+
+            let tweak = G2Scheme::child_tweak(public_poly.public_key(), CHILD_CONTEXT).unwrap();
+            let encoded = hex::encode(bincode::serialize(&tweak).unwrap());
+            assert_eq!(encoded, EXPECTED_CHILD_TWEAK);
+
+            let child = G2Scheme::child_polynomial(&public_poly, CHILD_CONTEXT).unwrap();
+            let encoded = hex::encode(bincode::serialize(child.public_key()).unwrap());
+            assert_eq!(encoded, EXPECTED_CHILD_PUBLIC_KEY);
+
+            let share = G2Scheme::child_share(&public_poly, &shares[1], CHILD_CONTEXT).unwrap();
+            let encoded = hex::encode(bincode::serialize(&share).unwrap());
+            assert_eq!(encoded, EXPECTED_CHILD_SHARE);
+        }
+
+        #[test]
+        fn test_oprf_output() {
+            let (_, public_key) = get_keypair(0);
+            let signature = hex::decode(EXPECTED_SIGNATURES[0][0]).unwrap();
+
+            let output = oprf::finalize::<G2Scheme>(&public_key, MESSAGES[0], &signature).unwrap();
+            assert_eq!(hex::encode(output), EXPECTED_OPRF_OUTPUT);
+        }
+
         #[test]
         fn test_signature_aggregation() {
             // Test threshold signatures with deterministic outputs
@@ -321,4 +367,200 @@ mod tests {
             }
         }
     }
+    /// The same fixed seed under `G1Scheme`, which swaps the groups: public
+    /// keys on G1, signatures on G2.
+    ///
+    /// The private keys and shares are the scalars `bls12_377_vectors` pins,
+    /// drawn from the same RNG in the same order, so they are not repeated
+    /// here. Everything in a group is.
+    #[cfg(test)]
+    mod bls12_377_g1_vectors {
+        use crate::poly::{Eval, Idx, Poly};
+        use crate::schemes::bls12_377::G1Scheme;
+        use crate::serialization;
+        use crate::sig::{Scheme, Share, SignatureScheme, ThresholdScheme};
+        use rand_chacha::ChaChaRng;
+        use rand_chacha::rand_core::SeedableRng;
+
+        type PrivateKey = <G1Scheme as Scheme>::Private;
+        type PublicKey = <G1Scheme as Scheme>::Public;
+
+        const SEED: [u8; 32] = [42u8; 32];
+        const MESSAGES: [&[u8; 32]; 3] = [&[0x00; 32], &[0x56; 32], &[0xab; 32]];
+
+        const EXPECTED_PUBLIC_KEYS: [&str; 3] = [
+            "f8283175235ebc0deb8f30173e0941b67f4f236d35f0dc313836692ab1ba79366db9c8f74dc2d1bba972f66273462381",
+            "898dc836f8c307babc7f3112680c59f2820779f066eebff81368146219b801f394641a1685a00bcc52a197bd047fd680",
+            "c53d61d798fc1f1de869d3d50e2f3d2f96df243d49678017509508c8d708b168d9a331e1c1a599ea44d668a6ffc6ca00",
+        ];
+
+        // One row per private key, one column per message.
+        const EXPECTED_SIGNATURES: [[&str; 3]; 3] = [
+            [
+                "cb35a5d79f7928b7740ab755d1451a4eab2d73dc8dc292291c055dd62a864c54eb1382be56b5d356e8c2383fb490ed00578ee0918f4d0f5d877cf03026eac5b7d7fb3a1f0b53183b559fddb2b68cbdfd9be98668953589f959936ab4ab080380",
+                "a08c04218e81cc47e04f084f758d9b362637d031ad27225862b0d4d7ade704741b4efe56d4eebdefaab3f427937c1701005d54c9da8be8c9125eadcfe8e6d47b6a1acc7a5997d3547b339a2bc0cd190a00425e58b08c8a0f1f32ed5f87a61581",
+                "5025615a785c2d60ae5ac4ae00c6b7d8764666134516ac5a1a5fb205d5b08e694c6dffe41b5a951c6156b23f6abc3f01e119499a07c2f8cbcf47c3cadf53cee6f062c1a18c909b5a0d7d0a78e36b5910a4c7a1c47bf1937e956c192888c25901",
+            ],
+            [
+                "e1196412fed355841e9dae2184f233fb51584e1917f2da7a3568e9a104365fb236fbae839d6c50a4ccebd4fb548e6e005583e0c000cc6e6a1849f02a5485b6abd32268190cdb58f6caf3463e74ec8774b5a5a2159857d5caebe7f349c73ed300",
+                "239060257b408d6136beb3794bacea9bded204329cd3326918cdc412941d67fb5319a22aa8a48035f9b0b7559d2e3b01c3d325f9e3e57a42e6caf1065940b82acae5adbb7e018a2158e4b9e52ef8343886c4958a24cd4e8a5074e74c15f11901",
+                "e0f3001c1644ec19f5ddbbb21b505974d1ee67b80e2fa70bb0071499984ae5304cd99a35c5445717e2d03388961a20003b213899537b94088da78e2a44b3dfcd4f2a7ae2689ed2e41a1267b0141a12fa7252fe5ddd6b8c35ab7ec0df141e0881",
+            ],
+            [
+                "8d78ecdef74c8ce86b7679ace689756e6049ef49efe22aa2ab8157bc4822bd326ad37f2ee9fc48a1d4369111fc2402018d1c00036d5dd848c1a17a3140f6370a9cb078d593615d906c444ecc1565d6ad2f933b01c2d4e4e3c9d702ec97c23881",
+                "302d8fb14c3f87f672a4c60b943ac8700f37ea7bce0eac1679eedcd98deee2a4fe80a1750ccc72dbf861bea28c4f3b01f69ddd7a183fcd76f6764e138d00e4175ff192f880d4edeeff84341d1e338ebcca9f7f04bccf0a22d47ab3ac69cc8200",
+                "369b58ec6ad9929fd72a80a6e4daedbe143638398a261571a971fd7153c62d2a4ecac89ca44c59e816b1de834cc37f009c9e5fc5757b0bd46b0ca208e20584efc625a5ff3856b18b1e5f6f83649eac57c3a92c23c8e1b3e9675646f5628f1100",
+            ],
+        ];
+
+        const EXPECTED_AGGREGATED_SIGNATURES: [&str; 3] = [
+            "cb35a5d79f7928b7740ab755d1451a4eab2d73dc8dc292291c055dd62a864c54eb1382be56b5d356e8c2383fb490ed00578ee0918f4d0f5d877cf03026eac5b7d7fb3a1f0b53183b559fddb2b68cbdfd9be98668953589f959936ab4ab080380",
+            "a08c04218e81cc47e04f084f758d9b362637d031ad27225862b0d4d7ade704741b4efe56d4eebdefaab3f427937c1701005d54c9da8be8c9125eadcfe8e6d47b6a1acc7a5997d3547b339a2bc0cd190a00425e58b08c8a0f1f32ed5f87a61581",
+            "5025615a785c2d60ae5ac4ae00c6b7d8764666134516ac5a1a5fb205d5b08e694c6dffe41b5a951c6156b23f6abc3f01e119499a07c2f8cbcf47c3cadf53cee6f062c1a18c909b5a0d7d0a78e36b5910a4c7a1c47bf1937e956c192888c25901",
+        ];
+
+        // The three-coefficient polynomial of `bls12_377_vectors`, committed
+        // on G1.
+        const EXPECTED_PUBLIC_POLYNOMIAL: &str = "0300000000000000f8283175235ebc0deb8f30173e0941b67f4f236d35f0dc313836692ab1ba79366db9c8f74dc2d1bba972f66273462381898dc836f8c307babc7f3112680c59f2820779f066eebff81368146219b801f394641a1685a00bcc52a197bd047fd680c53d61d798fc1f1de869d3d50e2f3d2f96df243d49678017509508c8d708b168d9a331e1c1a599ea44d668a6ffc6ca00";
+
+        // Partial signatures on MESSAGES[0] by the shares at indices 0, 1, 2.
+        const EXPECTED_PARTIAL_SIGS_ON_MSG0: [&str; 3] = [
+            "6000000000000000b78cbb10640b10a61393c619ceacae0dd74f5e65ae2a70e515c322baa78047bd852642780b063c9670c7c88d4172f200233c449b6d87b04df43c295fc74e3b34f0f0daf28568f236f83bd1299321cda76f6bc5f66ecdcd39142f066a0cbc2a0000000000",
+            "600000000000000007451ae15e7776326a3b6f9b72f2bd69ed7d440f70a54b141e8d0bd9544fa0eadcf0459f77e1982f07e0464875d6a00145b9a0a2ba72b4ece02d8bae974383fa2aa39737b4f8ea742c72056004bfa20f949104153a1b6a074e2baa5b6f62c90001000000",
+            "6000000000000000beb576e48286d8ab8fd2abe150017130670394e9adef3b82f5f8170b3f6fd461e0acff26d9c98698885f56b35d9a1200ad407294d0eaf858d4a10267bc47c61d2c676b0d4ec99ac71b8f8660c0cf91f6d6c0c7c08bc58170d001fb6ea1fced8002000000",
+        ];
+
+        fn get_keypair(index: usize) -> (PrivateKey, PublicKey) {
+            let mut rng = ChaChaRng::from_seed(SEED);
+            for _ in 0..index {
+                G1Scheme::keypair(&mut rng);
+            }
+            G1Scheme::keypair(&mut rng)
+        }
+
+        fn fixed_threshold_setup() -> (Vec<Share<PrivateKey>>, Poly<PublicKey>) {
+            let n = 3;
+            let private_keys: Vec<PrivateKey> = (0..n)
+                .map(get_keypair)
+                .map(|(priv_key, _)| priv_key)
+                .collect();
+            let private_poly = Poly::<PrivateKey>::try_from(private_keys).unwrap();
+            let shares = (0..n)
+                .map(|i| {
+                    let eval = private_poly.eval(i as Idx);
+                    Share {
+                        index: eval.index,
+                        private: eval.value,
+                    }
+                })
+                .collect::<Vec<Share<PrivateKey>>>();
+            (shares, private_poly.commit())
+        }
+
+        #[test]
+        fn sign_and_verify() {
+            for i in 0..3 {
+                let (privkey, pubkey) = get_keypair(i);
+
+                let pub_hex = hex::encode(bincode::serialize(&pubkey).unwrap());
+                assert_eq!(
+                    pub_hex, EXPECTED_PUBLIC_KEYS[i],
+                    "Public key {} mismatch",
+                    i
+                );
+
+                for (j, &msg) in MESSAGES.iter().enumerate() {
+                    let sig = G1Scheme::sign(&privkey, msg).expect("Error signing");
+                    assert_eq!(
+                        hex::encode(&sig),
+                        EXPECTED_SIGNATURES[i][j],
+                        "Signature for key[{}] and message[{}] mismatch",
+                        i,
+                        j
+                    );
+                    assert!(G1Scheme::verify(&pubkey, msg, &sig).is_ok());
+                }
+            }
+        }
+
+        #[test]
+        fn test_wire_format_public_polynomial() {
+            let (_, public_poly) = fixed_threshold_setup();
+
+            let encoded = hex::encode(bincode::serialize(&public_poly).unwrap());
+            assert_eq!(encoded, EXPECTED_PUBLIC_POLYNOMIAL);
+
+            let bytes = hex::decode(EXPECTED_PUBLIC_POLYNOMIAL).unwrap();
+            let decoded: Poly<PublicKey> = serialization::deserialize(&bytes)
+                .expect("bounded deserialize must accept pinned wire data");
+            assert_eq!(decoded, public_poly);
+        }
+
+        /// The G1 column of the `docs/wire-format.md` table.
+        #[test]
+        fn test_wire_format_layout() {
+            let (shares, public_poly) = fixed_threshold_setup();
+            let share = &shares[1];
+
+            let partial = G1Scheme::partial_sign(share, MESSAGES[0]).unwrap();
+            assert_eq!(partial.len(), 108);
+            assert_eq!(&partial[..8], &96u64.to_le_bytes());
+            assert_eq!(&partial[partial.len() - 4..], &share.index.to_le_bytes());
+
+            let (privkey, pubkey) = get_keypair(0);
+            let sig = G1Scheme::sign(&privkey, MESSAGES[0]).unwrap();
+            assert_eq!(sig.len(), 96);
+            assert_eq!(bincode::serialize(&pubkey).unwrap().len(), 48);
+            assert_eq!(bincode::serialize(&privkey).unwrap().len(), 32);
+
+            let encoded = bincode::serialize(&public_poly).unwrap();
+            let coefficients = (public_poly.degree() + 1) as u64;
+            assert_eq!(&encoded[..8], &coefficients.to_le_bytes());
+            assert_eq!(encoded.len(), 8 + 48 * coefficients as usize);
+        }
+
+        #[test]
+        fn test_wire_format_partial_signatures() {
+            let (shares, _) = fixed_threshold_setup();
+            for (i, share) in shares.iter().enumerate() {
+                let partial = G1Scheme::partial_sign(share, MESSAGES[0]).unwrap();
+                assert_eq!(
+                    hex::encode(&partial),
+                    EXPECTED_PARTIAL_SIGS_ON_MSG0[i],
+                    "partial signature {} wire format drift",
+                    i
+                );
+
+                let bytes = hex::decode(EXPECTED_PARTIAL_SIGS_ON_MSG0[i]).unwrap();
+                let decoded: Eval<Vec<u8>> = serialization::deserialize(&bytes)
+                    .expect("bounded deserialize must accept pinned wire data");
+                assert_eq!(decoded.index, share.index);
+                assert_eq!(bincode::serialize(&decoded).unwrap(), bytes);
+            }
+        }
+
+        #[test]
+        fn test_signature_aggregation() {
+            let (shares, public_poly) = fixed_threshold_setup();
+            for (msg_idx, &msg) in MESSAGES.iter().enumerate() {
+                let partials = shares
+                    .iter()
+                    .map(|s| G1Scheme::partial_sign(s, msg).unwrap())
+                    .collect::<Vec<_>>();
+                for partial in &partials {
+                    assert!(G1Scheme::partial_verify(&public_poly, msg, partial).is_ok());
+                }
+
+                let aggregated =
+                    G1Scheme::aggregate(&public_poly, &partials).expect("Failed to aggregate");
+                assert_eq!(
+                    hex::encode(&aggregated),
+                    EXPECTED_AGGREGATED_SIGNATURES[msg_idx],
+                    "Aggregated signature for message {} mismatch",
+                    msg_idx
+                );
+                assert!(G1Scheme::verify(public_poly.public_key(), msg, &aggregated).is_ok());
+            }
+        }
+    }
 }
@@ -0,0 +1,530 @@
+//! Unlinkable single-use tokens, issued with blind signatures in the manner of
+//! Privacy Pass.
+//!
+//! A client draws a random nonce and blinds it ([`TokenRequest::new`]), the
+//! issuer signs the blinded message without seeing the nonce ([`issue`]), and
+//! the client unblinds the signature into a [`RedemptionToken`]
+//! ([`TokenRequest::finalize`]). Later the client hands the token over, and the
+//! verifier checks the signature and records the nonce as spent in a
+//! [`SpendStore`] ([`redeem`]). The issuer saw only blinded messages, so it
+//! cannot link a redeemed token to the request it was issued for.
+//!
+//! Threshold issuers sign the blinded message with
+//! [`BlindThresholdScheme::sign_blind_partial`] instead, and the client
+//! aggregates their partials with [`TokenRequest::finalize_partials`].
+//!
+//! Tokens are signatures on the nonce behind a fixed prefix, so a signature the
+//! key makes for any other purpose is not a token. The issuing key should still
+//! serve no other purpose: a blind signer signs whatever it is sent, and a
+//! blinded message cannot be told apart from a blinded token.
+//!
+//! ```
+//! use threshold_bls::{
+//!     schemes::bls12_377::G2Scheme as SigScheme,
+//!     sig::Scheme,
+//!     token::{self, MemorySpendStore, TokenError, TokenRequest},
+//! };
+//!
+//! let rng = &mut rand::thread_rng();
+//! let (private, public) = SigScheme::keypair(rng);
+//!
+//! let request = TokenRequest::<SigScheme>::new(rng).unwrap();
+//! let blind_signature = token::issue::<SigScheme>(&private, request.blinded_message()).unwrap();
+//! let redemption = request.finalize(&public, &blind_signature).unwrap();
+//!
+//! let spent = MemorySpendStore::default();
+//! token::redeem::<SigScheme, _>(&public, &redemption, &spent).unwrap();
+//! assert!(matches!(
+//!     token::redeem::<SigScheme, _>(&public, &redemption, &spent),
+//!     Err(TokenError::DoubleSpend)
+//! ));
+//! ```
+use crate::group::Element;
+use crate::poly::Poly;
+use crate::serialization;
+use crate::sig::{
+    BlindScheme, BlindThresholdScheme, Partial, SignatureScheme, ThresholdError, ThresholdScheme,
+};
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::error::Error;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Bytes in a token's nonce.
+pub const NONCE_LEN: usize = 32;
+
+/// Prefix of the message a token's signature signs, ahead of its nonce.
+const TOKEN_PREFIX: &[u8] = b"BLS-TOKEN-v1:";
+
+/// Errors associated with issuing and redeeming tokens
+#[derive(Debug, Error)]
+pub enum TokenError<S: BlindScheme + SignatureScheme> {
+    /// Raised when blinding, blind signing or unblinding fails
+    #[error("blinding failed: {0}")]
+    BlindError(<S as BlindScheme>::Error),
+
+    /// Raised when a token's signature does not verify under the issuer's key
+    #[error("invalid token signature: {0}")]
+    SignatureError(<S as SignatureScheme>::Error),
+
+    /// Raised when aggregating threshold issuers' partials fails
+    #[error(transparent)]
+    ThresholdError(ThresholdError<S>),
+
+    /// Raised when the blind signature at an index of a batch does not verify
+    /// on its blinded message
+    #[error("invalid blind signature at index {0} of the batch")]
+    InvalidIssuance(usize),
+
+    /// Raised when a batch has a different number of blind signatures than
+    /// requests
+    #[error("{0} blind signatures for {1} requests")]
+    BatchLengthMismatch(usize, usize),
+
+    /// Raised when the token's nonce has already been redeemed
+    #[error("token already redeemed")]
+    DoubleSpend,
+
+    /// Raised when the spend store fails to record a nonce
+    #[error("could not record the spend: {0}")]
+    StoreError(Box<dyn Error + Send + Sync>),
+
+    /// Raised when a blinded message or a blind signature in a batch cannot be
+    /// (de)serialized
+    #[error(transparent)]
+    BincodeError(#[from] bincode::Error),
+}
+
+/// A client's request for one token: the nonce, and the blinding of it that
+/// the issuer signs. It is kept until the blind signature comes back, and is
+/// consumed turning it into a [`RedemptionToken`].
+pub struct TokenRequest<S: BlindScheme> {
+    nonce: [u8; NONCE_LEN],
+    blinding: S::Token,
+    blinded: Vec<u8>,
+}
+
+/// A redeemable token: a nonce and the issuer's signature on it. Tokens with
+/// the same issuer key are indistinguishable to the issuer, and each redeems
+/// once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedemptionToken {
+    /// The random nonce the client drew
+    pub nonce: [u8; NONCE_LEN],
+    /// The issuer's signature on the nonce, unblinded
+    pub signature: Vec<u8>,
+}
+
+/// Records redeemed nonces, so that each token redeems once.
+///
+/// Redemption checks a token's signature before it records the nonce, so a
+/// forged token does not burn the nonce of a genuine one.
+pub trait SpendStore {
+    /// Error raised when the store cannot record a nonce
+    type Error: Error + Send + Sync + 'static;
+
+    /// Records `nonce` as spent, returning whether it was not spent already.
+    /// Verifiers that share a store race on it, so the check and the record
+    /// must be one atomic operation.
+    fn insert(&self, nonce: &[u8; NONCE_LEN]) -> Result<bool, Self::Error>;
+}
+
+/// A [`SpendStore`] that holds the nonces in memory, for a single verifier
+/// process. It forgets them when dropped, so a token can be redeemed again
+/// after a restart unless the issuer key is rotated with it.
+#[derive(Debug, Default)]
+pub struct MemorySpendStore {
+    spent: Mutex<HashSet<[u8; NONCE_LEN]>>,
+}
+
+impl MemorySpendStore {
+    /// The number of nonces recorded as spent
+    pub fn len(&self) -> usize {
+        self.spent
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .len()
+    }
+
+    /// Whether no nonce has been recorded as spent
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl SpendStore for MemorySpendStore {
+    type Error = Infallible;
+
+    fn insert(&self, nonce: &[u8; NONCE_LEN]) -> Result<bool, Infallible> {
+        // A panic elsewhere while holding the lock leaves the set as it was
+        // before or after one insertion, either of which is consistent.
+        let mut spent = self.spent.lock().unwrap_or_else(|err| err.into_inner());
+        Ok(spent.insert(*nonce))
+    }
+}
+
+impl<S: BlindScheme + SignatureScheme> TokenRequest<S> {
+    /// Draws a nonce from `rng`, which must be a CSPRNG, and blinds it.
+    pub fn new<R: RngCore>(rng: &mut R) -> Result<Self, TokenError<S>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+        let (blinding, blinded) =
+            S::blind_msg(&token_message(&nonce), rng).map_err(TokenError::BlindError)?;
+
+        Ok(Self {
+            nonce,
+            blinding,
+            blinded,
+        })
+    }
+
+    /// The blinded message to send the issuer.
+    pub fn blinded_message(&self) -> &[u8] {
+        &self.blinded
+    }
+
+    /// Unblinds the issuer's blind signature into a token, and checks it under
+    /// the issuer's public key so that a bad issuance is caught now rather than
+    /// at redemption.
+    pub fn finalize(
+        self,
+        public: &S::Public,
+        blind_signature: &[u8],
+    ) -> Result<RedemptionToken, TokenError<S>> {
+        let signature =
+            S::unblind_sig(&self.blinding, blind_signature).map_err(TokenError::BlindError)?;
+        let token = RedemptionToken {
+            nonce: self.nonce,
+            signature,
+        };
+        verify::<S>(public, &token)?;
+        Ok(token)
+    }
+
+    /// Aggregates threshold issuers' partial signatures on the blinded message
+    /// and finalizes the result as [`TokenRequest::finalize`] does.
+    pub fn finalize_partials(
+        self,
+        public: &Poly<S::Public>,
+        partials: &[Partial],
+    ) -> Result<RedemptionToken, TokenError<S>>
+    where
+        S: BlindThresholdScheme,
+    {
+        let blind_signature = S::aggregate(public, partials).map_err(TokenError::ThresholdError)?;
+        self.finalize(public.public_key(), &blind_signature)
+    }
+}
+
+/// Signs a blinded token request with the issuer's private key.
+pub fn issue<S: BlindScheme + SignatureScheme>(
+    private: &S::Private,
+    blinded_message: &[u8],
+) -> Result<Vec<u8>, TokenError<S>> {
+    S::blind_sign(private, blinded_message).map_err(TokenError::BlindError)
+}
+
+/// Signs a batch of blinded token requests, returning the blind signatures in
+/// the requests' order.
+pub fn issue_batch<S: BlindScheme + SignatureScheme>(
+    private: &S::Private,
+    blinded_messages: &[&[u8]],
+) -> Result<Vec<Vec<u8>>, TokenError<S>> {
+    blinded_messages
+        .iter()
+        .map(|blinded| issue::<S>(private, blinded))
+        .collect()
+}
+
+/// Finalizes a batch of requests with the issuer's blind signatures, in the
+/// requests' order, checking them all with one pairing check rather than one
+/// per token.
+///
+/// The check is on a random linear combination of the blind signatures
+/// against the same combination of the blinded messages, with coefficients
+/// drawn from `rng`: an issuer who does not know them cannot make a bad
+/// signature cancel out. If it fails, the signatures are checked one by one to
+/// report which is bad.
+pub fn finalize_batch<S, R>(
+    public: &S::Public,
+    requests: Vec<TokenRequest<S>>,
+    blind_signatures: &[Vec<u8>],
+    rng: &mut R,
+) -> Result<Vec<RedemptionToken>, TokenError<S>>
+where
+    S: BlindScheme + SignatureScheme,
+    R: RngCore,
+{
+    if blind_signatures.len() != requests.len() {
+        return Err(TokenError::BatchLengthMismatch(
+            blind_signatures.len(),
+            requests.len(),
+        ));
+    }
+    if requests.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let (mut messages, mut signatures) = (S::Signature::zero(), S::Signature::zero());
+    for (request, blind_signature) in requests.iter().zip(blind_signatures) {
+        let coefficient = S::Private::rand(rng);
+
+        let mut message: S::Signature = serialization::deserialize(&request.blinded)?;
+        message.mul(&coefficient);
+        messages.add(&message);
+
+        let mut signature: S::Signature = serialization::deserialize(blind_signature)?;
+        signature.mul(&coefficient);
+        signatures.add(&signature);
+    }
+
+    let (messages, signatures) = (
+        bincode::serialize(&messages)?,
+        bincode::serialize(&signatures)?,
+    );
+    if S::blind_verify(public, &messages, &signatures).is_err() {
+        let bad = requests
+            .iter()
+            .zip(blind_signatures)
+            .position(|(request, blind_signature)| {
+                S::blind_verify(public, &request.blinded, blind_signature).is_err()
+            })
+            // A combination fails only if some term does; were none found, the
+            // combination itself must be degenerate, which the first term is
+            // reported for.
+            .unwrap_or(0);
+        return Err(TokenError::InvalidIssuance(bad));
+    }
+
+    requests
+        .into_iter()
+        .zip(blind_signatures)
+        .map(|(request, blind_signature)| {
+            let signature = S::unblind_sig(&request.blinding, blind_signature)
+                .map_err(TokenError::BlindError)?;
+            Ok(RedemptionToken {
+                nonce: request.nonce,
+                signature,
+            })
+        })
+        .collect()
+}
+
+/// Checks a token's signature under the issuer's public key, without spending
+/// it.
+pub fn verify<S: BlindScheme + SignatureScheme>(
+    public: &S::Public,
+    token: &RedemptionToken,
+) -> Result<(), TokenError<S>> {
+    S::verify(public, &token_message(&token.nonce), &token.signature)
+        .map_err(TokenError::SignatureError)
+}
+
+/// Redeems a token: checks its signature under the issuer's public key, then
+/// records its nonce as spent, failing if it already was.
+pub fn redeem<S, D>(
+    public: &S::Public,
+    token: &RedemptionToken,
+    store: &D,
+) -> Result<(), TokenError<S>>
+where
+    S: BlindScheme + SignatureScheme,
+    D: SpendStore,
+{
+    verify::<S>(public, token)?;
+
+    match store.insert(&token.nonce) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(TokenError::DoubleSpend),
+        Err(err) => Err(TokenError::StoreError(Box::new(err))),
+    }
+}
+
+/// The message a token's signature signs.
+fn token_message(nonce: &[u8; NONCE_LEN]) -> Vec<u8> {
+    [TOKEN_PREFIX, nonce].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        curve::bls12377::PairingCurve as PCurve,
+        sig::{
+            Scheme,
+            bls::{G1Scheme, G2Scheme},
+            shares,
+        },
+    };
+    use rand::thread_rng;
+
+    fn issues_and_redeems<S: BlindScheme + SignatureScheme>() {
+        let rng = &mut thread_rng();
+        let (private, public) = S::keypair(rng);
+
+        let request = TokenRequest::<S>::new(rng).unwrap();
+        let blind_signature = issue::<S>(&private, request.blinded_message()).unwrap();
+        let token = request.finalize(&public, &blind_signature).unwrap();
+
+        // the token survives a round trip through the client's storage
+        let token: RedemptionToken =
+            bincode::deserialize(&bincode::serialize(&token).unwrap()).unwrap();
+
+        let store = MemorySpendStore::default();
+        redeem::<S, _>(&public, &token, &store).unwrap();
+        assert!(matches!(
+            redeem::<S, _>(&public, &token, &store),
+            Err(TokenError::DoubleSpend)
+        ));
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn issues_and_redeems_g1() {
+        issues_and_redeems::<G1Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn issues_and_redeems_g2() {
+        issues_and_redeems::<G2Scheme<PCurve>>();
+    }
+
+    #[test]
+    fn a_forged_token_does_not_burn_the_nonce() {
+        type S = G2Scheme<PCurve>;
+        let rng = &mut thread_rng();
+        let (private, public) = S::keypair(rng);
+        let (other, _) = S::keypair(rng);
+
+        let request = TokenRequest::<S>::new(rng).unwrap();
+        let blinded = request.blinded_message().to_vec();
+        let token = request
+            .finalize(&public, &issue::<S>(&private, &blinded).unwrap())
+            .unwrap();
+
+        let forged = RedemptionToken {
+            nonce: token.nonce,
+            signature: S::sign(&other, &token_message(&token.nonce)).unwrap(),
+        };
+        let store = MemorySpendStore::default();
+        assert!(matches!(
+            redeem::<S, _>(&public, &forged, &store),
+            Err(TokenError::SignatureError(_))
+        ));
+        assert!(store.is_empty());
+        redeem::<S, _>(&public, &token, &store).unwrap();
+
+        // nor is a plain signature on the bare nonce a token
+        let bare = RedemptionToken {
+            nonce: [7; NONCE_LEN],
+            signature: S::sign(&private, &[7; NONCE_LEN]).unwrap(),
+        };
+        assert!(redeem::<S, _>(&public, &bare, &store).is_err());
+    }
+
+    #[test]
+    fn a_bad_issuance_is_caught_when_finalizing() {
+        type S = G2Scheme<PCurve>;
+        let rng = &mut thread_rng();
+        let (_, public) = S::keypair(rng);
+        let (other, _) = S::keypair(rng);
+
+        let request = TokenRequest::<S>::new(rng).unwrap();
+        let blind_signature = issue::<S>(&other, request.blinded_message()).unwrap();
+        assert!(matches!(
+            request.finalize(&public, &blind_signature),
+            Err(TokenError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn batches_are_checked_at_once() {
+        type S = G2Scheme<PCurve>;
+        let rng = &mut thread_rng();
+        let (private, public) = S::keypair(rng);
+        let (other, _) = S::keypair(rng);
+
+        let requests = || {
+            (0..4)
+                .map(|_| TokenRequest::<S>::new(&mut thread_rng()).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let blinded = |requests: &[TokenRequest<S>]| {
+            requests
+                .iter()
+                .map(|request| request.blinded_message().to_vec())
+                .collect::<Vec<_>>()
+        };
+
+        let batch = requests();
+        let messages = blinded(&batch);
+        let signatures = issue_batch::<S>(
+            &private,
+            &messages.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let tokens = finalize_batch::<S, _>(&public, batch, &signatures, rng).unwrap();
+        assert_eq!(tokens.len(), 4);
+        let store = MemorySpendStore::default();
+        for token in &tokens {
+            redeem::<S, _>(&public, token, &store).unwrap();
+        }
+
+        // one signature under another key is found
+        let batch = requests();
+        let messages = blinded(&batch);
+        let mut signatures = issue_batch::<S>(
+            &private,
+            &messages.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        signatures[2] = issue::<S>(&other, &messages[2]).unwrap();
+        assert!(matches!(
+            finalize_batch::<S, _>(&public, batch, &signatures, rng),
+            Err(TokenError::InvalidIssuance(2))
+        ));
+
+        // and so are swapped ones, which a plain sum would let through
+        let batch = requests();
+        let messages = blinded(&batch);
+        let mut signatures = issue_batch::<S>(
+            &private,
+            &messages.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        signatures.swap(0, 1);
+        assert!(matches!(
+            finalize_batch::<S, _>(&public, batch, &signatures, rng),
+            Err(TokenError::InvalidIssuance(0))
+        ));
+
+        let batch = requests();
+        assert!(matches!(
+            finalize_batch::<S, _>(&public, batch, &signatures[..3], rng),
+            Err(TokenError::BatchLengthMismatch(3, 4))
+        ));
+    }
+
+    #[test]
+    fn threshold_issuers() {
+        type S = G2Scheme<PCurve>;
+        let (shares, public) = shares::<S>(3, 2);
+
+        let request = TokenRequest::<S>::new(&mut thread_rng()).unwrap();
+        let partials = shares[1..]
+            .iter()
+            .map(|share| S::sign_blind_partial(share, request.blinded_message()).unwrap())
+            .collect::<Vec<_>>();
+        let token = request.finalize_partials(&public, &partials).unwrap();
+        redeem::<S, _>(public.public_key(), &token, &MemorySpendStore::default()).unwrap();
+
+        let request = TokenRequest::<S>::new(&mut thread_rng()).unwrap();
+        assert!(matches!(
+            request.finalize_partials(&public, &partials[..1]),
+            Err(TokenError::ThresholdError(_))
+        ));
+    }
+}